@@ -7,6 +7,7 @@ pub async fn build(
     test: bool,
     check_build: bool,
     zip_url: Option<&str>,
+    jobs: usize,
 ) -> fastn_core::Result<()> {
     let build_dir = config.ds.root().join(".build");
     // Default css and js
@@ -29,14 +30,16 @@ pub async fn build(
                 return handle_only_id(id, config, base_url, ignore_failed, test, documents).await;
             }
             None => {
-                incremental_build(config, &documents, base_url, ignore_failed, test).await?;
+                incremental_build(config, &documents, base_url, ignore_failed, test, jobs).await?;
             }
         }
     }
 
-    // All redirect html files under .build
+    // All redirect html files under .build, and a `_redirects` file for static hosts, which
+    // also has the patterns and status codes
     if let Some(ref r) = config.package.redirects {
-        for (redirect_from, redirect_to) in r.iter() {
+        for redirect in r.iter().filter(|r| !r.is_pattern()) {
+            let (redirect_from, redirect_to) = (&redirect.from, &redirect.to);
             println!(
                 "Processing redirect {}/{} -> {}... ",
                 config.package.name.as_str(),
@@ -58,8 +61,20 @@ pub async fn build(
                 .await
                 .ok();
         }
+
+        if !r.is_empty() {
+            fastn_core::seo::write_file(
+                config,
+                &build_dir,
+                fastn_core::package::redirects::REDIRECTS_FILE,
+                fastn_core::package::redirects::redirects_file(r),
+            )
+            .await?;
+        }
     }
 
+    fastn_core::seo::write_seo_files(config, &build_dir).await?;
+
     if !test {
         config.download_fonts().await?;
     }
@@ -181,6 +196,12 @@ mod cache {
     pub(crate) struct Document {
         pub(crate) html_checksum: String,
         pub(crate) dependencies: Vec<String>,
+        /// the checksums of `dependencies` when the document was rendered
+        #[serde(default)]
+        pub(crate) dependency_checksums: std::collections::BTreeMap<String, String>,
+        /// the text of the page, for the search index, so cached pages are indexed too
+        #[serde(default)]
+        pub(crate) page_text: Option<ftd::executor::PageText>,
     }
 }
 
@@ -202,41 +223,23 @@ fn is_virtual_dep(path: &str) -> bool {
         || path.ends_with("/-/assets.ftd")
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn handle_dependency_file(
-    config: &fastn_core::Config,
-    cache: &mut cache::Cache,
+// adds the documents named `name_without_package_name` to the documents to build
+fn plan_dependency_file(
     documents: &std::collections::BTreeMap<String, fastn_core::File>,
-    base_url: &str,
-    ignore_failed: bool,
-    test: bool,
-    name_without_package_name: String,
-    processed: &mut Vec<String>,
-) -> fastn_core::Result<()> {
+    name_without_package_name: &str,
+    plan: &mut Vec<String>,
+) {
     for document in documents.values() {
-        if remove_extension(document.get_id()).eq(name_without_package_name.as_str())
-            || remove_extension(&document.get_id_with_package())
-                .eq(name_without_package_name.as_str())
+        if remove_extension(document.get_id()).eq(name_without_package_name)
+            || remove_extension(&document.get_id_with_package()).eq(name_without_package_name)
         {
             let id = document.get_id().to_string();
-            if processed.contains(&id) {
+            if plan.contains(&id) {
                 continue;
             }
-            handle_file(
-                document,
-                config,
-                base_url,
-                ignore_failed,
-                test,
-                true,
-                Some(cache),
-            )
-            .await?;
-            processed.push(id);
+            plan.push(id);
         }
     }
-
-    Ok(())
 }
 
 // removes deleted documents from cache and build folder
@@ -298,13 +301,16 @@ async fn incremental_build(
     base_url: &str,
     ignore_failed: bool,
     test: bool,
+    jobs: usize,
 ) -> fastn_core::Result<()> {
     // https://fastn.com/rfc/incremental-build/
     use itertools::Itertools;
 
-    let (cache_hit, mut c) = cache::get()?;
+    let (cache_hit, c) = cache::get()?;
+    let c = antidote::Mutex::new(c);
 
-    let mut processed: Vec<String> = vec![];
+    // the ids of the documents to build, every document comes after its dependencies
+    let mut plan: Vec<String> = vec![];
 
     if cache_hit {
         let mut unresolved_dependencies = vec![];
@@ -314,16 +320,7 @@ async fn incremental_build(
         for file in documents.values() {
             // copy static files
             if file.is_static() {
-                handle_file(
-                    file,
-                    config,
-                    base_url,
-                    ignore_failed,
-                    test,
-                    true,
-                    Some(&mut c),
-                )
-                .await?;
+                handle_file(file, config, base_url, ignore_failed, test, true, Some(&c)).await?;
                 continue;
             }
 
@@ -332,7 +329,12 @@ async fn incremental_build(
 
         while let Some(unresolved_dependency) = unresolved_dependencies.pop() {
             // println!("Current UR: {}", unresolved_dependency.as_str());
-            if let Some(doc) = c.documents.get(unresolved_dependency.as_str()) {
+            let cached_dependencies = c
+                .lock()
+                .documents
+                .get(unresolved_dependency.as_str())
+                .map(|doc| doc.dependencies.clone());
+            if let Some(cached_dependencies) = cached_dependencies {
                 // println!(
                 //     "[INCREMENTAL BUILD][CACHE FOUND] Processing: {}",
                 //     &unresolved_dependency
@@ -340,8 +342,7 @@ async fn incremental_build(
 
                 let mut own_resolved_dependencies: Vec<String> = vec![];
 
-                let dependencies: Vec<String> = doc
-                    .dependencies
+                let dependencies: Vec<String> = cached_dependencies
                     .iter()
                     .map(|dep| get_dependency_name_without_package_name(&config.package.name, dep))
                     .collect_vec();
@@ -363,17 +364,7 @@ async fn incremental_build(
                 // );
 
                 if own_resolved_dependencies.eq(&dependencies) {
-                    handle_dependency_file(
-                        config,
-                        &mut c,
-                        documents,
-                        base_url,
-                        ignore_failed,
-                        test,
-                        unresolved_dependency.to_string(),
-                        &mut processed,
-                    )
-                    .await?;
+                    plan_dependency_file(documents, unresolved_dependency.as_str(), &mut plan);
 
                     resolved_dependencies.push(unresolved_dependency.to_string());
                     if unresolved_dependencies.is_empty() {
@@ -395,17 +386,7 @@ async fn incremental_build(
                 } else {
                     // println!("Not found in cache UR: {}", unresolved_dependency.as_str());
 
-                    handle_dependency_file(
-                        config,
-                        &mut c,
-                        documents,
-                        base_url,
-                        ignore_failed,
-                        test,
-                        unresolved_dependency.to_string(),
-                        &mut processed,
-                    )
-                    .await?;
+                    plan_dependency_file(documents, unresolved_dependency.as_str(), &mut plan);
 
                     resolved_dependencies.push(unresolved_dependency.clone());
                 }
@@ -420,14 +401,20 @@ async fn incremental_build(
                 }
             }
         }
+    } else {
+        plan.extend(documents.keys().cloned());
+    }
 
-        remove_deleted_documents(config, &mut c, documents).await?;
+    let waves = build_waves(&plan, documents, &c.lock(), &config.package.name);
+    // the threads need a multi thread runtime to drive their IO while this one is blocked
+    let multi_thread = tokio::runtime::Handle::current().runtime_flavor()
+        == tokio::runtime::RuntimeFlavor::MultiThread;
+    if jobs > 1 && multi_thread {
+        tokio::task::block_in_place(|| {
+            render_concurrently(&waves, config, base_url, ignore_failed, test, &c, jobs)
+        })?;
     } else {
-        for document in documents.values() {
-            let id = document.get_id().to_string();
-            if processed.contains(&id) {
-                continue;
-            }
+        for document in waves.iter().flatten() {
             handle_file(
                 document,
                 config,
@@ -435,18 +422,193 @@ async fn incremental_build(
                 ignore_failed,
                 test,
                 true,
-                Some(&mut c),
+                Some(&c),
             )
             .await?;
-            processed.push(id);
         }
     }
 
+    let mut c = c.into_inner();
+    if cache_hit {
+        remove_deleted_documents(config, &mut c, documents).await?;
+    }
+
+    let pages = documents
+        .keys()
+        .filter(|id| id.as_str() != "FASTN.ftd" && id.as_str() != "404.ftd")
+        .filter_map(|id| {
+            c.documents
+                .get(remove_extension(id).as_str())
+                .and_then(|d| d.page_text.clone())
+                .map(|text| (fastn_core::search::url(id), text))
+        })
+        .collect();
+    fastn_core::search::write_index(config, pages).await?;
+
     c.cache_it()?;
 
     Ok(())
 }
 
+/// Groups the documents of `plan` into waves: the documents of a wave only depend on documents
+/// of earlier waves, according to the dependencies recorded in the cache, so the documents of a
+/// wave can be rendered at the same time. Dependencies that are not planned are already built,
+/// and the dependency that closes a cycle is ignored. Documents keep their order in `plan` within
+/// a wave.
+fn build_waves<'a>(
+    plan: &[String],
+    documents: &'a std::collections::BTreeMap<String, fastn_core::File>,
+    cache: &cache::Cache,
+    package_name: &str,
+) -> Vec<Vec<&'a fastn_core::File>> {
+    let planned: Vec<(String, &fastn_core::File)> = plan
+        .iter()
+        .filter_map(|id| documents.get(id))
+        .map(|document| (remove_extension(document.get_id()), document))
+        .collect();
+    let dependencies: std::collections::HashMap<String, Vec<String>> = planned
+        .iter()
+        .map(|(name, _)| {
+            let dependencies = cache
+                .documents
+                .get(name.as_str())
+                .into_iter()
+                .flat_map(|doc| doc.dependencies.iter())
+                .map(|dep| get_dependency_name_without_package_name(package_name, dep))
+                .filter(|dep| dep != name && planned.iter().any(|(n, _)| n == dep))
+                .collect();
+            (name.clone(), dependencies)
+        })
+        .collect();
+
+    let mut waves: Vec<Vec<&fastn_core::File>> = vec![];
+    let mut wave_of = std::collections::HashMap::new();
+    for (name, document) in planned.iter() {
+        let wave = document_wave(
+            name,
+            &dependencies,
+            &mut wave_of,
+            &mut std::collections::HashSet::new(),
+        );
+        if waves.len() <= wave {
+            waves.resize_with(wave + 1, Vec::new);
+        }
+        waves[wave].push(*document);
+    }
+    waves
+}
+
+/// The wave of `name`, one after the last wave of its dependencies. `visiting` are the documents
+/// whose wave is being found, a dependency on one of them closes a cycle.
+fn document_wave(
+    name: &str,
+    dependencies: &std::collections::HashMap<String, Vec<String>>,
+    wave_of: &mut std::collections::HashMap<String, usize>,
+    visiting: &mut std::collections::HashSet<String>,
+) -> usize {
+    if let Some(wave) = wave_of.get(name) {
+        return *wave;
+    }
+
+    visiting.insert(name.to_string());
+    let mut wave = 0;
+    for dep in dependencies.get(name).into_iter().flatten() {
+        if !visiting.contains(dep.as_str()) {
+            wave = wave.max(document_wave(dep, dependencies, wave_of, visiting) + 1);
+        }
+    }
+    visiting.remove(name);
+
+    wave_of.insert(name.to_string(), wave);
+    wave
+}
+
+/// Renders `waves` with `jobs` threads, a wave is rendered after the previous one is done.
+///
+/// Rendering futures are not `Send`, so every thread runs them with `Handle::block_on()` of the
+/// runtime of the build, which also drives their IO. Clients and connection pools used by the
+/// processors are tied to that runtime, so they can be shared by the threads. The result of
+/// every document is printed in the order of `waves`, so the output does not depend on which
+/// thread finished first, and the first failed document stops the build like it does without
+/// `--jobs`. A thread that panics fails the build once the other threads are done.
+fn render_concurrently(
+    waves: &[Vec<&fastn_core::File>],
+    config: &fastn_core::Config,
+    base_url: &str,
+    ignore_failed: bool,
+    test: bool,
+    cache: &antidote::Mutex<cache::Cache>,
+    jobs: usize,
+) -> fastn_core::Result<()> {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    let runtime = tokio::runtime::Handle::current();
+    let failed = AtomicBool::new(false);
+    for wave in waves {
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let result = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..jobs.min(wave.len()))
+                .map(|_| {
+                    let sender = sender.clone();
+                    let (runtime, next, failed) = (&runtime, &next, &failed);
+                    scope.spawn(move || loop {
+                        let i = next.fetch_add(1, Ordering::SeqCst);
+                        if i >= wave.len() || failed.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        let start = std::time::Instant::now();
+                        let status = runtime.block_on(handle_file_(
+                            wave[i],
+                            config,
+                            base_url,
+                            ignore_failed,
+                            test,
+                            true,
+                            Some(cache),
+                        ));
+                        if status.is_err() {
+                            failed.store(true, Ordering::SeqCst);
+                        }
+                        if sender.send((i, status, start)).is_err() {
+                            break;
+                        }
+                    })
+                })
+                .collect();
+            drop(sender);
+
+            let mut finished = std::collections::BTreeMap::new();
+            let mut printed = 0;
+            let mut result = Ok(());
+            for (i, status, start) in receiver {
+                finished.insert(i, (status, start));
+                while let Some((status, start)) = finished.remove(&printed) {
+                    let document = wave[printed];
+                    print!("Processing {} ... ", document.get_id_with_package());
+                    print_status(document, config, &status, start);
+                    if result.is_ok() {
+                        result = status;
+                    }
+                    printed += 1;
+                }
+            }
+
+            for thread in threads {
+                if thread.join().is_err() && result.is_ok() {
+                    failed.store(true, Ordering::SeqCst);
+                    result = Err(fastn_core::Error::GenericError(
+                        "a rendering thread panicked".to_string(),
+                    ));
+                }
+            }
+            result
+        });
+        result?;
+    }
+    Ok(())
+}
+
 #[tracing::instrument(skip(config, documents))]
 async fn handle_only_id(
     id: &str,
@@ -476,11 +638,10 @@ async fn handle_file(
     ignore_failed: bool,
     test: bool,
     build_static_files: bool,
-    cache: Option<&mut cache::Cache>,
+    cache: Option<&antidote::Mutex<cache::Cache>>,
 ) -> fastn_core::Result<()> {
     let start = std::time::Instant::now();
     print!("Processing {} ... ", document.get_id_with_package());
-    let package_name = config.package.name.to_string();
     let process_status = handle_file_(
         document,
         config,
@@ -491,42 +652,38 @@ async fn handle_file(
         cache,
     )
     .await;
+    print_status(document, config, &process_status, start);
+    process_status
+}
+
+fn print_status(
+    document: &fastn_core::File,
+    config: &fastn_core::Config,
+    process_status: &fastn_core::Result<()>,
+    start: std::time::Instant,
+) {
+    let package_name = config.package.name.as_str();
     if process_status.is_ok() {
         fastn_core::utils::print_end(
-            format!("Processed {}/{}", package_name.as_str(), document.get_id()).as_str(),
+            format!("Processed {}/{}", package_name, document.get_id()).as_str(),
             start,
         );
-    }
-    if process_status.is_err() {
+    } else {
         fastn_core::utils::print_error(
-            format!("Failed {}/{}", package_name.as_str(), document.get_id()).as_str(),
+            format!("Failed {}/{}", package_name, document.get_id()).as_str(),
             start,
         );
-        return process_status;
     }
-    Ok(())
 }
 
-fn is_cached<'a>(
-    cache: Option<&'a mut cache::Cache>,
-    doc: &fastn_core::Document,
-    file_path: &str,
-) -> (Option<&'a mut cache::Cache>, bool) {
-    let cache: &mut cache::Cache = match cache {
-        Some(c) => c,
-        None => {
-            // println!("cache miss: no have cache");
-            return (cache, false);
-        }
-    };
-
+fn is_cached(cache: &mut cache::Cache, doc: &fastn_core::Document, file_path: &str) -> bool {
     let id = remove_extension(doc.id.as_str());
 
     let cached_doc: cache::Document = match cache.documents.get(id.as_str()).cloned() {
         Some(cached_doc) => cached_doc,
         None => {
             // println!("cache miss: no cache entry for {}", id.as_str());
-            return (Some(cache), false);
+            return false;
         }
     };
 
@@ -537,7 +694,7 @@ fn is_cached<'a>(
         Some(doc_hash) => doc_hash,
         None => {
             // println!("cache miss: document not present in .build: {}", file_path);
-            return (Some(cache), false);
+            return false;
         }
     };
 
@@ -545,47 +702,72 @@ fn is_cached<'a>(
 
     if doc_hash != &cached_doc.html_checksum {
         // println!("cache miss: html file checksums don't match");
-        return (Some(cache), false);
+        return false;
     }
 
     let file_checksum = match cache.file_checksum.get(id.as_str()).cloned() {
         Some(file_checksum) => file_checksum,
         None => {
             // println!("cache miss: no cache entry for {}", id.as_str());
-            return (Some(cache), false);
+            return false;
         }
     };
 
     if file_checksum != fastn_core::utils::generate_hash(doc.content.as_str()) {
         // println!("cache miss: ftd file checksums don't match");
-        return (Some(cache), false);
+        return false;
     }
 
     for dep in &cached_doc.dependencies {
-        let file_checksum = match cache.file_checksum.get(dep) {
+        let file_checksum = match cached_doc.dependency_checksums.get(dep) {
             None => {
                 // println!("cache miss: file {} not present in cache", dep);
-                return (Some(cache), false);
+                return false;
             }
             Some(file_checksum) => file_checksum.clone(),
         };
 
-        let current_hash = match cache.get_file_hash(dep.as_str()) {
+        let current_hash = match cache.get_file_hash(format!("{dep}/").as_str()) {
             Ok(hash) => hash,
             Err(_) => {
                 // println!("cache miss: dependency {} not present current folder", dep);
-                return (Some(cache), false);
+                return false;
             }
         };
 
         if file_checksum != current_hash {
             // println!("cache miss: dependency {} checksums don't match", dep);
-            return (Some(cache), false);
+            return false;
         }
     }
 
     // println!("cache hit");
-    (Some(cache), true)
+    true
+}
+
+/// The modules of the current package `id` imported while it was rendered, named like the keys
+/// of `cache::Cache::documents`. Modules of other packages and virtual modules are left out, they
+/// are not built with the package.
+fn own_dependencies(package_name: &str, id: &str, dependencies: Vec<String>) -> Vec<String> {
+    let id = remove_extension(id);
+    let mut own = vec![];
+    for dep in dependencies {
+        if is_virtual_dep(dep.as_str()) || !dep.starts_with(&format!("{}/", package_name)) {
+            continue;
+        }
+        let dep = match get_dependency_name_without_package_name(package_name, dep.as_str()) {
+            dep if dep.is_empty() => "index".to_string(),
+            dep => dep,
+        };
+        // `-/` modules are generated by fastn, like the virtual ones
+        if dep.starts_with("-/") {
+            continue;
+        }
+        if dep != id && !own.contains(&dep) {
+            own.push(dep);
+        }
+    }
+    own
 }
 
 fn remove_extension(id: &str) -> String {
@@ -604,7 +786,7 @@ async fn handle_file_(
     ignore_failed: bool,
     test: bool,
     build_static_files: bool,
-    cache: Option<&mut cache::Cache>,
+    cache: Option<&antidote::Mutex<cache::Cache>>,
 ) -> fastn_core::Result<()> {
     match document {
         fastn_core::File::Ftd(doc) => {
@@ -616,9 +798,10 @@ async fn handle_file_(
                 fastn_core::utils::replace_last_n(doc.id.as_str(), 1, ".ftd", "/index.html")
             };
 
-            let (cache, is_cached) = is_cached(cache, doc, file_path.as_str());
-            if is_cached {
-                return Ok(());
+            if let Some(cache) = cache {
+                if is_cached(&mut cache.lock(), doc, file_path.as_str()) {
+                    return Ok(());
+                }
             }
 
             fastn_core::utils::copy(
@@ -638,6 +821,7 @@ async fn handle_file_(
                 let mut req_config =
                     fastn_core::RequestConfig::new(config, &req, doc.id.as_str(), base_url);
                 req_config.current_document = Some(document.get_id().to_string());
+                req_config.collect_page_text = true;
 
                 fastn_core::package::package_doc::process_ftd(
                     &mut req_config,
@@ -648,19 +832,35 @@ async fn handle_file_(
                     file_path.as_str(),
                 )
                 .await
+                .map(|r| {
+                    (
+                        r,
+                        req_config.page_text.take(),
+                        std::mem::take(&mut req_config.dependencies_during_render),
+                    )
+                })
             };
 
             match (resp, ignore_failed) {
-                (Ok(r), _) => {
-                    // TODO: what to do with dependencies?
-                    // let dependencies = req_config.dependencies_during_render;
-                    let dependencies = vec![];
+                (Ok((r, page_text, dependencies)), _) => {
                     if let Some(cache) = cache {
+                        let mut cache = cache.lock();
+                        let dependencies =
+                            own_dependencies(&config.package.name, doc.id.as_str(), dependencies);
+                        let dependency_checksums = dependencies
+                            .iter()
+                            .filter_map(|dep| {
+                                let hash = cache.get_file_hash(format!("{dep}/").as_str()).ok()?;
+                                Some((dep.to_string(), hash))
+                            })
+                            .collect();
                         cache.documents.insert(
                             remove_extension(doc.id.as_str()),
                             cache::Document {
                                 html_checksum: r.checksum(),
                                 dependencies,
+                                dependency_checksums,
+                                page_text,
                             },
                         );
                         cache.file_checksum.insert(
@@ -716,12 +916,13 @@ pub async fn default_build_files(
     ds: &fastn_ds::DocumentStore,
 ) -> fastn_core::Result<()> {
     if ftd_edition.is_2023() {
-        let default_ftd_js_content = ftd::js::all_js_without_test(package_name);
-        let hashed_ftd_js_name = fastn_core::utils::hashed_default_ftd_js(package_name);
-        let save_default_ftd_js = base_path.join(hashed_ftd_js_name);
-        fastn_core::utils::update(&save_default_ftd_js, default_ftd_js_content.as_bytes(), ds)
-            .await
-            .ok();
+        // every bundle is written, pages that are not rebuilt may use any of them
+        for runtime_js in fastn_core::utils::runtime_js(package_name) {
+            let save_runtime_js = base_path.join(runtime_js.name.as_str());
+            fastn_core::utils::update(&save_runtime_js, runtime_js.content.as_bytes(), ds)
+                .await
+                .ok();
+        }
 
         let markdown_js_content = ftd::markdown_js();
         let hashed_markdown_js_name = fastn_core::utils::hashed_markdown_js();
@@ -853,3 +1054,95 @@ async fn process_static(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    /// The ids of the documents in every wave of `plan`, `dependencies` are the imports recorded
+    /// in the cache for each document.
+    fn waves(plan: &[&str], dependencies: &[(&str, &[&str])]) -> Vec<Vec<String>> {
+        let documents: std::collections::BTreeMap<String, fastn_core::File> = plan
+            .iter()
+            .map(|id| {
+                (
+                    id.to_string(),
+                    fastn_core::File::Ftd(fastn_core::Document {
+                        package_name: "foo.com".to_string(),
+                        id: id.to_string(),
+                        content: String::new(),
+                        parent_path: fastn_ds::Path::new("."),
+                    }),
+                )
+            })
+            .collect();
+        let cache = super::cache::Cache {
+            build_content: Default::default(),
+            ftd_cache: Default::default(),
+            documents: dependencies
+                .iter()
+                .map(|(name, dependencies)| {
+                    (
+                        name.to_string(),
+                        super::cache::Document {
+                            html_checksum: String::new(),
+                            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+                            dependency_checksums: Default::default(),
+                            page_text: None,
+                        },
+                    )
+                })
+                .collect(),
+            file_checksum: Default::default(),
+        };
+        let plan: Vec<String> = plan.iter().map(|id| id.to_string()).collect();
+
+        super::build_waves(&plan, &documents, &cache, "foo.com")
+            .into_iter()
+            .map(|wave| wave.iter().map(|d| d.get_id().to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn dependencies_are_in_earlier_waves() {
+        assert_eq!(
+            waves(
+                &["index.ftd", "blog/index.ftd", "lib.ftd", "about.ftd"],
+                &[
+                    ("index", &["lib", "blog"]),
+                    ("blog", &["lib"]),
+                    ("about", &["foo.com/lib/"]),
+                ],
+            ),
+            vec![
+                vec!["lib.ftd"],
+                vec!["blog/index.ftd", "about.ftd"],
+                vec!["index.ftd"],
+            ]
+        );
+    }
+
+    #[test]
+    fn cycles_terminate() {
+        assert_eq!(
+            waves(
+                &["a.ftd", "b.ftd", "c.ftd", "d.ftd"],
+                &[("a", &["b"]), ("b", &["c"]), ("c", &["a"]), ("d", &["d"])],
+            ),
+            vec![vec!["c.ftd", "d.ftd"], vec!["b.ftd"], vec!["a.ftd"]]
+        );
+    }
+
+    #[test]
+    fn waves_keep_the_order_of_the_plan() {
+        let plan = ["e.ftd", "b.ftd", "d.ftd", "a.ftd", "c.ftd"];
+        let dependencies: &[(&str, &[&str])] = &[("d", &["a"]), ("c", &["a"])];
+        assert_eq!(
+            waves(&plan, dependencies),
+            vec![vec!["e.ftd", "b.ftd", "a.ftd"], vec!["d.ftd", "c.ftd"]]
+        );
+        // dependencies that are not planned are already built
+        assert_eq!(
+            waves(&["d.ftd", "c.ftd"], dependencies),
+            vec![vec!["d.ftd", "c.ftd"]]
+        );
+    }
+}
@@ -20,6 +20,117 @@ pub async fn post_build_check(config: &fastn_core::Config) -> fastn_core::Result
     Ok(())
 }
 
+/// Type checks every `.ftd` document of the package with `ftd-tc`, and prints all the errors
+/// found. Imports are resolved from the package and its dependencies, but processors are not run
+/// and nothing is rendered.
+pub async fn type_check(config: &fastn_core::Config) -> fastn_core::Result<()> {
+    let mut checked = 0;
+    let mut errors = vec![];
+    for file in config.get_files(&config.package).await? {
+        let fastn_core::File::Ftd(document) = file else {
+            continue;
+        };
+        if document.id == "FASTN.ftd" {
+            continue;
+        }
+        checked += 1;
+        errors.extend(type_check_document(config, &document).await);
+    }
+
+    for error in errors.iter() {
+        eprintln!("{error}");
+    }
+    if !errors.is_empty() {
+        return Err(fastn_core::Error::GenericError(format!(
+            "found {} type errors in {checked} documents",
+            errors.len()
+        )));
+    }
+
+    println!("Type checked {checked} documents, no errors found.");
+    Ok(())
+}
+
+async fn type_check_document(
+    config: &fastn_core::Config,
+    document: &fastn_core::Document,
+) -> Vec<fastn_issues::diagnostic::Diagnostic> {
+    let name = document.id_with_package();
+    let name = name.trim_matches('/');
+    let (body, line_number) = match fastn_core::doc::prefixed_body(config, document) {
+        Ok(v) => v,
+        Err(e) => return vec![fastn_core::doc::diagnostic(&e, document)],
+    };
+
+    let mut lib = fastn_core::RequestConfig::new(
+        config,
+        &fastn_core::http::Request::default(),
+        document.id.as_str(),
+        "/",
+    );
+    lib.current_document = Some(document.id.to_string());
+    lib.module_package_map
+        .insert(name.to_string(), config.package.name.to_string());
+
+    let mut errors = vec![];
+    let mut s = ftd_tc::State::from_document(
+        body.as_str(),
+        ftd_tc::DocumentID::new(name, document.id.as_str()),
+        line_number,
+    );
+    let state = loop {
+        match s {
+            ftd_tc::TCState::StuckOnImport {
+                document: module,
+                caller,
+                state,
+            } => {
+                // `resolve_import_2022()` does not use the interpreter state
+                let mut interpreter_state = ftd::interpreter::InterpreterState::default();
+                s = match fastn_core::doc::resolve_import_2022(
+                    &mut lib,
+                    &mut interpreter_state,
+                    module.as_str(),
+                    caller.as_str(),
+                )
+                .await
+                {
+                    Ok((source, path, foreign_variable, _foreign_function, line_number)) => state
+                        .continue_after_import(
+                            ftd_tc::DocumentID::new(module.as_str(), path.as_str()),
+                            source.as_str(),
+                            line_number,
+                            foreign_variable,
+                        ),
+                    Err(e) => {
+                        errors.push(
+                            fastn_issues::diagnostic::Diagnostic::new(
+                                fastn_issues::diagnostic::Code::NotFound,
+                                format!("can not import `{module}`: {e}"),
+                            )
+                            .set_location(name, 0)
+                            .set_source(document.id.as_str(), document.content.as_str()),
+                        );
+                        state.continue_after_opaque_import(module.as_str())
+                    }
+                };
+            }
+            ftd_tc::TCState::Done(state) => break state,
+        }
+    };
+
+    errors.extend(state.errors.iter().map(|e| {
+        let diagnostic = fastn_issues::diagnostic::Diagnostic::from(e);
+        if diagnostic.is_in(name) {
+            diagnostic.set_source(document.id.as_str(), document.content.as_str())
+        } else {
+            diagnostic
+        }
+    }));
+    errors.sort_by_key(|d| d.line_number);
+    errors
+}
+
 // Todo: Rewrite this code
 /*#[async_recursion::async_recursion]
 async fn check_index_in_folders(
@@ -2,6 +2,7 @@ pub async fn fmt(
     config: &fastn_core::Config,
     file: Option<&str>,
     no_indentation: bool,
+    check: bool,
 ) -> fastn_core::Result<()> {
     use colored::Colorize;
     use itertools::Itertools;
@@ -13,6 +14,7 @@ pub async fn fmt(
         .filter_map(|v| v.get_ftd_document())
         .collect_vec();
 
+    let mut unformatted = 0;
     for ftd_document in documents {
         if let Some(file) = file {
             if !ftd_document.id.eq(file) {
@@ -20,345 +22,420 @@ pub async fn fmt(
             }
         }
 
-        print!("Formatting {} ... ", ftd_document.id);
+        let formatted = format(
+            ftd_document.content.as_str(),
+            ftd_document.id.as_str(),
+            !no_indentation,
+        )
+        .map_err(|e| {
+            fastn_issues::diagnostic::Diagnostic::from(&e)
+                .set_source(ftd_document.id.as_str(), ftd_document.content.as_str())
+        })?;
+
+        if check {
+            if formatted != ftd_document.content {
+                unformatted += 1;
+                print_diff(
+                    ftd_document.id.as_str(),
+                    ftd_document.content.as_str(),
+                    formatted.as_str(),
+                );
+            }
+            continue;
+        }
 
-        let parsed_content =
-            parsed_to_sections(format!("{}\n\n", ftd_document.content.as_str()).as_str());
-        let format_sections = format_sections(parsed_content, !no_indentation);
-        config
-            .ds
-            .write_content(&ftd_document.get_full_path(), &format_sections.into_bytes())
-            .await?;
+        print!("Formatting {} ... ", ftd_document.id);
+        if formatted != ftd_document.content {
+            config
+                .ds
+                .write_content(&ftd_document.get_full_path(), &formatted.into_bytes())
+                .await?;
+        }
         println!("{}", "Done".green())
     }
 
+    if unformatted > 0 {
+        return Err(fastn_core::Error::GenericError(format!(
+            "{unformatted} files are not formatted, run `fastn fmt` to format them"
+        )));
+    }
+
     Ok(())
 }
 
-#[derive(Debug)]
-struct Section {
-    value: String,
-    kind: SectionKind,
+/// Formats the document read from stdin and prints it to stdout, for editors to format on save.
+pub fn fmt_stdin(no_indentation: bool) -> fastn_core::Result<()> {
+    let source = std::io::read_to_string(std::io::stdin())?;
+    let formatted = format(source.as_str(), "<stdin>", !no_indentation).map_err(|e| {
+        fastn_issues::diagnostic::Diagnostic::from(&e).set_source("<stdin>", source.as_str())
+    })?;
+    print!("{formatted}");
+    Ok(())
+}
+
+fn print_diff(id: &str, original: &str, formatted: &str) {
+    let patch = diffy::create_patch(original, formatted).to_string();
+    // `diffy` names the files `original` and `modified`
+    let hunks = patch
+        .split_once("+++ modified\n")
+        .map_or(patch.as_str(), |(_, hunks)| hunks);
+    println!("--- a/{id}\n+++ b/{id}\n{hunks}");
+}
+
+/// Formats an ftd document. Sections are printed from what `ftd_p1` parsed, with one empty
+/// line between them, and nested sections are indented with a tab if `indentation` is set.
+/// Comments, and the values of headers and bodies are kept as written.
+///
+/// The formatted document is parsed again, and it is an error if it does not parse to the same
+/// sections, so formatting never changes the meaning of a document.
+pub fn format(source: &str, doc_id: &str, indentation: bool) -> ftd_p1::Result<String> {
+    let sections = ftd_p1::parse(source, doc_id)?;
+    let mut starts = std::collections::HashMap::new();
+    section_starts(sections.as_slice(), 0, &mut starts);
+
+    let lines: Vec<Line> = source
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| Line::new(line, starts.get(&(i + 1)).copied()))
+        .collect();
+    let depths = depths(lines.as_slice());
+
+    let mut output: Vec<String> = vec![];
+    for (i, (line, (depth, base))) in lines.iter().zip(depths).enumerate() {
+        let indent = if indentation {
+            "\t".repeat(depth)
+        } else {
+            String::new()
+        };
+        let next = lines[i + 1..]
+            .iter()
+            .find(|l| !matches!(l, Line::Empty | Line::Comment(_)));
+        let starts_block = matches!(next, None | Some(Line::Structural { .. }));
+
+        match line {
+            // many empty lines before a section are kept as one, there are none at the start
+            Line::Empty => {
+                if starts_block && matches!(output.last().map(String::as_str), Some("") | None) {
+                    continue;
+                }
+                output.push(String::new());
+            }
+            // sections are separated by an empty line, comments before a section stay with it
+            Line::Comment(comment) => {
+                if starts_block && follows_section(lines.as_slice(), i) {
+                    output.push(String::new());
+                }
+                output.push(format!("{indent}{comment}"));
+            }
+            Line::Structural { text, .. } => {
+                if follows_section(lines.as_slice(), i) {
+                    output.push(String::new());
+                }
+                output.push(format!("{indent}{text}"));
+            }
+            Line::Content(content) => {
+                // the indentation of the section is replaced, the rest is part of the value
+                let content = strip_indentation(content, base);
+                output.push(format!("{indent}{content}"));
+            }
+        }
+    }
+    while output.last().is_some_and(|l| l.is_empty()) {
+        output.pop();
+    }
+    let formatted = format!("{}\n", output.join("\n"));
+
+    let formatted_sections = ftd_p1::parse(formatted.as_str(), doc_id)?;
+    if !same_sections(sections.as_slice(), formatted_sections.as_slice()) {
+        return Err(ftd_p1::Error::ParseError {
+            message: "formatting would change the meaning of the document".to_string(),
+            doc_id: doc_id.to_string(),
+            line_number: 0,
+        });
+    }
+
+    Ok(formatted)
 }
 
 #[derive(Debug)]
-enum SectionKind {
-    Comment,
+enum Line<'a> {
     Empty,
-    Section {
+    Comment(&'a str),
+    /// The first line of a section, of a header that is a section, or an `-- end:` line.
+    Structural {
+        text: String,
+        /// The depth of the section, `None` for headers and `-- end:` lines, whose depth
+        /// depends on the section they belong to.
+        depth: Option<usize>,
+        /// The name of the section or header, or the name of the section an `-- end:` ends.
         name: String,
         end: bool,
-        sub_sections: Vec<Section>,
+        indentation: usize,
     },
+    Content(&'a str),
 }
 
-impl Section {
-    fn new_comment(value: &str) -> Section {
-        Section {
-            value: value.to_string(),
-            kind: SectionKind::Comment,
+impl<'a> Line<'a> {
+    fn new(line: &'a str, start: Option<(usize, &ftd_p1::Section)>) -> Line<'a> {
+        let trimmed = line.trim();
+        let indentation = line.len() - line.trim_start().len();
+        if let Some((depth, section)) = start {
+            return Line::Structural {
+                text: section_line(section, trimmed),
+                depth: Some(depth),
+                name: section.name.to_string(),
+                end: false,
+                indentation,
+            };
         }
-    }
 
-    fn new_empty(value: &str) -> Section {
-        Section {
-            value: value.to_string(),
-            kind: SectionKind::Empty,
+        if trimmed.is_empty() {
+            return Line::Empty;
+        }
+        if trimmed.starts_with(";;") {
+            return Line::Comment(trimmed);
+        }
+        let Some(rest) = trimmed
+            .strip_prefix("-- ")
+            .or_else(|| trimmed.strip_prefix("/-- "))
+        else {
+            return Line::Content(line);
+        };
+        let (name, value) = rest.split_once(':').unwrap_or((rest, ""));
+        let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        let value = value.trim();
+        if name == "end" {
+            return Line::Structural {
+                text: format!("-- end: {value}"),
+                depth: None,
+                name: value.trim_start_matches('$').to_string(),
+                end: true,
+                indentation,
+            };
         }
-    }
 
-    fn new_section(name: &str, value: &str) -> Section {
-        Section {
-            value: value.to_string(),
-            kind: SectionKind::Section {
-                name: name.to_string(),
-                end: false,
-                sub_sections: vec![],
+        let prefix = if trimmed.starts_with('/') {
+            "/-- "
+        } else {
+            "-- "
+        };
+        Line::Structural {
+            text: match value {
+                "" => format!("{prefix}{name}:"),
+                value => format!("{prefix}{name}: {value}"),
             },
+            depth: None,
+            name,
+            end: false,
+            indentation,
         }
     }
 }
 
-fn format_sections(sections: Vec<Section>, indentation: bool) -> String {
-    let mut output = vec![];
+/// The line of each section, and the depth of the section in the document.
+fn section_starts<'a>(
+    sections: &'a [ftd_p1::Section],
+    depth: usize,
+    starts: &mut std::collections::HashMap<usize, (usize, &'a ftd_p1::Section)>,
+) {
     for section in sections {
-        output.push(format_section(
-            &section,
-            if indentation { Some(-1) } else { None },
-        ))
-    }
-    format!("{}\n", output.join("\n").trim_end())
-}
-
-fn format_section(section: &Section, indentation: Option<i32>) -> String {
-    match &section.kind {
-        SectionKind::Comment => add_indentation(section.value.as_str(), indentation),
-        SectionKind::Empty => section.value.to_string(),
-        SectionKind::Section {
-            name,
-            end,
-            sub_sections,
-        } => format_section_kind(
-            name.as_str(),
-            *end,
-            sub_sections.as_slice(),
-            section.value.as_str(),
-            indentation,
-        ),
+        starts.insert(section.line_number, (depth, section));
+        for header in section.headers.0.iter() {
+            // `-- section.header:` is one level deeper than the section, and its sections
+            // another level deeper
+            if let ftd_p1::Header::Section(header) = header {
+                section_starts(header.section.as_slice(), depth + 2, starts);
+            }
+        }
+        section_starts(section.sub_sections.as_slice(), depth + 1, starts);
     }
 }
 
-fn format_section_kind(
-    section_name: &str,
-    end: bool,
-    sub_sections: &[Section],
-    value: &str,
-    indentation: Option<i32>,
-) -> String {
-    let mut output = vec![add_indentation(value, indentation)];
-    for section in sub_sections {
-        output.push(format_section(section, indentation.map(|v| v + 1)));
-    }
-    if end {
-        output.push(add_indentation(
-            format!("-- end: {section_name}").as_str(),
-            indentation,
-        ));
+/// The first line of a section, as parsed, followed by the caption as written. The parsed
+/// caption has no inline comments.
+fn section_line(section: &ftd_p1::Section, line: &str) -> String {
+    let mut output = if section.is_commented { "/-- " } else { "-- " }.to_string();
+    if let Some(kind) = section.kind.as_ref() {
+        output.push_str(kind.as_str());
+        output.push(' ');
     }
-    output.join("\n")
-}
-
-fn add_indentation(input: &str, indentation: Option<i32>) -> String {
-    let indentation = match indentation {
-        Some(indentation) if indentation > 0 => indentation,
-        _ => {
-            return input.to_string();
-        }
-    };
-    let mut value = vec![];
-    for i in input.split('\n') {
-        value.push(format!("{}{}", "\t".repeat(indentation as usize), i));
+    output.push_str(section.name.as_str());
+    output.push(':');
+    let caption = line
+        .split_once(':')
+        .map_or("", |(_, caption)| caption.trim());
+    if !caption.is_empty() {
+        output.push(' ');
+        output.push_str(caption);
     }
-    value.join("\n")
+    output
 }
 
-fn parsed_to_sections(input: &str) -> Vec<Section> {
-    let mut sections = vec![];
-    let mut input = input.to_string();
-    while !input.is_empty() {
-        if end_section(&mut input, &mut sections) {
-            continue;
-        } else if let Some(empty_section) = empty_section(&mut input) {
-            sections.push(empty_section);
-        } else if let Some(comment_section) = comment_section(&mut input) {
-            sections.push(comment_section);
-        } else if let Some(section) = section(&mut input) {
-            sections.push(section);
-        } else {
-            panic!(
-                "`{}`: can't parse",
-                input
-                    .split_once('\n')
-                    .map(|(v, _)| v.to_string())
-                    .unwrap_or_else(|| input.to_string())
-            );
-        }
+/// The depth of every line, and the indentation of the line starting the section the line is
+/// in, in the source.
+fn depths(lines: &[Line]) -> Vec<(usize, usize)> {
+    // the sections and headers that can still be ended, with their depth
+    let mut open: Vec<(&str, usize)> = vec![];
+    let mut current = (0, 0);
+    let mut depths: Vec<Option<(usize, usize)>> = vec![];
+    for line in lines {
+        let depth = match line {
+            Line::Structural {
+                depth: Some(depth),
+                name,
+                indentation,
+                ..
+            } => {
+                open.retain(|(_, d)| d < depth);
+                open.push((name.as_str(), *depth));
+                current = (*depth, *indentation);
+                Some(current)
+            }
+            Line::Structural {
+                depth: None,
+                name,
+                end: false,
+                indentation,
+                ..
+            } => {
+                // a header is one level deeper than the section it belongs to
+                let owner = open
+                    .iter()
+                    .rposition(|(n, _)| name.starts_with(format!("{n}.").as_str()));
+                let depth = owner.map_or(0, |i| open[i].1 + 1);
+                open.truncate(owner.map_or(0, |i| i + 1));
+                open.push((name.as_str(), depth));
+                current = (depth, *indentation);
+                Some(current)
+            }
+            Line::Structural {
+                depth: None,
+                name,
+                end: true,
+                indentation,
+                ..
+            } => {
+                let ended = open
+                    .iter()
+                    .rposition(|(n, _)| n.trim_start_matches('$') == name.as_str());
+                let depth = ended.map_or(0, |i| open[i].1);
+                open.truncate(ended.unwrap_or(0));
+                current = (depth, *indentation);
+                Some(current)
+            }
+            Line::Content(_) => Some(current),
+            Line::Empty | Line::Comment(_) => None,
+        };
+        depths.push(depth);
     }
 
-    sections
-}
-
-fn end_section(input: &mut String, sections: &mut Vec<Section>) -> bool {
-    let mut remaining = None;
-    let first_line = if let Some((first_line, rem)) = input.split_once('\n') {
-        remaining = Some(rem.to_string());
-        first_line.to_string()
-    } else {
-        input.to_string()
-    };
-
-    let section_name = if let Some(section_name) = end_section_name(first_line.as_str()) {
-        section_name
-    } else {
-        return false;
-    };
-
-    *input = remaining.unwrap_or_default();
-
-    let mut sub_sections = vec![];
-    while let Some(mut section) = sections.pop() {
-        match &mut section.kind {
-            SectionKind::Section {
-                name,
-                end,
-                sub_sections: s,
-            } if section_name
-                .trim_start_matches('$')
-                .eq(name.trim_start_matches('$'))
-                && !*end =>
-            {
-                *end = true;
-                *s = sub_sections;
-                sections.push(section);
-                return true;
+    // comments are indented like the line they are followed by, inside the section an
+    // `-- end:` line ends
+    let mut next = (0, 0);
+    let mut output = vec![(0, 0); lines.len()];
+    for (i, line) in lines.iter().enumerate().rev() {
+        match (depths[i], line) {
+            (Some(depth), Line::Structural { end: true, .. }) => {
+                output[i] = depth;
+                next = (depth.0 + 1, depth.1);
             }
-            _ => {
-                sub_sections.insert(0, section);
+            (Some(depth), _) => {
+                output[i] = depth;
+                next = depth;
             }
+            (None, _) => output[i] = next,
         }
     }
-
-    panic!("cannot find section {} to end", section_name)
+    output
 }
 
-fn end_section_name(input: &str) -> Option<String> {
-    use itertools::Itertools;
-
-    let input = input.split_whitespace().join(" ");
-    input.strip_prefix("-- end:").map(|v| v.trim().to_string())
+/// If the line before `i` is a section, header or value line, and not an empty line or a comment.
+fn follows_section(lines: &[Line], i: usize) -> bool {
+    matches!(
+        i.checked_sub(1).map(|p| &lines[p]),
+        Some(Line::Structural { .. } | Line::Content(_))
+    )
 }
 
-fn section(input: &mut String) -> Option<Section> {
-    let section_name = get_section_name(input)?;
-    let mut value = vec![];
-    let mut first_time_encounter_section = true;
-    let mut leading_spaces_count = 0;
-    while !input.is_empty() {
-        let (first_line, remaining) = match input.split_once('\n') {
-            Some((first_line, remaining)) => (first_line.to_string(), remaining.to_string()),
-            None => (input.to_string(), String::new()),
-        };
-        let mut trimmed_line = first_line.trim_start().to_string();
-        let current_leading_spaces_count = first_line.len() - trimmed_line.len();
-
-        if trimmed_line.starts_with("-- ") || trimmed_line.starts_with("/-- ") {
-            // If the first_time_encounter_section then store the indentation here in the
-            // `leading_spaces_count`
-            // Also, don't break code, for this is section definition line.
-            if first_time_encounter_section {
-                first_time_encounter_section = false;
-                *input = remaining.to_string();
-                value.push(trimmed_line.trim().to_string());
-                leading_spaces_count = current_leading_spaces_count;
-                continue;
-            }
-            *input = format!("{first_line}\n{remaining}");
-            break;
-        }
-
-        // Use the indentation saved in `leading_spaces_count` and add indentation upto the
-        // extra space left when deducting the `leading_spaces_count`. This ensures the section body
-        // keeps the indentation as intended by user
-        if !trimmed_line.is_empty() {
-            trimmed_line = format!(
-                "{}{}",
-                " ".repeat(if current_leading_spaces_count > leading_spaces_count {
-                    current_leading_spaces_count - leading_spaces_count
-                } else {
-                    0
-                }),
-                trimmed_line.trim()
-            );
-        }
-        value.push(trimmed_line);
-        *input = remaining.to_string();
-    }
+fn strip_indentation(line: &str, indentation: usize) -> &str {
+    let trimmed = line.trim_start();
+    let leading = line.len() - trimmed.len();
+    &line[std::cmp::min(leading, indentation)..]
+}
 
-    if !value.is_empty() {
-        let mut value = value.join("\n").to_string();
-        remove_comment_from_section_value_if_its_comment_for_other_section(input, &mut value);
-        Some(Section::new_section(section_name.as_str(), value.as_str()))
-    } else {
-        None
-    }
+fn same_sections(a: &[ftd_p1::Section], b: &[ftd_p1::Section]) -> bool {
+    without_line_numbers(a) == without_line_numbers(b)
 }
 
-fn remove_comment_from_section_value_if_its_comment_for_other_section(
-    input: &mut String,
-    value: &mut String,
-) {
-    if !value.trim().is_empty() {
-        if let Some((v, probable_comment_section)) = value.rsplit_once("\n\n") {
-            let mut probable_comment_section = probable_comment_section.to_string();
-            if let Some(comment_section) = comment_section(&mut probable_comment_section) {
-                if probable_comment_section.trim().is_empty() {
-                    *input = format!("{}\n{}", comment_section.value, input);
-                    *value = format!("{v}\n");
+/// `Section::without_line_number()` keeps the line numbers of the fields of block headers.
+fn without_line_numbers(sections: &[ftd_p1::Section]) -> serde_json::Value {
+    fn strip(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.remove("line_number");
+                // the body of a block header is its value and its line number
+                if let Some(serde_json::Value::Array(body)) = map.get_mut("body") {
+                    body.truncate(1);
                 }
+                map.values_mut().for_each(strip);
             }
+            serde_json::Value::Array(values) => values.iter_mut().for_each(strip),
+            _ => {}
         }
     }
-}
 
-fn get_section_name(input: &str) -> Option<String> {
-    use itertools::Itertools;
+    let mut value = serde_json::to_value(sections).unwrap_or_default();
+    strip(&mut value);
+    value
+}
 
-    let first_line = if let Some((first_line, _)) = input.split_once('\n') {
-        first_line.trim().to_string()
-    } else {
-        input.trim().to_string()
-    };
-    if !first_line.starts_with("-- ") && !first_line.starts_with("/-- ") {
-        None
-    } else if let Some((section_name_kind, _)) = first_line.split_once(':') {
-        Some(
-            section_name_kind
-                .split_whitespace()
-                .join(" ")
-                .rsplit_once(' ')
-                .map(|(_, v)| v.to_string())
-                .unwrap_or_else(|| section_name_kind.to_string()),
-        )
-    } else {
-        None
+#[cfg(test)]
+mod tests {
+    #[track_caller]
+    fn p(source: &str, expected: &str) {
+        let formatted = super::format(source, "foo", true).unwrap();
+        assert_eq!(formatted, expected);
+        // formatting is idempotent
+        assert_eq!(
+            super::format(formatted.as_str(), "foo", true).unwrap(),
+            expected
+        );
     }
-}
 
-fn empty_section(input: &mut String) -> Option<Section> {
-    let mut value = vec![];
-    while !input.is_empty() {
-        let (first_line, remaining) = match input.split_once('\n') {
-            Some((first_line, remaining)) => (first_line.to_string(), remaining.to_string()),
-            None => (input.to_string(), String::new()),
-        };
-        let trimmed_line = first_line.trim().to_string();
-        if !trimmed_line.is_empty() {
-            *input = format!("{first_line}\n{remaining}");
-            break;
-        }
-        value.push(trimmed_line);
-        *input = remaining.to_string();
+    #[test]
+    fn sections() {
+        p(
+            "-- import:   lib\n-- ftd.text:hello\ncolor: red\n\n\n\n-- ftd.text: world\n\n",
+            "-- import: lib\n\n-- ftd.text: hello\ncolor: red\n\n-- ftd.text: world\n",
+        );
     }
 
-    if !value.is_empty() {
-        Some(Section::new_empty(value.join("\n").as_str()))
-    } else {
-        None
+    #[test]
+    fn nested() {
+        p(
+            "-- ftd.column:\npadding.px: 10\n\n-- ftd.text: hi\n\n  -- ftd.row:\n   -- end: ftd.row\n-- end:   ftd.column\n",
+            "-- ftd.column:\npadding.px: 10\n\n\t-- ftd.text: hi\n\n\t-- ftd.row:\n\n\t-- end: ftd.row\n\n-- end: ftd.column\n",
+        );
     }
-}
 
-fn comment_section(input: &mut String) -> Option<Section> {
-    let mut value = vec![];
+    #[test]
+    fn comments_and_body() {
+        p(
+            ";; about the card\n-- component card: ;; a card\ncaption title:\n\n;; the text\n-- ftd.text: $card.title\n\n  some body ;; kept\n\n\n    indented\n\n-- end: card\n",
+            ";; about the card\n-- component card: ;; a card\ncaption title:\n\n\t;; the text\n\t-- ftd.text: $card.title\n\n\t  some body ;; kept\n\n\n\t    indented\n\n-- end: card\n",
+        );
+    }
 
-    while !input.is_empty() {
-        let (first_line, remaining) = match input.split_once('\n') {
-            Some((first_line, remaining)) => (first_line.to_string(), remaining.to_string()),
-            None => (input.to_string(), String::new()),
-        };
-        let trimmed_line = first_line.trim().to_string();
-        if trimmed_line.starts_with("-- ")
-            || trimmed_line.starts_with("/-- ")
-            || !trimmed_line.starts_with(";;")
-        {
-            *input = format!("{first_line}\n{remaining}");
-            break;
-        }
-        value.push(trimmed_line);
-        *input = remaining.to_string();
+    #[test]
+    fn headers() {
+        p(
+            "-- ftd.column foo:\n\n-- foo.bar:\n\n-- ftd.text: in header\n\n-- end: foo.bar\n\n-- end: foo\n\n-- person p:\nname: a\n\n-- p.address:\ncity: x\n",
+            "-- ftd.column foo:\n\n\t-- foo.bar:\n\n\t\t-- ftd.text: in header\n\n\t-- end: foo.bar\n\n-- end: foo\n\n-- person p:\nname: a\n\n\t-- p.address:\n\tcity: x\n",
+        );
     }
-    if !value.is_empty() {
-        Some(Section::new_comment(value.join("\n").as_str()))
-    } else {
-        None
+
+    #[test]
+    fn invalid() {
+        assert!(super::format("-- ftd.text: hi\n-- end: ftd.column\n", "foo", true).is_err());
     }
 }
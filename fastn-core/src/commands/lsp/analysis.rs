@@ -0,0 +1,420 @@
+/// What `fastn lsp` knows about an open document: the errors found while interpreting it, and
+/// the things it could resolve before stopping.
+pub(crate) struct Analysis {
+    pub diagnostics: Vec<Diagnostic>,
+    pub symbols: Option<Symbols>,
+}
+
+pub(crate) struct Diagnostic {
+    /// 1-based, as reported by ftd, `0` if the error has no location.
+    pub line_number: usize,
+    pub message: String,
+    pub code: fastn_issues::diagnostic::Code,
+}
+
+pub(crate) struct Symbols {
+    /// The name of the document in the interpreter, e.g. `my-package/blog/`.
+    pub name: String,
+    pub bag: indexmap::IndexMap<String, ftd::interpreter::Thing>,
+    pub parsed: ftd::Map<ftd::interpreter::ParsedDocument>,
+}
+
+/// Interprets `content`, the unsaved content of the file at `path`, the same way `fastn serve`
+/// interprets documents: with the auto imports of the package and imports resolved from the
+/// package and its dependencies.
+///
+/// Processors are not run, they can talk to databases and other services on every key stroke.
+/// The analysis stops at the first processor, with the symbols resolved so far.
+pub(crate) async fn analyse(
+    config: &fastn_core::Config,
+    path: &fastn_ds::Path,
+    content: &str,
+) -> Analysis {
+    let id = match path.strip_prefix(&config.ds.root()) {
+        Some(id) => id.to_string().replace(std::path::MAIN_SEPARATOR, "/"),
+        None => path.file_name().unwrap_or_default(),
+    };
+    let document = fastn_core::Document {
+        package_name: config.package.name.to_string(),
+        id,
+        content: content.to_string(),
+        parent_path: config.ds.root(),
+    };
+    let name = document.id_with_package();
+
+    let mut symbols = None;
+    let mut diagnostics = match fastn_core::doc::prefixed_body(config, &document) {
+        Ok((body, line_number)) => {
+            // the interpreter stops at the first syntax error, all of them are reported at once
+            let (_, errors) = ftd_p1::parse_recovering_with_line_number(
+                body.as_str(),
+                name.as_str(),
+                line_number,
+            );
+            if errors.is_empty() {
+                let interpreted = interpret(
+                    config,
+                    &document,
+                    name.as_str(),
+                    body.as_str(),
+                    line_number,
+                    &mut symbols,
+                )
+                .await;
+                match interpreted {
+                    Ok(()) => vec![],
+                    Err(e) => vec![diagnostic(name.as_str(), &e, symbols.as_ref())],
+                }
+            } else {
+                errors
+                    .into_iter()
+                    .map(|e| diagnostic(name.as_str(), &e.into(), None))
+                    .collect()
+            }
+        }
+        Err(e) => vec![diagnostic(name.as_str(), &e, None)],
+    };
+    // lines of the auto imports prefixed to the document are not in the file
+    let lines = content.split('\n').count();
+    for diagnostic in diagnostics.iter_mut() {
+        if diagnostic.line_number > lines {
+            diagnostic.line_number = 0;
+        }
+    }
+
+    Analysis {
+        diagnostics,
+        symbols,
+    }
+}
+
+async fn interpret(
+    config: &fastn_core::Config,
+    document: &fastn_core::Document,
+    name: &str,
+    body: &str,
+    line_number: usize,
+    symbols: &mut Option<Symbols>,
+) -> ftd::interpreter::Result<()> {
+    let mut lib = fastn_core::RequestConfig::new(
+        config,
+        &fastn_core::http::Request::default(),
+        document.id.as_str(),
+        "/",
+    );
+    lib.current_document = Some(document.id.to_string());
+    lib.module_package_map.insert(
+        name.trim_matches('/').to_string(),
+        config.package.name.to_string(),
+    );
+
+    let parsed = ftd::interpreter::ParsedDocument::parse_with_line_number(name, body, line_number)?;
+    let mut parsed_docs = ftd::Map::new();
+    parsed_docs.insert(name.to_string(), parsed.clone());
+    let mut s = ftd::interpreter::interpret_with_line_number(name, parsed)?;
+
+    loop {
+        match s {
+            ftd::interpreter::Interpreter::Done { document } => {
+                *symbols = Some(Symbols {
+                    name: name.to_string(),
+                    bag: document.data,
+                    parsed: parsed_docs,
+                });
+                return Ok(());
+            }
+            ftd::interpreter::Interpreter::StuckOnImport {
+                module,
+                state: mut st,
+                caller_module,
+            } => {
+                // keep what was resolved so far, in case this import fails
+                *symbols = Some(Symbols {
+                    name: name.to_string(),
+                    bag: st.bag.clone(),
+                    parsed: parsed_docs.clone(),
+                });
+                let (source, _path, foreign_variable, foreign_function, ignore_line_numbers) =
+                    fastn_core::doc::resolve_import_2022(
+                        &mut lib,
+                        &mut st,
+                        module.as_str(),
+                        caller_module.as_str(),
+                    )
+                    .await?;
+                let doc = ftd::interpreter::ParsedDocument::parse_with_line_number(
+                    module.as_str(),
+                    source.as_str(),
+                    ignore_line_numbers,
+                )?;
+                parsed_docs.insert(module.to_string(), doc.clone());
+                s = st.continue_after_import(
+                    module.as_str(),
+                    doc,
+                    foreign_variable,
+                    foreign_function,
+                    ignore_line_numbers,
+                )?;
+            }
+            ftd::interpreter::Interpreter::StuckOnProcessor { state, .. } => {
+                *symbols = Some(Symbols {
+                    name: name.to_string(),
+                    bag: state.bag,
+                    parsed: parsed_docs,
+                });
+                return Ok(());
+            }
+            ftd::interpreter::Interpreter::StuckOnForeignVariable {
+                state,
+                module,
+                variable,
+                caller_module,
+            } => {
+                let value = fastn_core::doc::resolve_foreign_variable2022(
+                    variable.as_str(),
+                    module.as_str(),
+                    &mut lib,
+                    "/",
+                    false,
+                    caller_module.as_str(),
+                )
+                .await?;
+                s = state.continue_after_variable(module.as_str(), variable.as_str(), value)?;
+            }
+        }
+    }
+}
+
+/// Errors in imported documents are reported on the line importing them, the client only shows
+/// diagnostics of the open document.
+fn diagnostic(name: &str, e: &ftd::interpreter::Error, symbols: Option<&Symbols>) -> Diagnostic {
+    let d = fastn_issues::diagnostic::Diagnostic::from(e);
+    match d.doc_id.as_deref() {
+        Some(doc_id) if !doc_id.is_empty() && !d.is_in(name) => {
+            let import_line = symbols
+                .and_then(|s| s.parsed.get(name))
+                .and_then(|doc| {
+                    doc.ast.iter().find_map(|ast| match ast {
+                        ftd_ast::Ast::Import(i)
+                            if i.module.trim_matches('/') == doc_id.trim_matches('/') =>
+                        {
+                            Some(i.line_number)
+                        }
+                        _ => None,
+                    })
+                })
+                .unwrap_or_default();
+            Diagnostic {
+                line_number: import_line,
+                message: format!("{doc_id}:{}: {}", d.line_number, d.message),
+                code: d.code,
+            }
+        }
+        _ => Diagnostic {
+            line_number: d.line_number,
+            message: d.message,
+            code: d.code,
+        },
+    }
+}
+
+impl Symbols {
+    /// The thing `name` refers to in this document, e.g. `ftd#text` for `ftd.text`. Names of
+    /// fields and arguments, like `$person.name`, resolve to the thing they belong to.
+    pub(crate) fn resolve(&self, name: &str) -> Option<(String, &ftd::interpreter::Thing)> {
+        let parsed = self.parsed.get(self.name.as_str())?;
+        let mut full_name =
+            ftd::interpreter::utils::resolve_name(name, self.name.as_str(), &parsed.doc_aliases);
+
+        loop {
+            if let Some(thing) = self.bag.get(full_name.as_str()) {
+                return Some((full_name, thing));
+            }
+            let (module, thing_name) = full_name.split_once('#')?;
+            let (parent, _) = thing_name.rsplit_once('.')?;
+            full_name = format!("{module}#{parent}");
+        }
+    }
+
+    /// The module and line where `name` is defined. Things that were not interpreted, like
+    /// components of an imported module that are not used yet, are found in the parsed module.
+    pub(crate) fn definition(&self, name: &str) -> Option<(String, usize)> {
+        if let Some((full_name, thing)) = self.resolve(name) {
+            let (module, _) = full_name.split_once('#')?;
+            return Some((module.to_string(), thing.line_number()));
+        }
+
+        let parsed = self.parsed.get(self.name.as_str())?;
+        let full_name =
+            ftd::interpreter::utils::resolve_name(name, self.name.as_str(), &parsed.doc_aliases);
+        let (module, thing_name) = full_name.split_once('#')?;
+        let thing_name = thing_name.split('.').next()?;
+        self.parsed
+            .get(module)?
+            .ast
+            .iter()
+            .find(|ast| !ast.is_import() && ast.name() == thing_name)
+            .map(|ast| (module.to_string(), ast.line_number()))
+    }
+
+    /// The arguments of the component `name`, with their types.
+    pub(crate) fn arguments(&self, name: &str) -> Vec<(String, String)> {
+        let arguments = match self.resolve(name) {
+            Some((_, ftd::interpreter::Thing::Component(c))) => &c.arguments,
+            Some((_, ftd::interpreter::Thing::WebComponent(c))) => &c.arguments,
+            _ => return vec![],
+        };
+
+        arguments
+            .iter()
+            .map(|a| (a.name.to_string(), kind_data_name(&a.kind)))
+            .collect()
+    }
+
+    /// The declaration of the thing `name` refers to, in ftd syntax.
+    pub(crate) fn hover(&self, name: &str) -> Option<String> {
+        let (_, thing) = self.resolve(name)?;
+        Some(match thing {
+            ftd::interpreter::Thing::Variable(v) => format!(
+                "-- {} {}{}:",
+                kind_data_name(&v.kind),
+                if v.mutable { "$" } else { "" },
+                short_name(v.name.as_str())
+            ),
+            ftd::interpreter::Thing::Component(c) => {
+                declaration("component", c.name.as_str(), &c.arguments)
+            }
+            ftd::interpreter::Thing::WebComponent(c) => {
+                declaration("web-component", c.name.as_str(), &c.arguments)
+            }
+            ftd::interpreter::Thing::Record(r) => declaration("record", r.name.as_str(), &r.fields),
+            ftd::interpreter::Thing::OrType(o) => {
+                format!("-- or-type {}:", short_name(o.name.as_str()))
+            }
+            ftd::interpreter::Thing::OrTypeWithVariant { or_type, variant } => format!(
+                "{}.{}",
+                short_name(or_type.as_str()),
+                short_name(variant.name().as_str())
+            ),
+            ftd::interpreter::Thing::Function(f) => format!(
+                "-- {} {}({}):",
+                kind_data_name(&f.return_kind),
+                short_name(f.name.as_str()),
+                f.arguments
+                    .iter()
+                    .map(|a| format!("{} {}", kind_data_name(&a.kind), a.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ftd::interpreter::Thing::Export { from, to, .. } => {
+                format!("{to}: exported from {from}")
+            }
+        })
+    }
+}
+
+fn declaration(keyword: &str, name: &str, fields: &[ftd::interpreter::Field]) -> String {
+    let mut declaration = format!("-- {keyword} {}:", short_name(name));
+    for field in fields {
+        declaration.push_str(
+            format!(
+                "\n{} {}{}",
+                kind_data_name(&field.kind),
+                if field.mutable { "$" } else { "" },
+                field.name
+            )
+            .as_str(),
+        );
+    }
+    declaration
+}
+
+fn short_name(name: &str) -> &str {
+    name.rsplit_once('#').map_or(name, |(_, name)| name)
+}
+
+/// The type of a variable or argument, as it is written in ftd, e.g. `caption or body string`.
+pub(crate) fn kind_data_name(kind: &ftd::interpreter::KindData) -> String {
+    let name = kind_name(&kind.kind);
+    match (kind.caption, kind.body) {
+        (true, true) => format!("caption or body {name}"),
+        (true, false) => format!("caption {name}"),
+        (false, true) => format!("body {name}"),
+        (false, false) => name,
+    }
+}
+
+fn kind_name(kind: &ftd::interpreter::Kind) -> String {
+    match kind {
+        ftd::interpreter::Kind::List { kind } => format!("{} list", kind_name(kind)),
+        ftd::interpreter::Kind::Optional { kind } => format!("optional {}", kind_name(kind)),
+        ftd::interpreter::Kind::Constant { kind } => format!("constant {}", kind_name(kind)),
+        ftd::interpreter::Kind::Record { name } | ftd::interpreter::Kind::OrType { name, .. } => {
+            short_name(name).to_string()
+        }
+        ftd::interpreter::Kind::UI { .. } => "ftd.ui".to_string(),
+        kind => kind.get_name(),
+    }
+}
+
+/// The file a module is read from: a file of this package, or of a dependency in `.packages`.
+pub(crate) async fn module_path(
+    config: &fastn_core::Config,
+    module: &str,
+) -> Option<fastn_ds::Path> {
+    let module = module.trim_matches('/');
+    let (package_name, package) = match config.find_package_id_in_all_packages(module) {
+        Some(package) => package,
+        None => config
+            .package
+            .aliases()
+            .into_iter()
+            .find(|(alias, _)| module == *alias || module.starts_with(format!("{alias}/").as_str()))
+            .map(|(alias, package)| (alias.to_string(), package.to_owned()))?,
+    };
+
+    let root = package.package_root_with_default(None).unwrap_or_else(|_| {
+        config
+            .ds
+            .root()
+            .join(".packages")
+            .join(package.name.as_str())
+    });
+    let id = format!(
+        "{}/",
+        module.strip_prefix(package_name.as_str()).unwrap_or(module)
+    );
+
+    for name in fastn_core::package::package_doc::file_id_to_names(id.as_str()) {
+        let path = root.join(name);
+        if config.ds.exists(&path).await {
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn kind_data_name() {
+        let kind = ftd::interpreter::Kind::List {
+            kind: Box::new(ftd::interpreter::Kind::Record {
+                name: "my-package/blog#post".to_string(),
+            }),
+        };
+        assert_eq!(super::kind_data_name(&kind.into_kind_data()), "post list");
+
+        let mut kind = ftd::interpreter::Kind::Optional {
+            kind: Box::new(ftd::interpreter::Kind::String),
+        }
+        .into_kind_data();
+        kind.caption = true;
+        kind.body = true;
+        assert_eq!(
+            super::kind_data_name(&kind),
+            "caption or body optional string"
+        );
+    }
+}
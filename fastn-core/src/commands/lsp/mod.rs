@@ -0,0 +1,279 @@
+//! `fastn lsp`: a language server for the `.ftd` files of the package in the current directory,
+//! speaking the Language Server Protocol over stdin and stdout.
+//!
+//! Open documents are interpreted on every change, the same way `fastn serve` would interpret
+//! them, and the errors are published as diagnostics. The things resolved while interpreting
+//! are used for go to definition, hover and completion of component arguments.
+
+mod analysis;
+mod protocol;
+
+struct Server {
+    config: fastn_core::Config,
+    /// The content of the open documents, by uri.
+    documents: std::collections::HashMap<String, String>,
+    /// The symbols of the last successful analysis of each open document. A document with errors
+    /// keeps the symbols of the previous analysis, so navigation keeps working while typing.
+    symbols: std::collections::HashMap<String, analysis::Symbols>,
+}
+
+pub async fn lsp(ds: fastn_ds::DocumentStore) -> fastn_core::Result<()> {
+    let config = fastn_core::Config::read_without_migrations(ds, false).await?;
+    let mut server = Server {
+        config,
+        documents: Default::default(),
+        symbols: Default::default(),
+    };
+
+    let mut stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    let mut stdout = tokio::io::stdout();
+
+    loop {
+        let message = match protocol::read_message(&mut stdin).await {
+            Ok(Some(message)) => message,
+            Ok(None) => return Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                let response = protocol::error_response(
+                    serde_json::Value::Null,
+                    protocol::PARSE_ERROR,
+                    e.to_string().as_str(),
+                );
+                protocol::write_message(&mut stdout, &response).await?;
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let method = message["method"].as_str().unwrap_or_default();
+        if method == "exit" {
+            return Ok(());
+        }
+        for response in server
+            .handle(method, message.get("id"), &message["params"])
+            .await
+        {
+            protocol::write_message(&mut stdout, &response).await?;
+        }
+    }
+}
+
+impl Server {
+    /// Handles a request or notification, and returns the messages to send back.
+    async fn handle(
+        &mut self,
+        method: &str,
+        id: Option<&serde_json::Value>,
+        params: &serde_json::Value,
+    ) -> Vec<serde_json::Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let result = match method {
+            "initialize" => serde_json::json!({
+                "capabilities": {
+                    // the full content is sent on every change
+                    "textDocumentSync": 1,
+                    "definitionProvider": true,
+                    "hoverProvider": true,
+                    "completionProvider": {},
+                },
+                "serverInfo": {"name": "fastn", "version": env!("CARGO_PKG_VERSION")},
+            }),
+            "shutdown" => serde_json::Value::Null,
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+                return self
+                    .publish_diagnostics(uri.as_str())
+                    .await
+                    .into_iter()
+                    .collect();
+            }
+            "textDocument/didChange" => {
+                // with full sync, the last change has the whole content
+                if let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                {
+                    self.documents.insert(uri.clone(), text.to_string());
+                }
+                return self
+                    .publish_diagnostics(uri.as_str())
+                    .await
+                    .into_iter()
+                    .collect();
+            }
+            "textDocument/didSave" => {
+                // other documents may import the saved one, and FASTN.ftd changes the
+                // dependencies and auto imports of every document
+                if uri.ends_with("/FASTN.ftd") {
+                    let ds = self.config.ds.clone();
+                    match fastn_core::Config::read_without_migrations(ds, false).await {
+                        Ok(config) => self.config = config,
+                        Err(e) => tracing::warn!("failed to reload FASTN.ftd: {e}"),
+                    }
+                }
+                let mut messages = vec![];
+                let uris: Vec<String> = self.documents.keys().cloned().collect();
+                for uri in uris {
+                    messages.extend(self.publish_diagnostics(uri.as_str()).await);
+                }
+                return messages;
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                self.symbols.remove(&uri);
+                return vec![protocol::notification(
+                    "textDocument/publishDiagnostics",
+                    serde_json::json!({"uri": uri, "diagnostics": []}),
+                )];
+            }
+            "textDocument/definition" => self.definition(uri.as_str(), params).await,
+            "textDocument/hover" => self.hover(uri.as_str(), params),
+            "textDocument/completion" => self.completion(uri.as_str(), params),
+            _ => {
+                // unknown notifications are ignored, unknown requests get an error
+                return id
+                    .map(|id| {
+                        protocol::error_response(
+                            id.clone(),
+                            protocol::METHOD_NOT_FOUND,
+                            format!("{method} is not supported").as_str(),
+                        )
+                    })
+                    .into_iter()
+                    .collect();
+            }
+        };
+
+        id.map(|id| protocol::response(id.clone(), result))
+            .into_iter()
+            .collect()
+    }
+
+    async fn publish_diagnostics(&mut self, uri: &str) -> Option<serde_json::Value> {
+        let content = self.documents.get(uri)?;
+        let path = uri_to_path(uri)?;
+        if path.extension().as_deref() != Some("ftd") {
+            return None;
+        }
+
+        let analysis = analysis::analyse(&self.config, &path, content).await;
+        if let Some(symbols) = analysis.symbols {
+            if analysis.diagnostics.is_empty() || !self.symbols.contains_key(uri) {
+                self.symbols.insert(uri.to_string(), symbols);
+            }
+        }
+
+        let diagnostics: Vec<_> = analysis
+            .diagnostics
+            .into_iter()
+            .map(|d| {
+                serde_json::json!({
+                    "range": protocol::range(d.line_number),
+                    "severity": 1,
+                    "source": "fastn",
+                    "message": d.message,
+                    "code": d.code.as_str(),
+                })
+            })
+            .collect();
+
+        Some(protocol::notification(
+            "textDocument/publishDiagnostics",
+            serde_json::json!({"uri": uri, "diagnostics": diagnostics}),
+        ))
+    }
+
+    /// The name under the cursor, and the symbols of the document it is in.
+    fn name_at(
+        &self,
+        uri: &str,
+        params: &serde_json::Value,
+    ) -> Option<(String, &analysis::Symbols)> {
+        let (line, character) = protocol::position(params)?;
+        let name = protocol::name_at(self.documents.get(uri)?, line, character)?;
+        Some((name, self.symbols.get(uri)?))
+    }
+
+    async fn definition(&self, uri: &str, params: &serde_json::Value) -> serde_json::Value {
+        let Some((name, symbols)) = self.name_at(uri, params) else {
+            return serde_json::Value::Null;
+        };
+        let Some((module, line_number)) = symbols.definition(name.as_str()) else {
+            return serde_json::Value::Null;
+        };
+
+        let target = if module.trim_matches('/') == symbols.name.trim_matches('/') {
+            Some(uri.to_string())
+        } else {
+            analysis::module_path(&self.config, module.as_str())
+                .await
+                .and_then(|path| url::Url::from_file_path(path.to_string()).ok())
+                .map(|url| url.to_string())
+        };
+
+        match target {
+            Some(target) => serde_json::json!({
+                "uri": target,
+                "range": protocol::range(line_number),
+            }),
+            // things of `ftd` and other built in modules are not defined in a file
+            None => serde_json::Value::Null,
+        }
+    }
+
+    fn hover(&self, uri: &str, params: &serde_json::Value) -> serde_json::Value {
+        let Some(declaration) = self
+            .name_at(uri, params)
+            .and_then(|(name, symbols)| symbols.hover(name.as_str()))
+        else {
+            return serde_json::Value::Null;
+        };
+
+        serde_json::json!({
+            "contents": {
+                "kind": "markdown",
+                "value": format!("```ftd\n{declaration}\n```"),
+            }
+        })
+    }
+
+    fn completion(&self, uri: &str, params: &serde_json::Value) -> serde_json::Value {
+        let (Some((line, _)), Some(content), Some(symbols)) = (
+            protocol::position(params),
+            self.documents.get(uri),
+            self.symbols.get(uri),
+        ) else {
+            return serde_json::json!([]);
+        };
+        let Some((component, headers)) = protocol::enclosing_section(content, line) else {
+            return serde_json::json!([]);
+        };
+
+        let items: Vec<_> = symbols
+            .arguments(component.as_str())
+            .into_iter()
+            .filter(|(name, _)| !headers.contains(name))
+            .map(|(name, kind)| {
+                serde_json::json!({
+                    "label": name,
+                    // CompletionItemKind.Property
+                    "kind": 10,
+                    "detail": kind,
+                    "insertText": format!("{name}: "),
+                })
+            })
+            .collect();
+
+        serde_json::json!(items)
+    }
+}
+
+fn uri_to_path(uri: &str) -> Option<fastn_ds::Path> {
+    let path = url::Url::parse(uri).ok()?.to_file_path().ok()?;
+    Some(fastn_ds::Path::new(path.to_str()?))
+}
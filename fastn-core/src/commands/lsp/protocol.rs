@@ -0,0 +1,234 @@
+//! The subset of the Language Server Protocol used by `fastn lsp`: JSON-RPC messages framed with
+//! a `Content-Length` header, and the conversions between LSP positions and ftd source lines.
+
+pub(crate) const PARSE_ERROR: i64 = -32700;
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Reads the next message, `None` once the client closes the stream.
+pub(crate) async fn read_message<R>(reader: &mut R) -> std::io::Result<Option<serde_json::Value>>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "message without a valid Content-Length header",
+        )
+    })?;
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+pub(crate) async fn write_message<W>(
+    writer: &mut W,
+    message: &serde_json::Value,
+) -> std::io::Result<()>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncWriteExt;
+
+    let body = message.to_string();
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).as_bytes())
+        .await?;
+    writer.flush().await
+}
+
+pub(crate) fn response(id: serde_json::Value, result: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({"jsonrpc": "2.0", "id": id, "result": result})
+}
+
+pub(crate) fn error_response(id: serde_json::Value, code: i64, message: &str) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": code, "message": message},
+    })
+}
+
+pub(crate) fn notification(method: &str, params: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({"jsonrpc": "2.0", "method": method, "params": params})
+}
+
+/// ftd line numbers start at 1, LSP lines at 0. Errors without a location are reported on the
+/// first line.
+pub(crate) fn range(ftd_line_number: usize) -> serde_json::Value {
+    let line = ftd_line_number.saturating_sub(1);
+    serde_json::json!({
+        "start": {"line": line, "character": 0},
+        "end": {"line": line + 1, "character": 0},
+    })
+}
+
+/// The `(line, character)` of a `TextDocumentPositionParams`.
+pub(crate) fn position(params: &serde_json::Value) -> Option<(usize, usize)> {
+    let position = params.get("position")?;
+    Some((
+        position.get("line")?.as_u64()? as usize,
+        position.get("character")?.as_u64()? as usize,
+    ))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '#' | '$' | '/')
+}
+
+/// The name around `character` on `line`, e.g. `ftd.text` in `-- ftd.text: hello`, or
+/// `$count` in `value: $count`.
+pub(crate) fn name_at(content: &str, line: usize, character: usize) -> Option<String> {
+    let chars: Vec<char> = content.lines().nth(line)?.chars().collect();
+    let character = character.min(chars.len());
+
+    let start = chars[..character]
+        .iter()
+        .rposition(|c| !is_name_char(*c))
+        .map_or(0, |p| p + 1);
+    let end = chars[character..]
+        .iter()
+        .position(|c| !is_name_char(*c))
+        .map_or(chars.len(), |p| character + p);
+
+    let name: String = chars[start..end].iter().collect();
+    let name = name.trim_end_matches('.');
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// The component invoked by the section the cursor is in, along with the headers already
+/// written for it above the cursor. Sections closed by an `-- end:` line are skipped, so the
+/// headers of a container are completed after its children.
+pub(crate) fn enclosing_section(content: &str, line: usize) -> Option<(String, Vec<String>)> {
+    let mut headers = vec![];
+    let mut closed: Vec<String> = vec![];
+
+    for l in content
+        .lines()
+        .take(line)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+    {
+        let l = l.trim();
+        let Some(section) = l.strip_prefix("-- ") else {
+            if closed.is_empty() {
+                if let Some((key, _)) = l.split_once(':') {
+                    headers.push(key.trim().to_string());
+                }
+            }
+            continue;
+        };
+        let Some((name, _)) = section.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+
+        if name == "end" {
+            closed.push(section["end:".len()..].trim().to_string());
+            continue;
+        }
+        if closed.last().map_or(false, |c| c == name) {
+            closed.pop();
+            continue;
+        }
+        if !closed.is_empty() {
+            continue;
+        }
+        // `-- string name:` and `-- component name:` declare things, they do not invoke a
+        // component
+        if name.contains(char::is_whitespace) {
+            return None;
+        }
+        return Some((name.to_string(), headers));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn read_write_message() {
+        let message = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "shutdown"});
+
+        let mut buffer = vec![];
+        super::write_message(&mut buffer, &message).await.unwrap();
+        let header = format!("Content-Length: {}\r\n\r\n", message.to_string().len());
+        assert!(buffer.starts_with(header.as_bytes()));
+
+        let mut reader = tokio::io::BufReader::new(buffer.as_slice());
+        assert_eq!(
+            super::read_message(&mut reader).await.unwrap(),
+            Some(message)
+        );
+        assert_eq!(super::read_message(&mut reader).await.unwrap(), None);
+    }
+
+    #[test]
+    fn name_at() {
+        let content = "-- ftd.text: hello\ncolor: $inherited.colors.text\n";
+        assert_eq!(super::name_at(content, 0, 5), Some("ftd.text".to_string()));
+        assert_eq!(super::name_at(content, 0, 11), Some("ftd.text".to_string()));
+        assert_eq!(
+            super::name_at(content, 1, 12),
+            Some("$inherited.colors.text".to_string())
+        );
+        assert_eq!(super::name_at(content, 0, 12), None);
+        assert_eq!(super::name_at(content, 5, 0), None);
+    }
+
+    #[test]
+    fn enclosing_section() {
+        let content = "\
+-- ftd.column:
+padding.px: 10
+
+-- ftd.row:
+
+-- ftd.text: hello
+color: red
+
+-- end: ftd.row
+
+-- end: ftd.column
+
+-- integer x: 1
+";
+        assert_eq!(
+            super::enclosing_section(content, 7),
+            Some(("ftd.text".to_string(), vec!["color".to_string()]))
+        );
+        assert_eq!(
+            super::enclosing_section(content, 10),
+            Some(("ftd.column".to_string(), vec!["padding.px".to_string()]))
+        );
+        assert_eq!(super::enclosing_section(content, 12), None);
+        assert_eq!(super::enclosing_section(content, 14), None);
+    }
+}
@@ -0,0 +1,53 @@
+pub enum MigrateCommand {
+    /// List migrations of fastn and of this package, and whether they are applied.
+    Status,
+    /// Apply all pending migrations.
+    Up { dry_run: bool },
+    /// Revert the last `count` applied migrations of this package.
+    Down { count: usize, dry_run: bool },
+}
+
+pub async fn migrate(
+    config: &fastn_core::Config,
+    command: MigrateCommand,
+) -> fastn_core::Result<()> {
+    match command {
+        MigrateCommand::Status => status(config).await,
+        MigrateCommand::Up { dry_run } => {
+            Ok(fastn_core::migrations::migrate_up(config, dry_run).await?)
+        }
+        MigrateCommand::Down { count, dry_run } => {
+            Ok(fastn_core::migrations::migrate_down(config, count, dry_run).await?)
+        }
+    }
+}
+
+async fn status(config: &fastn_core::Config) -> fastn_core::Result<()> {
+    use colored::Colorize;
+    use fastn_core::migrations::MigrationState;
+
+    let status = fastn_core::migrations::migration_status(config).await?;
+    if status.is_empty() {
+        println!("No migrations found.");
+        return Ok(());
+    }
+
+    for migration in status {
+        let state = match migration.state {
+            MigrationState::Applied { applied_on } => format!(
+                "applied on {}",
+                chrono::DateTime::from_timestamp_nanos(applied_on).format("%Y-%m-%d %H:%M:%S")
+            )
+            .green(),
+            MigrationState::Pending => "pending".yellow(),
+            MigrationState::Modified => "modified after it was applied".red(),
+            MigrationState::Deleted => "deleted after it was applied".red(),
+        };
+        println!(
+            "{}: {:>4} {:<40} {}",
+            migration.app_name, migration.number, migration.name, state
+        );
+    }
+
+    Ok(())
+}
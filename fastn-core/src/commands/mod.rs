@@ -1,6 +1,8 @@
 pub mod build;
 pub mod check;
 pub mod fmt;
+pub mod lsp;
+pub mod migrate;
 pub mod query;
 pub mod serve;
 pub mod test;
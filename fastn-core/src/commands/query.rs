@@ -19,7 +19,7 @@ pub async fn query(
             },
         )?;
 
-        let value = get_ftd_json(file, stage)?;
+        let value = get_document_json(config, file, stage).await?;
         println!(
             "{}",
             if with_null {
@@ -34,7 +34,7 @@ pub async fn query(
     let mut values: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
     for file in documents.values() {
         if file.is_ftd() {
-            let value = get_ftd_json(file, stage)?;
+            let value = get_document_json(config, file, stage).await?;
             values.insert(file.get_id().to_string(), value);
         }
     }
@@ -53,25 +53,126 @@ pub async fn query(
     Ok(())
 }
 
+/// The stages `fastn query --stage` can dump, in the order the compiler runs them.
+pub const STAGES: [&str; 6] = ["p1", "ast", "interpreter", "executor", "node", "js"];
+
+fn ftd_document(file: &fastn_core::File) -> fastn_core::Result<&fastn_core::Document> {
+    if let fastn_core::File::Ftd(document) = file {
+        Ok(document)
+    } else {
+        Err(fastn_core::Error::UsageError {
+            message: format!("{} is not an ftd file", file.get_id()),
+        })
+    }
+}
+
+/// The json of the stages that only need the document, `p1` and `ast`.
 pub(crate) fn get_ftd_json(
     file: &fastn_core::File,
     stage: &str,
 ) -> fastn_core::Result<serde_json::Value> {
-    let document = if let fastn_core::File::Ftd(document) = file {
-        document
-    } else {
-        return Err(fastn_core::Error::UsageError {
-            message: format!("{} is not an ftd file", file.get_id()),
-        });
-    };
+    let document = ftd_document(file)?;
 
     match stage {
         "p1" => get_p1_json(document),
         "ast" => get_ast_json(document),
-        _ => unimplemented!(),
+        _ => Err(fastn_core::Error::UsageError {
+            message: format!("stage `{stage}` is not supported here, supported stages: p1, ast"),
+        }),
+    }
+}
+
+/// The json of any stage, the stages after `ast` interpret the document, with its imports and
+/// processors.
+async fn get_document_json(
+    config: &fastn_core::Config,
+    file: &fastn_core::File,
+    stage: &str,
+) -> fastn_core::Result<serde_json::Value> {
+    let document = ftd_document(file)?;
+
+    match stage {
+        "p1" | "ast" => get_ftd_json(file, stage),
+        "interpreter" | "executor" | "node" | "js" => get_interpreted_json(
+            interpret(config, document).await?,
+            stage,
+            config.package.name.as_str(),
+        ),
+        _ => Err(fastn_core::Error::UsageError {
+            message: format!(
+                "unknown stage `{stage}`, supported stages: {}",
+                STAGES.join(", ")
+            ),
+        }),
+    }
+}
+
+async fn interpret(
+    config: &fastn_core::Config,
+    document: &fastn_core::Document,
+) -> fastn_core::Result<ftd::interpreter::Document> {
+    let mut req_config = fastn_core::RequestConfig::new(
+        config,
+        &fastn_core::http::Request::default(),
+        document.id.as_str(),
+        "/",
+    );
+    req_config.current_document = Some(document.id.to_string());
+
+    let (body, line_number) = fastn_core::doc::prefixed_body(config, document)?;
+    fastn_core::doc::interpret_helper(
+        document.id_with_package().as_str(),
+        body.as_str(),
+        &mut req_config,
+        "/",
+        false,
+        line_number,
+    )
+    .await
+    .map_err(|e| fastn_core::doc::diagnostic(&e, document).into())
+}
+
+/// The json of the stages that run on the interpreted document: `interpreter`, `executor`,
+/// `node` and `js`.
+fn get_interpreted_json(
+    document: ftd::interpreter::Document,
+    stage: &str,
+    package_name: &str,
+) -> fastn_core::Result<serde_json::Value> {
+    match stage {
+        "interpreter" => Ok(serde_json::to_value(document)?),
+        "executor" => {
+            let rt = ftd::executor::ExecuteDoc::from_interpreter(document)?;
+            Ok(serde_json::to_value(rt)?)
+        }
+        "node" => {
+            let rt = ftd::executor::ExecuteDoc::from_interpreter(document)?;
+            Ok(serde_json::to_value(ftd::node::NodeData::from_rt(rt))?)
+        }
+        "js" => get_js_json(document, package_name),
+        _ => Err(fastn_core::Error::UsageError {
+            message: format!(
+                "stage `{stage}` is not supported here, supported stages: interpreter, \
+                executor, node, js"
+            ),
+        }),
     }
 }
 
+/// The js ast of the document, and the JS generated from it.
+fn get_js_json(
+    document: ftd::interpreter::Document,
+    package_name: &str,
+) -> fastn_core::Result<serde_json::Value> {
+    let js_ast_data = ftd::js::document_into_js_ast(document);
+
+    Ok(serde_json::json!({
+        "asts": serde_json::to_value(&js_ast_data.asts)?,
+        "scripts": js_ast_data.scripts,
+        "js": fastn_js::to_js(js_ast_data.asts.as_slice(), package_name),
+    }))
+}
+
 fn get_p1_json(document: &fastn_core::Document) -> fastn_core::Result<serde_json::Value> {
     let p1 = ftd_p1::parse(
         document.content.as_str(),
@@ -91,3 +192,54 @@ fn get_ast_json(document: &fastn_core::Document) -> fastn_core::Result<serde_jso
 
     Ok(value)
 }
+
+#[cfg(test)]
+mod tests {
+    fn stage_json(stage: &str) -> serde_json::Value {
+        let document = ftd::test_helper::ftd_v2_interpret_helper(
+            "foo",
+            "-- string name: hello\n\n-- ftd.text: $name\n",
+        )
+        .unwrap();
+        super::get_interpreted_json(document, stage, "foo").unwrap()
+    }
+
+    #[test]
+    fn interpreter() {
+        let value = stage_json("interpreter");
+        assert_eq!(value["name"], "foo");
+        assert!(value["data"]["foo#name"].is_object());
+        assert_eq!(value["tree"].as_array().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn executor() {
+        let value = stage_json("executor");
+        assert_eq!(value["name"], "foo");
+        assert!(value["bag"]["foo#name"].is_object());
+        assert!(value["main"].is_object());
+    }
+
+    #[test]
+    fn node() {
+        let value = stage_json("node");
+        assert_eq!(value["name"], "foo");
+        assert!(value["node"].is_object());
+    }
+
+    #[test]
+    fn js() {
+        let value = stage_json("js");
+        let asts = value["asts"].as_array().unwrap();
+        assert!(asts
+            .iter()
+            .any(|ast| ast["StaticVariable"]["name"] == "foo#name"));
+        assert!(value["js"].as_str().unwrap().contains("hello"));
+    }
+
+    #[test]
+    fn unknown_stage() {
+        let document = ftd::test_helper::ftd_v2_interpret_helper("foo", "").unwrap();
+        assert!(super::get_interpreted_json(document, "p1", "foo").is_err());
+    }
+}
@@ -8,7 +8,7 @@ fn handle_redirect(
         .redirects
         .as_ref()
         .and_then(|v| fastn_core::package::redirects::find_redirect(v, path.as_str()))
-        .map(|r| fastn_core::http::permanent_redirect(r.to_string()))
+        .map(|(url, code)| fastn_core::http::redirect_with_code(url, code))
 }
 
 /// path: /-/<package-name>/<file-name>/
@@ -113,6 +113,8 @@ async fn serve_file(
                 }
             }
         },
+        // errors in the document are shown with the lines around them
+        Err(e @ fastn_core::Error::Diagnostic(_)) => e.to_html(),
         Err(e) => {
             tracing::error!(
                 msg = "fastn-Error",
@@ -199,6 +201,18 @@ pub async fn serve(
         return Ok((clear_sid2(&req), false));
     }
 
+    if config.source_maps
+        && req
+            .path()
+            .starts_with(fastn_core::source_map::SOURCE_MAP_PATH)
+    {
+        return Ok((fastn_core::source_map::serve(req.path()), false));
+    }
+
+    if let Some(search_response) = fastn_core::search::serve(config, &req).await {
+        return search_response.map(|r| (r, false));
+    }
+
     if let Some(endpoint_response) = handle_endpoints(config, &req).await {
         return endpoint_response.map(|r| (r, false));
     }
@@ -206,9 +220,12 @@ pub async fn serve(
     if let Some(app_response) = handle_apps(config, &req).await {
         return app_response.map(|r| (r, false));
     }
+    // the response cache is looked up before the access to the app is checked, so what an app
+    // with readers or writers answers is never cached
+    let shareable = is_shareable(config.package.apps.as_slice(), req.path());
 
     if let Some(default_response) = handle_default_route(&req, config.package.name.as_str()) {
-        return default_response.map(|r| (r, true));
+        return default_response.map(|r| (r, shareable));
     }
 
     let path: camino::Utf8PathBuf = req.path().replacen('/', "", 1).parse()?;
@@ -220,14 +237,20 @@ pub async fn serve(
     if fastn_core::utils::is_static_path(req.path()) {
         return handle_static_route(req.path(), config.package.name.as_str(), &config.ds)
             .await
-            .map(|r| (r, true));
+            .map(|r| (r, shareable));
     }
 
     let mut req_config = fastn_core::RequestConfig::new(config, &req, "", "/");
 
     serve_helper(&mut req_config, only_js, path)
         .await
-        .map(|r| (r, req_config.response_is_cacheable))
+        .map(|r| (r, shareable && req_config.response_is_cacheable))
+}
+
+/// Whether the response to a request for `path` can be served to everyone, `false` if an app
+/// that checks who is asking answers it.
+fn is_shareable(apps: &[fastn_core::package::app::App], path: &str) -> bool {
+    !apps.iter().any(|app| app.guards(path))
 }
 
 #[tracing::instrument(skip_all)]
@@ -358,14 +381,14 @@ pub fn handle_default_route(
                 ftd::build_js(),
                 fastn_core::fastn_2022_js()
             ))));
-    } else if req
-        .path()
-        .ends_with(fastn_core::utils::hashed_default_ftd_js(package_name))
+    } else if let Some(runtime_js) = fastn_core::utils::runtime_js(package_name)
+        .iter()
+        .find(|r| req.path().ends_with(r.name.as_str()))
     {
         return Some(Ok(actix_web::HttpResponse::Ok()
             .content_type(mime_guess::mime::TEXT_JAVASCRIPT)
             .append_header(("Cache-Control", "public, max-age=31536000"))
-            .body(ftd::js::all_js_without_test(package_name))));
+            .body(runtime_js.content.clone())));
     } else if req
         .path()
         .ends_with(fastn_core::utils::hashed_markdown_js())
@@ -558,51 +581,174 @@ async fn handle_endpoints(
     Some(Ok(actix_response))
 }
 
+/// Requests under the mount point of a `fastn.app` are for the app package: its endpoints,
+/// WASM ones included, and its static files are served here. Its documents are served like the
+/// other documents, `Config::get_mountpoint_sanitized_path()` finds them in the app package.
 async fn handle_apps(
     config: &fastn_core::Config,
     req: &fastn_core::http::Request,
 ) -> Option<fastn_core::Result<fastn_core::http::Response>> {
-    let matched_app = config.package.apps.iter().find(|a| {
-        req.path().starts_with(
-            a.end_point
-                .clone()
-                .unwrap_or_default()
-                .trim_end_matches('/'),
-        )
-    });
-
-    let _app = match matched_app {
-        Some(e) => e,
-        None => return None,
+    let Some((app, path)) = config
+        .package
+        .apps
+        .iter()
+        .find_map(|a| Some((a, a.path(req.path())?)))
+    else {
+        // the files of the app package can also be requested from `/-/<package name>/`, they are
+        // served like other static files once the user is allowed to
+        let app = config
+            .package
+            .apps
+            .iter()
+            .find(|a| a.is_package_path(req.path()))?;
+        return check_app_access(config, req, app).await;
     };
 
-    // app.package.endpoints
-    // app.package.apps
+    if let Some(denied) = check_app_access(config, req, app).await {
+        return Some(denied);
+    }
+
+    if let Some(endpoint) = app
+        .package
+        .endpoints
+        .iter()
+        .find(|ep| path.starts_with(ep.mountpoint.trim_end_matches('/')))
+    {
+        return Some(handle_app_endpoint(config, req, app, endpoint).await);
+    }
 
-    // see if app.pack
+    if fastn_core::utils::is_static_path(req.path()) {
+        let path = format!(
+            "/-/{}/{}",
+            app.package.name.trim_matches('/'),
+            path.trim_start_matches('/')
+        );
+        return Some(
+            handle_static_route(path.as_str(), config.package.name.as_str(), &config.ds).await,
+        );
+    }
 
     None
 }
 
+/// The response for a `req` the user can not make to `app`, `None` if they can: 401 for visitors
+/// who are not logged in and 403 for logged in users.
+async fn check_app_access(
+    config: &fastn_core::Config,
+    req: &fastn_core::http::Request,
+    app: &fastn_core::package::app::App,
+) -> Option<fastn_core::Result<fastn_core::http::Response>> {
+    // apps without readers and writers are open to everyone, no need to look up the user
+    if app.readers.is_empty() && app.writers.is_empty() {
+        return None;
+    }
+    let user = match fastn_core::library2022::processor::user_details::ud(
+        &config.ds,
+        config.get_db_url().await.as_str(),
+        req.cookie(ft_sys_shared::SESSION_KEY),
+    )
+    .await
+    {
+        Ok(user) => user,
+        Err(e) => return Some(Err(fastn_core::Error::GenericError(e.to_string()))),
+    };
+    if app.allows(user.as_ref(), req.method()) {
+        return None;
+    }
+    Some(Ok(match user {
+        Some(user) => {
+            fastn_core::forbidden!("{} can not {} {}", user.identity, req.method(), req.path())
+        }
+        None => fastn_core::unauthorised!("anonymous user can not {} {}", req.method(), req.path()),
+    }))
+}
+
+/// Proxies `req` to `endpoint` of `app`, the config of the app is sent as headers.
+async fn handle_app_endpoint(
+    config: &fastn_core::Config,
+    req: &fastn_core::http::Request,
+    app: &fastn_core::package::app::App,
+    endpoint: &fastn_package::old_fastn::EndpointData,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    let mountpoint = format!(
+        "/{}/{}",
+        app.mount_point.trim_matches('/'),
+        endpoint.mountpoint.trim_start_matches('/')
+    );
+    let remaining = req
+        .full_path()
+        .trim_start_matches('/')
+        .trim_start_matches(app.mount_point.trim_matches('/'))
+        .trim_start_matches(endpoint.mountpoint.trim_end_matches('/'))
+        .trim_start_matches('/')
+        .to_string();
+
+    let mut req = req.clone();
+    req.set_headers(&app.endpoint_config());
+
+    if let Some(wasm_file) = endpoint.endpoint.strip_prefix("wasm+proxy://") {
+        // the WASM file is in the app package
+        let url = format!(
+            "wasm+proxy://{}/{}",
+            config.get_root_for_package(&app.package),
+            remaining_url(wasm_file, remaining.as_str())
+        );
+        return config
+            .ds
+            .handle_wasm(url, &req, mountpoint)
+            .await
+            .map(fastn_ds::wasm::to_response)
+            .map_err(Into::into);
+    }
+
+    let url = remaining_url(endpoint.endpoint.as_str(), remaining.as_str());
+    let response = config
+        .ds
+        .http(url::Url::parse(url.as_str())?, &req, &app.endpoint_config())
+        .await
+        .map_err(fastn_core::Error::DSHttpError)?;
+    Ok(fastn_core::http::ResponseBuilder::from_reqwest(response).await)
+}
+
+fn remaining_url(endpoint: &str, remaining: &str) -> String {
+    format!("{}/{}", endpoint.trim_end_matches('/'), remaining)
+}
+
 #[tracing::instrument(skip_all)]
 async fn actual_route(
     config: &fastn_core::Config,
     req: actix_web::HttpRequest,
     body: actix_web::web::Bytes,
+    response_cache: Option<&fastn_core::response_cache::ResponseCache>,
 ) -> fastn_core::Result<fastn_core::http::Response> {
     tracing::info!(method = req.method().as_str(), uri = req.path());
     let req = fastn_core::http::Request::from_actix(req, body);
 
-    serve(config, req, false).await.map(|(r, _)| r)
+    match response_cache {
+        Some(response_cache) => {
+            response_cache
+                .get_or_render(&req, serve(config, req.clone(), false))
+                .await
+        }
+        None => serve(config, req, false).await.map(|(r, _)| r),
+    }
 }
 
 #[tracing::instrument(skip_all)]
 async fn route(
     req: actix_web::HttpRequest,
     body: actix_web::web::Bytes,
-    config: actix_web::web::Data<std::sync::Arc<fastn_core::Config>>,
+    config: actix_web::web::Data<fastn_core::live_reload::SharedConfig>,
+    response_cache: Option<actix_web::web::Data<fastn_core::response_cache::ResponseCache>>,
 ) -> fastn_core::Result<fastn_core::http::Response> {
-    actual_route(&config, req, body).await
+    let config = std::sync::Arc::clone(&config.read());
+    actual_route(&config, req, body, response_cache.as_deref()).await
+}
+
+async fn live_reload_route(
+    live_reload: actix_web::web::Data<fastn_core::live_reload::LiveReload>,
+) -> fastn_core::http::Response {
+    live_reload.events()
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -610,10 +756,17 @@ pub async fn listen(
     config: std::sync::Arc<fastn_core::Config>,
     bind_address: &str,
     port: Option<u16>,
+    tls: Option<fastn_core::Tls>,
 ) -> fastn_core::Result<()> {
     use colored::Colorize;
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
+    // certificate problems are reported before we pick a port
+    let tls_config = match tls.as_ref() {
+        Some(tls) => Some(tls.server_config(bind_address)?),
+        None => None,
+    };
+
     let tcp_listener = match fastn_core::http::get_available_port(port, bind_address) {
         Some(listener) => listener,
         None => {
@@ -634,8 +787,31 @@ You can try without providing port, it will automatically pick unused port."#,
         }
     };
 
+    let live_reload = config.live_reload;
+    let response_cache = config.response_cache.clone();
+    let config: fastn_core::live_reload::SharedConfig =
+        std::sync::Arc::new(antidote::RwLock::new(config));
+    let live_reload = if live_reload {
+        Some(fastn_core::live_reload::LiveReload::start(
+            std::sync::Arc::clone(&config),
+        )?)
+    } else {
+        None
+    };
+    fastn_core::search::watch(std::sync::Arc::clone(&config))?;
+    let response_cache = match response_cache {
+        Some(storage) => Some(
+            fastn_core::response_cache::ResponseCache::start(
+                std::sync::Arc::clone(&config),
+                storage,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
     let app = move || {
-        actix_web::App::new()
+        let app = actix_web::App::new()
             .app_data(actix_web::web::Data::new(std::sync::Arc::clone(&config)))
             .app_data(actix_web::web::PayloadConfig::new(1024 * 1024 * 10))
             .wrap(actix_web::middleware::Compress::default())
@@ -645,19 +821,107 @@ You can try without providing port, it will automatically pick unused port."#,
                     r#""%r" %Ts %s %b %a "%{Referer}i" "%{User-Agent}i""#,
                 )
                 .log_target(""),
-            )
-            .route("/{path:.*}", actix_web::web::route().to(route))
+            );
+
+        let app = match response_cache.clone() {
+            Some(response_cache) => app.app_data(actix_web::web::Data::new(response_cache)),
+            None => app,
+        };
+
+        let app = match live_reload.clone() {
+            Some(live_reload) => app.app_data(actix_web::web::Data::new(live_reload)).route(
+                fastn_core::live_reload::LIVE_RELOAD_PATH,
+                actix_web::web::get().to(live_reload_route),
+            ),
+            None => app,
+        };
+
+        app.route("/{path:.*}", actix_web::web::route().to(route))
     };
 
     println!("### Server Started ###");
     println!(
-        "Go to: http://{}:{}",
+        "Go to: {}://{}:{}",
+        if tls_config.is_some() {
+            "https"
+        } else {
+            "http"
+        },
         bind_address,
         tcp_listener.local_addr()?.port()
     );
-    actix_web::HttpServer::new(app)
-        .listen(tcp_listener)?
-        .run()
-        .await?;
+    if let Some(fastn_core::Tls::SelfSigned) = tls {
+        println!(
+            "{}",
+            "Using a self-signed certificate, your browser will warn about it.".yellow()
+        );
+    }
+
+    let server = actix_web::HttpServer::new(app);
+    let server = match tls_config {
+        Some(tls_config) => server.listen_rustls_0_22(tcp_listener, tls_config)?,
+        None => server.listen(tcp_listener)?,
+    };
+    server.run().await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    /// What `serve()` answers for `/todos/` of an app only `amitu` can read, `sid` is the session
+    /// of `amitu`, if they are logged in.
+    async fn get_todos(
+        cache: &fastn_core::response_cache::ResponseCache,
+        sid: Option<&str>,
+    ) -> actix_web::http::StatusCode {
+        let apps = vec![fastn_core::package::app::App {
+            name: "todos".to_string(),
+            package: fastn_core::Package::new("fifthtry.github.io/todos"),
+            mount_point: "/todos/".to_string(),
+            end_point: None,
+            user_id: None,
+            config: Default::default(),
+            readers: vec!["amitu".to_string()],
+            writers: vec![],
+        }];
+        let mut req = actix_web::test::TestRequest::get().uri("/todos/");
+        if let Some(sid) = sid {
+            req = req.insert_header(("cookie", format!("{}={sid}", ft_sys_shared::SESSION_KEY)));
+        }
+        let req = fastn_core::http::Request::from_actix(req.to_http_request(), Default::default());
+
+        let render = async {
+            let user = req
+                .cookie(ft_sys_shared::SESSION_KEY)
+                .map(|_| ft_sys_shared::UserData {
+                    id: 7,
+                    identity: "amitu".to_string(),
+                    name: "Amit".to_string(),
+                    email: "amitu@example.com".to_string(),
+                    verified_email: true,
+                });
+            let response = if apps[0].allows(user.as_ref(), req.method()) {
+                actix_web::HttpResponse::Ok().body("the todos of amitu")
+            } else {
+                fastn_core::unauthorised!("anonymous user can not GET /todos/")
+            };
+            Ok::<_, fastn_core::Error>((response, super::is_shareable(apps.as_slice(), req.path())))
+        };
+        cache.get_or_render(&req, render).await.unwrap().status()
+    }
+
+    #[actix_web::test]
+    async fn app_pages_are_not_cached() {
+        let cache = fastn_core::response_cache::ResponseCache::in_memory(
+            fastn_ds::DocumentStore::new(".", actix_web::web::Data::new(Default::default())),
+        );
+        assert_eq!(
+            get_todos(&cache, Some("1")).await,
+            actix_web::http::StatusCode::OK
+        );
+        assert_eq!(
+            get_todos(&cache, None).await,
+            actix_web::http::StatusCode::UNAUTHORIZED
+        );
+    }
+}
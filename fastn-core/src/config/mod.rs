@@ -40,6 +40,14 @@ pub struct Config {
     pub ftd_external_css: Vec<String>,
     pub ftd_inline_css: Vec<String>,
     pub test_command_running: bool,
+    /// set by `fastn serve --live-reload`, pages get a script that reloads them when any file
+    /// in the package changes
+    pub live_reload: bool,
+    /// set by `fastn serve --source-maps`, the JS of pages points to a source map that maps it
+    /// back to the `.ftd` documents
+    pub source_maps: bool,
+    /// set by `fastn serve` with `--cache` or `--cache-dir`, where rendered pages are cached
+    pub response_cache: Option<fastn_core::response_cache::Storage>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +71,13 @@ pub struct RequestConfig {
     pub processor_set_response: Option<ft_sys_shared::Request>,
     /// we use this to determine if the response is cacheable or not
     pub response_is_cacheable: bool,
+    /// set when the page is rendered for the search index, `page_text` gets the text of the
+    /// page, and the `search` processor returns nothing
+    pub collect_page_text: bool,
+    pub page_text: Option<ftd::executor::PageText>,
+    /// set when the page is only read for its text, by the search index of `fastn serve`.
+    /// Processors are not run, their variables are empty.
+    pub skip_processors: bool,
 }
 
 impl RequestConfig {
@@ -96,6 +111,9 @@ impl RequestConfig {
             processor_set_cookies: Default::default(),
             processor_set_response: None,
             response_is_cacheable: true,
+            collect_page_text: false,
+            page_text: None,
+            skip_processors: false,
         }
     }
 
@@ -536,6 +554,7 @@ impl Config {
         let mut ignored_files = vec![
             ".history".to_string(),
             ".packages".to_string(),
+            ".fastn-vendor".to_string(),
             ".tracks".to_string(),
             "fastn".to_string(),
             "rust-toolchain".to_string(),
@@ -585,7 +604,7 @@ impl Config {
         }
 
         for (mp, dep, app) in package.apps.iter().map(|x| (&x.mount_point, &x.package, x)) {
-            if path.starts_with(mp.trim_matches('/')) {
+            if app.path(path).is_some() {
                 // TODO: Need to handle for recursive dependencies mount-point
                 // Note: Currently not working because dependency of package does not contain dependencies
                 let package_name = dep.name.trim_matches('/');
@@ -770,7 +789,10 @@ impl Config {
         Ok((self.package.name.to_string(), self.package.to_owned()))
     }
 
-    fn find_package_id_in_all_packages(&self, id: &str) -> Option<(String, fastn_core::Package)> {
+    pub(crate) fn find_package_id_in_all_packages(
+        &self,
+        id: &str,
+    ) -> Option<(String, fastn_core::Package)> {
         let mut item = self.all_packages.first_entry();
         while let Some(package) = item {
             let package_name = package.key();
@@ -939,11 +961,61 @@ impl Config {
         config
     }
 
+    pub fn set_live_reload(self, live_reload: bool) -> Self {
+        let mut config = self;
+        config.live_reload = live_reload;
+        config
+    }
+
+    pub fn set_source_maps(self, source_maps: bool) -> Self {
+        let mut config = self;
+        config.source_maps = source_maps;
+        config
+    }
+
+    pub fn set_response_cache(
+        self,
+        response_cache: Option<fastn_core::response_cache::Storage>,
+    ) -> Self {
+        let mut config = self;
+        config.response_cache = response_cache;
+        config
+    }
+
+    /// `reload()` reads FASTN.ftd again, so changes to sitemap, redirects, dependencies etc. are
+    /// picked up, and keeps the options that were passed on the command line.
+    #[tracing::instrument(name = "Config::reload", skip_all)]
+    pub async fn reload(&self) -> fastn_core::Result<fastn_core::Config> {
+        let mut config = Config::read(self.ds.clone(), false).await?;
+        config.ftd_edition = self.ftd_edition.clone();
+        config.ftd_external_js.clone_from(&self.ftd_external_js);
+        config.ftd_inline_js.clone_from(&self.ftd_inline_js);
+        config.ftd_external_css.clone_from(&self.ftd_external_css);
+        config.ftd_inline_css.clone_from(&self.ftd_inline_css);
+        config.test_command_running = self.test_command_running;
+        config.live_reload = self.live_reload;
+        config.source_maps = self.source_maps;
+        config.response_cache.clone_from(&self.response_cache);
+        Ok(config)
+    }
+
     /// `read()` is the way to read a Config.
     #[tracing::instrument(name = "Config::read", skip_all)]
     pub async fn read(
         ds: fastn_ds::DocumentStore,
         resolve_sitemap: bool,
+    ) -> fastn_core::Result<fastn_core::Config> {
+        let config = Config::read_without_migrations(ds, resolve_sitemap).await?;
+        fastn_core::migrations::migrate(&config).await?;
+        Ok(config)
+    }
+
+    /// Like `read()`, but does not apply pending migrations. Used by `fastn migrate`, which
+    /// decides itself what to do with them.
+    #[tracing::instrument(name = "Config::read_without_migrations", skip_all)]
+    pub async fn read_without_migrations(
+        ds: fastn_ds::DocumentStore,
+        resolve_sitemap: bool,
     ) -> fastn_core::Result<fastn_core::Config> {
         let original_directory = fastn_ds::Path::new(std::env::current_dir()?.to_str().unwrap()); // todo: remove unwrap()
         let fastn_doc = utils::fastn_doc(&ds, &fastn_ds::Path::new("FASTN.ftd")).await?;
@@ -962,6 +1034,9 @@ impl Config {
             ftd_external_css: Default::default(),
             ftd_inline_css: Default::default(),
             test_command_running: false,
+            live_reload: false,
+            source_maps: false,
+            response_cache: None,
             ds,
         };
         // Update global_ids map from the current package files
@@ -1030,8 +1105,6 @@ impl Config {
             package.to_owned(),
         );
 
-        fastn_core::migrations::migrate(&config).await?;
-
         Ok(config)
     }
 
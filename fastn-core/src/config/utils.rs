@@ -115,14 +115,10 @@ pub fn get_clean_url(
         if let Some(ep) = &app.end_point {
             if let Some(remaining_url) = trim_package_name(url.as_str(), app.package.name.as_str())
             {
-                let mut app_conf = app.config.clone();
-                if let Some(user_id) = &app.user_id {
-                    app_conf.insert("user-id".to_string(), user_id.clone());
-                }
                 return Ok((
                     url::Url::parse(format!("{}{}", ep, remaining_url).as_str())?,
                     Some(app.mount_point.to_string()),
-                    app_conf,
+                    app.endpoint_config(),
                 ));
             }
         }
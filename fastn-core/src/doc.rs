@@ -25,6 +25,34 @@ fn cached_parse(
     fastn_core::utils::cache_it(id, C { doc, hash }).map(|v| v.doc)
 }
 
+/// The content of the document with the auto imports of the package, and the number of lines
+/// added before the content.
+pub(crate) fn prefixed_body(
+    config: &fastn_core::Config,
+    document: &fastn_core::Document,
+) -> ftd::interpreter::Result<(String, usize)> {
+    let current_package = config.find_package_else_default(document.package_name.as_str(), None);
+    let body =
+        current_package.get_prefixed_body(document.content.as_str(), document.id.as_str(), true);
+    let body = current_package.fix_imports_in_body(body.as_str(), document.id.as_str())?;
+    let line_number = body.split('\n').count() - document.content.split('\n').count();
+    Ok((body, line_number))
+}
+
+/// The diagnostic for an error found while interpreting `document`, with the lines of the
+/// document around it if the error is in the document and not in something it imports.
+pub(crate) fn diagnostic(
+    e: &ftd::interpreter::Error,
+    document: &fastn_core::Document,
+) -> fastn_issues::diagnostic::Diagnostic {
+    let diagnostic = fastn_issues::diagnostic::Diagnostic::from(e);
+    if diagnostic.is_in(document.id_with_package().as_str()) {
+        diagnostic.set_source(document.id.as_str(), document.content.as_str())
+    } else {
+        diagnostic
+    }
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn interpret_helper(
     name: &str,
@@ -154,6 +182,7 @@ pub async fn resolve_import_2022(
                 "document-name".to_string(),
                 "user-details".to_string(),
                 "fastn-apps".to_string(),
+                "app-config".to_string(),
                 "is-reader".to_string(),
                 "sql-query".to_string(),
                 "sql-execute".to_string(),
@@ -163,12 +192,21 @@ pub async fn resolve_import_2022(
                 "package-tree".to_string(),
                 "fetch-file".to_string(),
                 "query".to_string(),
+                "search".to_string(),
                 "current-language".to_string(),
                 "current-url".to_string(),
                 "translation-info".to_string(),
             ],
             0,
         )
+    } else if module.eq("fastn/search") {
+        (
+            fastn_core::search_ftd().to_string(),
+            "$fastn$/search.ftd".to_string(),
+            vec![],
+            vec![],
+            0,
+        )
     } else if module.ends_with("assets") {
         let foreign_variable = vec!["files".to_string()];
 
@@ -239,6 +277,7 @@ pub async fn resolve_import_2022(
                 "request-data".to_string(),
                 "user-details".to_string(),
                 "fastn-apps".to_string(),
+                "app-config".to_string(),
                 "is-reader".to_string(),
                 "current-language".to_string(),
                 "current-url".to_string(),
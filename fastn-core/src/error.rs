@@ -30,6 +30,10 @@ pub enum Error {
     #[error("FTDInterpreterError: {}", _0)]
     FTDInterpreterError(#[from] ftd::interpreter::Error),
 
+    /// An error in a document, with the lines of the document around it.
+    #[error("{}", _0)]
+    Diagnostic(Box<fastn_issues::diagnostic::Diagnostic>),
+
     #[error("FTDHtmlError: {}", _0)]
     FTDHtmlError(#[from] ftd::html::Error),
 
@@ -81,6 +85,9 @@ pub enum Error {
     #[error("UpdateError: {message}")]
     UpdateError { message: String },
 
+    #[error("TlsError: {message}")]
+    TlsError { message: String },
+
     #[error("GenericError: {}", _0)]
     GenericError(String),
 
@@ -141,6 +148,9 @@ pub enum Error {
     #[error("SSRError: {:?}", _0)]
     SSRError(#[from] fastn_js::SSRError),
 
+    #[error("NotifyError: {}", _0)]
+    NotifyError(#[from] notify::Error),
+
     #[error("MigrationError: {0}")]
     MigrationError(#[from] fastn_core::migrations::MigrationError),
 }
@@ -151,6 +161,12 @@ impl From<std::convert::Infallible> for Error {
     }
 }
 
+impl From<fastn_issues::diagnostic::Diagnostic> for Error {
+    fn from(d: fastn_issues::diagnostic::Diagnostic) -> Self {
+        Self::Diagnostic(Box::new(d))
+    }
+}
+
 impl Error {
     pub fn generic<T: AsRef<str> + ToString>(error: T) -> Self {
         Self::GenericError(error.to_string())
@@ -174,6 +190,12 @@ impl Error {
                 tracing::info!("not found: {:?}", message);
                 fastn_core::http::Response::NotFound().body(message.to_string())
             }
+            Error::Diagnostic(d) => {
+                tracing::error!("error: {}", d.message);
+                fastn_core::http::Response::InternalServerError()
+                    .content_type(mime_guess::mime::TEXT_HTML_UTF_8)
+                    .body(d.to_html())
+            }
             Error::DSReadError(fastn_ds::ReadError::NotFound(f)) => {
                 tracing::info!("ds read error, not found: {f}");
                 fastn_core::http::Response::NotFound().body("page not found: {f}")
@@ -21,6 +21,13 @@ macro_rules! unauthorised {
     }};
 }
 
+#[macro_export]
+macro_rules! forbidden {
+    ($($t:tt)*) => {{
+        fastn_core::http::forbidden_(format!($($t)*))
+    }};
+}
+
 pub fn api_ok(data: impl serde::Serialize) -> serde_json::Result<fastn_core::http::Response> {
     #[derive(serde::Serialize)]
     struct SuccessResponse<T: serde::Serialize> {
@@ -44,6 +51,11 @@ pub fn unauthorised_(msg: String) -> fastn_core::http::Response {
     actix_web::HttpResponse::Unauthorized().body(msg)
 }
 
+pub fn forbidden_(msg: String) -> fastn_core::http::Response {
+    fastn_core::warning!("forbidden: {}", msg);
+    actix_web::HttpResponse::Forbidden().body(msg)
+}
+
 pub fn server_error_(msg: String) -> fastn_core::http::Response {
     fastn_core::warning!("server error: {}", msg);
     server_error_without_warning(msg)
@@ -350,6 +362,15 @@ impl Request {
             None => true,
         }
     }
+
+    /// The `ftd#device` a page is server rendered for: `mobile` if the user agent says it is
+    /// one, `desktop` otherwise. The browser switches to the actual device after hydrating.
+    pub fn device(&self) -> &'static str {
+        match self.user_agent() {
+            Some(user_agent) if user_agent.contains("Mobi") => "mobile",
+            _ => "desktop",
+        }
+    }
 }
 
 pub(crate) fn url_regex() -> regex::Regex {
@@ -13,13 +13,19 @@ mod file;
 mod font;
 pub mod manifest;
 pub mod package;
+pub mod response_cache;
 #[macro_use]
 pub mod http;
 mod ds;
 mod error;
 pub mod library;
+mod live_reload;
+mod search;
+mod seo;
 pub mod sitemap;
 mod snapshot;
+mod source_map;
+mod tls;
 mod tracker;
 mod translation;
 mod version;
@@ -31,7 +37,14 @@ mod migrations;
 
 pub(crate) use auto_import::AutoImport;
 pub use commands::{
-    build::build, check::post_build_check, fmt::fmt, query::query, serve::listen, test::test,
+    build::build,
+    check::{post_build_check, type_check},
+    fmt::{fmt, fmt_stdin},
+    lsp::lsp,
+    migrate::{migrate, MigrateCommand},
+    query::query,
+    serve::listen,
+    test::test,
 };
 pub use config::{config_temp, Config, ConfigTemp, FTDEdition, RequestConfig};
 pub use doc::resolve_foreign_variable2;
@@ -45,6 +58,7 @@ pub use manifest::Manifest;
 pub(crate) use package::dependency::Dependency;
 pub use package::Package;
 pub(crate) use snapshot::Snapshot;
+pub use tls::Tls;
 pub(crate) use tracker::Track;
 pub(crate) use translation::{TranslatedDocument, TranslationData};
 
@@ -64,6 +78,10 @@ fn processor_ftd() -> &'static str {
     include_str!("../ftd/processors.ftd")
 }
 
+fn search_ftd() -> &'static str {
+    include_str!("../ftd/search.ftd")
+}
+
 fn fastn_2022_js() -> &'static str {
     if fastn_core::utils::is_test() {
         return "FASTN_JS";
@@ -241,6 +241,9 @@ impl Library2022 {
         );
         let line_number = ast.line_number();
         let (_processor, variable_name, value, kind) = get_processor_data(ast, doc)?;
+        if self.skip_processors {
+            return empty_value(kind, doc.name, line_number);
+        }
         match processor.as_str() {
             "figma-typo-token" => {
                 processor::figma_typography_tokens::process_typography_tokens(value, kind, doc)
@@ -284,6 +287,7 @@ impl Library2022 {
             "fetch-file" => processor::fetch_file::fetch_files(value, kind, doc, self).await,
             "user-details" => processor::user_details::process(value, kind, doc, self).await,
             "fastn-apps" => processor::apps::process(value, kind, doc, self),
+            "app-config" => processor::apps::config(value, kind, doc, self),
             "is-reader" => processor::user_group::is_reader(value, kind, doc, self).await,
             "sql-query" => processor::sql::process(value, kind, doc, self, "sql-query").await,
             "sql-execute" => processor::sql::process(value, kind, doc, self, "sql-execute").await,
@@ -291,6 +295,7 @@ impl Library2022 {
             // "package-query" => processor::package_query::process(value, kind, doc, self).await,
             // "pg" => processor::pg::process(value, kind, doc, self).await,
             "query" => processor::query::process(value, kind, doc, self).await,
+            "search" => processor::search::process(value, kind, doc, self).await,
             t => Err(ftd::interpreter::Error::ParseError {
                 doc_id: self.document_id.to_string(),
                 line_number,
@@ -300,6 +305,42 @@ impl Library2022 {
     }
 }
 
+/// The value of a variable whose processor is not run: nothing, an empty list or string, zero
+/// or false. Other kinds, eg records, have no empty value.
+fn empty_value(
+    kind: ftd::interpreter::Kind,
+    doc_id: &str,
+    line_number: usize,
+) -> ftd::interpreter::Result<ftd::interpreter::Value> {
+    Ok(match kind {
+        ftd::interpreter::Kind::Optional { kind } => ftd::interpreter::Value::Optional {
+            data: Box::new(None),
+            kind: (*kind).into_kind_data(),
+        },
+        ftd::interpreter::Kind::List { kind } => ftd::interpreter::Value::List {
+            data: vec![],
+            kind: (*kind).into_kind_data(),
+        },
+        ftd::interpreter::Kind::String => ftd::interpreter::Value::String {
+            text: String::new(),
+        },
+        ftd::interpreter::Kind::Integer => ftd::interpreter::Value::Integer { value: 0 },
+        ftd::interpreter::Kind::Decimal => ftd::interpreter::Value::Decimal { value: 0.0 },
+        ftd::interpreter::Kind::Boolean => ftd::interpreter::Value::Boolean { value: false },
+        ftd::interpreter::Kind::Constant { kind } => empty_value(*kind, doc_id, line_number)?,
+        kind => {
+            return ftd::interpreter::utils::e2(
+                format!(
+                    "processors are not run, a {} has no empty value",
+                    kind.get_name()
+                ),
+                doc_id,
+                line_number,
+            )
+        }
+    })
+}
+
 fn get_processor_data(
     ast: ftd_ast::Ast,
     doc: &mut ftd::interpreter::TDoc,
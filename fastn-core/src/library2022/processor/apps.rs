@@ -30,3 +30,22 @@ pub fn process(
     let installed_apps = fastn_core::ds::LengthList::from_owned(apps);
     doc.from_json(&installed_apps, &kind, &value)
 }
+
+/// processor: app-config
+/// The `config` of the `fastn.app` the current request is for, empty outside apps
+pub fn config(
+    value: ftd_ast::VariableValue,
+    kind: ftd::interpreter::Kind,
+    doc: &ftd::interpreter::TDoc,
+    req_config: &fastn_core::RequestConfig,
+) -> ftd::interpreter::Result<ftd::interpreter::Value> {
+    let config = req_config
+        .config
+        .package
+        .apps
+        .iter()
+        .find(|a| a.path(req_config.request.path()).is_some())
+        .map(|a| a.config.clone())
+        .unwrap_or_default();
+    doc.from_json(&config, &kind, &value)
+}
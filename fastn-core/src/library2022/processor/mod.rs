@@ -12,6 +12,7 @@ pub(crate) mod lang_details;
 // pub(crate) mod pg;
 pub(crate) mod query;
 pub(crate) mod request_data;
+pub(crate) mod search;
 pub(crate) mod sitemap;
 pub(crate) mod sql;
 pub(crate) mod sqlite;
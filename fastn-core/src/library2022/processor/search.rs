@@ -0,0 +1,45 @@
+/// The pages of the package that match `query`, the header or else the `q` query parameter,
+/// best match first, at most `limit` of them.
+pub async fn process(
+    value: ftd_ast::VariableValue,
+    kind: ftd::interpreter::Kind,
+    doc: &ftd::interpreter::TDoc<'_>,
+    req_config: &mut fastn_core::RequestConfig,
+) -> ftd::interpreter::Result<ftd::interpreter::Value> {
+    req_config.response_is_cacheable = false;
+
+    let headers = match value.get_record(doc.name) {
+        Ok(val) => val.2.to_owned(),
+        Err(_e) => ftd_ast::HeaderValues::new(vec![]),
+    };
+    let (q, limit) = fastn_core::search::query(&req_config.request);
+    let query = headers
+        .get_optional_string_by_key("query", doc.name, value.line_number())?
+        .unwrap_or(q);
+    let limit = match headers.get_optional_string_by_key("limit", doc.name, value.line_number())? {
+        Some(l) => l.parse().map_err(|e| ftd::interpreter::Error::ParseError {
+            message: format!("limit must be a number: {:?}", e),
+            doc_id: req_config.document_id.to_string(),
+            line_number: value.line_number(),
+        })?,
+        None => limit,
+    };
+
+    // the page is being indexed, searching would index it again
+    if req_config.collect_page_text {
+        return doc.from_json(
+            &Vec::<fastn_core::search::SearchResult>::new(),
+            &kind,
+            &value,
+        );
+    }
+
+    let index = fastn_core::search::index(&req_config.config)
+        .await
+        .map_err(|e| ftd::interpreter::Error::ParseError {
+            message: format!("Cannot build the search index: {:?}", e),
+            doc_id: req_config.document_id.to_string(),
+            line_number: value.line_number(),
+        })?;
+    doc.from_json(&index.search(query.as_str(), limit), &kind, &value)
+}
@@ -3,8 +3,10 @@ pub async fn process(
     value: ftd_ast::VariableValue,
     kind: ftd::interpreter::Kind,
     doc: &ftd::interpreter::TDoc<'_>,
-    req_config: &fastn_core::RequestConfig,
+    req_config: &mut fastn_core::RequestConfig,
 ) -> ftd::interpreter::Result<ftd::interpreter::Value> {
+    req_config.response_is_cacheable = false;
+
     match ud(
         &req_config.config.ds,
         req_config.config.get_db_url().await.as_str(),
@@ -0,0 +1,198 @@
+//! `fastn serve --live-reload` watches the package directory and asks every open page to reload
+//! itself, over a server sent events stream at `/-/live-reload/`, when a file changes.
+//!
+//! If `FASTN.ftd` changes we also re-read the config, so sitemap, redirects and dependency
+//! changes take effect without restarting the server.
+
+pub const LIVE_RELOAD_PATH: &str = "/-/live-reload/";
+
+/// The config used by `fastn serve`. It sits behind a lock so it can be swapped when `FASTN.ftd`
+/// changes; request handlers clone the inner `Arc` and never hold the lock across an await.
+pub type SharedConfig = std::sync::Arc<antidote::RwLock<std::sync::Arc<fastn_core::Config>>>;
+
+#[derive(Clone)]
+pub struct LiveReload {
+    sender: tokio::sync::broadcast::Sender<()>,
+    // the watcher stops watching when dropped, so we keep it around as long as the server runs
+    _watcher: std::sync::Arc<notify::RecommendedWatcher>,
+}
+
+impl LiveReload {
+    pub fn start(config: SharedConfig) -> fastn_core::Result<LiveReload> {
+        let root = std::path::PathBuf::from(config.read().ds.root().to_string());
+        let (watcher, mut changes) = watch(root)?;
+
+        let (sender, _) = tokio::sync::broadcast::channel(16);
+        let reload_sender = sender.clone();
+
+        tokio::spawn(async move {
+            while let Some(changed) = changes.recv().await {
+                tracing::info!(msg = "live-reload", changed = ?changed);
+
+                if changed
+                    .iter()
+                    .any(|p| p.as_path() == std::path::Path::new("FASTN.ftd"))
+                {
+                    let current = std::sync::Arc::clone(&config.read());
+                    match current.reload().await {
+                        Ok(c) => *config.write() = std::sync::Arc::new(c),
+                        Err(e) => {
+                            // we keep serving with the old config till FASTN.ftd is fixed
+                            fastn_core::warning!("failed to reload FASTN.ftd: {}", e);
+                            continue;
+                        }
+                    }
+                }
+
+                // no one may be listening, which is fine
+                let _ = reload_sender.send(());
+            }
+        });
+
+        Ok(LiveReload {
+            sender,
+            _watcher: std::sync::Arc::new(watcher),
+        })
+    }
+
+    /// A `text/event-stream` response that sends a `reload` message for every change.
+    pub fn events(&self) -> fastn_core::http::Response {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let stream = futures::stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(()) => {
+                        return Some((
+                            Ok::<_, actix_web::Error>(bytes::Bytes::from_static(
+                                b"data: reload\n\n",
+                            )),
+                            receiver,
+                        ))
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        actix_web::HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .append_header(("Cache-Control", "no-cache"))
+            // the compress middleware buffers the body, which would hold back the events
+            .insert_header(actix_web::http::header::ContentEncoding::Identity)
+            .streaming(stream)
+    }
+}
+
+/// Watches `root` and sends the paths, relative to `root`, that changed. The watcher stops
+/// watching when dropped.
+pub(crate) fn watch(
+    root: std::path::PathBuf,
+) -> fastn_core::Result<(
+    notify::RecommendedWatcher,
+    tokio::sync::mpsc::UnboundedReceiver<Vec<std::path::PathBuf>>,
+)> {
+    use notify::Watcher;
+
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            // the receiver only goes away when the server is shutting down
+            Ok(event) => {
+                let _ = event_sender.send(event);
+            }
+            Err(e) => tracing::error!(msg = "watch error", error = %e),
+        })?;
+    watcher.watch(root.as_path(), notify::RecursiveMode::Recursive)?;
+
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Some(event) = event_receiver.recv().await {
+            let mut changed = changed_paths(root.as_path(), event);
+
+            // editors often write a file in more than one step, so we wait a little and
+            // collect everything that changed in the meantime
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            while let Ok(event) = event_receiver.try_recv() {
+                changed.extend(changed_paths(root.as_path(), event));
+            }
+
+            if changed.is_empty() {
+                continue;
+            }
+
+            if sender.send(changed).is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok((watcher, receiver))
+}
+
+/// Paths, relative to the package root, touched by `event` that should trigger a reload.
+fn changed_paths(root: &std::path::Path, event: notify::Event) -> Vec<std::path::PathBuf> {
+    if !matches!(
+        event.kind,
+        notify::EventKind::Create(_) | notify::EventKind::Modify(_) | notify::EventKind::Remove(_)
+    ) {
+        return vec![];
+    }
+
+    event
+        .paths
+        .into_iter()
+        .filter_map(|p| p.strip_prefix(root).ok().map(|p| p.to_path_buf()))
+        .filter(|p| !is_ignored(p))
+        .collect()
+}
+
+/// We ignore hidden files and folders (`.build`, `.git`, editor swap files etc.), except for
+/// `.packages`, and files that change as a side effect of serving, like the sqlite database.
+fn is_ignored(path: &std::path::Path) -> bool {
+    let hidden = path.components().any(|c| {
+        let c = c.as_os_str().to_string_lossy();
+        c.starts_with('.') && c != ".packages"
+    });
+
+    let generated = path
+        .to_str()
+        .map(|p| {
+            p.ends_with('~')
+                || [
+                    ".sqlite",
+                    ".sqlite-journal",
+                    ".sqlite-wal",
+                    ".sqlite-shm",
+                    ".wasmc",
+                ]
+                .iter()
+                .any(|ext| p.ends_with(ext))
+        })
+        .unwrap_or_default();
+
+    hidden || generated
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn is_ignored() {
+        let ignored = |p: &str| super::is_ignored(std::path::Path::new(p));
+
+        assert!(!ignored("index.ftd"));
+        assert!(!ignored("FASTN.ftd"));
+        assert!(!ignored("static/logo.png"));
+        assert!(!ignored(
+            ".packages/fastn-community.github.io/doc-site/index.ftd"
+        ));
+        assert!(ignored(".build/index.html"));
+        assert!(ignored(".git/index"));
+        assert!(ignored(".index.ftd.swp"));
+        assert!(ignored("index.ftd~"));
+        assert!(ignored("fastn.sqlite"));
+        assert!(ignored("fastn.sqlite-journal"));
+        assert!(ignored("backend.wasmc"));
+    }
+}
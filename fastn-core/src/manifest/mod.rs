@@ -8,6 +8,9 @@ pub struct Manifest {
     pub files: std::collections::BTreeMap<String, File>,
     pub zip_url: String,
     pub checksum: String,
+    /// The `version` of the package, from its `FASTN.ftd`, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 impl Manifest {
@@ -20,6 +23,7 @@ impl Manifest {
             files,
             zip_url,
             checksum,
+            version: None,
         }
     }
 }
@@ -96,7 +100,8 @@ pub async fn write_manifest_file(
 
     let checksum = format!("{:X}", hasher.finalize_fixed());
 
-    let manifest = fastn_core::Manifest::new(files, zip_url, checksum);
+    let mut manifest = fastn_core::Manifest::new(files, zip_url, checksum);
+    manifest.version = config.package.version.clone();
 
     let mut serialized_manifest = serde_json::ser::to_vec_pretty(&manifest)?;
     // Append newline character
@@ -1,7 +1,7 @@
 static GITHUB_PAGES_REGEX: once_cell::sync::Lazy<regex::Regex> =
     once_cell::sync::Lazy::new(|| regex::Regex::new(r"([^/]+)\.github\.io/([^/]+)").unwrap());
 
-fn extract_github_details(package_name: &str) -> Option<(String, String)> {
+pub fn extract_github_details(package_name: &str) -> Option<(String, String)> {
     if let Some(captures) = GITHUB_PAGES_REGEX.captures(package_name) {
         let username = captures.get(1).unwrap().as_str().to_string();
         let repository = captures.get(2).unwrap().as_str().to_string();
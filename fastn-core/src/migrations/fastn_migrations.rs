@@ -1,8 +1,17 @@
-pub(crate) fn fastn_migrations() -> Vec<fastn_core::package::MigrationData> {
+pub(crate) fn fastn_migrations(postgres: bool) -> Vec<fastn_core::package::MigrationData> {
     vec![fastn_core::package::MigrationData {
         number: 0,
         name: "initial".to_string(),
-        content: r#"
+        content: if postgres {
+            INITIAL_PG.to_string()
+        } else {
+            INITIAL.to_string()
+        },
+        down: None,
+    }]
+}
+
+const INITIAL: &str = r#"
             CREATE TABLE IF NOT EXISTS fastn_user
             (
                 id           INTEGER PRIMARY KEY,
@@ -48,10 +57,55 @@ pub(crate) fn fastn_migrations() -> Vec<fastn_core::package::MigrationData> {
                 status       TEXT              NOT NULL
             ) STRICT;
 
-            "#
-        .to_string(),
-    }]
-}
+            "#;
+
+const INITIAL_PG: &str = r#"
+            CREATE TABLE IF NOT EXISTS fastn_user
+            (
+                id           BIGSERIAL PRIMARY KEY,
+                name         TEXT,
+                identity     TEXT      UNIQUE,
+                data         TEXT      NOT NULL,
+                created_at   BIGINT    NOT NULL,
+                updated_at   BIGINT    NOT NULL
+            );
+
+
+            CREATE TABLE IF NOT EXISTS fastn_session
+            (
+                id         TEXT    NOT NULL PRIMARY KEY,
+                uid        BIGINT,
+                data       TEXT    NOT NULL,
+                created_at BIGINT  NOT NULL,
+                updated_at BIGINT  NOT NULL,
+
+                CONSTRAINT fk_fastn_user
+                FOREIGN KEY (uid)
+                REFERENCES fastn_user (id)
+            );
+
+
+            CREATE TABLE IF NOT EXISTS fastn_email_queue
+            (
+                id           BIGSERIAL        PRIMARY KEY,
+                from_address TEXT             NOT NULL,
+                from_name    TEXT             NOT NULL,
+                reply_to     TEXT,
+                to_address   TEXT             NOT NULL,
+                cc_address   TEXT,
+                bcc_address  TEXT,
+                subject      TEXT             NOT NULL,
+                body_text    TEXT             NOT NULL,
+                body_html    TEXT             NOT NULL,
+                retry_count  BIGINT DEFAULT 0 NOT NULL,
+                created_at   BIGINT           NOT NULL,
+                updated_at   BIGINT           NOT NULL,
+                sent_at      BIGINT           NOT NULL,
+                mkind        TEXT             NOT NULL,
+                status       TEXT             NOT NULL
+            );
+
+            "#;
 
 pub const MIGRATION_TABLE: &str = r#"
 
@@ -62,7 +116,23 @@ CREATE TABLE IF NOT EXISTS fastn_migration
     migration_number INTEGER NOT NULL,
     migration_name   TEXT NOT NULL,
     applied_on       INTEGER NOT NULL,
+    checksum         TEXT,
     UNIQUE (app_name, migration_number)
 ) STRICT;
 
 "#;
+
+pub const MIGRATION_TABLE_PG: &str = r#"
+
+CREATE TABLE IF NOT EXISTS fastn_migration
+(
+    id               BIGSERIAL PRIMARY KEY,
+    app_name         TEXT NOT NULL,
+    migration_number BIGINT NOT NULL,
+    migration_name   TEXT NOT NULL,
+    applied_on       BIGINT NOT NULL,
+    checksum         TEXT,
+    UNIQUE (app_name, migration_number)
+);
+
+"#;
@@ -9,28 +9,193 @@ pub(crate) async fn migrate(config: &fastn_core::Config) -> Result<(), Migration
     create_migration_table(config).await?;
 
     let now = chrono::Utc::now().timestamp_nanos_opt().unwrap();
-    migrate_fastn(config, now).await?;
-    migrate_app(config, now).await?;
+    migrate_fastn(config, now, false).await?;
+    migrate_app(config, now, false).await?;
 
     Ok(())
 }
 
-async fn migrate_app(config: &fastn_core::Config, now: i64) -> Result<(), MigrationError> {
+/// Applies the pending migrations of this package, used by `fastn migrate up`. With `dry_run`
+/// the SQL is printed instead of executed, and the database is not changed at all.
+pub(crate) async fn migrate_up(
+    config: &fastn_core::Config,
+    dry_run: bool,
+) -> Result<(), MigrationError> {
+    if !dry_run {
+        create_migration_table(config).await?;
+    }
+
+    let now = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+    migrate_fastn(config, now, dry_run).await?;
+    migrate_app(config, now, dry_run).await?;
+
+    Ok(())
+}
+
+/// Reverts the last `count` applied migrations of this package, latest first, used by
+/// `fastn migrate down`. Every one of them must have `down` SQL.
+pub(crate) async fn migrate_down(
+    config: &fastn_core::Config,
+    count: usize,
+    dry_run: bool,
+) -> Result<(), MigrationError> {
+    if !dry_run {
+        create_migration_table(config).await?;
+    }
+
+    let app_name = config.package.name.as_str();
+    let available_migrations = config.package.migrations.as_slice();
+    let applied_migrations = find_applied_migrations(config, app_name).await?;
+    verify_applied_migrations(available_migrations, applied_migrations.as_slice())?;
+
+    for applied in applied_migrations.iter().rev().take(count) {
+        // verify_applied_migrations ensures every applied migration is still available
+        let migration = available_migrations
+            .iter()
+            .find(|m| m.number == applied.number)
+            .unwrap();
+        let down = match migration.down.as_ref() {
+            Some(down) => down,
+            None => {
+                return Err(MigrationError::MissingDownMigration {
+                    name: migration.name.to_string(),
+                })
+            }
+        };
+
+        let statements = revert_migration_statements(app_name, migration, down);
+
+        if dry_run {
+            println!("Would revert Migration for {app_name}: {}", migration.name);
+            print_statements(statements.as_slice());
+            continue;
+        }
+
+        println!("Reverting Migration for {app_name}: {}", migration.name);
+        let db = config.get_db_url().await;
+        config.ds.sql_transaction(db.as_str(), statements).await?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum MigrationState {
+    Applied {
+        applied_on: i64,
+    },
+    Pending,
+    /// The migration was applied, but its name or content has changed since.
+    Modified,
+    /// The migration was applied, but is no longer in FASTN.ftd.
+    Deleted,
+}
+
+#[derive(Debug)]
+pub(crate) struct MigrationStatus {
+    pub app_name: String,
+    pub number: i64,
+    pub name: String,
+    pub state: MigrationState,
+}
+
+/// The state of every known migration, of fastn itself and of this package, used by
+/// `fastn migrate status`.
+pub(crate) async fn migration_status(
+    config: &fastn_core::Config,
+) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let db = config.get_db_url().await;
+    let mut status = vec![];
+    for (app_name, available_migrations) in [
+        (
+            "fastn",
+            fastn_migrations::fastn_migrations(fastn_ds::is_pg_url(db.as_str())),
+        ),
+        (
+            config.package.name.as_str(),
+            config.package.migrations.clone(),
+        ),
+    ] {
+        let applied_migrations = find_applied_migrations(config, app_name).await?;
+        status.extend(
+            migration_states(available_migrations.as_slice(), &applied_migrations)
+                .into_iter()
+                .map(|(number, name, state)| MigrationStatus {
+                    app_name: app_name.to_string(),
+                    number,
+                    name,
+                    state,
+                }),
+        );
+    }
+
+    Ok(status)
+}
+
+fn migration_states(
+    available_migrations: &[fastn_core::package::MigrationData],
+    applied_migrations: &[AppliedMigration],
+) -> Vec<(i64, String, MigrationState)> {
+    let mut states = vec![];
+
+    for applied in applied_migrations {
+        let state = match available_migrations
+            .iter()
+            .find(|m| m.number == applied.number)
+        {
+            None => MigrationState::Deleted,
+            Some(m) if !applied.matches(m) => MigrationState::Modified,
+            Some(_) => MigrationState::Applied {
+                applied_on: applied.applied_on,
+            },
+        };
+        states.push((applied.number, applied.name.to_string(), state));
+    }
+
+    for migration in available_migrations {
+        if !applied_migrations
+            .iter()
+            .any(|a| a.number == migration.number)
+        {
+            states.push((
+                migration.number,
+                migration.name.to_string(),
+                MigrationState::Pending,
+            ));
+        }
+    }
+
+    states.sort_by_key(|(number, _, _)| *number);
+    states
+}
+
+async fn migrate_app(
+    config: &fastn_core::Config,
+    now: i64,
+    dry_run: bool,
+) -> Result<(), MigrationError> {
     migrate_(
         config,
         config.package.migrations.as_slice(),
         config.package.name.as_str(),
         now,
+        dry_run,
     )
     .await
 }
 
-async fn migrate_fastn(config: &fastn_core::Config, now: i64) -> Result<(), MigrationError> {
+async fn migrate_fastn(
+    config: &fastn_core::Config,
+    now: i64,
+    dry_run: bool,
+) -> Result<(), MigrationError> {
+    let db = config.get_db_url().await;
     migrate_(
         config,
-        fastn_migrations::fastn_migrations().as_slice(),
+        fastn_migrations::fastn_migrations(fastn_ds::is_pg_url(db.as_str())).as_slice(),
         "fastn",
         now,
+        dry_run,
     )
     .await
 }
@@ -40,13 +205,32 @@ async fn migrate_(
     available_migrations: &[fastn_core::package::MigrationData],
     app_name: &str,
     now: i64,
+    dry_run: bool,
 ) -> Result<(), MigrationError> {
-    let latest_applied_migration_number =
-        find_latest_applied_migration_number(config, app_name).await?;
+    validate_migrations(available_migrations)?;
+
+    let applied_migrations = find_applied_migrations(config, app_name).await?;
+    verify_applied_migrations(available_migrations, applied_migrations.as_slice())?;
+    if !dry_run {
+        backfill_checksums(
+            config,
+            app_name,
+            available_migrations,
+            applied_migrations.as_slice(),
+        )
+        .await?;
+    }
+
+    let latest_applied_migration_number = applied_migrations.last().map(|m| m.number);
     let migrations =
         find_migrations_to_apply(available_migrations, latest_applied_migration_number)?;
 
     for migration in migrations {
+        if dry_run {
+            println!("Would apply Migration for {app_name}: {}", migration.name);
+            print_statements(apply_migration_statements(app_name, &migration, now).as_slice());
+            continue;
+        }
         println!("Applying Migration for {app_name}: {}", migration.name);
         apply_migration(config, app_name, &migration, now).await?;
     }
@@ -61,25 +245,56 @@ async fn apply_migration(
     now: i64,
 ) -> Result<(), MigrationError> {
     let db = config.get_db_url().await;
-    validate_migration(migration)?;
-    // Create the SQL to mark the migration as applied.
-    let mark_migration_applied_content = mark_migration_applied_content(app_name, migration, now);
-
-    // Combine the user-provided migration content and the marking content to run in a
-    // transaction.
-    let migration_content = format!(
-        "BEGIN;\n{}\n\n{}\nCOMMIT;",
-        migration.content, mark_migration_applied_content
-    );
 
     config
         .ds
-        .sql_batch(db.as_str(), migration_content.as_str())
+        .sql_transaction(
+            db.as_str(),
+            apply_migration_statements(app_name, migration, now),
+        )
         .await?;
 
     Ok(())
 }
 
+/// The user-provided migration content and the statement marking it applied, to run in a
+/// single transaction.
+fn apply_migration_statements(
+    app_name: &str,
+    migration: &fastn_core::package::MigrationData,
+    now: i64,
+) -> Vec<fastn_ds::SqlStatement> {
+    vec![
+        fastn_ds::SqlStatement::Batch(migration.content.to_string()),
+        mark_migration_applied_statement(app_name, migration, now),
+    ]
+}
+
+/// The user-provided `down` content and the statement marking the migration reverted, to run
+/// in a single transaction.
+fn revert_migration_statements(
+    app_name: &str,
+    migration: &fastn_core::package::MigrationData,
+    down: &str,
+) -> Vec<fastn_ds::SqlStatement> {
+    vec![
+        fastn_ds::SqlStatement::Batch(down.to_string()),
+        mark_migration_reverted_statement(app_name, migration),
+    ]
+}
+
+fn print_statements(statements: &[fastn_ds::SqlStatement]) {
+    for statement in statements {
+        match statement {
+            fastn_ds::SqlStatement::Batch(query) => println!("{query}"),
+            fastn_ds::SqlStatement::Execute { query, params } => {
+                println!("{query}");
+                println!("-- params: {params:?}");
+            }
+        }
+    }
+}
+
 fn find_migrations_to_apply(
     available_migrations: &[fastn_core::package::MigrationData],
     after: Option<i64>,
@@ -95,6 +310,21 @@ fn find_migrations_to_apply(
     Ok(migrations)
 }
 
+fn validate_migrations(
+    migrations: &[fastn_core::package::MigrationData],
+) -> Result<(), MigrationError> {
+    let mut names = std::collections::HashSet::new();
+    for migration in migrations {
+        validate_migration(migration)?;
+        if !names.insert(migration.name.as_str()) {
+            return Err(MigrationError::MigrationNameConflict {
+                name: migration.name.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
 fn validate_migration(
     migration: &fastn_core::package::MigrationData,
 ) -> Result<(), MigrationError> {
@@ -108,6 +338,32 @@ fn validate_migration(
     Ok(())
 }
 
+/// Every applied migration must still be present in FASTN.ftd, with the same name and content.
+fn verify_applied_migrations(
+    available_migrations: &[fastn_core::package::MigrationData],
+    applied_migrations: &[AppliedMigration],
+) -> Result<(), MigrationError> {
+    for applied in applied_migrations {
+        match available_migrations
+            .iter()
+            .find(|m| m.number == applied.number)
+        {
+            None => {
+                return Err(MigrationError::AppliedMigrationDeletion {
+                    name: applied.name.to_string(),
+                })
+            }
+            Some(m) if !applied.matches(m) => {
+                return Err(MigrationError::AppliedMigrationMismatch {
+                    name: applied.name.to_string(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
 fn has_migrations(config: &fastn_core::Config) -> bool {
     !config.package.migrations.is_empty()
 }
@@ -115,75 +371,328 @@ fn has_migrations(config: &fastn_core::Config) -> bool {
 async fn create_migration_table(config: &fastn_core::Config) -> Result<(), fastn_utils::SqlError> {
     let db = config.get_db_url().await;
 
+    if fastn_ds::is_pg_url(db.as_str()) {
+        config
+            .ds
+            .sql_batch(&db, fastn_migrations::MIGRATION_TABLE_PG)
+            .await?;
+        return Ok(());
+    }
+
     config
         .ds
         .sql_batch(&db, fastn_migrations::MIGRATION_TABLE)
         .await?;
+
+    // tables created before we started storing checksums do not have the column yet
+    if !migration_table_columns(config)
+        .await?
+        .iter()
+        .any(|c| c == "checksum")
+    {
+        config
+            .ds
+            .sql_batch(&db, "ALTER TABLE fastn_migration ADD COLUMN checksum TEXT;")
+            .await?;
+    }
+
     Ok(())
 }
 
-async fn find_latest_applied_migration_number(
+/// The columns of the migration table, empty if it has not been created yet.
+async fn migration_table_columns(
+    config: &fastn_core::Config,
+) -> Result<Vec<String>, fastn_utils::SqlError> {
+    let db = config.get_db_url().await;
+
+    let (query, name_column) = if fastn_ds::is_pg_url(db.as_str()) {
+        (
+            "SELECT column_name FROM information_schema.columns \
+            WHERE table_name = 'fastn_migration';",
+            0,
+        )
+    } else {
+        ("PRAGMA table_info(fastn_migration);", 1)
+    };
+
+    Ok(config
+        .ds
+        .sql_query(&db, query, vec![])
+        .await?
+        .iter()
+        .filter_map(|c| c.get(name_column).and_then(|v| v.as_str()))
+        .map(ToString::to_string)
+        .collect())
+}
+
+#[derive(Debug)]
+struct AppliedMigration {
+    number: i64,
+    name: String,
+    /// `None` for migrations applied before we started storing checksums
+    checksum: Option<String>,
+    applied_on: i64,
+}
+
+impl AppliedMigration {
+    fn matches(&self, migration: &fastn_core::package::MigrationData) -> bool {
+        self.name == migration.name
+            && self
+                .checksum
+                .as_ref()
+                .map_or(true, |c| *c == migration.checksum())
+    }
+}
+
+/// All applied migrations of `app_name`, ordered by migration number. None are applied if the
+/// migration table does not exist yet, e.g. in a dry run against a new database.
+async fn find_applied_migrations(
     config: &fastn_core::Config,
     app_name: &str,
-) -> Result<Option<i64>, MigrationError> {
+) -> Result<Vec<AppliedMigration>, MigrationError> {
     let db = config.get_db_url().await;
 
+    let columns = migration_table_columns(config).await?;
+    if columns.is_empty() {
+        return Ok(vec![]);
+    }
+    // dry runs and `fastn migrate status` do not add the checksum column to old tables
+    let checksum = if columns.iter().any(|c| c == "checksum") {
+        "checksum"
+    } else {
+        "NULL AS checksum"
+    };
+
     let results = config
         .ds
         .sql_query(
             db.as_str(),
             format!(
                 r#"
-                    SELECT
-                        migration_number
-                    FROM
-                        fastn_migration
-                    WHERE
-                        app_name = '{app_name}'
-                    ORDER BY migration_number DESC
-                    LIMIT 1;
-                "#,
+                SELECT
+                    migration_number, migration_name, {checksum}, applied_on
+                FROM
+                    fastn_migration
+                WHERE
+                    app_name = $1
+                ORDER BY migration_number ASC;
+            "#
             )
             .as_str(),
-            vec![],
+            vec![ft_sys_shared::SqliteRawValue::Text(app_name.to_string())],
         )
         .await?;
 
-    match results.len() {
-        0 => Ok(None),
-        1 => Ok(Some(
-            serde_json::from_value::<i64>(results[0].first().unwrap().clone()).unwrap(),
-        )), // Unwrap is okay here
-        _ => unreachable!(),
+    Ok(results
+        .into_iter()
+        .map(|row| AppliedMigration {
+            // Unwrap is okay here, the columns are NOT NULL integers and text
+            number: row[0].as_i64().unwrap(),
+            name: row[1].as_str().unwrap().to_string(),
+            checksum: row[2].as_str().map(ToString::to_string),
+            applied_on: row[3].as_i64().unwrap(),
+        })
+        .collect())
+}
+
+/// Stores checksums for migrations that were applied before we started storing them.
+async fn backfill_checksums(
+    config: &fastn_core::Config,
+    app_name: &str,
+    available_migrations: &[fastn_core::package::MigrationData],
+    applied_migrations: &[AppliedMigration],
+) -> Result<(), MigrationError> {
+    let db = config.get_db_url().await;
+
+    for applied in applied_migrations.iter().filter(|a| a.checksum.is_none()) {
+        if let Some(migration) = available_migrations
+            .iter()
+            .find(|m| m.number == applied.number)
+        {
+            config
+                .ds
+                .sql_execute(
+                    db.as_str(),
+                    r#"
+                        UPDATE
+                            fastn_migration
+                        SET
+                            checksum = $1
+                        WHERE
+                            app_name = $2 AND migration_number = $3;
+                    "#,
+                    vec![
+                        ft_sys_shared::SqliteRawValue::Text(migration.checksum()),
+                        ft_sys_shared::SqliteRawValue::Text(app_name.to_string()),
+                        ft_sys_shared::SqliteRawValue::Integer(migration.number),
+                    ],
+                )
+                .await?;
+        }
     }
+
+    Ok(())
 }
 
-fn mark_migration_applied_content(
+// `$1`, `$2` etc work as positional parameters on both sqlite and postgres, as long as they
+// appear in the query in order.
+fn mark_migration_applied_statement(
     app_name: &str,
     migration_data: &fastn_core::package::MigrationData,
     now: i64,
-) -> String {
-    format!(
-        r#"
+) -> fastn_ds::SqlStatement {
+    fastn_ds::SqlStatement::Execute {
+        query: r#"
             INSERT INTO
                 fastn_migration
-                    (app_name, migration_number, migration_name, applied_on)
+                    (app_name, migration_number, migration_name, applied_on, checksum)
             VALUES
-                ('{}', {}, '{}', {});
-        "#,
-        app_name, migration_data.number, migration_data.name, now
-    )
+                ($1, $2, $3, $4, $5);
+        "#
+        .to_string(),
+        params: vec![
+            ft_sys_shared::SqliteRawValue::Text(app_name.to_string()),
+            ft_sys_shared::SqliteRawValue::Integer(migration_data.number),
+            ft_sys_shared::SqliteRawValue::Text(migration_data.name.to_string()),
+            ft_sys_shared::SqliteRawValue::Integer(now),
+            ft_sys_shared::SqliteRawValue::Text(migration_data.checksum()),
+        ],
+    }
 }
+
+fn mark_migration_reverted_statement(
+    app_name: &str,
+    migration_data: &fastn_core::package::MigrationData,
+) -> fastn_ds::SqlStatement {
+    fastn_ds::SqlStatement::Execute {
+        query: r#"
+            DELETE FROM
+                fastn_migration
+            WHERE
+                app_name = $1 AND migration_number = $2;
+        "#
+        .to_string(),
+        params: vec![
+            ft_sys_shared::SqliteRawValue::Text(app_name.to_string()),
+            ft_sys_shared::SqliteRawValue::Integer(migration_data.number),
+        ],
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum MigrationError {
     #[error("Sql Error: {0}")]
     SqlError(#[from] fastn_utils::SqlError),
-    #[error("Cannot delete applied migration")]
-    AppliedMigrationDeletion,
-    #[error("The migration order has changed or its content has been altered")]
-    AppliedMigrationMismatch,
+    #[error("Cannot delete applied migration: {name}")]
+    AppliedMigrationDeletion { name: String },
+    #[error("The migration order has changed or content of `{name}` has been altered")]
+    AppliedMigrationMismatch { name: String },
     #[error("Multiple migrations found with the same name: {name}.")]
     MigrationNameConflict { name: String },
     #[error("`{name}` is invalid migration name. It must contain only alphanumeric characters, underscores, and hyphens.")]
     InvalidMigrationName { name: String },
+    #[error("Migration `{name}` can not be reverted, it has no `down` SQL.")]
+    MissingDownMigration { name: String },
+}
+
+#[cfg(test)]
+mod tests {
+    fn migration(number: i64, name: &str, content: &str) -> fastn_core::package::MigrationData {
+        fastn_core::package::MigrationData {
+            number,
+            name: name.to_string(),
+            content: content.to_string(),
+            down: None,
+        }
+    }
+
+    fn applied(
+        migration: &fastn_core::package::MigrationData,
+        checksum: Option<String>,
+    ) -> super::AppliedMigration {
+        super::AppliedMigration {
+            number: migration.number,
+            name: migration.name.to_string(),
+            checksum,
+            applied_on: 1,
+        }
+    }
+
+    #[test]
+    fn verify_applied_migrations() {
+        let first = migration(0, "first", "CREATE TABLE a (id INTEGER);");
+        let second = migration(1, "second", "CREATE TABLE b (id INTEGER);");
+        let available = vec![first.clone(), second.clone()];
+
+        assert!(super::verify_applied_migrations(
+            &available,
+            &[
+                applied(&first, Some(first.checksum())),
+                applied(&second, None)
+            ]
+        )
+        .is_ok());
+
+        let edited = migration(0, "first", "CREATE TABLE a (id TEXT);");
+        assert!(matches!(
+            super::verify_applied_migrations(
+                &available,
+                &[applied(&edited, Some(edited.checksum()))]
+            ),
+            Err(super::MigrationError::AppliedMigrationMismatch { .. })
+        ));
+
+        let renamed = migration(1, "renamed", "CREATE TABLE b (id INTEGER);");
+        assert!(matches!(
+            super::verify_applied_migrations(&available, &[applied(&renamed, None)]),
+            Err(super::MigrationError::AppliedMigrationMismatch { .. })
+        ));
+
+        let deleted = migration(2, "third", "CREATE TABLE c (id INTEGER);");
+        assert!(matches!(
+            super::verify_applied_migrations(&available, &[applied(&deleted, None)]),
+            Err(super::MigrationError::AppliedMigrationDeletion { .. })
+        ));
+    }
+
+    #[test]
+    fn migration_states() {
+        let first = migration(0, "first", "CREATE TABLE a (id INTEGER);");
+        let second = migration(1, "second", "CREATE TABLE b (id INTEGER);");
+
+        assert_eq!(
+            super::migration_states(
+                &[first.clone(), second.clone()],
+                &[applied(&first, Some(first.checksum()))]
+            ),
+            vec![
+                (
+                    0,
+                    "first".to_string(),
+                    super::MigrationState::Applied { applied_on: 1 }
+                ),
+                (1, "second".to_string(), super::MigrationState::Pending),
+            ]
+        );
+    }
+
+    #[test]
+    fn mark_migration_applied_statement() {
+        let first = migration(0, "first", "CREATE TABLE a (id INTEGER);");
+        match super::mark_migration_applied_statement("it's", &first, 1) {
+            fastn_ds::SqlStatement::Execute { query, params } => {
+                assert!(!query.contains("it's"));
+                assert_eq!(params.len(), 5);
+            }
+            fastn_ds::SqlStatement::Batch(_) => panic!("expected a parameterised statement"),
+        }
+    }
+
+    #[test]
+    fn validate_migrations() {
+        assert!(matches!(
+            super::validate_migrations(&[migration(0, "same", ""), migration(1, "same", "")]),
+            Err(super::MigrationError::MigrationNameConflict { .. })
+        ));
+    }
 }
@@ -11,6 +11,76 @@ pub struct App {
     pub writers: Vec<String>,
 }
 
+impl App {
+    /// The path of `path` inside the app, starting with `/`, `None` if `path` is not under the
+    /// mount point of the app.
+    pub fn path(&self, path: &str) -> Option<String> {
+        let mount_point = self.mount_point.trim_matches('/');
+        let path = path.trim_start_matches('/');
+        let rest = if mount_point.is_empty() {
+            path
+        } else {
+            let rest = path.strip_prefix(mount_point)?;
+            if !rest.is_empty() && !rest.starts_with('/') {
+                return None;
+            }
+            rest
+        };
+        Some(format!("/{}", rest.trim_start_matches('/')))
+    }
+
+    /// Whether `path` is a file of the app package, they are served from `/-/<package name>/`.
+    pub fn is_package_path(&self, path: &str) -> bool {
+        path.trim_start_matches('/')
+            .strip_prefix("-/")
+            .and_then(|p| p.strip_prefix(self.package.name.trim_matches('/')))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+
+    /// Whether what the app answers for `path`, under its mount point or `/-/<package name>/`,
+    /// depends on who asks, i.e. the app has readers or writers.
+    pub fn guards(&self, path: &str) -> bool {
+        (!self.readers.is_empty() || !self.writers.is_empty())
+            && (self.path(path).is_some() || self.is_package_path(path))
+    }
+
+    /// The config the endpoints of the app get as headers, with `user-id` if the app has one.
+    pub fn endpoint_config(&self) -> std::collections::HashMap<String, String> {
+        let mut config = self.config.clone();
+        if let Some(user_id) = &self.user_id {
+            config.insert("user-id".to_string(), user_id.clone());
+        }
+        config
+    }
+
+    /// Whether `user`, the logged in user if any, can make a request with `method`.
+    ///
+    /// Readers can make `GET` and `HEAD` requests, writers any request. `everyone` lets in all
+    /// visitors, other entries are the identity, email or id of a user. Without readers everyone
+    /// can read, and without writers everyone who can read can write.
+    pub fn allows(&self, user: Option<&ft_sys_shared::UserData>, method: &str) -> bool {
+        let listed = |list: &[String]| {
+            list.iter().any(|entry| {
+                entry.eq("everyone")
+                    || user.is_some_and(|u| {
+                        entry.eq(&u.identity)
+                            || entry.eq_ignore_ascii_case(&u.email)
+                            || entry.eq(&u.id.to_string())
+                    })
+            })
+        };
+        let can_read = self.readers.is_empty() || listed(&self.readers) || listed(&self.writers);
+        if method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD") {
+            return can_read;
+        }
+        if self.writers.is_empty() {
+            can_read
+        } else {
+            listed(&self.writers)
+        }
+    }
+}
+
 #[derive(serde::Deserialize, Debug, Clone)]
 pub struct AppTemp {
     pub name: String,
@@ -92,3 +162,78 @@ impl AppTemp {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    fn app(mount_point: &str, readers: &[&str], writers: &[&str]) -> super::App {
+        super::App {
+            name: "todos".to_string(),
+            package: fastn_core::Package::new("fifthtry.github.io/todos"),
+            mount_point: mount_point.to_string(),
+            end_point: None,
+            user_id: None,
+            config: Default::default(),
+            readers: readers.iter().map(|r| r.to_string()).collect(),
+            writers: writers.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn path() {
+        let todos = app("/todos/", &[], &[]);
+        assert_eq!(todos.path("/todos/"), Some("/".to_string()));
+        assert_eq!(todos.path("/todos"), Some("/".to_string()));
+        assert_eq!(todos.path("/todos/add/"), Some("/add/".to_string()));
+        assert_eq!(todos.path("/todos-old/"), None);
+        assert_eq!(todos.path("/blog/"), None);
+        assert_eq!(app("/", &[], &[]).path("/add/"), Some("/add/".to_string()));
+    }
+
+    #[test]
+    fn is_package_path() {
+        let todos = app("/todos/", &[], &[]);
+        assert!(todos.is_package_path("/-/fifthtry.github.io/todos/logo.svg"));
+        assert!(todos.is_package_path("/-/fifthtry.github.io/todos/"));
+        assert!(!todos.is_package_path("/-/fifthtry.github.io/todos-old/logo.svg"));
+        assert!(!todos.is_package_path("/todos/logo.svg"));
+    }
+
+    #[test]
+    fn guards() {
+        let private = app("/todos/", &["amitu"], &[]);
+        assert!(private.guards("/todos/"));
+        assert!(private.guards("/-/fifthtry.github.io/todos/logo.svg"));
+        assert!(!private.guards("/blog/"));
+        assert!(!app("/todos/", &[], &[]).guards("/todos/"));
+    }
+
+    #[test]
+    fn allows() {
+        let user = ft_sys_shared::UserData {
+            id: 7,
+            identity: "amitu".to_string(),
+            name: "Amit".to_string(),
+            email: "amitu@example.com".to_string(),
+            verified_email: true,
+        };
+        let user = Some(&user);
+
+        let open = app("/todos/", &[], &[]);
+        assert!(open.allows(None, "GET"));
+        assert!(open.allows(None, "POST"));
+
+        let private = app("/todos/", &["amitu@example.com"], &["7"]);
+        assert!(!private.allows(None, "GET"));
+        assert!(private.allows(user, "GET"));
+        assert!(private.allows(user, "POST"));
+
+        let read_only = app("/todos/", &["everyone"], &["someone-else"]);
+        assert!(read_only.allows(None, "HEAD"));
+        assert!(!read_only.allows(user, "DELETE"));
+
+        let writers_only = app("/todos/", &[], &["amitu"]);
+        assert!(writers_only.allows(None, "GET"));
+        assert!(!writers_only.allows(None, "POST"));
+        assert!(writers_only.allows(user, "POST"));
+    }
+}
@@ -6,6 +6,8 @@ pub mod redirects;
 #[derive(Debug, Clone)]
 pub struct Package {
     pub name: String,
+    /// The semver version of this package, written to `manifest.json` by `fastn build`.
+    pub version: Option<String>,
     /// The `versioned` stores the boolean value storing of the fastn package is versioned or not
     pub files: Vec<String>,
     pub versioned: bool,
@@ -65,7 +67,7 @@ pub struct Package {
     pub icon: Option<ftd::ImageSrc>,
 
     /// Redirect URLs
-    pub redirects: Option<ftd::Map<String>>,
+    pub redirects: Option<Vec<redirects::Redirect>>,
     pub system: Option<String>,
     pub system_is_confidential: Option<bool>,
 
@@ -73,12 +75,16 @@ pub struct Package {
 
     /// Migrations
     pub migrations: Vec<MigrationData>,
+
+    /// The body of `fastn.robots`, written to `robots.txt` by `fastn build`.
+    pub robots: Option<String>,
 }
 
 impl Package {
     pub fn new(name: &str) -> fastn_core::Package {
         fastn_core::Package {
             name: name.to_string(),
+            version: None,
             files: vec![],
             versioned: false,
             translation_of: Box::new(None),
@@ -109,6 +115,7 @@ impl Package {
             system: None,
             system_is_confidential: None,
             migrations: vec![],
+            robots: None,
         }
     }
 
@@ -606,6 +613,7 @@ impl Package {
             package.redirects = Some(url_mappings.redirects);
             package.endpoints = url_mappings.endpoints;
         }
+        package.robots = get_robots(&fastn_document)?;
 
         package.translation_status_summary =
             fastn_document.get("fastn#translation-status-summary")?;
@@ -678,6 +686,7 @@ impl Package {
             package.redirects = Some(url_mappings.redirects);
             package.endpoints = url_mappings.endpoints;
         }
+        package.robots = get_robots(fastn_doc)?;
 
         // reading dependencies
         let deps = {
@@ -811,6 +820,18 @@ pub(crate) fn get_migration_data(
     Ok(migrations)
 }
 
+pub(crate) fn get_robots(doc: &ftd::ftd2021::p2::Document) -> fastn_core::Result<Option<String>> {
+    Ok(doc
+        .get::<Option<RobotsTemp>>("fastn#robots")?
+        .map(|robots| robots.body))
+}
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct RobotsTemp {
+    #[serde(rename = "robots-body")]
+    pub body: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct Lang {
     pub default_lang: String,
@@ -951,6 +972,7 @@ impl PackageTempIntoPackage for fastn_package::old_fastn::PackageTemp {
 
         Package {
             name: self.name.clone(),
+            version: self.version,
             files: vec![],
             versioned: self.versioned,
             translation_of: Box::new(translation_of),
@@ -981,6 +1003,7 @@ impl PackageTempIntoPackage for fastn_package::old_fastn::PackageTemp {
             system: self.system,
             system_is_confidential: self.system_is_confidential,
             migrations: vec![],
+            robots: None,
         }
     }
 }
@@ -990,12 +1013,23 @@ pub struct MigrationData {
     pub number: i64,
     pub name: String,
     pub content: String,
+    /// SQL that undoes `content`, used by `fastn migrate down`
+    pub down: Option<String>,
+}
+
+impl MigrationData {
+    /// Stored along with applied migrations so we can tell if an applied migration was edited
+    /// later.
+    pub fn checksum(&self) -> String {
+        fastn_core::utils::generate_hash(self.content.as_str())
+    }
 }
 
 #[derive(Debug, serde::Deserialize, Clone)]
 pub struct MigrationDataTemp {
     pub name: String,
     pub content: String,
+    pub down: Option<String>,
 }
 
 impl MigrationDataTemp {
@@ -1004,6 +1038,7 @@ impl MigrationDataTemp {
             number,
             name: self.name,
             content: self.content,
+            down: self.down,
         }
     }
 }
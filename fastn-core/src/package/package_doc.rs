@@ -394,9 +394,7 @@ pub(crate) async fn read_ftd_2022(
         Ok(v) => v,
         Err(e) => {
             tracing::error!(msg = "failed to parse", doc = main.id.as_str());
-            return Err(fastn_core::Error::PackageError {
-                message: format!("failed to parse {:?}", &e),
-            });
+            return Err(fastn_core::doc::diagnostic(&e, main).into());
         }
     };
 
@@ -405,6 +403,9 @@ pub(crate) async fn read_ftd_2022(
     }
 
     let executor = ftd::executor::ExecuteDoc::from_interpreter(main_ftd_doc)?;
+    if config.collect_page_text {
+        config.page_text = Some(executor.text());
+    }
     let node = ftd::node::NodeData::from_rt(executor);
     let html_ui = ftd::html::HtmlUI::from_node_data(node, "main", test)?;
 
@@ -460,9 +461,7 @@ pub(crate) async fn read_ftd_2023(
         Ok(v) => v,
         Err(e) => {
             tracing::error!(msg = "failed to parse", doc = main.id.as_str());
-            return Err(fastn_core::Error::PackageError {
-                message: format!("failed to parse {:?}", &e),
-            });
+            return Err(fastn_core::doc::diagnostic(&e, main).into());
         }
     };
     if let Some((url, code)) = main_ftd_doc.get_redirect()? {
@@ -470,28 +469,53 @@ pub(crate) async fn read_ftd_2023(
     }
 
     let js_ast_data = ftd::js::document_into_js_ast(main_ftd_doc);
-    let js_document_script = fastn_js::to_js(js_ast_data.asts.as_slice(), package_name.as_str());
+    let js_document_script = if c.source_maps && !only_js {
+        fastn_core::source_map::to_js(main, &js_ast_data, package_name.as_str())
+    } else {
+        fastn_js::to_js(js_ast_data.asts.as_slice(), package_name.as_str())
+    };
     let js_ftd_script = fastn_js::to_js(
         ftd::js::default_bag_into_js_ast().as_slice(),
         package_name.as_str(),
     );
+    let runtime_modules = fastn_js::runtime_modules(js_document_script.as_str());
     let file_content = if only_js {
         fastn_js::ssr_raw_string_without_test(
             &package_name,
             format!("{js_ftd_script}\n{js_document_script}").as_str(),
         )
     } else {
-        let ssr_body = if config.request.is_bot() {
-            fastn_js::ssr_with_js_string(
-                &package_name,
-                format!("{js_ftd_script}\n{js_document_script}").as_str(),
-            )?
-        } else {
-            EMPTY_HTML_BODY.to_string()
+        // every visitor gets the server rendered page, the browser hydrates it
+        let device = config.request.device();
+        let ssr_body = match fastn_js::ssr_with_js_string(
+            &package_name,
+            format!(
+                "{js_ftd_script}\n{js_document_script}\nftd.device = fastn.mutable(\"{device}\");"
+            )
+            .as_str(),
+        ) {
+            Ok(body) => {
+                if config.collect_page_text {
+                    config.page_text = Some(ftd::executor::PageText::from_html(body.as_str()));
+                }
+                body
+            }
+            Err(e) => {
+                // the page is rendered in the browser instead
+                tracing::warn!(msg = "ssr failed", doc = main.id.as_str(), error = %e);
+                EMPTY_HTML_BODY.to_string()
+            }
+        };
+        // the `sourceMappingURL` comment has to stay the last line
+        let ssr_device = format!("fastnVirtual.ssrDevice = \"{device}\";\n");
+        let js_document_script = match js_document_script.rsplit_once("//# sourceMappingURL=") {
+            Some((js, map)) => format!("{js}{ssr_device}//# sourceMappingURL={map}"),
+            None => format!("{js_document_script}{ssr_device}"),
         };
 
         fastn_core::utils::replace_markers_2023(
             js_document_script.as_str(),
+            runtime_modules.as_slice(),
             js_ast_data.scripts.join("").as_str(),
             ssr_body.as_str(),
             config.config.get_font_style().as_str(),
@@ -1,6 +1,9 @@
+/// The file `fastn build` writes the redirects to, for static hosts.
+pub const REDIRECTS_FILE: &str = "_redirects";
+
 #[derive(Debug, PartialEq)]
 pub struct UrlMappings {
-    pub redirects: ftd::Map<String>,
+    pub redirects: Vec<Redirect>,
     pub endpoints: Vec<fastn_package::old_fastn::EndpointData>,
     // todo: add dynamic-urls
     // pub dynamic_urls: <some-type>
@@ -8,7 +11,7 @@ pub struct UrlMappings {
 
 impl UrlMappings {
     pub fn new(
-        redirects: ftd::Map<String>,
+        redirects: Vec<Redirect>,
         endpoints: Vec<fastn_package::old_fastn::EndpointData>,
     ) -> UrlMappings {
         UrlMappings {
@@ -18,6 +21,154 @@ impl UrlMappings {
     }
 }
 
+/// A redirect of `fastn.url-mappings`, `<from> -> <to>`, optionally followed by the status code:
+/// 301, 302, 307 or 308, which is the default.
+///
+/// A segment of `from` can be a `:name` parameter, which matches any one segment, and the last
+/// segment can be `*`, which matches the rest of the path. `to` can use what they matched:
+///
+/// ```ftd
+/// /blog/:slug/ -> /posts/:slug/
+/// /docs/* -> https://docs.example.com/* 302
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Redirect {
+    pub from: String,
+    pub to: String,
+    pub code: u16,
+}
+
+impl Redirect {
+    pub const DEFAULT_CODE: u16 = 308;
+    const CODES: [u16; 4] = [301, 302, 307, 308];
+
+    /// Parses `from` and `to`, the two sides of `->`. `to` may end with the status code.
+    fn parse(from: &str, to: &str) -> fastn_core::Result<Redirect> {
+        let from = from.trim().to_string();
+        let (to, code) = match to.trim().rsplit_once(char::is_whitespace) {
+            Some((to, code)) if code.chars().all(|c| c.is_ascii_digit()) => {
+                let code: u16 = code.parse().unwrap_or_default();
+                if !Self::CODES.contains(&code) {
+                    return fastn_core::assert_error(format!(
+                        "Redirect {} -> {} has status code {}, it must be one of 301, 302, 307 \
+                        or 308",
+                        from,
+                        to.trim(),
+                        code
+                    ));
+                }
+                (to.trim().to_string(), code)
+            }
+            _ => (to.trim().to_string(), Self::DEFAULT_CODE),
+        };
+
+        let segments: Vec<&str> = segments(from.as_str()).collect();
+        if segments.iter().rev().skip(1).any(|s| *s == "*") {
+            return fastn_core::assert_error(format!(
+                "Redirect {} -> {} is invalid, * can only be the last segment",
+                from, to
+            ));
+        }
+        for name in parameters(to.as_str()) {
+            if name == "*" && segments.last() != Some(&"*") {
+                return fastn_core::assert_error(format!(
+                    "Redirect {} -> {} is invalid, {} does not end with *",
+                    from, to, from
+                ));
+            }
+            if name != "*" && !segments.contains(&format!(":{}", name).as_str()) {
+                return fastn_core::assert_error(format!(
+                    "Redirect {} -> {} is invalid, {} has no :{} segment",
+                    from, to, from, name
+                ));
+            }
+        }
+
+        Ok(Redirect { from, to, code })
+    }
+
+    /// Whether `from` has parameters or `*`, instead of being a path.
+    pub fn is_pattern(&self) -> bool {
+        segments(self.from.as_str()).any(|s| s == "*" || s.starts_with(':'))
+    }
+
+    /// Where `path` redirects to, if `from` matches it. `*` captures the rest of `path` as is,
+    /// it ends with a `/` only if `path` does.
+    fn target(&self, path: &str) -> Option<String> {
+        let trailing_slash = path.ends_with('/');
+        let mut path = segments(path);
+        let mut captures = std::collections::HashMap::new();
+        for pattern in segments(self.from.as_str()) {
+            if pattern == "*" {
+                let rest: Vec<&str> = path.by_ref().collect();
+                let rest = if rest.is_empty() || !trailing_slash {
+                    rest.join("/")
+                } else {
+                    format!("{}/", rest.join("/"))
+                };
+                captures.insert("*", rest);
+                break;
+            }
+            let segment = path.next()?;
+            match pattern.strip_prefix(':') {
+                Some(name) => {
+                    captures.insert(name, segment.to_string());
+                }
+                None if pattern != segment => return None,
+                None => {}
+            }
+        }
+        if path.next().is_some() {
+            return None;
+        }
+        Some(substitute(self.to.as_str(), |name| {
+            captures.get(name).cloned().unwrap_or_default()
+        }))
+    }
+}
+
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+/// The `:name` parameters and `*` used in `to`.
+fn parameters(to: &str) -> Vec<String> {
+    let mut names = vec![];
+    substitute(to, |name| {
+        names.push(name.to_string());
+        String::new()
+    });
+    names
+}
+
+/// `to` with every `:name` and `*` replaced by `value(name)`. A `:name` starts a path segment,
+/// right after a `/`, and names start with a letter or `_`, so the `:` of `https://`,
+/// `mailto:`, a port or a path like `/wiki/Help:Contents` is kept.
+fn substitute(to: &str, mut value: impl FnMut(&str) -> String) -> String {
+    let mut result = String::new();
+    let mut chars = to.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '*' => result.push_str(value("*").as_str()),
+            ':' if to[..i].ends_with('/')
+                && matches!(chars.peek(), Some((_, n)) if n.is_ascii_alphabetic() || *n == '_') =>
+            {
+                let mut end = to.len();
+                while let Some((j, n)) = chars.peek() {
+                    if !(n.is_ascii_alphanumeric() || *n == '_' || *n == '-') {
+                        end = *j;
+                        break;
+                    }
+                    chars.next();
+                }
+                result.push_str(value(&to[i + 1..end]).as_str());
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
 #[derive(Debug, serde::Deserialize, Clone)]
 pub struct UrlMappingsTemp {
     #[serde(rename = "url-mappings-body")]
@@ -35,7 +186,7 @@ impl UrlMappingsTemp {
     ///
     /// and returns UrlMappings { redirects, endpoints }
     fn find_url_mappings(&self, body: &str) -> fastn_core::Result<UrlMappings> {
-        let mut redirects: ftd::Map<String> = ftd::Map::new();
+        let mut redirects: Vec<Redirect> = vec![];
         let mut endpoints = vec![];
         for line in body.lines() {
             let line = line.trim();
@@ -84,6 +235,9 @@ impl UrlMappingsTemp {
             // Supported Redirects Syntax under fastn.url-mappings
             // <some link>: <link to redirect>
             // <some link> -> <link to redirect>
+            // <some link> -> <link to redirect> <status code>
+            // /blog/:slug/ -> /posts/:slug/
+            // /docs/* -> https://docs.example.com/* 302
 
             if let Some((key, value)) = line.split_once("->") {
                 Self::assert_and_insert_redirect(key, value, &mut redirects)?;
@@ -108,32 +262,27 @@ impl UrlMappingsTemp {
     fn assert_and_insert_redirect(
         from: &str,
         to: &str,
-        redirects: &mut ftd::Map<String>,
+        redirects: &mut Vec<Redirect>,
     ) -> fastn_core::Result<()> {
-        let from = from.trim().to_owned();
-        let to = to.trim().to_owned();
-
-        assert!(
-            !from.eq(to.as_str()),
-            "Redirect {} -> {} is invalid",
-            from,
-            to
-        );
-        assert!(
-            !redirects.contains_key(from.as_str()),
-            "Redirect {} -> {} is invalid, since {} -> {} already exists",
-            from.as_str(),
-            to.as_str(),
-            from.as_str(),
-            redirects.get(from.as_str()).unwrap(),
-        );
+        let redirect = Redirect::parse(from, to)?;
+        let (from, to) = (redirect.from.as_str(), redirect.to.as_str());
+
+        assert!(!from.eq(to), "Redirect {} -> {} is invalid", from, to);
+        if let Some(existing) = redirects.iter().find(|r| r.from.eq(from)) {
+            panic!(
+                "Redirect {} -> {} is invalid, since {} -> {} already exists",
+                from, to, from, existing.to,
+            );
+        }
 
-        redirects.insert(from, to);
+        redirects.push(redirect);
         Ok(())
     }
 }
 
-pub fn find_redirect<'a>(redirects: &'a ftd::Map<String>, path: &str) -> Option<&'a String> {
+/// The url `path` redirects to, and the status code of the redirect. Redirects of paths are
+/// looked at before the patterns, which are tried in the order they are written.
+pub fn find_redirect(redirects: &[Redirect], path: &str) -> Option<(String, u16)> {
     let original = path;
     let fixed = format!(
         "/{}/",
@@ -142,13 +291,53 @@ pub fn find_redirect<'a>(redirects: &'a ftd::Map<String>, path: &str) -> Option<
             .trim_end_matches(".ftd")
     );
 
-    return if redirects.contains_key(original) {
-        redirects.get(original)
-    } else if redirects.contains_key(fixed.as_str()) {
-        redirects.get(fixed.as_str())
+    let exact = redirects
+        .iter()
+        .filter(|r| !r.is_pattern())
+        .find(|r| r.from.eq(original))
+        .or_else(|| {
+            redirects
+                .iter()
+                .filter(|r| !r.is_pattern())
+                .find(|r| r.from.eq(fixed.as_str()))
+        });
+    if let Some(r) = exact {
+        return Some((r.to.to_string(), r.code));
+    }
+
+    // a document is matched as the folder it is served at, any other path as it is requested, so
+    // `/docs/logo.png` is not redirected to `.../logo.png/`
+    let requested = if original.ends_with(".ftd") {
+        fixed
     } else {
-        None
+        format!("/{}", original.trim_start_matches('/'))
     };
+    redirects
+        .iter()
+        .filter(|r| r.is_pattern())
+        .find_map(|r| Some((r.target(requested.as_str())?, r.code)))
+}
+
+/// The `_redirects` file `fastn build` writes, in the format static hosts like Netlify and
+/// Cloudflare Pages read: `<from> <to> <code>` per line, `*` is `:splat` in `<to>`.
+pub fn redirects_file(redirects: &[Redirect]) -> String {
+    let mut file = String::new();
+    for redirect in redirects {
+        let from = format!(
+            "/{}",
+            redirect
+                .from
+                .trim_start_matches('/')
+                .trim_end_matches("index.ftd")
+                .replace(".ftd", "/")
+        );
+        let to = substitute(redirect.to.as_str(), |name| match name {
+            "*" => ":splat".to_string(),
+            name => format!(":{}", name),
+        });
+        file.push_str(format!("{} {} {}\n", from, to, redirect.code).as_str());
+    }
+    file
 }
 
 #[cfg(test)]
@@ -178,11 +367,18 @@ mod tests {
             },
         ];
 
-        let mut expected_redirects: ftd::Map<String> = ftd::Map::new();
-        expected_redirects.extend([
-            ("/blog/".to_string(), "/blogs/".to_string()),
-            ("/docs/".to_string(), "http://fastn.com/docs/".to_string()),
-        ]);
+        let expected_redirects = vec![
+            crate::package::redirects::Redirect {
+                from: "/blog/".to_string(),
+                to: "/blogs/".to_string(),
+                code: 308,
+            },
+            crate::package::redirects::Redirect {
+                from: "/docs/".to_string(),
+                to: "http://fastn.com/docs/".to_string(),
+                code: 308,
+            },
+        ];
 
         assert!(url_mappings.is_some());
         let url_mappings = url_mappings.unwrap();
@@ -244,4 +440,114 @@ mod tests {
             e => panic!("Was expecting assert error, found: {:?}", e),
         }
     }
+
+    fn redirects(body: &str) -> Vec<crate::package::redirects::Redirect> {
+        crate::package::redirects::UrlMappingsTemp {
+            body: body.to_string(),
+        }
+        .url_mappings_from_body()
+        .unwrap()
+        .redirects
+    }
+
+    #[test]
+    fn find_redirect() {
+        let redirects = redirects(
+            "
+                /blog/ -> /blogs/ 301
+                /blog/:year/:slug/ -> /posts/:year/:slug/ 302
+                /docs/* -> https://docs.fastn.com/* 307
+                /old/:page/ -> http://localhost:8000/:page/
+                /contact/ -> mailto:foo@x.com
+                /help/:topic/ -> https://host/wiki/Help:Contents 302
+            ",
+        );
+        let find = |path: &str| crate::package::redirects::find_redirect(&redirects, path);
+
+        assert_eq!(find("blog/"), Some(("/blogs/".to_string(), 301)));
+        assert_eq!(find("blog.ftd"), Some(("/blogs/".to_string(), 301)));
+        assert_eq!(
+            find("blog/2024/hello/"),
+            Some(("/posts/2024/hello/".to_string(), 302))
+        );
+        assert_eq!(find("blog/2024/"), None);
+        assert_eq!(
+            find("docs/install/linux/"),
+            Some(("https://docs.fastn.com/install/linux/".to_string(), 307))
+        );
+        assert_eq!(
+            find("docs/"),
+            Some(("https://docs.fastn.com/".to_string(), 307))
+        );
+        assert_eq!(
+            find("/docs/logo.png"),
+            Some(("https://docs.fastn.com/logo.png".to_string(), 307))
+        );
+        assert_eq!(
+            find("/docs/install/linux"),
+            Some(("https://docs.fastn.com/install/linux".to_string(), 307))
+        );
+        assert_eq!(
+            find("docs/install.ftd"),
+            Some(("https://docs.fastn.com/install/".to_string(), 307))
+        );
+        assert_eq!(
+            find("old/about.ftd"),
+            Some(("http://localhost:8000/about/".to_string(), 308))
+        );
+        assert_eq!(
+            find("contact/"),
+            Some(("mailto:foo@x.com".to_string(), 308))
+        );
+        assert_eq!(
+            find("help/install/"),
+            Some(("https://host/wiki/Help:Contents".to_string(), 302))
+        );
+        assert_eq!(find("about/"), None);
+    }
+
+    #[test]
+    fn invalid_redirects() {
+        for body in [
+            "/a/ -> /b/ 200",
+            "/a/*/b/ -> /c/*",
+            "/a/ -> /b/*",
+            "/a/:x/ -> /b/:y/",
+        ] {
+            let url_mappings = crate::package::redirects::UrlMappingsTemp {
+                body: body.to_string(),
+            }
+            .url_mappings_from_body();
+            assert!(
+                matches!(url_mappings, Err(fastn_core::Error::AssertError { .. })),
+                "{}: {:?}",
+                body,
+                url_mappings
+            );
+        }
+    }
+
+    #[test]
+    fn redirects_file() {
+        let redirects = redirects(
+            "
+                /blog/ -> /blogs/ 301
+                about.ftd -> /team/
+                /blog/:slug/ -> /posts/:slug/ 302
+                /docs/* -> https://docs.fastn.com/*
+            ",
+        );
+        assert_eq!(
+            crate::package::redirects::redirects_file(&redirects),
+            indoc::indoc!(
+                "
+                /blog/ /blogs/ 301
+                /about/ /team/ 308
+                /blog/:slug/ /posts/:slug/ 302
+                /docs/* https://docs.fastn.com/:splat 308
+                "
+            )
+            .trim_start()
+        );
+    }
 }
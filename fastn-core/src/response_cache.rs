@@ -0,0 +1,463 @@
+//! With `--cache`, `fastn serve` keeps the pages it renders, so a document that does not depend
+//! on the request (see `RequestConfig::response_is_cacheable`) is interpreted once, and served
+//! from memory, or from disk with `--cache-dir`, after that. Logged in users always get pages
+//! rendered for them, what they see can depend on who they are.
+//!
+//! Entries are keyed by the path, query string and `fastn-lang` cookie of the request, and a
+//! checksum of every file of the package and its dependencies, so entries written before a file
+//! changed are never served, even across restarts. While serving we also watch the package and
+//! drop all entries as soon as something changes.
+//!
+//! Cached responses get an `ETag` and `Cache-Control: no-cache`, so browsers revalidate them with
+//! `If-None-Match`, and get a `304 Not Modified` if the page did not change.
+
+/// Where `fastn serve` keeps rendered pages.
+#[derive(Debug, Clone)]
+pub enum Storage {
+    Memory,
+    /// Entries are also written to this directory, so they survive a restart of `fastn serve`.
+    Disk(camino::Utf8PathBuf),
+}
+
+/// Responses bigger than this are not cached.
+const MAX_ENTRY_SIZE: usize = 1024 * 1024;
+/// Once the memory cache has this many entries, new ones only go to the disk cache, if any.
+const MAX_ENTRIES: usize = 1024;
+
+#[derive(Clone)]
+struct Entry {
+    headers: Vec<(String, String)>,
+    body: bytes::Bytes,
+}
+
+#[derive(Clone)]
+pub struct ResponseCache {
+    inner: std::sync::Arc<Inner>,
+}
+
+struct Inner {
+    ds: fastn_ds::DocumentStore,
+    dir: Option<fastn_ds::Path>,
+    fingerprint: antidote::RwLock<String>,
+    entries: antidote::RwLock<std::collections::HashMap<String, Entry>>,
+    // the watcher stops watching when dropped, so we keep it around as long as the server runs
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl ResponseCache {
+    pub async fn start(
+        config: fastn_core::live_reload::SharedConfig,
+        storage: Storage,
+    ) -> fastn_core::Result<ResponseCache> {
+        let current = std::sync::Arc::clone(&config.read());
+        let ds = current.ds.clone();
+        let dir = match storage {
+            Storage::Memory => None,
+            Storage::Disk(dir) => Some(ds.root().join(dir.as_str())),
+        };
+
+        let fingerprint = fingerprint(&current, dir.as_ref()).await?;
+        if let Some(dir) = dir.as_ref() {
+            remove_stale_dirs(&ds, dir, fingerprint.as_str()).await;
+        }
+
+        let root = std::path::PathBuf::from(ds.root().to_string());
+        let (watcher, mut changes) = fastn_core::live_reload::watch(root)?;
+
+        let cache = ResponseCache {
+            inner: std::sync::Arc::new(Inner {
+                ds,
+                dir,
+                fingerprint: antidote::RwLock::new(fingerprint),
+                entries: Default::default(),
+                _watcher: watcher,
+            }),
+        };
+
+        // the watcher task must not keep the cache alive, or the watcher would never be dropped
+        let weak = std::sync::Arc::downgrade(&cache.inner);
+        tokio::spawn(async move {
+            while let Some(changed) = changes.recv().await {
+                let inner = match weak.upgrade() {
+                    Some(inner) => inner,
+                    None => return,
+                };
+                if !changed.iter().any(|p| !inner.is_in_cache_dir(p)) {
+                    continue;
+                }
+
+                // live reload may have just swapped the config, so we read it again here
+                let current = std::sync::Arc::clone(&config.read());
+                match fingerprint(&current, inner.dir.as_ref()).await {
+                    Ok(fingerprint) => inner.invalidate(fingerprint).await,
+                    Err(e) => tracing::error!(msg = "response-cache", error = %e),
+                }
+            }
+        });
+
+        Ok(cache)
+    }
+
+    /// A cache that is only kept in memory and does not watch any files.
+    #[cfg(test)]
+    pub(crate) fn in_memory(ds: fastn_ds::DocumentStore) -> ResponseCache {
+        ResponseCache {
+            inner: std::sync::Arc::new(Inner {
+                ds,
+                dir: None,
+                fingerprint: Default::default(),
+                entries: Default::default(),
+                _watcher: notify::recommended_watcher(|_: notify::Result<notify::Event>| {})
+                    .unwrap(),
+            }),
+        }
+    }
+
+    /// Serves `req` from the cache, if it can, and caches the response of `render` otherwise.
+    pub async fn get_or_render<F>(
+        &self,
+        req: &fastn_core::http::Request,
+        render: F,
+    ) -> fastn_core::Result<fastn_core::http::Response>
+    where
+        F: std::future::Future<Output = fastn_core::Result<(fastn_core::http::Response, bool)>>,
+    {
+        if req.method() != "GET" {
+            return render.await.map(|(r, _)| r);
+        }
+        // the cache is looked up before the access of the user is checked, and neither the page
+        // nor the key know who the user is, so their pages are neither served from nor written to
+        // the cache
+        if req.cookie(ft_sys_shared::SESSION_KEY).is_some() {
+            return render.await.map(|(r, _)| uncacheable(r));
+        }
+
+        let key = self.inner.key(req);
+        if let Some(entry) = self.inner.get(key.as_str()).await {
+            tracing::info!(msg = "response-cache hit", path = req.path());
+            return Ok(entry.to_response(req));
+        }
+
+        let (response, cacheable) = render.await?;
+        if !cacheable || response.status() != actix_web::http::StatusCode::OK {
+            return Ok(uncacheable(response));
+        }
+        // pages that set cookies are specific to this request
+        if response
+            .headers()
+            .contains_key(actix_web::http::header::SET_COOKIE)
+        {
+            return Ok(uncacheable(response));
+        }
+
+        // only bodies we already have in memory are cached
+        match actix_web::body::MessageBody::size(response.body()) {
+            actix_web::body::BodySize::Sized(size) if size <= MAX_ENTRY_SIZE as u64 => {}
+            _ => return Ok(uncacheable(response)),
+        }
+
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+            .collect();
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .map_err(|e| fastn_core::Error::GenericError(e.to_string()))?;
+
+        let entry = Entry::new(headers, body);
+        let response = entry.to_response(req);
+        self.inner.insert(key, entry).await;
+        Ok(response)
+    }
+}
+
+fn uncacheable(mut response: fastn_core::http::Response) -> fastn_core::http::Response {
+    if !response
+        .headers()
+        .contains_key(actix_web::http::header::CACHE_CONTROL)
+    {
+        response.headers_mut().insert(
+            actix_web::http::header::CACHE_CONTROL,
+            actix_web::http::header::HeaderValue::from_static("no-store"),
+        );
+    }
+    response
+}
+
+impl Inner {
+    fn key(&self, req: &fastn_core::http::Request) -> String {
+        fastn_core::utils::generate_hash(format!(
+            "{}\n{}\n{}\n{}\n{}",
+            self.fingerprint.read(),
+            req.path(),
+            req.query_string(),
+            req.cookie("fastn-lang").unwrap_or_default(),
+            // pages are server rendered for the device
+            req.device(),
+        ))
+    }
+
+    async fn get(&self, key: &str) -> Option<Entry> {
+        if let Some(entry) = self.entries.read().get(key) {
+            return Some(entry.clone());
+        }
+
+        let entry = self.read_entry(key).await?;
+        let mut entries = self.entries.write();
+        if entries.len() < MAX_ENTRIES {
+            entries.insert(key.to_string(), entry.clone());
+        }
+        Some(entry)
+    }
+
+    async fn insert(&self, key: String, entry: Entry) {
+        if let Err(e) = self.write_entry(key.as_str(), &entry).await {
+            tracing::error!(msg = "response-cache write", error = %e);
+        }
+
+        let mut entries = self.entries.write();
+        if entries.len() < MAX_ENTRIES {
+            entries.insert(key, entry);
+        }
+    }
+
+    async fn invalidate(&self, fingerprint: String) {
+        tracing::info!(msg = "response-cache invalidated");
+        *self.fingerprint.write() = fingerprint;
+        self.entries.write().clear();
+
+        if let Some(dir) = self.dir.as_ref() {
+            if let Err(e) = self.ds.remove(dir).await {
+                tracing::error!(msg = "response-cache clear", error = %e);
+            }
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> Option<fastn_ds::Path> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(self.fingerprint.read().as_str()).join(key))
+    }
+
+    async fn read_entry(&self, key: &str) -> Option<Entry> {
+        let path = self.entry_path(key)?;
+        let headers = self
+            .ds
+            .read_content(&path.with_extension("json"))
+            .await
+            .ok()?;
+        let headers = serde_json::from_slice(&headers).ok()?;
+        let body = self
+            .ds
+            .read_content(&path.with_extension("body"))
+            .await
+            .ok()?;
+
+        Some(Entry {
+            headers,
+            body: body.into(),
+        })
+    }
+
+    async fn write_entry(&self, key: &str, entry: &Entry) -> fastn_core::Result<()> {
+        let path = match self.entry_path(key) {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        // the headers are written last, an entry without them is never read
+        self.ds
+            .write_content(&path.with_extension("body"), &entry.body)
+            .await?;
+        self.ds
+            .write_content(
+                &path.with_extension("json"),
+                &serde_json::to_vec(&entry.headers)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn is_in_cache_dir(&self, path: &std::path::Path) -> bool {
+        let dir = match self.dir.as_ref() {
+            Some(dir) => dir,
+            None => return false,
+        };
+        self.ds
+            .root()
+            .join(path.to_string_lossy())
+            .strip_prefix(dir)
+            .is_some()
+    }
+}
+
+impl Entry {
+    fn new(mut headers: Vec<(String, String)>, body: bytes::Bytes) -> Entry {
+        // the compress middleware changes the body, so the etag is weak
+        let etag = format!("W/\"{}\"", fastn_core::utils::generate_hash(&body));
+        headers.retain(|(k, _)| !k.eq_ignore_ascii_case("etag"));
+        headers.push(("etag".to_string(), etag));
+        if !headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case("cache-control"))
+        {
+            headers.push(("cache-control".to_string(), "no-cache".to_string()));
+        }
+
+        Entry { headers, body }
+    }
+
+    fn etag(&self) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == "etag")
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn to_response(&self, req: &fastn_core::http::Request) -> fastn_core::http::Response {
+        let not_modified = match (
+            req.headers()
+                .get("if-none-match")
+                .and_then(|v| v.to_str().ok()),
+            self.etag(),
+        ) {
+            (Some(if_none_match), Some(etag)) => if_none_match
+                .split(',')
+                .any(|t| t.trim() == etag || t.trim() == "*"),
+            _ => false,
+        };
+
+        let mut builder = if not_modified {
+            actix_web::HttpResponse::NotModified()
+        } else {
+            actix_web::HttpResponse::Ok()
+        };
+        for (k, v) in self.headers.iter() {
+            builder.append_header((k.as_str(), v.as_str()));
+        }
+
+        if not_modified {
+            builder.finish()
+        } else {
+            builder.body(self.body.clone())
+        }
+    }
+}
+
+/// A checksum of every file of the package, and of the dependencies installed in `.packages`.
+async fn fingerprint(
+    config: &fastn_core::Config,
+    cache_dir: Option<&fastn_ds::Path>,
+) -> fastn_core::Result<String> {
+    let mut paths = config.get_all_file_paths(&config.package).await?;
+    paths.sort_by_key(|p| p.to_string());
+
+    let mut checksums = String::new();
+    for path in paths {
+        if cache_dir.map_or(false, |dir| path.strip_prefix(dir).is_some()) {
+            continue;
+        }
+        let content = config.ds.read_content(&path).await?;
+        checksums.push_str(
+            format!(
+                "{path} {}\n",
+                fastn_core::utils::generate_hash(content.as_slice())
+            )
+            .as_str(),
+        );
+    }
+
+    // `fastn update` records the checksum of every installed dependency here
+    if let Ok(content) = config
+        .ds
+        .read_content(&config.ds.root().join(".fastn").join("config.json"))
+        .await
+    {
+        checksums.push_str(fastn_core::utils::generate_hash(content.as_slice()).as_str());
+    }
+
+    Ok(fastn_core::utils::generate_hash(checksums))
+}
+
+/// Entries are written in a directory per fingerprint, the ones for older fingerprints can never
+/// be served.
+async fn remove_stale_dirs(ds: &fastn_ds::DocumentStore, dir: &fastn_ds::Path, fingerprint: &str) {
+    let mut entries = match ds.read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name != fingerprint {
+            if let Err(e) = ds.remove(&dir.join(name)).await {
+                tracing::error!(msg = "response-cache clear", error = %e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[actix_web::test]
+    async fn logged_in_users_are_not_cached() {
+        let cache = super::ResponseCache::in_memory(fastn_ds::DocumentStore::new(
+            ".",
+            actix_web::web::Data::new(Default::default()),
+        ));
+        let logged_in = fastn_core::http::Request::from_actix(
+            actix_web::test::TestRequest::get()
+                .uri("/foo/")
+                .insert_header(("cookie", format!("{}=1", ft_sys_shared::SESSION_KEY)))
+                .to_http_request(),
+            Default::default(),
+        );
+        let render = |body: &'static str| async move {
+            Ok::<_, fastn_core::Error>((actix_web::HttpResponse::Ok().body(body), true))
+        };
+
+        let response = cache
+            .get_or_render(&logged_in, render("for amitu"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers().get("cache-control").unwrap(), "no-store");
+
+        let response = cache
+            .get_or_render(&request(None), render("for everyone"))
+            .await
+            .unwrap();
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        assert_eq!(body, "for everyone");
+    }
+
+    fn request(if_none_match: Option<&str>) -> fastn_core::http::Request {
+        let mut req = actix_web::test::TestRequest::get().uri("/foo/");
+        if let Some(v) = if_none_match {
+            req = req.insert_header(("If-None-Match", v));
+        }
+        fastn_core::http::Request::from_actix(req.to_http_request(), Default::default())
+    }
+
+    #[test]
+    fn etag() {
+        let entry = super::Entry::new(
+            vec![("content-type".to_string(), "text/html".to_string())],
+            bytes::Bytes::from_static(b"<h1>hello</h1>"),
+        );
+        let etag = entry.etag().unwrap().to_string();
+        assert!(etag.starts_with("W/\""));
+
+        let response = entry.to_response(&request(None));
+        assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(response.headers().get("cache-control").unwrap(), "no-cache");
+
+        let response = entry.to_response(&request(Some(etag.as_str())));
+        assert_eq!(response.status(), actix_web::http::StatusCode::NOT_MODIFIED);
+
+        let response = entry.to_response(&request(Some("W/\"something-else\"")));
+        assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+    }
+}
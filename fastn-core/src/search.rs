@@ -0,0 +1,409 @@
+//! Full-text search over the pages of the package, without a third-party service.
+//!
+//! `fastn build` keeps the text of every page it renders, see `ftd::executor::RT::text()` and
+//! `ftd::executor::PageText::from_html()`, and writes an inverted index of it to
+//! `.build/-/search-index.json`. Pages with readers in the sitemap are not indexed. The
+//! `fastn/search` module has a search box that queries that index in the browser, with
+//! `fastn_search` from the JS runtime. `fastn serve` serves the same index, answers
+//! `/-/search/?q=<query>` and has the `search` processor. It indexes the pages on the first
+//! search, without running their processors, and again when a `.ftd` file changes.
+//!
+//! `fastn-js/js/search.js` searches like `Index::search()` does, keep the two in sync.
+
+pub const INDEX_PATH: &str = "/-/search-index.json";
+pub const SEARCH_PATH: &str = "/-/search/";
+
+/// How many results the endpoint and the processor return by default.
+const DEFAULT_LIMIT: usize = 10;
+/// The maximum number of characters of the excerpt of a page.
+const EXCERPT_LENGTH: usize = 160;
+const TITLE_WEIGHT: usize = 10;
+const HEADING_WEIGHT: usize = 5;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq)]
+pub struct Index {
+    pub pages: Vec<Page>,
+    /// Every term, with the pages it occurs in and how much it weighs in each, flattened to
+    /// `[page, weight, page, weight, ...]` to keep the index small.
+    pub terms: std::collections::BTreeMap<String, Vec<usize>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Page {
+    pub url: String,
+    pub title: String,
+    pub excerpt: String,
+}
+
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub url: String,
+    pub title: String,
+    pub excerpt: String,
+    pub score: usize,
+}
+
+impl Index {
+    /// The index of `pages`, the url and text of every page.
+    pub fn new(pages: Vec<(String, ftd::executor::PageText)>) -> Index {
+        let mut index = Index::default();
+        for (url, text) in pages {
+            let page = index.pages.len();
+            let mut weights: std::collections::BTreeMap<String, usize> = Default::default();
+            for (text, weight) in std::iter::once((text.title.as_deref(), TITLE_WEIGHT))
+                .chain(
+                    text.headings
+                        .iter()
+                        .map(|h| (Some(h.as_str()), HEADING_WEIGHT)),
+                )
+                .chain(std::iter::once((Some(text.body.as_str()), 1)))
+            {
+                for term in terms(text.unwrap_or_default()) {
+                    *weights.entry(term).or_default() += weight;
+                }
+            }
+            for (term, weight) in weights {
+                index.terms.entry(term).or_default().extend([page, weight]);
+            }
+            index.pages.push(Page {
+                title: text.title.unwrap_or_else(|| url.clone()),
+                excerpt: excerpt(text.body.as_str()),
+                url,
+            });
+        }
+        index
+    }
+
+    /// The pages with every term of `query`, the best matches first. The last term also
+    /// matches the terms it is a prefix of, so results show up while the query is typed.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        let query: Vec<String> = terms(query).collect();
+        let mut scores: Option<std::collections::BTreeMap<usize, usize>> = None;
+        for (i, term) in query.iter().enumerate() {
+            let mut term_scores = std::collections::BTreeMap::new();
+            let matching = self
+                .terms
+                .range::<String, _>(term..)
+                .take_while(|(t, _)| *t == term || (i + 1 == query.len() && t.starts_with(term)));
+            for (_, postings) in matching {
+                for posting in postings.chunks(2) {
+                    *term_scores.entry(posting[0]).or_default() += posting[1];
+                }
+            }
+            scores = Some(match scores {
+                None => term_scores,
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(page, score)| term_scores.get(&page).map(|s| (page, score + s)))
+                    .collect(),
+            });
+        }
+
+        let mut scores: Vec<(usize, usize)> = scores.unwrap_or_default().into_iter().collect();
+        scores.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        scores
+            .into_iter()
+            .take(limit)
+            .filter_map(|(page, score)| {
+                let page = self.pages.get(page)?;
+                Some(SearchResult {
+                    url: page.url.clone(),
+                    title: page.title.clone(),
+                    excerpt: page.excerpt.clone(),
+                    score,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The lowercase words of `text` with at least two characters.
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().nth(1).is_some())
+        .map(|t| t.to_lowercase())
+}
+
+/// The start of `body`, cut at a word boundary.
+fn excerpt(body: &str) -> String {
+    let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    if body.chars().count() <= EXCERPT_LENGTH {
+        return body;
+    }
+    let cut: String = body.chars().take(EXCERPT_LENGTH).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(cut, _)| cut);
+    format!("{}…", cut.trim_end_matches(|c: char| !c.is_alphanumeric()))
+}
+
+/// `/foo/` for `foo.ftd` and `foo/index.ftd`.
+pub(crate) fn url(document_id: &str) -> String {
+    let path = fastn_core::utils::id_to_path(document_id)
+        .replace(std::path::MAIN_SEPARATOR, "/")
+        .trim_start_matches('/')
+        .to_string();
+    format!("/{path}")
+}
+
+pub(crate) async fn write_index(
+    config: &fastn_core::Config,
+    pages: Vec<(String, ftd::executor::PageText)>,
+) -> fastn_core::Result<()> {
+    let start = std::time::Instant::now();
+    print!(
+        "Processing {}{} ... ",
+        config.package.name.as_str(),
+        INDEX_PATH
+    );
+    let index = serde_json::to_vec(&Index::new(public(config, pages)))?;
+    config
+        .ds
+        .write_content(
+            &config.build_dir().join(INDEX_PATH.trim_start_matches('/')),
+            &index,
+        )
+        .await?;
+    fastn_core::utils::print_end(
+        format!("Processed {}{}", config.package.name.as_str(), INDEX_PATH).as_str(),
+        start,
+    );
+    Ok(())
+}
+
+/// The index `fastn serve` answers searches from, built on the first search and kept up to date
+/// by `watch()`.
+static INDEX: once_cell::sync::Lazy<antidote::RwLock<Option<std::sync::Arc<Index>>>> =
+    once_cell::sync::Lazy::new(Default::default);
+/// held while the index is built, so concurrent first searches build it only once
+static BUILDING: once_cell::sync::Lazy<tokio::sync::Mutex<()>> =
+    once_cell::sync::Lazy::new(Default::default);
+// the watcher stops watching when dropped, so we keep it around as long as the server runs
+static WATCHER: once_cell::sync::Lazy<antidote::Mutex<Option<notify::RecommendedWatcher>>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+/// The index of the current package, built on the first call.
+pub(crate) async fn index(
+    config: &fastn_core::Config,
+) -> fastn_core::Result<std::sync::Arc<Index>> {
+    if let Some(index) = INDEX.read().clone() {
+        return Ok(index);
+    }
+    let _building = BUILDING.lock().await;
+    if let Some(index) = INDEX.read().clone() {
+        return Ok(index);
+    }
+
+    let index = std::sync::Arc::new(build(config).await?);
+    *INDEX.write() = Some(std::sync::Arc::clone(&index));
+    Ok(index)
+}
+
+/// Builds the index of `fastn serve` again when a `.ftd` file of the package changes, in the
+/// background. Searches are answered from the old index till the new one is ready.
+pub(crate) fn watch(config: fastn_core::live_reload::SharedConfig) -> fastn_core::Result<()> {
+    let root = std::path::PathBuf::from(config.read().ds.root().to_string());
+    let (watcher, mut changes) = fastn_core::live_reload::watch(root)?;
+    *WATCHER.lock() = Some(watcher);
+
+    tokio::spawn(async move {
+        while let Some(changed) = changes.recv().await {
+            // a page can change because a document it imports did, so we index every page again
+            if !changed
+                .iter()
+                .any(|p| p.extension().is_some_and(|e| e == "ftd"))
+            {
+                continue;
+            }
+            // no one has searched yet, the index is built on the first search
+            if INDEX.read().is_none() {
+                continue;
+            }
+
+            // live reload may have just swapped the config, so we read it again here
+            let current = std::sync::Arc::clone(&config.read());
+            let _building = BUILDING.lock().await;
+            match build(&current).await {
+                Ok(index) => *INDEX.write() = Some(std::sync::Arc::new(index)),
+                Err(e) => tracing::error!(msg = "search-index", error = %e),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+async fn build(config: &fastn_core::Config) -> fastn_core::Result<Index> {
+    let start = std::time::Instant::now();
+    let mut pages = vec![];
+    for file in config.get_files(&config.package).await? {
+        let fastn_core::File::Ftd(document) = file else {
+            continue;
+        };
+        if document.id.eq("FASTN.ftd") || document.id.eq("404.ftd") {
+            continue;
+        }
+        if let Some(text) = page_text(config, &document).await {
+            pages.push((url(document.id.as_str()), text));
+        }
+    }
+    tracing::info!(msg = "search-index built", pages = pages.len(), elapsed = ?start.elapsed());
+    Ok(Index::new(public(config, pages)))
+}
+
+/// `pages` without the ones with readers in the sitemap, like `sitemap.xml`.
+fn public(
+    config: &fastn_core::Config,
+    pages: Vec<(String, ftd::executor::PageText)>,
+) -> Vec<(String, ftd::executor::PageText)> {
+    let Some(ref sitemap) = config.package.sitemap else {
+        return pages;
+    };
+    let private = fastn_core::seo::urls_with_readers(sitemap);
+    pages
+        .into_iter()
+        .filter(|(url, _)| !private.contains(url))
+        .collect()
+}
+
+async fn page_text(
+    config: &fastn_core::Config,
+    document: &fastn_core::Document,
+) -> Option<ftd::executor::PageText> {
+    let req = fastn_core::http::Request::default();
+    let mut req_config = fastn_core::RequestConfig::new(config, &req, document.id.as_str(), "/");
+    req_config.current_document = Some(document.id.to_string());
+    req_config.collect_page_text = true;
+    // only the text is needed, processors can be slow or have side effects, eg `sql-execute`
+    req_config.skip_processors = true;
+
+    match fastn_core::package::package_doc::read_ftd(&mut req_config, document, "/", false, false)
+        .await
+    {
+        Ok(_) => req_config.page_text,
+        Err(e) => {
+            tracing::warn!(msg = "page not indexed", doc = document.id.as_str(), error = %e);
+            None
+        }
+    }
+}
+
+/// Serves the index and the search endpoint, `None` for other paths.
+pub(crate) async fn serve(
+    config: &fastn_core::Config,
+    req: &fastn_core::http::Request,
+) -> Option<fastn_core::Result<fastn_core::http::Response>> {
+    if req.path() != INDEX_PATH && req.path() != SEARCH_PATH {
+        return None;
+    }
+    Some(serve_(config, req).await)
+}
+
+async fn serve_(
+    config: &fastn_core::Config,
+    req: &fastn_core::http::Request,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    let index = index(config).await?;
+    let json = if req.path() == INDEX_PATH {
+        serde_json::to_vec(&*index)?
+    } else {
+        let (query, limit) = query(req);
+        serde_json::to_vec(&index.search(query.as_str(), limit))?
+    };
+    Ok(fastn_core::http::ok_with_content_type(
+        json,
+        mime_guess::mime::APPLICATION_JSON,
+    ))
+}
+
+/// The `q` and `limit` query parameters of `req`.
+pub(crate) fn query(req: &fastn_core::http::Request) -> (String, usize) {
+    let query = req
+        .query()
+        .get("q")
+        .and_then(|q| q.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let limit = req
+        .query()
+        .get("limit")
+        .and_then(|l| l.as_str())
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(DEFAULT_LIMIT);
+    (query, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    fn index() -> super::Index {
+        let page = |title: &str, headings: &[&str], body: &str| ftd::executor::PageText {
+            title: Some(title.to_string()),
+            headings: headings.iter().map(|h| h.to_string()).collect(),
+            body: body.to_string(),
+        };
+        super::Index::new(vec![
+            (
+                "/".to_string(),
+                page("Home", &[], "fastn is a web framework."),
+            ),
+            (
+                "/install/".to_string(),
+                page(
+                    "Install fastn",
+                    &["Installing on Linux"],
+                    "Run the install script, then fastn serve.",
+                ),
+            ),
+            (
+                "/blog/".to_string(),
+                page("Blog", &["Framework news"], "Posts about the framework."),
+            ),
+        ])
+    }
+
+    #[test]
+    fn index_terms() {
+        let index = index();
+        assert_eq!(index.terms["fastn"], vec![0, 1, 1, 11]);
+        assert_eq!(index.terms["install"], vec![1, 11]);
+        assert!(!index.terms.contains_key("a"));
+        assert_eq!(
+            index.pages[1].excerpt,
+            "Run the install script, then fastn serve."
+        );
+    }
+
+    #[test]
+    fn search() {
+        let index = index();
+        let urls = |query: &str| {
+            index
+                .search(query, 10)
+                .into_iter()
+                .map(|r| r.url)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(urls("fastn"), vec!["/install/", "/"]);
+        assert_eq!(urls("Framework"), vec!["/blog/", "/"]);
+        assert_eq!(urls("fastn frame"), vec!["/"]);
+        assert_eq!(urls("inst"), vec!["/install/"]);
+        assert_eq!(urls("inst fastn"), Vec::<String>::new());
+        assert_eq!(urls(""), Vec::<String>::new());
+        assert_eq!(index.search("fastn", 1).len(), 1);
+        assert_eq!(index.search("linux", 1)[0].score, 5);
+    }
+
+    #[test]
+    fn excerpt() {
+        assert_eq!(super::excerpt("Hello\n  world"), "Hello world");
+        let long = "word. ".repeat(40);
+        let excerpt = super::excerpt(long.as_str());
+        assert!(excerpt.ends_with("word…"));
+        assert!(excerpt.chars().count() <= super::EXCERPT_LENGTH + 1);
+    }
+
+    #[test]
+    fn url() {
+        assert_eq!(super::url("index.ftd"), "/");
+        assert_eq!(super::url("foo/index.ftd"), "/foo/");
+        assert_eq!(super::url("foo/bar.ftd"), "/foo/bar/");
+    }
+}
@@ -0,0 +1,710 @@
+//! The files for search engines and feed readers `fastn build` writes next to the pages:
+//! `sitemap.xml`, `robots.txt`, and an Atom (`atom.xml`) and RSS (`rss.xml`) feed for every
+//! section of the sitemap marked as a blog.
+//!
+//! URLs are absolute, based on the `canonical-url` of the package, or its name if it has none.
+//! Pages get their dates from the sitemap, e.g. a blog looks like:
+//!
+//! ```ftd
+//! -- fastn.sitemap:
+//!
+//! # Blog: /blog/
+//!   blog: true
+//!
+//! - Hello World: /blog/hello-world/
+//!   date: 2024-01-15
+//!   updated: 2024-02-01T10:30:00+05:30
+//!   description: Our first post
+//!   author: Jane
+//! ```
+//!
+//! A `robots.txt` or `sitemap.xml` in the package is copied as is instead.
+
+pub const SITEMAP_FILE: &str = "sitemap.xml";
+pub const ROBOTS_FILE: &str = "robots.txt";
+pub const ATOM_FILE: &str = "atom.xml";
+pub const RSS_FILE: &str = "rss.xml";
+
+/// A page of the sitemap we can link to.
+#[derive(Debug, PartialEq)]
+struct Page<'a> {
+    url: String,
+    title: Option<&'a str>,
+    extra_data: &'a std::collections::BTreeMap<String, String>,
+}
+
+impl<'a> Page<'a> {
+    /// `None` for pages that are not public or do not have a url of their own: skipped items,
+    /// links to other sites, dynamic urls and items with readers.
+    fn new(
+        base: &str,
+        id: Option<&str>,
+        title: Option<&'a str>,
+        extra_data: &'a std::collections::BTreeMap<String, String>,
+        skip: bool,
+        readers: &[String],
+        dynamic: bool,
+    ) -> Option<Page<'a>> {
+        let id = id?;
+        if skip || dynamic || !readers.is_empty() || id.contains("://") {
+            return None;
+        }
+        Some(Page {
+            url: format!("{base}{}", path(id)),
+            title,
+            extra_data,
+        })
+    }
+
+    fn date(&self, key: &str) -> fastn_core::Result<Option<chrono::DateTime<chrono::Utc>>> {
+        self.extra_data
+            .get(key)
+            .map(|date| parse_date(date.as_str(), self.url.as_str()))
+            .transpose()
+    }
+
+    /// When the page was last changed: its `updated` date, or else its `date`.
+    fn updated(&self) -> fastn_core::Result<Option<chrono::DateTime<chrono::Utc>>> {
+        match self.date("updated")? {
+            Some(updated) => Ok(Some(updated)),
+            None => self.date("date"),
+        }
+    }
+
+    fn title(&self) -> &str {
+        self.title.unwrap_or(self.url.as_str())
+    }
+}
+
+/// Writes `sitemap.xml`, `robots.txt` and the blog feeds to `build_dir`.
+pub(crate) async fn write_seo_files(
+    config: &fastn_core::Config,
+    build_dir: &fastn_ds::Path,
+) -> fastn_core::Result<()> {
+    let base = base_url(&config.package);
+    let mut sitemap_url = None;
+
+    if let Some(ref sitemap) = config.package.sitemap {
+        write_file(
+            config,
+            build_dir,
+            SITEMAP_FILE,
+            sitemap_xml(sitemap, base.as_str())?,
+        )
+        .await?;
+        sitemap_url = Some(format!("{base}{SITEMAP_FILE}"));
+
+        for (path, atom, rss) in feeds(sitemap, base.as_str())? {
+            write_file(
+                config,
+                build_dir,
+                format!("{path}{ATOM_FILE}").as_str(),
+                atom,
+            )
+            .await?;
+            write_file(config, build_dir, format!("{path}{RSS_FILE}").as_str(), rss).await?;
+        }
+    }
+
+    write_file(
+        config,
+        build_dir,
+        ROBOTS_FILE,
+        robots_txt(config.package.robots.as_deref(), sitemap_url.as_deref()),
+    )
+    .await
+}
+
+/// Writes `content` to `name` in `build_dir`, unless the package has a file with that name.
+pub(crate) async fn write_file(
+    config: &fastn_core::Config,
+    build_dir: &fastn_ds::Path,
+    name: &str,
+    content: String,
+) -> fastn_core::Result<()> {
+    if config.ds.exists(&config.ds.root().join(name)).await {
+        return Ok(());
+    }
+
+    let start = std::time::Instant::now();
+    print!("Processing {}/{} ... ", config.package.name.as_str(), name);
+    config
+        .ds
+        .write_content(&build_dir.join(name), &content.into_bytes())
+        .await?;
+    fastn_core::utils::print_end(
+        format!("Processed {}/{}", config.package.name.as_str(), name).as_str(),
+        start,
+    );
+    Ok(())
+}
+
+/// The url the pages are served at, ending with a `/`.
+fn base_url(package: &fastn_core::Package) -> String {
+    let base = package
+        .canonical_url
+        .clone()
+        .unwrap_or_else(|| package.name.to_string());
+    let base = base.trim_end_matches('/');
+    if base.contains("://") {
+        format!("{base}/")
+    } else {
+        format!("https://{base}/")
+    }
+}
+
+/// The path of the sitemap item `id`, relative to the base url.
+fn path(id: &str) -> String {
+    let path = id.split_once('#').map_or(id, |(path, _)| path);
+    let path = path.trim_matches('/');
+    if path.is_empty() || path.ends_with(".html") {
+        path.to_string()
+    } else {
+        format!("{path}/")
+    }
+}
+
+/// The urls, like `/foo/`, of the sitemap items with readers, they are left out of the sitemap
+/// and the search index.
+pub(crate) fn urls_with_readers(
+    sitemap: &fastn_core::sitemap::Sitemap,
+) -> std::collections::HashSet<String> {
+    fn toc_urls(
+        toc: &fastn_core::sitemap::toc::TocItem,
+        urls: &mut std::collections::HashSet<String>,
+    ) {
+        if !toc.readers.is_empty() {
+            urls.insert(format!("/{}", path(toc.id.as_str())));
+        }
+        for child in toc.children.iter() {
+            toc_urls(child, urls);
+        }
+    }
+
+    let mut urls = std::collections::HashSet::new();
+    for section in sitemap.sections.iter() {
+        if !section.readers.is_empty() {
+            urls.insert(format!("/{}", path(section.id.as_str())));
+        }
+        for subsection in section.subsections.iter() {
+            if let Some(id) = subsection
+                .id
+                .as_deref()
+                .filter(|_| !subsection.readers.is_empty())
+            {
+                urls.insert(format!("/{}", path(id)));
+            }
+            for toc in subsection.toc.iter() {
+                toc_urls(toc, &mut urls);
+            }
+        }
+    }
+    urls
+}
+
+/// Every public page of the sitemap, in the order of the sitemap.
+fn pages<'a>(sitemap: &'a fastn_core::sitemap::Sitemap, base: &str) -> Vec<Page<'a>> {
+    let mut pages = vec![];
+    for section in sitemap.sections.iter() {
+        pages.extend(section_pages(section, base));
+    }
+    pages
+}
+
+/// The pages of `section`, starting with the section itself.
+fn section_pages<'a>(
+    section: &'a fastn_core::sitemap::section::Section,
+    base: &str,
+) -> Vec<Page<'a>> {
+    let mut pages: Vec<_> = Page::new(
+        base,
+        Some(section.id.as_str()),
+        section.title.as_deref(),
+        &section.extra_data,
+        section.skip,
+        &section.readers,
+        !section.path_parameters.is_empty(),
+    )
+    .into_iter()
+    .collect();
+
+    for subsection in section.subsections.iter() {
+        if subsection.visible {
+            pages.extend(Page::new(
+                base,
+                subsection.id.as_deref(),
+                subsection.title.as_deref(),
+                &subsection.extra_data,
+                subsection.skip,
+                &subsection.readers,
+                !subsection.path_parameters.is_empty(),
+            ));
+        }
+        for toc in subsection.toc.iter() {
+            toc_pages(toc, base, &mut pages);
+        }
+    }
+    pages
+}
+
+fn toc_pages<'a>(
+    toc: &'a fastn_core::sitemap::toc::TocItem,
+    base: &str,
+    pages: &mut Vec<Page<'a>>,
+) {
+    pages.extend(Page::new(
+        base,
+        Some(toc.id.as_str()),
+        toc.title.as_deref(),
+        &toc.extra_data,
+        toc.skip,
+        &toc.readers,
+        !toc.path_parameters.is_empty(),
+    ));
+    for child in toc.children.iter() {
+        toc_pages(child, base, pages);
+    }
+}
+
+/// The [sitemap](https://www.sitemaps.org/protocol.html) of the public pages, each url once.
+fn sitemap_xml(sitemap: &fastn_core::sitemap::Sitemap, base: &str) -> fastn_core::Result<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for page in pages(sitemap, base) {
+        if !seen.insert(page.url.clone()) {
+            continue;
+        }
+        xml.push_str(format!("  <url>\n    <loc>{}</loc>\n", escape(page.url.as_str())).as_str());
+        if let Some(updated) = page.updated()? {
+            xml.push_str(format!("    <lastmod>{}</lastmod>\n", rfc3339(updated)).as_str());
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    Ok(xml)
+}
+
+/// `robots` as given in `fastn.robots`, or else one allowing everything, with a `Sitemap:` line
+/// for `sitemap_url` if it does not have one.
+fn robots_txt(robots: Option<&str>, sitemap_url: Option<&str>) -> String {
+    let mut txt = match robots.map(str::trim) {
+        Some(robots) if !robots.is_empty() => format!("{robots}\n"),
+        _ => "User-agent: *\nAllow: /\n".to_string(),
+    };
+    if let Some(sitemap_url) = sitemap_url {
+        if !txt
+            .lines()
+            .any(|l| l.to_lowercase().starts_with("sitemap:"))
+        {
+            txt.push_str(format!("\nSitemap: {sitemap_url}\n").as_str());
+        }
+    }
+    txt
+}
+
+/// The path (with a trailing `/`), Atom feed and RSS feed of every section with `blog: true`.
+/// The posts are the pages of the section with a `date`, newest first.
+fn feeds(
+    sitemap: &fastn_core::sitemap::Sitemap,
+    base: &str,
+) -> fastn_core::Result<Vec<(String, String, String)>> {
+    let mut feeds = vec![];
+    for section in sitemap.sections.iter() {
+        if section.extra_data.get("blog").map(|v| v.trim()) != Some("true") {
+            continue;
+        }
+        let mut pages = section_pages(section, base).into_iter();
+        let Some(blog) = pages.next() else {
+            continue;
+        };
+
+        let mut posts = vec![];
+        for page in pages {
+            if let Some(date) = page.date("date")? {
+                let updated = page.updated()?.unwrap_or(date);
+                posts.push((page, date, updated));
+            }
+        }
+        posts.sort_by_key(|(_, date, _)| std::cmp::Reverse(*date));
+
+        let path = blog.url.trim_start_matches(base).to_string();
+        feeds.push((
+            path.clone(),
+            atom_xml(
+                &blog,
+                posts.as_slice(),
+                format!("{base}{path}{ATOM_FILE}").as_str(),
+            ),
+            rss_xml(
+                &blog,
+                posts.as_slice(),
+                format!("{base}{path}{RSS_FILE}").as_str(),
+            ),
+        ));
+    }
+    Ok(feeds)
+}
+
+type Post<'a> = (
+    Page<'a>,
+    chrono::DateTime<chrono::Utc>,
+    chrono::DateTime<chrono::Utc>,
+);
+
+/// An [Atom](https://www.rfc-editor.org/rfc/rfc4287) feed of `posts`.
+fn atom_xml(blog: &Page, posts: &[Post], feed_url: &str) -> String {
+    let updated = posts
+        .iter()
+        .map(|(_, _, updated)| *updated)
+        .max()
+        .unwrap_or(chrono::DateTime::UNIX_EPOCH);
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <feed xmlns=\"http://www.w3.org/2005/Atom\">\n  \
+        <title>{title}</title>\n  \
+        <id>{url}</id>\n  \
+        <link href=\"{url}\"/>\n  \
+        <link rel=\"self\" href=\"{feed_url}\"/>\n  \
+        <updated>{updated}</updated>\n",
+        title = escape(blog.title()),
+        url = escape(blog.url.as_str()),
+        feed_url = escape(feed_url),
+        updated = rfc3339(updated),
+    );
+    if let Some(description) = blog.extra_data.get("description") {
+        xml.push_str(format!("  <subtitle>{}</subtitle>\n", escape(description)).as_str());
+    }
+    for (page, date, updated) in posts {
+        xml.push_str(
+            format!(
+                "  <entry>\n    \
+                <title>{title}</title>\n    \
+                <id>{url}</id>\n    \
+                <link href=\"{url}\"/>\n    \
+                <published>{published}</published>\n    \
+                <updated>{updated}</updated>\n",
+                title = escape(page.title()),
+                url = escape(page.url.as_str()),
+                published = rfc3339(*date),
+                updated = rfc3339(*updated),
+            )
+            .as_str(),
+        );
+        if let Some(author) = page
+            .extra_data
+            .get("author")
+            .or_else(|| blog.extra_data.get("author"))
+        {
+            xml.push_str(
+                format!("    <author><name>{}</name></author>\n", escape(author)).as_str(),
+            );
+        }
+        if let Some(description) = page.extra_data.get("description") {
+            xml.push_str(format!("    <summary>{}</summary>\n", escape(description)).as_str());
+        }
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// An [RSS 2.0](https://www.rssboard.org/rss-specification) feed of `posts`.
+fn rss_xml(blog: &Page, posts: &[Post], feed_url: &str) -> String {
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n  \
+        <channel>\n    \
+        <title>{title}</title>\n    \
+        <link>{url}</link>\n    \
+        <description>{description}</description>\n    \
+        <atom:link href=\"{feed_url}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        title = escape(blog.title()),
+        url = escape(blog.url.as_str()),
+        description = escape(
+            blog.extra_data
+                .get("description")
+                .map_or(blog.title(), |d| d.as_str())
+        ),
+        feed_url = escape(feed_url),
+    );
+    if let Some((_, _, updated)) = posts.iter().max_by_key(|(_, _, updated)| *updated) {
+        xml.push_str(
+            format!(
+                "    <lastBuildDate>{}</lastBuildDate>\n",
+                updated.to_rfc2822()
+            )
+            .as_str(),
+        );
+    }
+    for (page, date, _) in posts {
+        xml.push_str(
+            format!(
+                "    <item>\n      \
+                <title>{title}</title>\n      \
+                <link>{url}</link>\n      \
+                <guid>{url}</guid>\n      \
+                <pubDate>{date}</pubDate>\n",
+                title = escape(page.title()),
+                url = escape(page.url.as_str()),
+                date = date.to_rfc2822(),
+            )
+            .as_str(),
+        );
+        if let Some(description) = page.extra_data.get("description") {
+            xml.push_str(
+                format!("      <description>{}</description>\n", escape(description)).as_str(),
+            );
+        }
+        xml.push_str("    </item>\n");
+    }
+    xml.push_str("  </channel>\n</rss>\n");
+    xml
+}
+
+/// Parses `YYYY-MM-DD` or an RFC 3339 date time, like `2024-01-15T10:30:00Z`.
+fn parse_date(date: &str, url: &str) -> fastn_core::Result<chrono::DateTime<chrono::Utc>> {
+    let date = date.trim();
+    if let Ok(date_time) = chrono::DateTime::parse_from_rfc3339(date) {
+        return Ok(date_time.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| fastn_core::Error::PackageError {
+            message: format!(
+                "invalid date `{date}` for {url} in the sitemap, expected `YYYY-MM-DD` or \
+                `YYYY-MM-DDTHH:MM:SSZ`"
+            ),
+        })
+}
+
+fn rfc3339(date: chrono::DateTime<chrono::Utc>) -> String {
+    date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    fn sitemap() -> fastn_core::sitemap::Sitemap {
+        use fastn_core::sitemap::{section::Section, section::Subsection, toc::TocItem};
+
+        let data = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let toc = |id: &str, title: &str, pairs: &[(&str, &str)]| TocItem {
+            id: id.to_string(),
+            title: Some(title.to_string()),
+            extra_data: data(pairs),
+            ..Default::default()
+        };
+
+        fastn_core::sitemap::Sitemap {
+            sections: vec![
+                Section {
+                    id: "/".to_string(),
+                    title: Some("Home".to_string()),
+                    subsections: vec![Subsection {
+                        id: Some("/about/".to_string()),
+                        title: Some("About".to_string()),
+                        visible: true,
+                        toc: vec![
+                            toc("/about/#team", "Team", &[]),
+                            TocItem {
+                                skip: true,
+                                ..toc("/drafts/", "Drafts", &[])
+                            },
+                            toc("https://github.com/fastn-stack", "GitHub", &[]),
+                        ],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                Section {
+                    id: "/blog/".to_string(),
+                    title: Some("Blog".to_string()),
+                    extra_data: data(&[("blog", "true"), ("author", "Team")]),
+                    subsections: vec![Subsection {
+                        visible: true,
+                        toc: vec![
+                            toc("/blog/first/", "First", &[("date", "2024-01-15")]),
+                            TocItem {
+                                children: vec![toc(
+                                    "/blog/second/",
+                                    "Second & last",
+                                    &[
+                                        ("date", "2024-02-01T10:30:00+05:30"),
+                                        ("description", "<b>bold</b>"),
+                                        ("author", "Jane"),
+                                    ],
+                                )],
+                                ..toc("/blog/tags/", "Tags", &[])
+                            },
+                        ],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            readers: vec![],
+            writers: vec![],
+        }
+    }
+
+    #[test]
+    fn sitemap_xml() {
+        assert_eq!(
+            super::sitemap_xml(&sitemap(), "https://fastn.com/").unwrap(),
+            indoc::indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                  <url>
+                    <loc>https://fastn.com/</loc>
+                  </url>
+                  <url>
+                    <loc>https://fastn.com/about/</loc>
+                  </url>
+                  <url>
+                    <loc>https://fastn.com/blog/</loc>
+                  </url>
+                  <url>
+                    <loc>https://fastn.com/blog/first/</loc>
+                    <lastmod>2024-01-15T00:00:00Z</lastmod>
+                  </url>
+                  <url>
+                    <loc>https://fastn.com/blog/tags/</loc>
+                  </url>
+                  <url>
+                    <loc>https://fastn.com/blog/second/</loc>
+                    <lastmod>2024-02-01T05:00:00Z</lastmod>
+                  </url>
+                </urlset>
+                "#
+            )
+        );
+
+        let mut invalid = sitemap();
+        invalid.sections[1].subsections[0].toc[0]
+            .extra_data
+            .insert("date".to_string(), "15/01/2024".to_string());
+        assert!(super::sitemap_xml(&invalid, "https://fastn.com/").is_err());
+    }
+
+    #[test]
+    fn feeds() {
+        let feeds = super::feeds(&sitemap(), "https://fastn.com/").unwrap();
+        assert_eq!(feeds.len(), 1);
+        let (path, atom, rss) = &feeds[0];
+        assert_eq!(path, "blog/");
+        assert_eq!(
+            atom,
+            indoc::indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <feed xmlns="http://www.w3.org/2005/Atom">
+                  <title>Blog</title>
+                  <id>https://fastn.com/blog/</id>
+                  <link href="https://fastn.com/blog/"/>
+                  <link rel="self" href="https://fastn.com/blog/atom.xml"/>
+                  <updated>2024-02-01T05:00:00Z</updated>
+                  <entry>
+                    <title>Second &amp; last</title>
+                    <id>https://fastn.com/blog/second/</id>
+                    <link href="https://fastn.com/blog/second/"/>
+                    <published>2024-02-01T05:00:00Z</published>
+                    <updated>2024-02-01T05:00:00Z</updated>
+                    <author><name>Jane</name></author>
+                    <summary>&lt;b&gt;bold&lt;/b&gt;</summary>
+                  </entry>
+                  <entry>
+                    <title>First</title>
+                    <id>https://fastn.com/blog/first/</id>
+                    <link href="https://fastn.com/blog/first/"/>
+                    <published>2024-01-15T00:00:00Z</published>
+                    <updated>2024-01-15T00:00:00Z</updated>
+                    <author><name>Team</name></author>
+                  </entry>
+                </feed>
+                "#
+            )
+        );
+        assert_eq!(
+            rss,
+            indoc::indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+                  <channel>
+                    <title>Blog</title>
+                    <link>https://fastn.com/blog/</link>
+                    <description>Blog</description>
+                    <atom:link href="https://fastn.com/blog/rss.xml" rel="self" type="application/rss+xml"/>
+                    <lastBuildDate>Thu, 1 Feb 2024 05:00:00 +0000</lastBuildDate>
+                    <item>
+                      <title>Second &amp; last</title>
+                      <link>https://fastn.com/blog/second/</link>
+                      <guid>https://fastn.com/blog/second/</guid>
+                      <pubDate>Thu, 1 Feb 2024 05:00:00 +0000</pubDate>
+                      <description>&lt;b&gt;bold&lt;/b&gt;</description>
+                    </item>
+                    <item>
+                      <title>First</title>
+                      <link>https://fastn.com/blog/first/</link>
+                      <guid>https://fastn.com/blog/first/</guid>
+                      <pubDate>Mon, 15 Jan 2024 00:00:00 +0000</pubDate>
+                    </item>
+                  </channel>
+                </rss>
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn urls_with_readers() {
+        let mut sitemap = sitemap();
+        sitemap.sections[0].subsections[0].readers = vec!["staff".to_string()];
+        sitemap.sections[1].subsections[0].toc[1].children[0].readers = vec!["staff".to_string()];
+        assert_eq!(
+            super::urls_with_readers(&sitemap),
+            ["/about/", "/blog/second/"]
+                .into_iter()
+                .map(String::from)
+                .collect()
+        );
+        assert!(!super::sitemap_xml(&sitemap, "https://fastn.com/")
+            .unwrap()
+            .contains("/blog/second/"));
+    }
+
+    #[test]
+    fn robots_txt() {
+        assert_eq!(
+            super::robots_txt(None, Some("https://fastn.com/sitemap.xml")),
+            "User-agent: *\nAllow: /\n\nSitemap: https://fastn.com/sitemap.xml\n"
+        );
+        assert_eq!(
+            super::robots_txt(Some("User-agent: *\nDisallow: /drafts/\n\n"), None),
+            "User-agent: *\nDisallow: /drafts/\n"
+        );
+        assert_eq!(
+            super::robots_txt(
+                Some("User-agent: *\nSitemap: https://example.com/s.xml"),
+                Some("https://fastn.com/sitemap.xml")
+            ),
+            "User-agent: *\nSitemap: https://example.com/s.xml\n"
+        );
+    }
+}
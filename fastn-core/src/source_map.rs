@@ -0,0 +1,55 @@
+//! `fastn serve --source-maps` adds a source map to the JS of every page it renders, so the
+//! browser's developer tools show errors, breakpoints and stack traces against the `.ftd`
+//! documents the JS was generated from.
+//!
+//! The JS of a page is inlined in its HTML, so the maps are kept in memory when a page is
+//! rendered and served from `/-/source-map/<document id>.map`. Only the content of the page's
+//! own document is embedded in its map, things from imported documents are mapped to their
+//! document and line but the browser can not show their source.
+
+pub const SOURCE_MAP_PATH: &str = "/-/source-map/";
+
+static SOURCE_MAPS: once_cell::sync::Lazy<
+    antidote::RwLock<std::collections::HashMap<String, String>>,
+> = once_cell::sync::Lazy::new(|| antidote::RwLock::new(Default::default()));
+
+/// Renders the JS of `main`, ending with a `sourceMappingURL` comment that points to its map.
+pub(crate) fn to_js(
+    main: &fastn_core::Document,
+    js_ast_data: &ftd::js::JSAstData,
+    package_name: &str,
+) -> String {
+    let (js, map) = fastn_js::to_js_with_source_map(
+        js_ast_data.asts.as_slice(),
+        js_ast_data.locations.as_slice(),
+        package_name,
+    );
+    let map = map
+        .set_line_offset(fastn_core::utils::js_script_line_offset())
+        .set_source_content(main.id_with_package().as_str(), main.content.as_str());
+    SOURCE_MAPS
+        .write()
+        .insert(main.id.to_string(), map.to_json(main.id.as_str()));
+
+    format!(
+        "{js}//# sourceMappingURL={SOURCE_MAP_PATH}{}.map\n",
+        main.id
+    )
+}
+
+pub(crate) fn serve(path: &str) -> fastn_core::http::Response {
+    let map = path
+        .strip_prefix(SOURCE_MAP_PATH)
+        .and_then(|id| id.strip_suffix(".map"))
+        .and_then(|id| SOURCE_MAPS.read().get(id).cloned());
+
+    match map {
+        Some(map) => fastn_core::http::ok_with_content_type(
+            map.into_bytes(),
+            mime_guess::mime::APPLICATION_JSON,
+        ),
+        None => fastn_core::http::not_found_without_warning(format!(
+            "no source map for {path}, is the page rendered?"
+        )),
+    }
+}
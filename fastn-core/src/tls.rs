@@ -0,0 +1,112 @@
+/// TLS for `fastn serve`, so secure cookies, service workers and OAuth redirects can be tested
+/// locally, and fastn can be deployed without a reverse proxy in front of it.
+#[derive(Debug, Clone)]
+pub enum Tls {
+    /// PEM files passed with `--tls-cert` and `--tls-key`. The certificate file can have the
+    /// whole chain, the key can be PKCS#8, PKCS#1 or SEC1.
+    Pem {
+        cert: camino::Utf8PathBuf,
+        key: camino::Utf8PathBuf,
+    },
+    /// `--tls-self-signed`: a certificate for `localhost` and the bind address, generated on
+    /// every start. Browsers warn about it, it is only meant for local development.
+    SelfSigned,
+}
+
+type CertificateChain = Vec<rustls::pki_types::CertificateDer<'static>>;
+
+impl Tls {
+    pub(crate) fn server_config(
+        &self,
+        bind_address: &str,
+    ) -> fastn_core::Result<rustls::ServerConfig> {
+        let (certs, key) = match self {
+            Tls::Pem { cert, key } => (read_certs(cert)?, read_key(key)?),
+            Tls::SelfSigned => self_signed(bind_address)?,
+        };
+
+        rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| fastn_core::Error::TlsError {
+                message: format!("invalid certificate or private key: {e}"),
+            })
+    }
+}
+
+fn read_certs(path: &camino::Utf8Path) -> fastn_core::Result<CertificateChain> {
+    let certs = rustls_pemfile::certs(&mut open(path)?)
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|e| io_error(e, path))?;
+
+    if certs.is_empty() {
+        return Err(fastn_core::Error::TlsError {
+            message: format!("no certificate found in {path}"),
+        });
+    }
+
+    Ok(certs)
+}
+
+fn read_key(
+    path: &camino::Utf8Path,
+) -> fastn_core::Result<rustls::pki_types::PrivateKeyDer<'static>> {
+    rustls_pemfile::private_key(&mut open(path)?)
+        .map_err(|e| io_error(e, path))?
+        .ok_or_else(|| fastn_core::Error::TlsError {
+            message: format!("no private key found in {path}"),
+        })
+}
+
+fn open(path: &camino::Utf8Path) -> fastn_core::Result<std::io::BufReader<std::fs::File>> {
+    std::fs::File::open(path)
+        .map(std::io::BufReader::new)
+        .map_err(|e| io_error(e, path))
+}
+
+fn io_error(io_error: std::io::Error, path: &camino::Utf8Path) -> fastn_core::Error {
+    fastn_core::Error::FastnIoError {
+        io_error,
+        path: path.to_string(),
+    }
+}
+
+fn self_signed(
+    bind_address: &str,
+) -> fastn_core::Result<(CertificateChain, rustls::pki_types::PrivateKeyDer<'static>)> {
+    let mut names = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+    if !names.iter().any(|n| n == bind_address) {
+        names.push(bind_address.to_string());
+    }
+
+    let rcgen::CertifiedKey { cert, key_pair } = rcgen::generate_simple_self_signed(names)
+        .map_err(|e| fastn_core::Error::TlsError {
+            message: format!("failed to generate a self-signed certificate: {e}"),
+        })?;
+
+    Ok((
+        vec![cert.der().clone()],
+        rustls::pki_types::PrivateKeyDer::Pkcs8(key_pair.serialize_der().into()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn self_signed() {
+        assert!(super::Tls::SelfSigned.server_config("0.0.0.0").is_ok());
+    }
+
+    #[test]
+    fn missing_key() {
+        let dir = std::env::temp_dir().join("fastn-tls-missing-key");
+        std::fs::create_dir_all(&dir).unwrap();
+        let empty = camino::Utf8PathBuf::try_from(dir.join("empty.pem")).unwrap();
+        std::fs::write(&empty, "").unwrap();
+
+        assert!(matches!(
+            super::read_key(&empty),
+            Err(fastn_core::Error::TlsError { .. })
+        ));
+    }
+}
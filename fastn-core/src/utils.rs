@@ -580,6 +580,14 @@ pub async fn get_inline_css_html(config: &fastn_core::Config, inline_js: &[Strin
     result
 }
 
+pub fn get_live_reload_js_html(config: &fastn_core::Config) -> String {
+    if config.live_reload {
+        format!("<script>{}</script>", fastn_js::live_reload_js())
+    } else {
+        "".to_string()
+    }
+}
+
 async fn get_extra_js(
     config: &fastn_core::Config,
     external_js: &[String],
@@ -588,11 +596,12 @@ async fn get_extra_js(
     rive_data: &str,
 ) -> String {
     format!(
-        "{}{}{}{}",
+        "{}{}{}{}{}",
         get_external_js_html(external_js),
         get_inline_js_html(config, inline_js).await,
         js,
-        rive_data
+        rive_data,
+        get_live_reload_js_html(config),
     )
 }
 
@@ -707,6 +716,7 @@ pub fn get_fastn_package_data(package: &fastn_core::Package) -> String {
 
 pub async fn replace_markers_2023(
     js_script: &str,
+    runtime_modules: &[fastn_js::RuntimeModule],
     scripts: &str,
     ssr_body: &str,
     font_style: &str,
@@ -734,16 +744,21 @@ pub async fn replace_markers_2023(
         js_script = format!("{js_script}{}", fastn_core::utils::available_code_themes()).as_str(),
         script_file = format!(
             r#"
+                {}
                 <script src="{}"></script>
-                <script src="{}"></script>
-                <script src="{}"></script>
-                <link rel="stylesheet" href="{}">
                 {}
             "#,
-            hashed_markdown_js(),
-            hashed_prism_js(),
-            hashed_default_ftd_js(config.package.name.as_str()),
-            hashed_prism_css(),
+            // prism is only needed to highlight code blocks
+            if runtime_modules.contains(&fastn_js::RuntimeModule::FtdLanguage) {
+                format!(
+                    r#"<script src="{}"></script><link rel="stylesheet" href="{}">"#,
+                    hashed_prism_js(),
+                    hashed_prism_css(),
+                )
+            } else {
+                "".to_string()
+            },
+            hashed_runtime_js(config.package.name.as_str(), runtime_modules),
             scripts,
         )
         .as_str(),
@@ -761,6 +776,16 @@ pub async fn replace_markers_2023(
     )
 }
 
+/// The number of lines between the `<script>` tag `replace_markers_2023()` puts the JS of the
+/// document in and the JS itself. Source maps of inline scripts count lines from the tag.
+pub(crate) fn js_script_line_offset() -> usize {
+    let template = include_str!("../../ftd/ftd-js.html");
+    let before = &template[..template.find("{js_script}").unwrap()];
+    before[before.rfind("<script>").unwrap()..]
+        .matches('\n')
+        .count()
+}
+
 pub fn is_test() -> bool {
     cfg!(test) || std::env::args().any(|e| e == "--test")
 }
@@ -924,17 +949,42 @@ pub fn hashed_default_js_name() -> &'static str {
     &JS_HASH
 }
 
-static FTD_JS_HASH: once_cell::sync::OnceCell<String> = once_cell::sync::OnceCell::new();
+/// A bundle of the JS runtime, pages load the one with the modules they use.
+pub struct RuntimeJs {
+    pub modules: Vec<fastn_js::RuntimeModule>,
+    /// `default-<hash>.js`
+    pub name: String,
+    pub content: String,
+}
 
-pub fn hashed_default_ftd_js(package_name: &str) -> &'static str {
-    FTD_JS_HASH.get_or_init(|| {
-        format!(
-            "default-{}.js",
-            generate_hash(ftd::js::all_js_without_test(package_name).as_str())
-        )
+static RUNTIME_JS: once_cell::sync::OnceCell<Vec<RuntimeJs>> = once_cell::sync::OnceCell::new();
+
+/// All the runtime bundles a page can use, see `fastn_js::runtime_modules()`.
+pub fn runtime_js(package_name: &str) -> &'static [RuntimeJs] {
+    RUNTIME_JS.get_or_init(|| {
+        fastn_js::all_runtime_module_sets()
+            .into_iter()
+            .map(|modules| {
+                let content = ftd::js::runtime_js(package_name, modules.as_slice());
+                RuntimeJs {
+                    name: format!("default-{}.js", generate_hash(content.as_str())),
+                    modules,
+                    content,
+                }
+            })
+            .collect()
     })
 }
 
+pub fn hashed_runtime_js(package_name: &str, modules: &[fastn_js::RuntimeModule]) -> &'static str {
+    runtime_js(package_name)
+        .iter()
+        .find(|r| r.modules == modules)
+        .expect("modules are not from fastn_js::runtime_modules()")
+        .name
+        .as_str()
+}
+
 static MARKDOWN_HASH: once_cell::sync::Lazy<String> =
     once_cell::sync::Lazy::new(|| format!("markdown-{}.js", generate_hash(ftd::markdown_js()),));
 
@@ -0,0 +1,6314 @@
+Prism.languages.ftd = {
+comment: [
+{
+pattern: /\/--\s*((?!--)[\S\s])*/g,
+greedy: true,
+alias: "section-comment",
+},
+{
+pattern: /[\s]*\/[\w]+(:).*\n/g,
+greedy: true,
+alias: "header-comment",
+},
+{
+pattern: /(;;).*\n/g,
+greedy: true,
+alias: "inline-or-line-comment",
+},
+],
+string: {
+pattern: /^[ \t\n]*--\s+(.*)(\n(?![ \n\t]*--).*)*/g,
+inside: {
+"section-identifier": /([ \t\n])*--\s+/g,
+punctuation: {
+pattern: /^(.*):/g,
+inside: {
+"semi-colon": /:/g,
+keyword: /^(component|record|end|or-type)/g,
+"value-type": /^(integer|boolean|decimal|string)/g,
+"kernel-type": /\s*ftd[\S]+/g,
+"type-modifier": {
+pattern: /(\s)+list(?=\s)/g,
+lookbehind: true,
+},
+"section-name": {
+pattern: /(\s)*.+/g,
+lookbehind: true,
+},
+},
+},
+"section-caption": /^.+(?=\n)*/g,
+regex: {
+pattern: /(?!--\s*).*[:]\s*(.*)(\n)*/g,
+inside: {
+"header-condition": /\s*if\s*:(.)+/g,
+event: /\s*\$on(.)+\$(?=:)/g,
+processor: /\s*\$[^:]+\$(?=:)/g,
+regex: {
+pattern: /[^:]+(?=:)/g,
+inside: {
+"header-condition": /if\s*{.+}/g,
+tag: {
+pattern: /(.)+(?=if)?/g,
+inside: {
+"kernel-type": /^\s*ftd[\S]+/g,
+"header-type":
+/^(record|caption|body|caption or body|body or caption|integer|boolean|decimal|string)/g,
+"type-modifier": {
+pattern: /(\s)+list(?=\s)/g,
+lookbehind: true,
+},
+"header-name": {
+pattern: /(\s)*(.)+/g,
+lookbehind: true,
+},
+},
+},
+},
+},
+"semi-colon": /:/g,
+"header-value": {
+pattern: /(\s)*(.+)/g,
+lookbehind: true,
+},
+},
+},
+},
+},
+};
+const fastn = (function (fastn) {
+class Closure {
+#cached_value;
+#node;
+#property;
+#formula;
+#inherited;
+constructor(func, execute = true) {
+if (execute) {
+this.#cached_value = func();
+}
+this.#formula = func;
+}
+get() {
+return this.#cached_value;
+}
+getFormula() {
+return this.#formula;
+}
+addNodeProperty(node, property, inherited) {
+this.#node = node;
+this.#property = property;
+this.#inherited = inherited;
+this.updateUi();
+return this;
+}
+update() {
+this.#cached_value = this.#formula();
+this.updateUi();
+}
+getNode() {
+return this.#node;
+}
+updateUi() {
+if (
+!this.#node ||
+this.#property === null ||
+this.#property === undefined ||
+!this.#node.getNode()
+) {
+return;
+}
+this.#node.setStaticProperty(
+this.#property,
+this.#cached_value,
+this.#inherited,
+);
+}
+}
+class Mutable {
+#value;
+#old_closure;
+#closures;
+#closureInstance;
+constructor(val) {
+this.#value = null;
+this.#old_closure = null;
+this.#closures = [];
+this.#closureInstance = fastn.closure(() =>
+this.#closures.forEach((closure) => closure.update()),
+);
+this.set(val);
+}
+closures() {
+return this.#closures;
+}
+get(key) {
+if (
+!fastn_utils.isNull(key) &&
+(this.#value instanceof RecordInstance ||
+this.#value instanceof MutableList ||
+this.#value instanceof Mutable)
+) {
+return this.#value.get(key);
+}
+return this.#value;
+}
+setWithoutUpdate(value) {
+if (this.#old_closure) {
+this.#value.removeClosure(this.#old_closure);
+}
+if (this.#value instanceof RecordInstance) {
+this.#value = value;
+} else {
+this.#value = value;
+}
+if (this.#value instanceof Mutable) {
+this.#old_closure = fastn.closureWithoutExecute(() =>
+this.#closureInstance.update(),
+);
+this.#value.addClosure(this.#old_closure);
+} else {
+this.#old_closure = null;
+}
+}
+set(value) {
+this.setWithoutUpdate(value);
+this.#closureInstance.update();
+}
+unlinkNode(node) {
+this.#closures = this.#closures.filter(
+(closure) => closure.getNode() !== node,
+);
+}
+addClosure(closure) {
+this.#closures.push(closure);
+fastn.recordClosure(this, closure);
+}
+removeClosure(closure) {
+this.#closures = this.#closures.filter((c) => c !== closure);
+}
+equalMutable(other) {
+if (!fastn_utils.deepEqual(this.get(), other.get())) {
+return false;
+}
+const thisClosures = this.#closures;
+const otherClosures = other.#closures;
+return thisClosures === otherClosures;
+}
+getClone() {
+return new Mutable(fastn_utils.clone(this.#value));
+}
+}
+class Proxy {
+#differentiator;
+#cached_value;
+#closures;
+#closureInstance;
+constructor(targets, differentiator) {
+this.#differentiator = differentiator;
+this.#cached_value = this.#differentiator().get();
+this.#closures = [];
+let proxy = this;
+for (let idx in targets) {
+targets[idx].addClosure(
+new Closure(function () {
+proxy.update();
+proxy.#closures.forEach((closure) => closure.update());
+}),
+);
+targets[idx].addClosure(this);
+}
+}
+addClosure(closure) {
+this.#closures.push(closure);
+fastn.recordClosure(this, closure);
+}
+removeClosure(closure) {
+this.#closures = this.#closures.filter((c) => c !== closure);
+}
+update() {
+this.#cached_value = this.#differentiator().get();
+}
+get(key) {
+if (
+!!key &&
+(this.#cached_value instanceof RecordInstance ||
+this.#cached_value instanceof MutableList ||
+this.#cached_value instanceof Mutable)
+) {
+return this.#cached_value.get(key);
+}
+return this.#cached_value;
+}
+set(value) {
+this.#differentiator().set(value);
+}
+}
+class MutableList {
+#list;
+#watchers;
+#closures;
+constructor(list) {
+this.#list = [];
+for (let idx in list) {
+this.#list.push({
+item: fastn.wrapMutable(list[idx]),
+index: new Mutable(parseInt(idx)),
+});
+}
+this.#watchers = [];
+this.#closures = [];
+}
+addClosure(closure) {
+this.#closures.push(closure);
+fastn.recordClosure(this, closure);
+}
+removeClosure(closure) {
+this.#closures = this.#closures.filter((c) => c !== closure);
+}
+unlinkNode(node) {
+this.#closures = this.#closures.filter(
+(closure) => closure.getNode() !== node,
+);
+}
+forLoop(root, dom_constructor) {
+let l = fastn_dom.forLoop(root, dom_constructor, this);
+this.#watchers.push(l);
+return l;
+}
+getList() {
+return this.#list;
+}
+getLength() {
+return this.#list.length;
+}
+get(idx) {
+if (fastn_utils.isNull(idx)) {
+return this.getList();
+}
+return this.#list[idx];
+}
+set(index, value) {
+if (value === undefined) {
+value = index;
+if (!(value instanceof MutableList)) {
+if (!Array.isArray(value)) {
+value = [value];
+}
+value = new MutableList(value);
+}
+let list = value.#list;
+this.#list = [];
+for (let i in list) {
+this.#list.push(list[i]);
+}
+for (let i in this.#watchers) {
+this.#watchers[i].createAllNode();
+}
+} else {
+index = fastn_utils.getFlattenStaticValue(index);
+this.#list[index].item.set(value);
+}
+this.#closures.forEach((closure) => closure.update());
+}
+insertAt(index, value) {
+index = fastn_utils.getFlattenStaticValue(index);
+let mutable = fastn.wrapMutable(value);
+this.#list.splice(index, 0, {
+item: mutable,
+index: new Mutable(index),
+});
+for (let i = index + 1; i < this.#list.length; i++) {
+this.#list[i].index.set(i);
+}
+for (let i in this.#watchers) {
+this.#watchers[i].createNode(index);
+}
+this.#closures.forEach((closure) => closure.update());
+}
+push(value) {
+this.insertAt(this.#list.length, value);
+}
+deleteAt(index) {
+index = fastn_utils.getFlattenStaticValue(index);
+this.#list.splice(index, 1);
+for (let i = index; i < this.#list.length; i++) {
+this.#list[i].index.set(i);
+}
+for (let i in this.#watchers) {
+let forLoop = this.#watchers[i];
+forLoop.deleteNode(index);
+}
+this.#closures.forEach((closure) => closure.update());
+}
+clearAll() {
+this.#list = [];
+for (let i in this.#watchers) {
+this.#watchers[i].deleteAllNode();
+}
+this.#closures.forEach((closure) => closure.update());
+}
+pop() {
+this.deleteAt(this.#list.length - 1);
+}
+getClone() {
+let current_list = this.#list;
+let new_list = [];
+for (let idx in current_list) {
+new_list.push(fastn_utils.clone(current_list[idx].item));
+}
+return new MutableList(new_list);
+}
+}
+fastn.mutable = function (val) {
+return new Mutable(val);
+};
+fastn.closure = function (func) {
+return new Closure(func);
+};
+fastn.closureWithoutExecute = function (func) {
+return new Closure(func, false);
+};
+fastn.recordedClosures = null;
+fastn.recordClosure = function (target, closure) {
+if (fastn.recordedClosures !== null) {
+fastn.recordedClosures.push([target, closure]);
+}
+};
+fastn.formula = function (deps, func) {
+let closure = fastn.closure(func);
+let mutable = new Mutable(closure.get());
+for (let idx in deps) {
+if (fastn_utils.isNull(deps[idx]) || !deps[idx].addClosure) {
+continue;
+}
+deps[idx].addClosure(
+new Closure(function () {
+closure.update();
+mutable.set(closure.get());
+}),
+);
+}
+return mutable;
+};
+fastn.proxy = function (targets, differentiator) {
+return new Proxy(targets, differentiator);
+};
+fastn.wrapMutable = function (obj) {
+if (
+!(obj instanceof Mutable) &&
+!(obj instanceof RecordInstance) &&
+!(obj instanceof MutableList)
+) {
+obj = new Mutable(obj);
+}
+return obj;
+};
+fastn.mutableList = function (list) {
+return new MutableList(list);
+};
+class RecordInstance {
+#fields;
+#closures;
+constructor(obj) {
+this.#fields = {};
+this.#closures = [];
+for (let key in obj) {
+if (obj[key] instanceof fastn.mutableClass) {
+this.#fields[key] = fastn.mutable(null);
+this.#fields[key].setWithoutUpdate(obj[key]);
+} else {
+this.#fields[key] = fastn.mutable(obj[key]);
+}
+}
+}
+getAllFields() {
+return this.#fields;
+}
+getClonedFields() {
+let clonedFields = {};
+for (let key in this.#fields) {
+let field_value = this.#fields[key];
+if (
+field_value instanceof fastn.recordInstanceClass ||
+field_value instanceof fastn.mutableClass ||
+field_value instanceof fastn.mutableListClass
+) {
+clonedFields[key] = this.#fields[key].getClone();
+} else {
+clonedFields[key] = this.#fields[key];
+}
+}
+return clonedFields;
+}
+addClosure(closure) {
+this.#closures.push(closure);
+fastn.recordClosure(this, closure);
+}
+removeClosure(closure) {
+this.#closures = this.#closures.filter((c) => c !== closure);
+}
+unlinkNode(node) {
+this.#closures = this.#closures.filter(
+(closure) => closure.getNode() !== node,
+);
+}
+get(key) {
+return this.#fields[key];
+}
+set(key, value) {
+if (value === undefined) {
+value = key;
+if (!(value instanceof RecordInstance)) {
+value = new RecordInstance(value);
+}
+let fields = {};
+for (let key in value.#fields) {
+this.#fields[key].set(value.#fields[key]);
+}
+} else if (this.#fields[key] === undefined) {
+this.#fields[key] = fastn.mutable(null);
+this.#fields[key].setWithoutUpdate(value);
+} else {
+this.#fields[key].set(value);
+}
+this.#closures.forEach((closure) => closure.update());
+}
+setAndReturn(key, value) {
+this.set(key, value);
+return this;
+}
+replace(obj) {
+for (let key in this.#fields) {
+if (!(key in obj.#fields)) {
+throw new Error(
+"RecordInstance.replace: key " +
+key +
+" not present in new object",
+);
+}
+this.#fields[key] = fastn.wrapMutable(obj.#fields[key]);
+}
+this.#closures.forEach((closure) => closure.update());
+}
+toObject() {
+return Object.fromEntries(
+Object.entries(this.#fields).map(([key, value]) => [
+key,
+fastn_utils.getFlattenStaticValue(value),
+]),
+);
+}
+getClone() {
+let current_fields = this.#fields;
+let cloned_fields = {};
+for (let key in current_fields) {
+let value = fastn_utils.clone(current_fields[key]);
+if (value instanceof fastn.mutableClass) {
+value = value.get();
+}
+cloned_fields[key] = value;
+}
+return new RecordInstance(cloned_fields);
+}
+}
+class Module {
+#name;
+#global;
+constructor(name, global) {
+this.#name = name;
+this.#global = global;
+}
+getName() {
+return this.#name;
+}
+get(function_name) {
+return this.#global[`${this.#name}__${function_name}`];
+}
+}
+fastn.recordInstance = function (obj) {
+return new RecordInstance(obj);
+};
+fastn.color = function (r, g, b) {
+return `rgb(${r},${g},${b})`;
+};
+fastn.mutableClass = Mutable;
+fastn.mutableListClass = MutableList;
+fastn.recordInstanceClass = RecordInstance;
+fastn.module = function (name, global) {
+return new Module(name, global);
+};
+fastn.moduleClass = Module;
+return fastn;
+})({});
+let fastn_dom = {};
+fastn_dom.styleClasses = "";
+fastn_dom.InternalClass = {
+FT_COLUMN: "ft_column",
+FT_ROW: "ft_row",
+FT_FULL_SIZE: "ft_full_size",
+};
+fastn_dom.codeData = {
+availableThemes: {},
+addedCssFile: [],
+};
+fastn_dom.externalCss = new Set();
+fastn_dom.externalJs = new Set();
+fastn_dom.webComponent = [];
+fastn_dom.commentNode = "comment";
+fastn_dom.wrapperNode = "wrapper";
+fastn_dom.commentMessage = "***FASTN***";
+fastn_dom.webComponentArgument = "args";
+fastn_dom.classes = {};
+fastn_dom.unsanitised_classes = {};
+fastn_dom.class_count = 0;
+fastn_dom.propertyMap = {
+"align-items": "ali",
+"align-self": "as",
+"background-color": "bgc",
+"background-image": "bgi",
+"background-position": "bgp",
+"background-repeat": "bgr",
+"background-size": "bgs",
+"border-bottom-color": "bbc",
+"border-bottom-left-radius": "bblr",
+"border-bottom-right-radius": "bbrr",
+"border-bottom-style": "bbs",
+"border-bottom-width": "bbw",
+"border-color": "bc",
+"border-left-color": "blc",
+"border-left-style": "bls",
+"border-left-width": "blw",
+"border-radius": "br",
+"border-right-color": "brc",
+"border-right-style": "brs",
+"border-right-width": "brw",
+"border-style": "bs",
+"border-top-color": "btc",
+"border-top-left-radius": "btlr",
+"border-top-right-radius": "btrr",
+"border-top-style": "bts",
+"border-top-width": "btw",
+"border-width": "bw",
+bottom: "b",
+color: "c",
+shadow: "sh",
+"text-shadow": "tsh",
+cursor: "cur",
+display: "d",
+download: "dw",
+"flex-wrap": "fw",
+"font-style": "fst",
+"font-weight": "fwt",
+gap: "g",
+height: "h",
+"justify-content": "jc",
+left: "l",
+link: "lk",
+"link-color": "lkc",
+margin: "m",
+"margin-bottom": "mb",
+"margin-horizontal": "mh",
+"margin-left": "ml",
+"margin-right": "mr",
+"margin-top": "mt",
+"margin-vertical": "mv",
+"max-height": "mxh",
+"max-width": "mxw",
+"min-height": "mnh",
+"min-width": "mnw",
+opacity: "op",
+overflow: "o",
+"overflow-x": "ox",
+"overflow-y": "oy",
+"object-fit": "of",
+padding: "p",
+"padding-bottom": "pb",
+"padding-horizontal": "ph",
+"padding-left": "pl",
+"padding-right": "pr",
+"padding-top": "pt",
+"padding-vertical": "pv",
+position: "pos",
+resize: "res",
+role: "rl",
+right: "r",
+sticky: "s",
+"text-align": "ta",
+"text-decoration": "td",
+"text-transform": "tt",
+top: "t",
+width: "w",
+"z-index": "z",
+"-webkit-box-orient": "wbo",
+"-webkit-line-clamp": "wlc",
+"backdrop-filter": "bdf",
+"mask-image": "mi",
+"-webkit-mask-image": "wmi",
+"mask-size": "ms",
+"-webkit-mask-size": "wms",
+"mask-repeat": "mre",
+"-webkit-mask-repeat": "wmre",
+"mask-position": "mp",
+"-webkit-mask-position": "wmp",
+"fetch-priority": "ftp",
+};
+fastn_dom.getClassesAsString = function () {
+return `<style id="styles">
+    ${fastn_dom.getClassesAsStringWithoutStyleTag()}
+    </style>`;
+};
+fastn_dom.getClassesAsStringWithoutStyleTag = function () {
+let classes = Object.entries(fastn_dom.classes).map((entry) => {
+return getClassAsString(entry[0], entry[1]);
+});
+return classes.join("\n\t");
+};
+function getClassAsString(className, obj) {
+if (typeof obj.value === "object" && obj.value !== null) {
+let value = "";
+for (let key in obj.value) {
+if (obj.value[key] === undefined || obj.value[key] === null) {
+continue;
+}
+value = `${value} ${key}: ${obj.value[key]}${
+                key === "color" ? " !important" : ""
+            };`;
+}
+return `${className} { ${value} }`;
+} else {
+return `${className} { ${obj.property}: ${obj.value}${
+            obj.property === "color" ? " !important" : ""
+        }; }`;
+}
+}
+fastn_dom.ElementKind = {
+Row: 0,
+Column: 1,
+Integer: 2,
+Decimal: 3,
+Boolean: 4,
+Text: 5,
+Image: 6,
+IFrame: 7,
+Comment: 8,
+CheckBox: 9,
+TextInput: 10,
+ContainerElement: 11,
+Rive: 12,
+Document: 13,
+Wrapper: 14,
+Code: 15,
+CodeChild: 16,
+WebComponent: (webcomponent, args) => {
+return [17, [webcomponent, args]];
+},
+Video: 18,
+};
+fastn_dom.PropertyKind = {
+Color: 0,
+IntegerValue: 1,
+StringValue: 2,
+DecimalValue: 3,
+BooleanValue: 4,
+Width: 5,
+Padding: 6,
+Height: 7,
+Id: 8,
+BorderWidth: 9,
+BorderStyle: 10,
+Margin: 11,
+Background: 12,
+PaddingHorizontal: 13,
+PaddingVertical: 14,
+PaddingLeft: 15,
+PaddingRight: 16,
+PaddingTop: 17,
+PaddingBottom: 18,
+MarginHorizontal: 19,
+MarginVertical: 20,
+MarginLeft: 21,
+MarginRight: 22,
+MarginTop: 23,
+MarginBottom: 24,
+Role: 25,
+ZIndex: 26,
+Sticky: 27,
+Top: 28,
+Bottom: 29,
+Left: 30,
+Right: 31,
+Overflow: 32,
+OverflowX: 33,
+OverflowY: 34,
+Spacing: 35,
+Wrap: 36,
+TextTransform: 37,
+TextIndent: 38,
+TextAlign: 39,
+LineClamp: 40,
+Opacity: 41,
+Cursor: 42,
+Resize: 43,
+MinHeight: 44,
+MaxHeight: 45,
+MinWidth: 46,
+MaxWidth: 47,
+WhiteSpace: 48,
+BorderTopWidth: 49,
+BorderBottomWidth: 50,
+BorderLeftWidth: 51,
+BorderRightWidth: 52,
+BorderRadius: 53,
+BorderTopLeftRadius: 54,
+BorderTopRightRadius: 55,
+BorderBottomLeftRadius: 56,
+BorderBottomRightRadius: 57,
+BorderStyleVertical: 58,
+BorderStyleHorizontal: 59,
+BorderLeftStyle: 60,
+BorderRightStyle: 61,
+BorderTopStyle: 62,
+BorderBottomStyle: 63,
+BorderColor: 64,
+BorderLeftColor: 65,
+BorderRightColor: 66,
+BorderTopColor: 67,
+BorderBottomColor: 68,
+AlignSelf: 69,
+Classes: 70,
+Anchor: 71,
+Link: 72,
+Children: 73,
+OpenInNewTab: 74,
+TextStyle: 75,
+Region: 76,
+AlignContent: 77,
+Display: 78,
+Checked: 79,
+Enabled: 80,
+TextInputType: 81,
+Placeholder: 82,
+Multiline: 83,
+DefaultTextInputValue: 84,
+Loading: 85,
+Src: 86,
+YoutubeSrc: 87,
+Code: 88,
+ImageSrc: 89,
+Alt: 90,
+DocumentProperties: {
+MetaTitle: 91,
+MetaOGTitle: 92,
+MetaTwitterTitle: 93,
+MetaDescription: 94,
+MetaOGDescription: 95,
+MetaTwitterDescription: 96,
+MetaOGImage: 97,
+MetaTwitterImage: 98,
+MetaThemeColor: 99,
+MetaFacebookDomainVerification: 100,
+},
+Shadow: 101,
+CodeTheme: 102,
+CodeLanguage: 103,
+CodeShowLineNumber: 104,
+Css: 105,
+Js: 106,
+LinkRel: 107,
+InputMaxLength: 108,
+Favicon: 109,
+Fit: 110,
+VideoSrc: 111,
+Autoplay: 112,
+Poster: 113,
+LoopVideo: 114,
+Controls: 115,
+Muted: 116,
+LinkColor: 117,
+TextShadow: 118,
+Selectable: 119,
+BackdropFilter: 120,
+Mask: 121,
+TextInputValue: 122,
+FetchPriority: 123,
+Download: 124,
+};
+fastn_dom.Loading = {
+Lazy: "lazy",
+Eager: "eager",
+};
+fastn_dom.LinkRel = {
+NoFollow: "nofollow",
+Sponsored: "sponsored",
+Ugc: "ugc",
+};
+fastn_dom.TextInputType = {
+Text: "text",
+Email: "email",
+Password: "password",
+Url: "url",
+DateTime: "datetime",
+Date: "date",
+Time: "time",
+Month: "month",
+Week: "week",
+Color: "color",
+File: "file",
+};
+fastn_dom.AlignContent = {
+TopLeft: "top-left",
+TopCenter: "top-center",
+TopRight: "top-right",
+Right: "right",
+Left: "left",
+Center: "center",
+BottomLeft: "bottom-left",
+BottomRight: "bottom-right",
+BottomCenter: "bottom-center",
+};
+fastn_dom.Region = {
+H1: "h1",
+H2: "h2",
+H3: "h3",
+H4: "h4",
+H5: "h5",
+H6: "h6",
+};
+fastn_dom.Anchor = {
+Window: [1, "fixed"],
+Parent: [2, "absolute"],
+Id: (value) => {
+return [3, value];
+},
+};
+fastn_dom.DeviceData = {
+Desktop: "desktop",
+Mobile: "mobile",
+};
+fastn_dom.TextStyle = {
+Underline: "underline",
+Italic: "italic",
+Strike: "line-through",
+Heavy: "900",
+Extrabold: "800",
+Bold: "700",
+SemiBold: "600",
+Medium: "500",
+Regular: "400",
+Light: "300",
+ExtraLight: "200",
+Hairline: "100",
+};
+fastn_dom.Resizing = {
+FillContainer: "100%",
+HugContent: "fit-content",
+Auto: "auto",
+Fixed: (value) => {
+return value;
+},
+};
+fastn_dom.Spacing = {
+SpaceEvenly: [1, "space-evenly"],
+SpaceBetween: [2, "space-between"],
+SpaceAround: [3, "space-around"],
+Fixed: (value) => {
+return [4, value];
+},
+};
+fastn_dom.BorderStyle = {
+Solid: "solid",
+Dashed: "dashed",
+Dotted: "dotted",
+Double: "double",
+Ridge: "ridge",
+Groove: "groove",
+Inset: "inset",
+Outset: "outset",
+};
+fastn_dom.Fit = {
+none: "none",
+fill: "fill",
+contain: "contain",
+cover: "cover",
+scaleDown: "scale-down",
+};
+fastn_dom.FetchPriority = {
+auto: "auto",
+high: "high",
+low: "low",
+};
+fastn_dom.Overflow = {
+Scroll: "scroll",
+Visible: "visible",
+Hidden: "hidden",
+Auto: "auto",
+};
+fastn_dom.Display = {
+Block: "block",
+Inline: "inline",
+InlineBlock: "inline-block",
+};
+fastn_dom.AlignSelf = {
+Start: "start",
+Center: "center",
+End: "end",
+};
+fastn_dom.TextTransform = {
+None: "none",
+Capitalize: "capitalize",
+Uppercase: "uppercase",
+Lowercase: "lowercase",
+Inherit: "inherit",
+Initial: "initial",
+};
+fastn_dom.TextAlign = {
+Start: "start",
+Center: "center",
+End: "end",
+Justify: "justify",
+};
+fastn_dom.Cursor = {
+None: "none",
+Default: "default",
+ContextMenu: "context-menu",
+Help: "help",
+Pointer: "pointer",
+Progress: "progress",
+Wait: "wait",
+Cell: "cell",
+CrossHair: "crosshair",
+Text: "text",
+VerticalText: "vertical-text",
+Alias: "alias",
+Copy: "copy",
+Move: "move",
+NoDrop: "no-drop",
+NotAllowed: "not-allowed",
+Grab: "grab",
+Grabbing: "grabbing",
+EResize: "e-resize",
+NResize: "n-resize",
+NeResize: "ne-resize",
+SResize: "s-resize",
+SeResize: "se-resize",
+SwResize: "sw-resize",
+Wresize: "w-resize",
+Ewresize: "ew-resize",
+NsResize: "ns-resize",
+NeswResize: "nesw-resize",
+NwseResize: "nwse-resize",
+ColResize: "col-resize",
+RowResize: "row-resize",
+AllScroll: "all-scroll",
+ZoomIn: "zoom-in",
+ZoomOut: "zoom-out",
+};
+fastn_dom.Resize = {
+Vertical: "vertical",
+Horizontal: "horizontal",
+Both: "both",
+};
+fastn_dom.WhiteSpace = {
+Normal: "normal",
+NoWrap: "nowrap",
+Pre: "pre",
+PreLine: "pre-line",
+PreWrap: "pre-wrap",
+BreakSpaces: "break-spaces",
+};
+fastn_dom.BackdropFilter = {
+Blur: (value) => {
+return [1, value];
+},
+Brightness: (value) => {
+return [2, value];
+},
+Contrast: (value) => {
+return [3, value];
+},
+Grayscale: (value) => {
+return [4, value];
+},
+Invert: (value) => {
+return [5, value];
+},
+Opacity: (value) => {
+return [6, value];
+},
+Sepia: (value) => {
+return [7, value];
+},
+Saturate: (value) => {
+return [8, value];
+},
+Multi: (value) => {
+return [9, value];
+},
+};
+fastn_dom.BackgroundStyle = {
+Solid: (value) => {
+return [1, value];
+},
+Image: (value) => {
+return [2, value];
+},
+LinearGradient: (value) => {
+return [3, value];
+},
+};
+fastn_dom.BackgroundRepeat = {
+Repeat: "repeat",
+RepeatX: "repeat-x",
+RepeatY: "repeat-y",
+NoRepeat: "no-repeat",
+Space: "space",
+Round: "round",
+};
+fastn_dom.BackgroundSize = {
+Auto: "auto",
+Cover: "cover",
+Contain: "contain",
+Length: (value) => {
+return value;
+},
+};
+fastn_dom.BackgroundPosition = {
+Left: "left",
+Right: "right",
+Center: "center",
+LeftTop: "left top",
+LeftCenter: "left center",
+LeftBottom: "left bottom",
+CenterTop: "center top",
+CenterCenter: "center center",
+CenterBottom: "center bottom",
+RightTop: "right top",
+RightCenter: "right center",
+RightBottom: "right bottom",
+Length: (value) => {
+return value;
+},
+};
+fastn_dom.LinearGradientDirection = {
+Angle: (value) => {
+return `${value}deg`;
+},
+Turn: (value) => {
+return `${value}turn`;
+},
+Left: "270deg",
+Right: "90deg",
+Top: "0deg",
+Bottom: "180deg",
+TopLeft: "315deg",
+TopRight: "45deg",
+BottomLeft: "225deg",
+BottomRight: "135deg",
+};
+fastn_dom.FontSize = {
+Px: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${value.get()}px`;
+});
+}
+return `${value}px`;
+},
+Em: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${value.get()}em`;
+});
+}
+return `${value}em`;
+},
+Rem: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${value.get()}rem`;
+});
+}
+return `${value}rem`;
+},
+};
+fastn_dom.Length = {
+Px: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}px`;
+});
+}
+return `${value}px`;
+},
+Em: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}em`;
+});
+}
+return `${value}em`;
+},
+Rem: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}rem`;
+});
+}
+return `${value}rem`;
+},
+Percent: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}%`;
+});
+}
+return `${value}%`;
+},
+Calc: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `calc(${fastn_utils.getStaticValue(value)})`;
+});
+}
+return `calc(${value})`;
+},
+Vh: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}vh`;
+});
+}
+return `${value}vh`;
+},
+Vw: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}vw`;
+});
+}
+return `${value}vw`;
+},
+Dvh: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}dvh`;
+});
+}
+return `${value}dvh`;
+},
+Lvh: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}lvh`;
+});
+}
+return `${value}lvh`;
+},
+Svh: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}svh`;
+});
+}
+return `${value}svh`;
+},
+Vmin: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}vmin`;
+});
+}
+return `${value}vmin`;
+},
+Vmax: (value) => {
+if (value instanceof fastn.mutableClass) {
+return fastn.formula([value], function () {
+return `${fastn_utils.getStaticValue(value)}vmax`;
+});
+}
+return `${value}vmax`;
+},
+Responsive: (length) => {
+return new PropertyValueAsClosure(() => {
+if (ftd.device.get() === "desktop") {
+return length.get("desktop");
+} else {
+let mobile = length.get("mobile");
+let desktop = length.get("desktop");
+return mobile ? mobile : desktop;
+}
+}, [ftd.device, length]);
+},
+};
+fastn_dom.Mask = {
+Image: (value) => {
+return [1, value];
+},
+Multi: (value) => {
+return [2, value];
+},
+};
+fastn_dom.MaskSize = {
+Auto: "auto",
+Cover: "cover",
+Contain: "contain",
+Fixed: (value) => {
+return value;
+},
+};
+fastn_dom.MaskRepeat = {
+Repeat: "repeat",
+RepeatX: "repeat-x",
+RepeatY: "repeat-y",
+NoRepeat: "no-repeat",
+Space: "space",
+Round: "round",
+};
+fastn_dom.MaskPosition = {
+Left: "left",
+Right: "right",
+Center: "center",
+LeftTop: "left top",
+LeftCenter: "left center",
+LeftBottom: "left bottom",
+CenterTop: "center top",
+CenterCenter: "center center",
+CenterBottom: "center bottom",
+RightTop: "right top",
+RightCenter: "right center",
+RightBottom: "right bottom",
+Length: (value) => {
+return value;
+},
+};
+fastn_dom.Event = {
+Click: 0,
+MouseEnter: 1,
+MouseLeave: 2,
+ClickOutside: 3,
+GlobalKey: (val) => {
+return [4, val];
+},
+GlobalKeySeq: (val) => {
+return [5, val];
+},
+Input: 6,
+Change: 7,
+Blur: 8,
+Focus: 9,
+};
+class PropertyValueAsClosure {
+closureFunction;
+deps;
+constructor(closureFunction, deps) {
+this.closureFunction = closureFunction;
+this.deps = deps;
+}
+}
+class Node2 {
+#node;
+#kind;
+#parent;
+#tagName;
+#rawInnerValue;
+#mutables;
+#extraData;
+#children;
+constructor(parentOrSibiling, kind) {
+this.#kind = kind;
+this.#parent = parentOrSibiling;
+this.#children = [];
+this.#rawInnerValue = null;
+let sibiling = undefined;
+if (parentOrSibiling instanceof ParentNodeWithSibiling) {
+this.#parent = parentOrSibiling.getParent();
+while (this.#parent instanceof ParentNodeWithSibiling) {
+this.#parent = this.#parent.getParent();
+}
+sibiling = parentOrSibiling.getSibiling();
+}
+this.createNode(kind);
+this.#mutables = [];
+this.#extraData = {};
+if (this.#parent.getNode) {
+this.#parent = this.#parent.getNode();
+}
+if (fastn_utils.isWrapperNode(this.#tagName)) {
+this.#parent = parentOrSibiling;
+return;
+}
+if (hydrating && this.#node.parentNode === this.#parent) {
+} else if (sibiling) {
+this.#parent.insertBefore(
+this.#node,
+fastn_utils.nextSibling(sibiling, this.#parent),
+);
+} else {
+this.#parent.appendChild(this.#node);
+}
+}
+createNode(kind) {
+if (kind === fastn_dom.ElementKind.Code) {
+let [node, classes, attributes] = fastn_utils.htmlNode(kind);
+[this.#tagName, this.#node] = fastn_utils.createNodeHelper(
+node,
+classes,
+attributes,
+);
+let codeNode = new Node2(
+this.#node,
+fastn_dom.ElementKind.CodeChild,
+);
+this.#children.push(codeNode);
+} else {
+let [node, classes, attributes] = fastn_utils.htmlNode(kind);
+[this.#tagName, this.#node] = fastn_utils.createNodeHelper(
+node,
+classes,
+attributes,
+);
+}
+}
+getTagName() {
+return this.#tagName;
+}
+getParent() {
+return this.#parent;
+}
+removeAllFaviconLinks() {
+if (doubleBuffering) {
+const links = document.head.querySelectorAll(
+'link[rel="shortcut icon"]',
+);
+links.forEach((link) => {
+link.parentNode.removeChild(link);
+});
+}
+}
+setFavicon(url) {
+if (doubleBuffering) {
+if (url instanceof fastn.recordInstanceClass) url = url.get("src");
+while (true) {
+if (url instanceof fastn.mutableClass) url = url.get();
+else break;
+}
+let link_element = document.createElement("link");
+link_element.rel = "shortcut icon";
+link_element.href = url;
+this.removeAllFaviconLinks();
+document.head.appendChild(link_element);
+}
+}
+updateTextInputValue() {
+if (fastn_utils.isNull(this.#rawInnerValue)) {
+this.attachAttribute("value");
+return;
+}
+if (!ssr && this.#node.tagName.toLowerCase() === "textarea") {
+this.#node.innerHTML = this.#rawInnerValue;
+} else {
+this.attachAttribute("value", this.#rawInnerValue);
+}
+}
+attachAttribute(property, value) {
+if (fastn_utils.isNull(value)) {
+this.#node.removeAttribute(property);
+return;
+}
+this.#node.setAttribute(property, value);
+}
+removeAttribute(property) {
+this.#node.removeAttribute(property);
+}
+updateTagName(name) {
+if (ssr) {
+this.#node.updateTagName(name);
+} else if (hydrating && this.#node.tagName.toLowerCase() === name) {
+} else {
+let newElement = document.createElement(name);
+newElement.innerHTML = this.#node.innerHTML;
+newElement.className = this.#node.className;
+newElement.style = this.#node.style;
+for (var i = 0; i < this.#node.attributes.length; i++) {
+var attr = this.#node.attributes[i];
+newElement.setAttribute(attr.name, attr.value);
+}
+var eventListeners = fastn_utils.getEventListeners(this.#node);
+for (var eventType in eventListeners) {
+newElement[eventType] = eventListeners[eventType];
+}
+this.#parent.replaceChild(newElement, this.#node);
+this.#node = newElement;
+}
+}
+updateToAnchor(url) {
+let node_kind = this.#kind;
+if (ssr) {
+if (node_kind !== fastn_dom.ElementKind.Image) {
+this.updateTagName("a");
+this.attachAttribute("href", url);
+}
+return;
+}
+if (node_kind === fastn_dom.ElementKind.Image) {
+let anchorElement = document.createElement("a");
+anchorElement.href = url;
+this.#parent.replaceChild(anchorElement, this.#node);
+anchorElement.appendChild(this.#node);
+this.#node = anchorElement;
+} else {
+this.updateTagName("a");
+this.#node.href = url;
+}
+}
+updatePositionForNodeById(node_id, value) {
+if (!ssr) {
+const target_node = fastnVirtual.root.querySelector(
+`[id="${node_id}"]`,
+);
+if (!fastn_utils.isNull(target_node))
+target_node.style["position"] = value;
+}
+}
+updateParentPosition(value) {
+if (ssr) {
+let parent = this.#parent;
+if (parent.style) parent.style["position"] = value;
+}
+if (!ssr) {
+let current_node = this.#node;
+if (current_node) {
+let parent_node = current_node.parentNode;
+parent_node.style["position"] = value;
+}
+}
+}
+updateMetaTitle(value) {
+if (!ssr && doubleBuffering) {
+if (!fastn_utils.isNull(value)) window.document.title = value;
+}
+}
+addMetaTagByName(name, value) {
+if (value === null || value === undefined) {
+this.removeMetaTagByName(name);
+return;
+}
+if (!ssr && doubleBuffering) {
+const metaTag = window.document.createElement("meta");
+metaTag.setAttribute("name", name);
+metaTag.setAttribute("content", value);
+document.head.appendChild(metaTag);
+}
+}
+addMetaTagByProperty(property, value) {
+if (value === null || value === undefined) {
+this.removeMetaTagByProperty(property);
+return;
+}
+if (!ssr && doubleBuffering) {
+const metaTag = window.document.createElement("meta");
+metaTag.setAttribute("property", property);
+metaTag.setAttribute("content", value);
+document.head.appendChild(metaTag);
+}
+}
+removeMetaTagByName(name) {
+if (!ssr && doubleBuffering) {
+const metaTags = document.getElementsByTagName("meta");
+for (let i = 0; i < metaTags.length; i++) {
+const metaTag = metaTags[i];
+if (metaTag.getAttribute("name") === name) {
+metaTag.remove();
+break;
+}
+}
+}
+}
+removeMetaTagByProperty(property) {
+if (!ssr && doubleBuffering) {
+const metaTags = document.getElementsByTagName("meta");
+for (let i = 0; i < metaTags.length; i++) {
+const metaTag = metaTags[i];
+if (metaTag.getAttribute("property") === property) {
+metaTag.remove();
+break;
+}
+}
+}
+}
+attachCss(property, value, createClass, className) {
+let propertyShort = fastn_dom.propertyMap[property] || property;
+propertyShort = `__${propertyShort}`;
+let cls = `${propertyShort}-${fastn_dom.class_count}`;
+if (!!className) {
+cls = className;
+} else {
+if (!fastn_dom.unsanitised_classes[cls]) {
+fastn_dom.unsanitised_classes[cls] = ++fastn_dom.class_count;
+}
+cls = `${propertyShort}-${fastn_dom.unsanitised_classes[cls]}`;
+}
+let cssClass = className ? cls : `.${cls}`;
+const obj = { property, value };
+if (value === undefined) {
+if (!ssr) {
+for (const className of this.#node.classList.values()) {
+if (className.startsWith(`${propertyShort}-`)) {
+this.#node.classList.remove(className);
+}
+}
+this.#node.style[property] = null;
+}
+return cls;
+}
+if (!ssr && !doubleBuffering) {
+if (!!className) {
+if (!fastn_dom.classes[cssClass]) {
+fastn_dom.classes[cssClass] =
+fastn_dom.classes[cssClass] || obj;
+fastn_utils.createStyle(cssClass, obj);
+}
+return cls;
+}
+for (const className of this.#node.classList.values()) {
+if (className.startsWith(`${propertyShort}-`)) {
+this.#node.classList.remove(className);
+}
+}
+if (createClass) {
+if (!fastn_dom.classes[cssClass]) {
+fastn_dom.classes[cssClass] =
+fastn_dom.classes[cssClass] || obj;
+fastn_utils.createStyle(cssClass, obj);
+}
+this.#node.style.removeProperty(property);
+this.#node.classList.add(cls);
+} else if (!fastn_dom.classes[cssClass]) {
+if (typeof value === "object" && value !== null) {
+for (let key in value) {
+this.#node.style[key] = value[key];
+}
+} else {
+this.#node.style[property] = value;
+}
+} else {
+this.#node.style.removeProperty(property);
+this.#node.classList.add(cls);
+}
+return cls;
+}
+fastn_dom.classes[cssClass] = fastn_dom.classes[cssClass] || obj;
+if (!!className) {
+return cls;
+}
+this.#node.classList.add(cls);
+return cls;
+}
+attachShadow(value) {
+if (fastn_utils.isNull(value)) {
+this.attachCss("box-shadow", value);
+return;
+}
+const color = value.get("color");
+const lightColor = fastn_utils.getStaticValue(color.get("light"));
+const darkColor = fastn_utils.getStaticValue(color.get("dark"));
+const blur = fastn_utils.getStaticValue(value.get("blur"));
+const xOffset = fastn_utils.getStaticValue(value.get("x_offset"));
+const yOffset = fastn_utils.getStaticValue(value.get("y_offset"));
+const spread = fastn_utils.getStaticValue(value.get("spread"));
+const inset = fastn_utils.getStaticValue(value.get("inset"));
+const shadowCommonCss = `${
+            inset ? "inset " : ""
+        }${xOffset} ${yOffset} ${blur} ${spread}`;
+const lightShadowCss = `${shadowCommonCss} ${lightColor}`;
+const darkShadowCss = `${shadowCommonCss} ${darkColor}`;
+if (lightShadowCss === darkShadowCss) {
+this.attachCss("box-shadow", lightShadowCss, false);
+} else {
+let lightClass = this.attachCss("box-shadow", lightShadowCss, true);
+this.attachCss(
+"box-shadow",
+darkShadowCss,
+true,
+`body.dark .${lightClass}`,
+);
+}
+}
+attachBackdropMultiFilter(value) {
+const filters = {
+blur: fastn_utils.getStaticValue(value.get("blur")),
+brightness: fastn_utils.getStaticValue(value.get("brightness")),
+contrast: fastn_utils.getStaticValue(value.get("contrast")),
+grayscale: fastn_utils.getStaticValue(value.get("grayscale")),
+invert: fastn_utils.getStaticValue(value.get("invert")),
+opacity: fastn_utils.getStaticValue(value.get("opacity")),
+sepia: fastn_utils.getStaticValue(value.get("sepia")),
+saturate: fastn_utils.getStaticValue(value.get("saturate")),
+};
+const filterString = Object.entries(filters)
+.filter(([_, value]) => !fastn_utils.isNull(value))
+.map(([name, value]) => `${name}(${value})`)
+.join(" ");
+this.attachCss("backdrop-filter", filterString, false);
+}
+attachTextShadow(value) {
+if (fastn_utils.isNull(value)) {
+this.attachCss("text-shadow", value);
+return;
+}
+const color = value.get("color");
+const lightColor = fastn_utils.getStaticValue(color.get("light"));
+const darkColor = fastn_utils.getStaticValue(color.get("dark"));
+const blur = fastn_utils.getStaticValue(value.get("blur"));
+const xOffset = fastn_utils.getStaticValue(value.get("x_offset"));
+const yOffset = fastn_utils.getStaticValue(value.get("y_offset"));
+const shadowCommonCss = `${xOffset} ${yOffset} ${blur}`;
+const lightShadowCss = `${shadowCommonCss} ${lightColor}`;
+const darkShadowCss = `${shadowCommonCss} ${darkColor}`;
+if (lightShadowCss === darkShadowCss) {
+this.attachCss("text-shadow", lightShadowCss, false);
+} else {
+let lightClass = this.attachCss("box-shadow", lightShadowCss, true);
+this.attachCss(
+"text-shadow",
+darkShadowCss,
+true,
+`body.dark .${lightClass}`,
+);
+}
+}
+getLinearGradientString(value) {
+var lightGradientString = "";
+var darkGradientString = "";
+let colorsList = value.get("colors").get().getList();
+colorsList.map(function (element) {
+let lg_color = element.item;
+let color = lg_color.get("color").get();
+let lightColor = fastn_utils.getStaticValue(color.get("light"));
+let darkColor = fastn_utils.getStaticValue(color.get("dark"));
+lightGradientString = `${lightGradientString} ${lightColor}`;
+darkGradientString = `${darkGradientString} ${darkColor}`;
+let start = fastn_utils.getStaticValue(lg_color.get("start"));
+if (start !== undefined && start !== null) {
+lightGradientString = `${lightGradientString} ${start}`;
+darkGradientString = `${darkGradientString} ${start}`;
+}
+let end = fastn_utils.getStaticValue(lg_color.get("end"));
+if (end !== undefined && end !== null) {
+lightGradientString = `${lightGradientString} ${end}`;
+darkGradientString = `${darkGradientString} ${end}`;
+}
+let stop_position = fastn_utils.getStaticValue(
+lg_color.get("stop_position"),
+);
+if (stop_position !== undefined && stop_position !== null) {
+lightGradientString = `${lightGradientString}, ${stop_position}`;
+darkGradientString = `${darkGradientString}, ${stop_position}`;
+}
+lightGradientString = `${lightGradientString},`;
+darkGradientString = `${darkGradientString},`;
+});
+lightGradientString = lightGradientString.trim().slice(0, -1);
+darkGradientString = darkGradientString.trim().slice(0, -1);
+return [lightGradientString, darkGradientString];
+}
+attachLinearGradientCss(value) {
+if (fastn_utils.isNull(value)) {
+this.attachCss("background-image", value);
+return;
+}
+const closure = fastn
+.closure(() => {
+let direction = fastn_utils.getStaticValue(
+value.get("direction"),
+);
+const [lightGradientString, darkGradientString] =
+this.getLinearGradientString(value);
+if (lightGradientString === darkGradientString) {
+this.attachCss(
+"background-image",
+`linear-gradient(${direction}, ${lightGradientString})`,
+false,
+);
+} else {
+let lightClass = this.attachCss(
+"background-image",
+`linear-gradient(${direction}, ${lightGradientString})`,
+true,
+);
+this.attachCss(
+"background-image",
+`linear-gradient(${direction}, ${darkGradientString})`,
+true,
+`body.dark .${lightClass}`,
+);
+}
+})
+.addNodeProperty(this, null, inherited);
+const colorsList = value.get("colors").get().getList();
+colorsList.forEach(({ item }) => {
+const color = item.get("color");
+[color.get("light"), color.get("dark")].forEach((variant) => {
+variant.addClosure(closure);
+this.#mutables.push(variant);
+});
+});
+}
+attachBackgroundImageCss(value) {
+if (fastn_utils.isNull(value)) {
+this.attachCss("background-repeat", value);
+this.attachCss("background-position", value);
+this.attachCss("background-size", value);
+this.attachCss("background-image", value);
+return;
+}
+let src = fastn_utils.getStaticValue(value.get("src"));
+let lightValue = fastn_utils.getStaticValue(src.get("light"));
+let darkValue = fastn_utils.getStaticValue(src.get("dark"));
+let position = fastn_utils.getStaticValue(value.get("position"));
+let positionX = null;
+let positionY = null;
+if (position !== null && position instanceof Object) {
+positionX = fastn_utils.getStaticValue(position.get("x"));
+positionY = fastn_utils.getStaticValue(position.get("y"));
+if (positionX !== null) position = `${positionX}`;
+if (positionY !== null) {
+if (positionX === null) position = `0px ${positionY}`;
+else position = `${position} ${positionY}`;
+}
+}
+let repeat = fastn_utils.getStaticValue(value.get("repeat"));
+let size = fastn_utils.getStaticValue(value.get("size"));
+let sizeX = null;
+let sizeY = null;
+if (size !== null && size instanceof Object) {
+sizeX = fastn_utils.getStaticValue(size.get("x"));
+sizeY = fastn_utils.getStaticValue(size.get("y"));
+if (sizeX !== null) size = `${sizeX}`;
+if (sizeY !== null) {
+if (sizeX === null) size = `0px ${sizeY}`;
+else size = `${size} ${sizeY}`;
+}
+}
+if (repeat !== null) this.attachCss("background-repeat", repeat);
+if (position !== null) this.attachCss("background-position", position);
+if (size !== null) this.attachCss("background-size", size);
+if (lightValue === darkValue) {
+this.attachCss("background-image", `url(${lightValue})`, false);
+} else {
+let lightClass = this.attachCss(
+"background-image",
+`url(${lightValue})`,
+true,
+);
+this.attachCss(
+"background-image",
+`url(${darkValue})`,
+true,
+`body.dark .${lightClass}`,
+);
+}
+}
+attachMaskImageCss(value, vendorPrefix) {
+const propertyWithPrefix = vendorPrefix
+? `${vendorPrefix}-mask-image`
+: "mask-image";
+if (fastn_utils.isNull(value)) {
+this.attachCss(propertyWithPrefix, value);
+return;
+}
+let src = fastn_utils.getStaticValue(value.get("src"));
+let linearGradient = fastn_utils.getStaticValue(
+value.get("linear_gradient"),
+);
+let color = fastn_utils.getStaticValue(value.get("color"));
+const maskLightImageValues = [];
+const maskDarkImageValues = [];
+if (!fastn_utils.isNull(src)) {
+let lightValue = fastn_utils.getStaticValue(src.get("light"));
+let darkValue = fastn_utils.getStaticValue(src.get("dark"));
+const lightUrl = `url(${lightValue})`;
+const darkUrl = `url(${darkValue})`;
+if (!fastn_utils.isNull(linearGradient)) {
+const lightImageValues = [lightUrl];
+const darkImageValues = [darkUrl];
+if (!fastn_utils.isNull(color)) {
+const lightColor = fastn_utils.getStaticValue(
+color.get("light"),
+);
+const darkColor = fastn_utils.getStaticValue(
+color.get("dark"),
+);
+lightImageValues.push(lightColor);
+darkImageValues.push(darkColor);
+}
+maskLightImageValues.push(
+`image(${lightImageValues.join(", ")})`,
+);
+maskDarkImageValues.push(
+`image(${darkImageValues.join(", ")})`,
+);
+} else {
+maskLightImageValues.push(lightUrl);
+maskDarkImageValues.push(darkUrl);
+}
+}
+if (!fastn_utils.isNull(linearGradient)) {
+let direction = fastn_utils.getStaticValue(
+linearGradient.get("direction"),
+);
+const [lightGradientString, darkGradientString] =
+this.getLinearGradientString(linearGradient);
+maskLightImageValues.push(
+`linear-gradient(${direction}, ${lightGradientString})`,
+);
+maskDarkImageValues.push(
+`linear-gradient(${direction}, ${darkGradientString})`,
+);
+}
+const maskLightImageString = maskLightImageValues.join(", ");
+const maskDarkImageString = maskDarkImageValues.join(", ");
+if (maskLightImageString === maskDarkImageString) {
+this.attachCss(propertyWithPrefix, maskLightImageString, true);
+} else {
+let lightClass = this.attachCss(
+propertyWithPrefix,
+maskLightImageString,
+true,
+);
+this.attachCss(
+propertyWithPrefix,
+maskDarkImageString,
+true,
+`body.dark .${lightClass}`,
+);
+}
+}
+attachMaskSizeCss(value, vendorPrefix) {
+const propertyNameWithPrefix = vendorPrefix
+? `${vendorPrefix}-mask-size`
+: "mask-size";
+if (fastn_utils.isNull(value)) {
+this.attachCss(propertyNameWithPrefix, value);
+}
+const [size, ...two_values] = ["size", "size_x", "size_y"].map((size) =>
+fastn_utils.getStaticValue(value.get(size)),
+);
+if (!fastn_utils.isNull(size)) {
+this.attachCss(propertyNameWithPrefix, size, true);
+} else {
+const [size_x, size_y] = two_values.map((value) => value || "auto");
+this.attachCss(propertyNameWithPrefix, `${size_x} ${size_y}`, true);
+}
+}
+attachMaskMultiCss(value, vendorPrefix) {
+if (fastn_utils.isNull(value)) {
+this.attachCss("mask-repeat", value);
+this.attachCss("mask-position", value);
+this.attachCss("mask-size", value);
+this.attachCss("mask-image", value);
+return;
+}
+const maskImage = fastn_utils.getStaticValue(value.get("image"));
+this.attachMaskImageCss(maskImage);
+this.attachMaskImageCss(maskImage, vendorPrefix);
+this.attachMaskSizeCss(value);
+this.attachMaskSizeCss(value, vendorPrefix);
+const maskRepeatValue = fastn_utils.getStaticValue(value.get("repeat"));
+if (fastn_utils.isNull(maskRepeatValue)) {
+this.attachCss("mask-repeat", maskRepeatValue, true);
+this.attachCss("-webkit-mask-repeat", maskRepeatValue, true);
+} else {
+this.attachCss("mask-repeat", maskRepeatValue, true);
+this.attachCss("-webkit-mask-repeat", maskRepeatValue, true);
+}
+const maskPositionValue = fastn_utils.getStaticValue(
+value.get("position"),
+);
+if (fastn_utils.isNull(maskPositionValue)) {
+this.attachCss("mask-position", maskPositionValue, true);
+this.attachCss("-webkit-mask-position", maskPositionValue, true);
+} else {
+this.attachCss("mask-position", maskPositionValue, true);
+this.attachCss("-webkit-mask-position", maskPositionValue, true);
+}
+}
+attachExternalCss(css) {
+if (!ssr) {
+let css_tag = document.createElement("link");
+css_tag.rel = "stylesheet";
+css_tag.type = "text/css";
+css_tag.href = css;
+let head =
+document.head || document.getElementsByTagName("head")[0];
+if (!fastn_dom.externalCss.has(css)) {
+head.appendChild(css_tag);
+fastn_dom.externalCss.add(css);
+}
+}
+}
+attachExternalJs(js) {
+if (!ssr) {
+let js_tag = document.createElement("script");
+js_tag.src = js;
+let head =
+document.head || document.getElementsByTagName("head")[0];
+if (!fastn_dom.externalJs.has(js)) {
+head.appendChild(js_tag);
+fastn_dom.externalCss.add(js);
+}
+}
+}
+attachColorCss(property, value, visited) {
+if (fastn_utils.isNull(value)) {
+this.attachCss(property, value);
+return;
+}
+value = value instanceof fastn.mutableClass ? value.get() : value;
+const lightValue = value.get("light");
+const darkValue = value.get("dark");
+const closure = fastn
+.closure(() => {
+let lightValueStatic = fastn_utils.getStaticValue(lightValue);
+let darkValueStatic = fastn_utils.getStaticValue(darkValue);
+if (lightValueStatic === darkValueStatic) {
+this.attachCss(property, lightValueStatic, false);
+} else {
+let lightClass = this.attachCss(
+property,
+lightValueStatic,
+true,
+);
+this.attachCss(
+property,
+darkValueStatic,
+true,
+`body.dark .${lightClass}`,
+);
+if (visited) {
+this.attachCss(
+property,
+lightValueStatic,
+true,
+`.${lightClass}:visited`,
+);
+this.attachCss(
+property,
+darkValueStatic,
+true,
+`body.dark  .${lightClass}:visited`,
+);
+}
+}
+})
+.addNodeProperty(this, null, inherited);
+[lightValue, darkValue].forEach((modeValue) => {
+modeValue.addClosure(closure);
+this.#mutables.push(modeValue);
+});
+}
+attachRoleCss(value) {
+if (fastn_utils.isNull(value)) {
+this.attachCss("role", value);
+return;
+}
+value.addClosure(
+fastn
+.closure(() => {
+let desktopValue = value.get("desktop");
+let mobileValue = value.get("mobile");
+if (
+fastn_utils.sameResponsiveRole(
+desktopValue,
+mobileValue,
+)
+) {
+this.attachCss(
+"role",
+fastn_utils.getRoleValues(desktopValue),
+true,
+);
+} else {
+let desktopClass = this.attachCss(
+"role",
+fastn_utils.getRoleValues(desktopValue),
+true,
+);
+this.attachCss(
+"role",
+fastn_utils.getRoleValues(mobileValue),
+true,
+`body.mobile .${desktopClass}`,
+);
+}
+})
+.addNodeProperty(this, null, inherited),
+);
+this.#mutables.push(value);
+}
+attachTextStyles(styles) {
+if (fastn_utils.isNull(styles)) {
+this.attachCss("font-style", styles);
+this.attachCss("font-weight", styles);
+this.attachCss("text-decoration", styles);
+return;
+}
+for (var s of styles) {
+switch (s) {
+case "italic":
+this.attachCss("font-style", s);
+break;
+case "underline":
+case "line-through":
+this.attachCss("text-decoration", s);
+break;
+default:
+this.attachCss("font-weight", s);
+}
+}
+}
+attachAlignContent(value, node_kind) {
+if (fastn_utils.isNull(value)) {
+this.attachCss("align-items", value);
+this.attachCss("justify-content", value);
+return;
+}
+if (node_kind === fastn_dom.ElementKind.Column) {
+switch (value) {
+case "top-left":
+this.attachCss("justify-content", "start");
+this.attachCss("align-items", "start");
+break;
+case "top-center":
+this.attachCss("justify-content", "start");
+this.attachCss("align-items", "center");
+break;
+case "top-right":
+this.attachCss("justify-content", "start");
+this.attachCss("align-items", "end");
+break;
+case "left":
+this.attachCss("justify-content", "center");
+this.attachCss("align-items", "start");
+break;
+case "center":
+this.attachCss("justify-content", "center");
+this.attachCss("align-items", "center");
+break;
+case "right":
+this.attachCss("justify-content", "center");
+this.attachCss("align-items", "end");
+break;
+case "bottom-left":
+this.attachCss("justify-content", "end");
+this.attachCss("align-items", "left");
+break;
+case "bottom-center":
+this.attachCss("justify-content", "end");
+this.attachCss("align-items", "center");
+break;
+case "bottom-right":
+this.attachCss("justify-content", "end");
+this.attachCss("align-items", "end");
+break;
+}
+}
+if (node_kind === fastn_dom.ElementKind.Row) {
+switch (value) {
+case "top-left":
+this.attachCss("justify-content", "start");
+this.attachCss("align-items", "start");
+break;
+case "top-center":
+this.attachCss("justify-content", "center");
+this.attachCss("align-items", "start");
+break;
+case "top-right":
+this.attachCss("justify-content", "end");
+this.attachCss("align-items", "start");
+break;
+case "left":
+this.attachCss("justify-content", "start");
+this.attachCss("align-items", "center");
+break;
+case "center":
+this.attachCss("justify-content", "center");
+this.attachCss("align-items", "center");
+break;
+case "right":
+this.attachCss("justify-content", "right");
+this.attachCss("align-items", "center");
+break;
+case "bottom-left":
+this.attachCss("justify-content", "start");
+this.attachCss("align-items", "end");
+break;
+case "bottom-center":
+this.attachCss("justify-content", "center");
+this.attachCss("align-items", "end");
+break;
+case "bottom-right":
+this.attachCss("justify-content", "end");
+this.attachCss("align-items", "end");
+break;
+}
+}
+}
+attachImageSrcClosures(staticValue) {
+if (fastn_utils.isNull(staticValue)) return;
+if (staticValue instanceof fastn.recordInstanceClass) {
+let value = staticValue;
+let fields = value.getAllFields();
+let light_field_value = fastn_utils.flattenMutable(fields["light"]);
+light_field_value.addClosure(
+fastn
+.closure(() => {
+const is_dark_mode = ftd.dark_mode.get();
+if (is_dark_mode) return;
+const src =
+fastn_utils.getStaticValue(light_field_value);
+if (!ssr) {
+let image_node = this.#node;
+if (!fastn_utils.isNull(image_node)) {
+if (image_node.nodeName.toLowerCase() === "a") {
+let childNodes = image_node.childNodes;
+childNodes.forEach(function (child) {
+if (
+child.nodeName.toLowerCase() ===
+"img"
+)
+image_node = child;
+});
+}
+image_node.setAttribute(
+"src",
+fastn_utils.getStaticValue(src),
+);
+}
+} else {
+this.attachAttribute(
+"src",
+fastn_utils.getStaticValue(src),
+);
+}
+})
+.addNodeProperty(this, null, inherited),
+);
+this.#mutables.push(light_field_value);
+let dark_field_value = fastn_utils.flattenMutable(fields["dark"]);
+dark_field_value.addClosure(
+fastn
+.closure(() => {
+const is_dark_mode = ftd.dark_mode.get();
+if (!is_dark_mode) return;
+const src =
+fastn_utils.getStaticValue(dark_field_value);
+if (!ssr) {
+let image_node = this.#node;
+if (!fastn_utils.isNull(image_node)) {
+if (image_node.nodeName.toLowerCase() === "a") {
+let childNodes = image_node.childNodes;
+childNodes.forEach(function (child) {
+if (
+child.nodeName.toLowerCase() ===
+"img"
+)
+image_node = child;
+});
+}
+image_node.setAttribute(
+"src",
+fastn_utils.getStaticValue(src),
+);
+}
+} else {
+this.attachAttribute(
+"src",
+fastn_utils.getStaticValue(src),
+);
+}
+})
+.addNodeProperty(this, null, inherited),
+);
+this.#mutables.push(dark_field_value);
+}
+}
+attachLinkColor(value) {
+ftd.dark_mode.addClosure(
+fastn
+.closure(() => {
+if (!ssr) {
+const anchors =
+this.#node.tagName.toLowerCase() === "a"
+? [this.#node]
+: Array.from(this.#node.querySelectorAll("a"));
+let propertyShort = `__${fastn_dom.propertyMap["link-color"]}`;
+if (fastn_utils.isNull(value)) {
+anchors.forEach((a) => {
+a.classList.values().forEach((className) => {
+if (
+className.startsWith(
+`${propertyShort}-`,
+)
+) {
+a.classList.remove(className);
+}
+});
+});
+} else {
+const lightValue = fastn_utils.getStaticValue(
+value.get("light"),
+);
+const darkValue = fastn_utils.getStaticValue(
+value.get("dark"),
+);
+let cls = `${propertyShort}-${JSON.stringify(
+                                lightValue,
+                            )}`;
+if (!fastn_dom.unsanitised_classes[cls]) {
+fastn_dom.unsanitised_classes[cls] =
+++fastn_dom.class_count;
+}
+cls = `${propertyShort}-${fastn_dom.unsanitised_classes[cls]}`;
+const cssClass = `.${cls}`;
+if (!fastn_dom.classes[cssClass]) {
+const obj = {
+property: "color",
+value: lightValue,
+};
+fastn_dom.classes[cssClass] =
+fastn_dom.classes[cssClass] || obj;
+let styles = document.getElementById("styles");
+styles.innerHTML = `${
+                                    styles.innerHTML
+                                }${getClassAsString(cssClass, obj)}\n`;
+}
+if (lightValue !== darkValue) {
+const obj = {
+property: "color",
+value: darkValue,
+};
+let darkCls = `body.dark ${cssClass}`;
+if (!fastn_dom.classes[darkCls]) {
+fastn_dom.classes[darkCls] =
+fastn_dom.classes[darkCls] || obj;
+let styles =
+document.getElementById("styles");
+styles.innerHTML = `${
+                                        styles.innerHTML
+                                    }${getClassAsString(darkCls, obj)}\n`;
+}
+}
+anchors.forEach((a) => a.classList.add(cls));
+}
+}
+})
+.addNodeProperty(this, null, inherited),
+);
+this.#mutables.push(ftd.dark_mode);
+}
+setStaticProperty(kind, value, inherited) {
+let staticValue = fastn_utils.getStaticValue(value);
+if (kind === fastn_dom.PropertyKind.Children) {
+if (fastn_utils.isWrapperNode(this.#tagName)) {
+let parentWithSibiling = this.#parent;
+if (Array.isArray(staticValue)) {
+staticValue.forEach((func, index) => {
+if (index !== 0) {
+parentWithSibiling = new ParentNodeWithSibiling(
+this.#parent.getParent(),
+this.#children[index - 1],
+);
+}
+this.#children.push(
+fastn_utils.getStaticValue(func.item)(
+parentWithSibiling,
+inherited,
+),
+);
+});
+} else {
+this.#children.push(
+staticValue(parentWithSibiling, inherited),
+);
+}
+} else {
+if (Array.isArray(staticValue)) {
+staticValue.forEach((func) =>
+this.#children.push(
+fastn_utils.getStaticValue(func.item)(
+this,
+inherited,
+),
+),
+);
+} else {
+this.#children.push(staticValue(this, inherited));
+}
+}
+} else if (kind === fastn_dom.PropertyKind.Id) {
+this.#node.id = staticValue;
+} else if (kind === fastn_dom.PropertyKind.BreakpointWidth) {
+if (fastn_utils.isNull(staticValue)) {
+return;
+}
+ftd.breakpoint_width.set(fastn_utils.getStaticValue(staticValue));
+} else if (kind === fastn_dom.PropertyKind.Css) {
+let css_list = staticValue.map((obj) =>
+fastn_utils.getStaticValue(obj.item),
+);
+css_list.forEach((css) => {
+this.attachExternalCss(css);
+});
+} else if (kind === fastn_dom.PropertyKind.Js) {
+let js_list = staticValue.map((obj) =>
+fastn_utils.getStaticValue(obj.item),
+);
+js_list.forEach((js) => {
+this.attachExternalJs(js);
+});
+} else if (kind === fastn_dom.PropertyKind.Width) {
+this.attachCss("width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Height) {
+fastn_utils.resetFullHeight();
+this.attachCss("height", staticValue);
+fastn_utils.setFullHeight();
+} else if (kind === fastn_dom.PropertyKind.Padding) {
+this.attachCss("padding", staticValue);
+} else if (kind === fastn_dom.PropertyKind.PaddingHorizontal) {
+this.attachCss("padding-left", staticValue);
+this.attachCss("padding-right", staticValue);
+} else if (kind === fastn_dom.PropertyKind.PaddingVertical) {
+this.attachCss("padding-top", staticValue);
+this.attachCss("padding-bottom", staticValue);
+} else if (kind === fastn_dom.PropertyKind.PaddingLeft) {
+this.attachCss("padding-left", staticValue);
+} else if (kind === fastn_dom.PropertyKind.PaddingRight) {
+this.attachCss("padding-right", staticValue);
+} else if (kind === fastn_dom.PropertyKind.PaddingTop) {
+this.attachCss("padding-top", staticValue);
+} else if (kind === fastn_dom.PropertyKind.PaddingBottom) {
+this.attachCss("padding-bottom", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Margin) {
+this.attachCss("margin", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MarginHorizontal) {
+this.attachCss("margin-left", staticValue);
+this.attachCss("margin-right", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MarginVertical) {
+this.attachCss("margin-top", staticValue);
+this.attachCss("margin-bottom", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MarginLeft) {
+this.attachCss("margin-left", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MarginRight) {
+this.attachCss("margin-right", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MarginTop) {
+this.attachCss("margin-top", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MarginBottom) {
+this.attachCss("margin-bottom", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderWidth) {
+this.attachCss("border-width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderTopWidth) {
+this.attachCss("border-top-width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderBottomWidth) {
+this.attachCss("border-bottom-width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderLeftWidth) {
+this.attachCss("border-left-width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderRightWidth) {
+this.attachCss("border-right-width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderRadius) {
+this.attachCss("border-radius", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderTopLeftRadius) {
+this.attachCss("border-top-left-radius", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderTopRightRadius) {
+this.attachCss("border-top-right-radius", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderBottomLeftRadius) {
+this.attachCss("border-bottom-left-radius", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderBottomRightRadius) {
+this.attachCss("border-bottom-right-radius", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderStyle) {
+this.attachCss("border-style", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderStyleVertical) {
+this.attachCss("border-top-style", staticValue);
+this.attachCss("border-bottom-style", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderStyleHorizontal) {
+this.attachCss("border-left-style", staticValue);
+this.attachCss("border-right-style", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderLeftStyle) {
+this.attachCss("border-left-style", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderRightStyle) {
+this.attachCss("border-right-style", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderTopStyle) {
+this.attachCss("border-top-style", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderBottomStyle) {
+this.attachCss("border-bottom-style", staticValue);
+} else if (kind === fastn_dom.PropertyKind.ZIndex) {
+this.attachCss("z-index", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Shadow) {
+this.attachShadow(staticValue);
+} else if (kind === fastn_dom.PropertyKind.TextShadow) {
+this.attachTextShadow(staticValue);
+} else if (kind === fastn_dom.PropertyKind.BackdropFilter) {
+if (fastn_utils.isNull(staticValue)) {
+this.attachCss("backdrop-filter", staticValue);
+return;
+}
+let backdropType = staticValue[0];
+switch (backdropType) {
+case 1:
+this.attachCss(
+"backdrop-filter",
+`blur(${fastn_utils.getStaticValue(staticValue[1])})`,
+);
+break;
+case 2:
+this.attachCss(
+"backdrop-filter",
+`brightness(${fastn_utils.getStaticValue(
+                            staticValue[1],
+                        )})`,
+);
+break;
+case 3:
+this.attachCss(
+"backdrop-filter",
+`contrast(${fastn_utils.getStaticValue(
+                            staticValue[1],
+                        )})`,
+);
+break;
+case 4:
+this.attachCss(
+"backdrop-filter",
+`greyscale(${fastn_utils.getStaticValue(
+                            staticValue[1],
+                        )})`,
+);
+break;
+case 5:
+this.attachCss(
+"backdrop-filter",
+`invert(${fastn_utils.getStaticValue(staticValue[1])})`,
+);
+break;
+case 6:
+this.attachCss(
+"backdrop-filter",
+`opacity(${fastn_utils.getStaticValue(
+                            staticValue[1],
+                        )})`,
+);
+break;
+case 7:
+this.attachCss(
+"backdrop-filter",
+`sepia(${fastn_utils.getStaticValue(staticValue[1])})`,
+);
+break;
+case 8:
+this.attachCss(
+"backdrop-filter",
+`saturate(${fastn_utils.getStaticValue(
+                            staticValue[1],
+                        )})`,
+);
+break;
+case 9:
+this.attachBackdropMultiFilter(staticValue[1]);
+break;
+}
+} else if (kind === fastn_dom.PropertyKind.Mask) {
+if (fastn_utils.isNull(staticValue)) {
+this.attachCss("mask-image", staticValue);
+return;
+}
+const [backgroundType, value] = staticValue;
+switch (backgroundType) {
+case fastn_dom.Mask.Image()[0]:
+this.attachMaskImageCss(value);
+this.attachMaskImageCss(value, "-webkit");
+break;
+case fastn_dom.Mask.Multi()[0]:
+this.attachMaskMultiCss(value);
+this.attachMaskMultiCss(value, "-webkit");
+break;
+}
+} else if (kind === fastn_dom.PropertyKind.Classes) {
+fastn_utils.removeNonFastnClasses(this);
+if (!fastn_utils.isNull(staticValue)) {
+let cls = staticValue.map((obj) =>
+fastn_utils.getStaticValue(obj.item),
+);
+cls.forEach((c) => {
+this.#node.classList.add(c);
+});
+}
+} else if (kind === fastn_dom.PropertyKind.Anchor) {
+if (fastn_utils.isNull(staticValue)) {
+this.attachCss("position", staticValue);
+return;
+}
+let anchorType = staticValue[0];
+switch (anchorType) {
+case 1:
+this.attachCss("position", staticValue[1]);
+break;
+case 2:
+this.attachCss("position", staticValue[1]);
+this.updateParentPosition("relative");
+break;
+case 3:
+const parent_node_id = staticValue[1];
+this.attachCss("position", "absolute");
+this.updatePositionForNodeById(parent_node_id, "relative");
+break;
+}
+} else if (kind === fastn_dom.PropertyKind.Sticky) {
+switch (staticValue) {
+case "true":
+case true:
+this.attachCss("position", "sticky");
+break;
+case "false":
+case false:
+this.attachCss("position", "static");
+break;
+default:
+this.attachCss("position", staticValue);
+}
+} else if (kind === fastn_dom.PropertyKind.Top) {
+this.attachCss("top", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Bottom) {
+this.attachCss("bottom", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Left) {
+this.attachCss("left", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Right) {
+this.attachCss("right", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Overflow) {
+this.attachCss("overflow", staticValue);
+} else if (kind === fastn_dom.PropertyKind.OverflowX) {
+this.attachCss("overflow-x", staticValue);
+} else if (kind === fastn_dom.PropertyKind.OverflowY) {
+this.attachCss("overflow-y", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Spacing) {
+if (fastn_utils.isNull(staticValue)) {
+this.attachCss("justify-content", staticValue);
+this.attachCss("gap", staticValue);
+return;
+}
+let spacingType = staticValue[0];
+switch (spacingType) {
+case fastn_dom.Spacing.SpaceEvenly[0]:
+case fastn_dom.Spacing.SpaceBetween[0]:
+case fastn_dom.Spacing.SpaceAround[0]:
+this.attachCss("justify-content", staticValue[1]);
+break;
+case fastn_dom.Spacing.Fixed()[0]:
+this.attachCss(
+"gap",
+fastn_utils.getStaticValue(staticValue[1]),
+);
+break;
+}
+} else if (kind === fastn_dom.PropertyKind.Wrap) {
+switch (staticValue) {
+case "true":
+case true:
+this.attachCss("flex-wrap", "wrap");
+break;
+case "false":
+case false:
+this.attachCss("flex-wrap", "no-wrap");
+break;
+default:
+this.attachCss("flex-wrap", staticValue);
+}
+} else if (kind === fastn_dom.PropertyKind.TextTransform) {
+this.attachCss("text-transform", staticValue);
+} else if (kind === fastn_dom.PropertyKind.TextIndent) {
+this.attachCss("text-indent", staticValue);
+} else if (kind === fastn_dom.PropertyKind.TextAlign) {
+this.attachCss("text-align", staticValue);
+} else if (kind === fastn_dom.PropertyKind.LineClamp) {
+this.attachCss("-webkit-line-clamp", staticValue);
+this.attachCss("display", "-webkit-box");
+this.attachCss("overflow", "hidden");
+this.attachCss("-webkit-box-orient", "vertical");
+} else if (kind === fastn_dom.PropertyKind.Opacity) {
+this.attachCss("opacity", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Cursor) {
+this.attachCss("cursor", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Resize) {
+this.attachCss("resize", staticValue);
+this.attachCss("overflow", "auto");
+} else if (kind === fastn_dom.PropertyKind.Selectable) {
+if (staticValue === false) {
+this.attachCss("user-select", "none");
+} else {
+this.attachCss("user-select", null);
+}
+} else if (kind === fastn_dom.PropertyKind.MinHeight) {
+this.attachCss("min-height", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MaxHeight) {
+this.attachCss("max-height", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MinWidth) {
+this.attachCss("min-width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.MaxWidth) {
+this.attachCss("max-width", staticValue);
+} else if (kind === fastn_dom.PropertyKind.WhiteSpace) {
+this.attachCss("white-space", staticValue);
+} else if (kind === fastn_dom.PropertyKind.AlignSelf) {
+this.attachCss("align-self", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderColor) {
+this.attachColorCss("border-color", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderLeftColor) {
+this.attachColorCss("border-left-color", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderRightColor) {
+this.attachColorCss("border-right-color", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderTopColor) {
+this.attachColorCss("border-top-color", staticValue);
+} else if (kind === fastn_dom.PropertyKind.BorderBottomColor) {
+this.attachColorCss("border-bottom-color", staticValue);
+} else if (kind === fastn_dom.PropertyKind.LinkColor) {
+this.attachLinkColor(staticValue);
+} else if (kind === fastn_dom.PropertyKind.Color) {
+this.attachColorCss("color", staticValue, true);
+} else if (kind === fastn_dom.PropertyKind.Background) {
+if (fastn_utils.isNull(staticValue)) {
+this.attachColorCss("background-color", staticValue);
+this.attachBackgroundImageCss(staticValue);
+this.attachLinearGradientCss(staticValue);
+return;
+}
+let backgroundType = staticValue[0];
+switch (backgroundType) {
+case fastn_dom.BackgroundStyle.Solid()[0]:
+this.attachColorCss("background-color", staticValue[1]);
+break;
+case fastn_dom.BackgroundStyle.Image()[0]:
+this.attachBackgroundImageCss(staticValue[1]);
+break;
+case fastn_dom.BackgroundStyle.LinearGradient()[0]:
+this.attachLinearGradientCss(staticValue[1]);
+break;
+}
+} else if (kind === fastn_dom.PropertyKind.Display) {
+this.attachCss("display", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Checked) {
+switch (staticValue) {
+case "true":
+case true:
+this.attachAttribute("checked", "");
+break;
+case "false":
+case false:
+this.removeAttribute("checked");
+break;
+default:
+this.attachAttribute("checked", staticValue);
+}
+if (!ssr) this.#node.checked = staticValue;
+} else if (kind === fastn_dom.PropertyKind.Enabled) {
+switch (staticValue) {
+case "false":
+case false:
+this.attachAttribute("disabled", "");
+break;
+case "true":
+case true:
+this.removeAttribute("disabled");
+break;
+default:
+this.attachAttribute("disabled", staticValue);
+}
+} else if (kind === fastn_dom.PropertyKind.TextInputType) {
+this.attachAttribute("type", staticValue);
+} else if (kind === fastn_dom.PropertyKind.TextInputValue) {
+this.#rawInnerValue = staticValue;
+this.updateTextInputValue();
+} else if (kind === fastn_dom.PropertyKind.DefaultTextInputValue) {
+if (!fastn_utils.isNull(this.#rawInnerValue)) {
+return;
+}
+this.#rawInnerValue = staticValue;
+this.updateTextInputValue();
+} else if (kind === fastn_dom.PropertyKind.InputMaxLength) {
+this.attachAttribute("maxlength", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Placeholder) {
+this.attachAttribute("placeholder", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Multiline) {
+switch (staticValue) {
+case "true":
+case true:
+this.updateTagName("textarea");
+break;
+case "false":
+case false:
+this.updateTagName("input");
+break;
+}
+this.updateTextInputValue();
+} else if (kind === fastn_dom.PropertyKind.Download) {
+if (fastn_utils.isNull(staticValue)) {
+return;
+}
+this.attachAttribute("download", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Link) {
+if (fastn_utils.isNull(staticValue)) {
+return;
+}
+this.updateToAnchor(staticValue);
+} else if (kind === fastn_dom.PropertyKind.LinkRel) {
+if (fastn_utils.isNull(staticValue)) {
+this.removeAttribute("rel");
+}
+let rel_list = staticValue.map((obj) =>
+fastn_utils.getStaticValue(obj.item),
+);
+this.attachAttribute("rel", rel_list.join(" "));
+} else if (kind === fastn_dom.PropertyKind.OpenInNewTab) {
+switch (staticValue) {
+case "true":
+case true:
+this.attachAttribute("target", "_blank");
+break;
+default:
+this.attachAttribute("target", staticValue);
+}
+} else if (kind === fastn_dom.PropertyKind.TextStyle) {
+let styles = staticValue?.map((obj) =>
+fastn_utils.getStaticValue(obj.item),
+);
+this.attachTextStyles(styles);
+} else if (kind === fastn_dom.PropertyKind.Region) {
+this.updateTagName(staticValue);
+if (this.#node.innerHTML) {
+this.#node.id = fastn_utils.slugify(this.#rawInnerValue);
+}
+} else if (kind === fastn_dom.PropertyKind.AlignContent) {
+let node_kind = this.#kind;
+this.attachAlignContent(staticValue, node_kind);
+} else if (kind === fastn_dom.PropertyKind.Loading) {
+this.attachAttribute("loading", staticValue);
+} else if (kind === fastn_dom.PropertyKind.Src) {
+this.attachAttribute("src", staticValue);
+} else if (kind === fastn_dom.PropertyKind.ImageSrc) {
+this.attachImageSrcClosures(staticValue);
+ftd.dark_mode.addClosure(
+fastn
+.closure(() => {
+if (fastn_utils.isNull(staticValue)) {
+this.attachAttribute("src", staticValue);
+return;
+}
+const is_dark_mode = ftd.dark_mode.get();
+const src = staticValue.get(
+is_dark_mode ? "dark" : "light",
+);
+if (!ssr) {
+let image_node = this.#node;
+if (!fastn_utils.isNull(image_node)) {
+if (image_node.nodeName.toLowerCase() === "a") {
+let childNodes = image_node.childNodes;
+childNodes.forEach(function (child) {
+if (
+child.nodeName.toLowerCase() ===
+"img"
+)
+image_node = child;
+});
+}
+image_node.setAttribute(
+"src",
+fastn_utils.getStaticValue(src),
+);
+}
+} else {
+this.attachAttribute(
+"src",
+fastn_utils.getStaticValue(src),
+);
+}
+})
+.addNodeProperty(this, null, inherited),
+);
+this.#mutables.push(ftd.dark_mode);
+} else if (kind === fastn_dom.PropertyKind.Alt) {
+this.attachAttribute("alt", staticValue);
+} else if (kind === fastn_dom.PropertyKind.VideoSrc) {
+ftd.dark_mode.addClosure(
+fastn
+.closure(() => {
+if (fastn_utils.isNull(staticValue)) {
+this.attachAttribute("src", staticValue);
+return;
+}
+const is_dark_mode = ftd.dark_mode.get();
+const src = staticValue.get(
+is_dark_mode ? "dark" : "light",
+);
+this.attachAttribute(
+"src",
+fastn_utils.getStaticValue(src),
+);
+})
+.addNodeProperty(this, null, inherited),
+);
+this.#mutables.push(ftd.dark_mode);
+} else if (kind === fastn_dom.PropertyKind.Autoplay) {
+if (staticValue) {
+this.attachAttribute("autoplay", staticValue);
+} else {
+this.removeAttribute("autoplay");
+}
+} else if (kind === fastn_dom.PropertyKind.Muted) {
+if (staticValue) {
+this.attachAttribute("muted", staticValue);
+} else {
+this.removeAttribute("muted");
+}
+} else if (kind === fastn_dom.PropertyKind.Controls) {
+if (staticValue) {
+this.attachAttribute("controls", staticValue);
+} else {
+this.removeAttribute("controls");
+}
+} else if (kind === fastn_dom.PropertyKind.LoopVideo) {
+if (staticValue) {
+this.attachAttribute("loop", staticValue);
+} else {
+this.removeAttribute("loop");
+}
+} else if (kind === fastn_dom.PropertyKind.Poster) {
+ftd.dark_mode.addClosure(
+fastn
+.closure(() => {
+if (fastn_utils.isNull(staticValue)) {
+this.attachAttribute("poster", staticValue);
+return;
+}
+const is_dark_mode = ftd.dark_mode.get();
+const posterSrc = staticValue.get(
+is_dark_mode ? "dark" : "light",
+);
+this.attachAttribute(
+"poster",
+fastn_utils.getStaticValue(posterSrc),
+);
+})
+.addNodeProperty(this, null, inherited),
+);
+this.#mutables.push(ftd.dark_mode);
+} else if (kind === fastn_dom.PropertyKind.Fit) {
+this.attachCss("object-fit", staticValue);
+} else if (kind === fastn_dom.PropertyKind.FetchPriority) {
+this.attachAttribute("fetchpriority", staticValue);
+} else if (kind === fastn_dom.PropertyKind.YoutubeSrc) {
+if (fastn_utils.isNull(staticValue)) {
+this.attachAttribute("src", staticValue);
+return;
+}
+const id_pattern = "^([a-zA-Z0-9_-]{11})$";
+let id = staticValue.match(id_pattern);
+if (!fastn_utils.isNull(id)) {
+this.attachAttribute(
+"src",
+`https:\/\/youtube.com/embed/${id[0]}`,
+);
+} else {
+this.attachAttribute("src", staticValue);
+}
+} else if (kind === fastn_dom.PropertyKind.Role) {
+this.attachRoleCss(staticValue);
+} else if (kind === fastn_dom.PropertyKind.Code) {
+if (!fastn_utils.isNull(staticValue)) {
+let { modifiedText, highlightedLines } =
+fastn_utils.findAndRemoveHighlighter(staticValue);
+if (highlightedLines.length !== 0) {
+this.attachAttribute("data-line", highlightedLines);
+}
+staticValue = modifiedText;
+}
+let codeNode = this.#children[0].getNode();
+let codeText = fastn_utils.escapeHtmlInCode(staticValue);
+codeNode.innerHTML = codeText;
+this.#extraData.code = this.#extraData.code
+? this.#extraData.code
+: {};
+fastn_utils.highlightCode(codeNode, this.#extraData.code);
+} else if (kind === fastn_dom.PropertyKind.CodeShowLineNumber) {
+if (staticValue) {
+this.#node.classList.add("line-numbers");
+} else {
+this.#node.classList.remove("line-numbers");
+}
+} else if (kind === fastn_dom.PropertyKind.CodeTheme) {
+this.#extraData.code = this.#extraData.code
+? this.#extraData.code
+: {};
+if (fastn_utils.isNull(staticValue)) {
+if (!fastn_utils.isNull(this.#extraData.code.theme)) {
+this.#node.classList.remove(this.#extraData.code.theme);
+}
+return;
+}
+if (!ssr) {
+fastn_utils.addCodeTheme(staticValue);
+}
+staticValue = fastn_utils.getStaticValue(staticValue);
+let theme = staticValue.replace(".", "-");
+if (this.#extraData.code.theme !== theme) {
+let codeNode = this.#children[0].getNode();
+this.#node.classList.remove(this.#extraData.code.theme);
+codeNode.classList.remove(this.#extraData.code.theme);
+this.#extraData.code.theme = theme;
+this.#node.classList.add(theme);
+codeNode.classList.add(theme);
+fastn_utils.highlightCode(codeNode, this.#extraData.code);
+}
+} else if (kind === fastn_dom.PropertyKind.CodeLanguage) {
+let language = `language-${staticValue}`;
+this.#extraData.code = this.#extraData.code
+? this.#extraData.code
+: {};
+if (this.#extraData.code.language) {
+this.#node.classList.remove(language);
+}
+this.#extraData.code.language = language;
+this.#node.classList.add(language);
+let codeNode = this.#children[0].getNode();
+codeNode.classList.add(language);
+fastn_utils.highlightCode(codeNode, this.#extraData.code);
+} else if (kind === fastn_dom.PropertyKind.Favicon) {
+if (fastn_utils.isNull(staticValue)) return;
+this.setFavicon(staticValue);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaTitle
+) {
+this.updateMetaTitle(staticValue);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaOGTitle
+) {
+this.addMetaTagByProperty("og:title", staticValue);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaTwitterTitle
+) {
+this.addMetaTagByName("twitter:title", staticValue);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaDescription
+) {
+this.addMetaTagByName("description", staticValue);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaOGDescription
+) {
+this.addMetaTagByProperty("og:description", staticValue);
+} else if (
+kind ===
+fastn_dom.PropertyKind.DocumentProperties.MetaTwitterDescription
+) {
+this.addMetaTagByName("twitter:description", staticValue);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaOGImage
+) {
+if (fastn_utils.isNull(staticValue)) {
+this.removeMetaTagByProperty("og:image");
+return;
+}
+this.addMetaTagByProperty(
+"og:image",
+fastn_utils.getStaticValue(staticValue.get("src")),
+);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaTwitterImage
+) {
+if (fastn_utils.isNull(staticValue)) {
+this.removeMetaTagByName("twitter:image");
+return;
+}
+this.addMetaTagByName(
+"twitter:image",
+fastn_utils.getStaticValue(staticValue.get("src")),
+);
+} else if (
+kind === fastn_dom.PropertyKind.DocumentProperties.MetaThemeColor
+) {
+if (fastn_utils.isNull(staticValue)) {
+this.removeMetaTagByName("theme-color");
+return;
+}
+this.addMetaTagByName(
+"theme-color",
+fastn_utils.getStaticValue(staticValue.get("light")),
+);
+} else if (
+kind ===
+fastn_dom.PropertyKind.DocumentProperties
+.MetaFacebookDomainVerification
+) {
+if (fastn_utils.isNull(staticValue)) {
+this.removeMetaTagByName("facebook-domain-verification");
+return;
+}
+this.addMetaTagByName(
+"facebook-domain-verification",
+fastn_utils.getStaticValue(staticValue),
+);
+} else if (
+kind === fastn_dom.PropertyKind.IntegerValue ||
+kind === fastn_dom.PropertyKind.DecimalValue ||
+kind === fastn_dom.PropertyKind.BooleanValue
+) {
+this.#node.innerHTML = staticValue;
+this.#rawInnerValue = staticValue;
+} else if (kind === fastn_dom.PropertyKind.StringValue) {
+this.#rawInnerValue = staticValue;
+staticValue = fastn_utils.markdown_inline(
+fastn_utils.escapeHtmlInMarkdown(staticValue),
+);
+staticValue = fastn_utils.process_post_markdown(
+this.#node,
+staticValue,
+);
+if (!fastn_utils.isNull(staticValue)) {
+this.#node.innerHTML = staticValue;
+} else {
+this.#node.innerHTML = "";
+}
+} else {
+throw "invalid fastn_dom.PropertyKind: " + kind;
+}
+}
+setProperty(kind, value, inherited) {
+if (value instanceof fastn.mutableClass) {
+this.setDynamicProperty(
+kind,
+[value],
+() => {
+return value.get();
+},
+inherited,
+);
+} else if (value instanceof PropertyValueAsClosure) {
+this.setDynamicProperty(
+kind,
+value.deps,
+value.closureFunction,
+inherited,
+);
+} else {
+this.setStaticProperty(kind, value, inherited);
+}
+}
+setDynamicProperty(kind, deps, func, inherited) {
+let closure = fastn
+.closure(func)
+.addNodeProperty(this, kind, inherited);
+for (let dep in deps) {
+if (fastn_utils.isNull(deps[dep]) || !deps[dep].addClosure) {
+continue;
+}
+deps[dep].addClosure(closure);
+this.#mutables.push(deps[dep]);
+}
+}
+getNode() {
+return this.#node;
+}
+getExtraData() {
+return this.#extraData;
+}
+getChildren() {
+return this.#children;
+}
+mergeFnCalls(current, newFunc) {
+return () => {
+if (current instanceof Function) current();
+if (newFunc instanceof Function) newFunc();
+};
+}
+addEventHandler(event, func) {
+if (event === fastn_dom.Event.Click) {
+let onclickEvents = this.mergeFnCalls(this.#node.onclick, func);
+if (fastn_utils.isNull(this.#node.onclick))
+this.attachCss("cursor", "pointer");
+this.#node.onclick = onclickEvents;
+} else if (event === fastn_dom.Event.MouseEnter) {
+let mouseEnterEvents = this.mergeFnCalls(
+this.#node.onmouseenter,
+func,
+);
+this.#node.onmouseenter = mouseEnterEvents;
+} else if (event === fastn_dom.Event.MouseLeave) {
+let mouseLeaveEvents = this.mergeFnCalls(
+this.#node.onmouseleave,
+func,
+);
+this.#node.onmouseleave = mouseLeaveEvents;
+} else if (event === fastn_dom.Event.ClickOutside) {
+ftd.clickOutsideEvents.push([this, func]);
+} else if (!!event[0] && event[0] === fastn_dom.Event.GlobalKey()[0]) {
+ftd.globalKeyEvents.push([this, func, event[1]]);
+} else if (
+!!event[0] &&
+event[0] === fastn_dom.Event.GlobalKeySeq()[0]
+) {
+ftd.globalKeySeqEvents.push([this, func, event[1]]);
+} else if (event === fastn_dom.Event.Input) {
+let onInputEvents = this.mergeFnCalls(this.#node.oninput, func);
+this.#node.oninput = onInputEvents;
+} else if (event === fastn_dom.Event.Change) {
+let onChangeEvents = this.mergeFnCalls(this.#node.onchange, func);
+this.#node.onchange = onChangeEvents;
+} else if (event === fastn_dom.Event.Blur) {
+let onBlurEvents = this.mergeFnCalls(this.#node.onblur, func);
+this.#node.onblur = onBlurEvents;
+} else if (event === fastn_dom.Event.Focus) {
+let onFocusEvents = this.mergeFnCalls(this.#node.onfocus, func);
+this.#node.onfocus = onFocusEvents;
+}
+}
+destroy() {
+for (let i = 0; i < this.#mutables.length; i++) {
+this.#mutables[i].unlinkNode(this);
+}
+if (!fastn_utils.isNull(this.#node)) {
+this.#node.remove();
+}
+this.#mutables = [];
+this.#parent = null;
+this.#node = null;
+}
+}
+class ConditionalDom {
+#marker;
+#parent;
+#node_constructor;
+#condition;
+#mutables;
+#conditionUI;
+constructor(parent, deps, condition, node_constructor) {
+this.#marker = fastn_dom.createKernel(
+parent,
+fastn_dom.ElementKind.Comment,
+);
+this.#parent = parent;
+this.#conditionUI = null;
+let closure = fastn.closure(() => {
+fastn_utils.resetFullHeight();
+if (condition()) {
+if (this.#conditionUI) {
+let conditionUI = fastn_utils.flattenArray(
+this.#conditionUI,
+);
+while (conditionUI.length > 0) {
+let poppedElement = conditionUI.pop();
+poppedElement.destroy();
+}
+}
+this.#conditionUI = node_constructor(
+new ParentNodeWithSibiling(this.#parent, this.#marker),
+);
+if (
+!Array.isArray(this.#conditionUI) &&
+fastn_utils.isWrapperNode(this.#conditionUI.getTagName())
+) {
+this.#conditionUI = this.#conditionUI.getChildren();
+}
+} else if (this.#conditionUI) {
+let conditionUI = fastn_utils.flattenArray(this.#conditionUI);
+while (conditionUI.length > 0) {
+let poppedElement = conditionUI.pop();
+poppedElement.destroy();
+}
+this.#conditionUI = null;
+}
+fastn_utils.setFullHeight();
+});
+deps.forEach((dep) => {
+if (!fastn_utils.isNull(dep) && dep.addClosure) {
+dep.addClosure(closure);
+}
+});
+this.#node_constructor = node_constructor;
+this.#condition = condition;
+this.#mutables = [];
+}
+getParent() {
+let nodes = [this.#marker];
+if (this.#conditionUI) {
+nodes.push(this.#conditionUI);
+}
+return nodes;
+}
+}
+fastn_dom.createKernel = function (parent, kind) {
+return new Node2(parent, kind);
+};
+fastn_dom.conditionalDom = function (
+parent,
+deps,
+condition,
+node_constructor,
+) {
+return new ConditionalDom(parent, deps, condition, node_constructor);
+};
+class ParentNodeWithSibiling {
+#parent;
+#sibiling;
+constructor(parent, sibiling) {
+this.#parent = parent;
+this.#sibiling = sibiling;
+}
+getParent() {
+return this.#parent;
+}
+getSibiling() {
+return this.#sibiling;
+}
+}
+class ForLoop {
+#node_constructor;
+#list;
+#wrapper;
+#parent;
+#nodes;
+constructor(parent, node_constructor, list) {
+this.#wrapper = fastn_dom.createKernel(
+parent,
+fastn_dom.ElementKind.Comment,
+);
+this.#parent = parent;
+this.#node_constructor = node_constructor;
+this.#list = list;
+this.#nodes = [];
+fastn_utils.resetFullHeight();
+for (let idx in list.getList()) {
+this.createNode(idx, false);
+}
+fastn_utils.setFullHeight();
+}
+createNode(index, resizeBodyHeight = true) {
+if (resizeBodyHeight) {
+fastn_utils.resetFullHeight();
+}
+let parentWithSibiling = new ParentNodeWithSibiling(
+this.#parent,
+this.#wrapper,
+);
+if (index !== 0) {
+parentWithSibiling = new ParentNodeWithSibiling(
+this.#parent,
+this.#nodes[index - 1],
+);
+}
+let v = this.#list.get(index);
+let node = this.#node_constructor(parentWithSibiling, v.item, v.index);
+this.#nodes.splice(index, 0, node);
+if (resizeBodyHeight) {
+fastn_utils.setFullHeight();
+}
+return node;
+}
+createAllNode() {
+fastn_utils.resetFullHeight();
+this.deleteAllNode(false);
+for (let idx in this.#list.getList()) {
+this.createNode(idx, false);
+}
+fastn_utils.setFullHeight();
+}
+deleteAllNode(resizeBodyHeight = true) {
+if (resizeBodyHeight) {
+fastn_utils.resetFullHeight();
+}
+while (this.#nodes.length > 0) {
+this.#nodes.pop().destroy();
+}
+if (resizeBodyHeight) {
+fastn_utils.setFullHeight();
+}
+}
+getWrapper() {
+return this.#wrapper;
+}
+deleteNode(index) {
+fastn_utils.resetFullHeight();
+let node = this.#nodes.splice(index, 1)[0];
+node.destroy();
+fastn_utils.setFullHeight();
+}
+getParent() {
+return this.#parent;
+}
+}
+fastn_dom.forLoop = function (parent, node_constructor, list) {
+return new ForLoop(parent, node_constructor, list);
+};
+let fastn_utils = {
+htmlNode(kind) {
+let node = "div";
+let css = [];
+let attributes = {};
+if (kind === fastn_dom.ElementKind.Column) {
+css.push(fastn_dom.InternalClass.FT_COLUMN);
+} else if (kind === fastn_dom.ElementKind.Document) {
+css.push(fastn_dom.InternalClass.FT_COLUMN);
+css.push(fastn_dom.InternalClass.FT_FULL_SIZE);
+} else if (kind === fastn_dom.ElementKind.Row) {
+css.push(fastn_dom.InternalClass.FT_ROW);
+} else if (kind === fastn_dom.ElementKind.IFrame) {
+node = "iframe";
+attributes["allowfullscreen"] = "";
+} else if (kind === fastn_dom.ElementKind.Image) {
+node = "img";
+} else if (kind === fastn_dom.ElementKind.Video) {
+node = "video";
+} else if (
+kind === fastn_dom.ElementKind.ContainerElement ||
+kind === fastn_dom.ElementKind.Text
+) {
+node = "div";
+} else if (kind === fastn_dom.ElementKind.Rive) {
+node = "canvas";
+} else if (kind === fastn_dom.ElementKind.CheckBox) {
+node = "input";
+attributes["type"] = "checkbox";
+} else if (kind === fastn_dom.ElementKind.TextInput) {
+node = "input";
+} else if (kind === fastn_dom.ElementKind.Comment) {
+node = fastn_dom.commentNode;
+} else if (kind === fastn_dom.ElementKind.Wrapper) {
+node = fastn_dom.wrapperNode;
+} else if (kind === fastn_dom.ElementKind.Code) {
+node = "pre";
+} else if (kind === fastn_dom.ElementKind.CodeChild) {
+node = "code";
+} else if (kind[0] === fastn_dom.ElementKind.WebComponent()[0]) {
+let [webcomponent, args] = kind[1];
+node = `${webcomponent}`;
+fastn_dom.webComponent.push(args);
+attributes[fastn_dom.webComponentArgument] =
+fastn_dom.webComponent.length - 1;
+}
+return [node, css, attributes];
+},
+createStyle(cssClass, obj) {
+if (doubleBuffering) {
+fastn_dom.styleClasses = `${
+                fastn_dom.styleClasses
+            }${getClassAsString(cssClass, obj)}\n`;
+} else {
+let styles = document.getElementById("styles");
+let newClasses = getClassAsString(cssClass, obj);
+let textNode = document.createTextNode(newClasses);
+if (styles.styleSheet) {
+styles.styleSheet.cssText = newClasses;
+} else {
+styles.appendChild(textNode);
+}
+}
+},
+getStaticValue(obj) {
+if (obj instanceof fastn.mutableClass) {
+return this.getStaticValue(obj.get());
+} else if (obj instanceof fastn.mutableListClass) {
+return obj.getList();
+}
+else {
+return obj;
+}
+},
+getInheritedValues(default_args, inherited, function_args) {
+let record_fields = {
+colors: ftd.default_colors.getClone().setAndReturn("is_root", true),
+types: ftd.default_types.getClone().setAndReturn("is_root", true),
+};
+Object.assign(record_fields, default_args);
+let fields = {};
+if (inherited instanceof fastn.recordInstanceClass) {
+fields = inherited.getClonedFields();
+if (fastn_utils.getStaticValue(fields["colors"].get("is_root"))) {
+delete fields.colors;
+}
+if (fastn_utils.getStaticValue(fields["types"].get("is_root"))) {
+delete fields.types;
+}
+}
+Object.assign(record_fields, fields);
+Object.assign(record_fields, function_args);
+return fastn.recordInstance({
+...record_fields,
+});
+},
+removeNonFastnClasses(node) {
+let classList = node.getNode().classList;
+let extraCodeData = node.getExtraData().code;
+let iterativeClassList = classList;
+if (ssr) {
+iterativeClassList = iterativeClassList.getClasses();
+}
+const internalClassNames = Object.values(fastn_dom.InternalClass);
+const classesToRemove = [];
+for (const className of iterativeClassList) {
+if (
+!className.startsWith("__") &&
+!internalClassNames.includes(className) &&
+className !== extraCodeData?.language &&
+className !== extraCodeData?.theme
+) {
+classesToRemove.push(className);
+}
+}
+for (const classNameToRemove of classesToRemove) {
+classList.remove(classNameToRemove);
+}
+},
+staticToMutables(obj) {
+if (
+!(obj instanceof fastn.mutableClass) &&
+!(obj instanceof fastn.mutableListClass) &&
+!(obj instanceof fastn.recordInstanceClass)
+) {
+if (Array.isArray(obj)) {
+let list = [];
+for (let index in obj) {
+list.push(fastn_utils.staticToMutables(obj[index]));
+}
+return fastn.mutableList(list);
+} else if (obj instanceof Object) {
+let fields = {};
+for (let objKey in obj) {
+fields[objKey] = fastn_utils.staticToMutables(obj[objKey]);
+}
+return fastn.recordInstance(fields);
+} else {
+return fastn.mutable(obj);
+}
+} else {
+return obj;
+}
+},
+flattenMutable(value) {
+if (!(value instanceof fastn.mutableClass)) return value;
+if (value.get() instanceof fastn.mutableClass)
+return this.flattenMutable(value.get());
+return value;
+},
+getFlattenStaticValue(obj) {
+let staticValue = fastn_utils.getStaticValue(obj);
+if (Array.isArray(staticValue)) {
+return staticValue.map((func) =>
+fastn_utils.getFlattenStaticValue(func.item),
+);
+}
+return staticValue;
+},
+getter(value) {
+if (value instanceof fastn.mutableClass) {
+return value.get();
+} else {
+return value;
+}
+},
+getterByKey(value, index) {
+if (
+value instanceof fastn.mutableClass ||
+value instanceof fastn.recordInstanceClass
+) {
+return value.get(index);
+} else if (value instanceof fastn.mutableListClass) {
+return value.get(index).item;
+} else {
+return value;
+}
+},
+setter(variable, value) {
+variable = fastn_utils.flattenMutable(variable);
+if (!fastn_utils.isNull(variable) && variable.set) {
+variable.set(value);
+return true;
+}
+return false;
+},
+defaultPropertyValue(_propertyValue) {
+return null;
+},
+sameResponsiveRole(desktop, mobile) {
+return (
+desktop.get("font_family") === mobile.get("font_family") &&
+desktop.get("letter_spacing") === mobile.get("letter_spacing") &&
+desktop.get("line_height") === mobile.get("line_height") &&
+desktop.get("size") === mobile.get("size") &&
+desktop.get("weight") === mobile.get("weight")
+);
+},
+getRoleValues(value) {
+let font_families = fastn_utils.getStaticValue(
+value.get("font_family"),
+);
+if (Array.isArray(font_families))
+font_families = font_families
+.map((obj) => fastn_utils.getStaticValue(obj.item))
+.join(", ");
+return {
+"font-family": font_families,
+"letter-spacing": fastn_utils.getStaticValue(
+value.get("letter_spacing"),
+),
+"font-size": fastn_utils.getStaticValue(value.get("size")),
+"font-weight": fastn_utils.getStaticValue(value.get("weight")),
+"line-height": fastn_utils.getStaticValue(value.get("line_height")),
+};
+},
+clone(value) {
+if (value === null || value === undefined) {
+return value;
+}
+if (
+value instanceof fastn.mutableClass ||
+value instanceof fastn.mutableListClass
+) {
+return value.getClone();
+}
+if (value instanceof fastn.recordInstanceClass) {
+return value.getClone();
+}
+return value;
+},
+getListItem(value) {
+if (value === undefined) {
+return null;
+}
+if (value instanceof Object && value.hasOwnProperty("item")) {
+value = value.item;
+}
+return value;
+},
+getEventKey(event) {
+if (65 <= event.keyCode && event.keyCode <= 90) {
+return String.fromCharCode(event.keyCode).toLowerCase();
+} else {
+return event.key;
+}
+},
+createNestedObject(currentObject, path, value) {
+const properties = path.split(".");
+for (let i = 0; i < properties.length - 1; i++) {
+let property = fastn_utils.private.addUnderscoreToStart(
+properties[i],
+);
+if (currentObject instanceof fastn.recordInstanceClass) {
+if (currentObject.get(property) === undefined) {
+currentObject.set(property, fastn.recordInstance({}));
+}
+currentObject = currentObject.get(property).get();
+} else {
+if (!currentObject.hasOwnProperty(property)) {
+currentObject[property] = fastn.recordInstance({});
+}
+currentObject = currentObject[property];
+}
+}
+const innermostProperty = properties[properties.length - 1];
+if (currentObject instanceof fastn.recordInstanceClass) {
+currentObject.set(innermostProperty, value);
+} else {
+currentObject[innermostProperty] = value;
+}
+},
+markdown_inline(i) {
+if (fastn_utils.isNull(i)) return;
+i = i.toString();
+const { space_before, space_after } = fastn_utils.private.spaces(i);
+const o = (() => {
+let g = fastn_utils.private.replace_last_occurrence(
+marked.parse(i),
+"<p>",
+"",
+);
+g = fastn_utils.private.replace_last_occurrence(g, "</p>", "");
+return g;
+})();
+return `${fastn_utils.private.repeated_space(
+            space_before,
+        )}${o}${fastn_utils.private.repeated_space(space_after)}`.replace(
+/\n+$/,
+"",
+);
+},
+process_post_markdown(node, body) {
+if (!ssr) {
+const divElement = document.createElement("div");
+divElement.innerHTML = body;
+const current_node = node;
+const colorClasses = Array.from(current_node.classList).filter(
+(className) => className.startsWith("__c"),
+);
+const roleClasses = Array.from(current_node.classList).filter(
+(className) => className.startsWith("__rl"),
+);
+const tableElements = Array.from(
+divElement.getElementsByTagName("table"),
+);
+const codeElements = Array.from(
+divElement.getElementsByTagName("code"),
+);
+tableElements.forEach((table) => {
+colorClasses.forEach((colorClass) => {
+table.classList.add(colorClass);
+});
+});
+codeElements.forEach((code) => {
+roleClasses.forEach((roleClass) => {
+var roleCls = "." + roleClass;
+let role = fastn_dom.classes[roleCls];
+let roleValue = role["value"];
+let fontFamily = roleValue["font-family"];
+code.style.fontFamily = fontFamily;
+});
+});
+body = divElement.innerHTML;
+}
+return body;
+},
+isNull(a) {
+return a === null || a === undefined;
+},
+isCommentNode(node) {
+return node === fastn_dom.commentNode;
+},
+isWrapperNode(node) {
+return node === fastn_dom.wrapperNode;
+},
+nextSibling(node, parent) {
+while (Array.isArray(node)) {
+node = node[node.length - 1];
+}
+if (node.nextSibling) {
+return node.nextSibling;
+}
+if (node.getNode && node.getNode().nextSibling !== undefined) {
+return node.getNode().nextSibling;
+}
+return parent.getChildren().indexOf(node.getNode()) + 1;
+},
+createNodeHelper(node, classes, attributes) {
+let tagName = node;
+let element = fastnVirtual.document.createElement(node);
+for (let key in attributes) {
+element.setAttribute(key, attributes[key]);
+}
+for (let c in classes) {
+element.classList.add(classes[c]);
+}
+return [tagName, element];
+},
+addCssFile(url) {
+const linkElement = document.createElement("link");
+linkElement.rel = "stylesheet";
+linkElement.href = url;
+document.head.appendChild(linkElement);
+},
+addCodeTheme(theme) {
+if (!fastn_dom.codeData.addedCssFile.includes(theme)) {
+let themeCssUrl = fastn_dom.codeData.availableThemes[theme];
+fastn_utils.addCssFile(themeCssUrl);
+fastn_dom.codeData.addedCssFile.push(theme);
+}
+},
+findAndRemoveHighlighter(text) {
+const lines = text.split("\n");
+const highlighter = ";; <hl>";
+const result = {
+modifiedText: "",
+highlightedLines: "",
+};
+let highlightedLines = [];
+for (let i = 0; i < lines.length; i++) {
+const line = lines[i];
+const highlighterIndex = line.indexOf(highlighter);
+if (highlighterIndex !== -1) {
+highlightedLines.push(i + 1);
+result.modifiedText +=
+line.substring(0, highlighterIndex) +
+line.substring(highlighterIndex + highlighter.length) +
+"\n";
+} else {
+result.modifiedText += line + "\n";
+}
+}
+result.highlightedLines =
+fastn_utils.private.mergeNumbers(highlightedLines);
+return result;
+},
+getNodeValue(node) {
+return node.getNode().value;
+},
+getNodeCheckedState(node) {
+return node.getNode().checked;
+},
+setFullHeight() {
+if (!ssr) {
+document.body.style.height = `max(${document.documentElement.scrollHeight}px, 100%)`;
+}
+},
+resetFullHeight() {
+if (!ssr) {
+document.body.style.height = `100%`;
+}
+},
+highlightCode(codeElement, extraCodeData) {
+if (
+!ssr &&
+!fastn_utils.isNull(extraCodeData.language) &&
+!fastn_utils.isNull(extraCodeData.theme)
+) {
+Prism.highlightElement(codeElement);
+}
+},
+slugify(str) {
+return String(str)
+.normalize("NFKD")
+.replace(".", "-")
+.replace(/[\u0300-\u036f]/g, "")
+.trim()
+.toLowerCase()
+.replace(/[^a-z0-9 -]/g, "")
+.replace(/\s+/g, "-")
+.replace(/-+/g, "-");
+},
+getEventListeners(node) {
+return {
+onclick: node.onclick,
+onmouseleave: node.onmouseleave,
+onmouseenter: node.onmouseenter,
+oninput: node.oninput,
+onblur: node.onblur,
+onfocus: node.onfocus,
+};
+},
+flattenArray(arr) {
+return fastn_utils.private.flattenArray([arr]);
+},
+toSnakeCase(value) {
+return value
+.trim()
+.split("")
+.map((v, i) => {
+const lowercased = v.toLowerCase();
+if (v == " ") {
+return "_";
+}
+if (v != lowercased && i > 0) {
+return `_${lowercased}`;
+}
+return lowercased;
+})
+.join("");
+},
+escapeHtmlInCode(str) {
+return str.replace(/[<]/g, "&lt;");
+},
+escapeHtmlInMarkdown(str) {
+if (typeof str !== "string") {
+return str;
+}
+let result = "";
+let ch_map = {
+"<": "&lt;",
+">": "&gt;",
+"&": "&amp;",
+'"': "&quot;",
+"'": "&#39;",
+"/": "&#47;",
+};
+let foundBackTick = false;
+for (var i = 0; i < str.length; i++) {
+let current = str[i];
+if (current === "`") {
+foundBackTick = !foundBackTick;
+}
+if (
+foundBackTick ||
+(current === "/" && !(i > 0 && str[i - 1] === "<"))
+) {
+result += current;
+continue;
+}
+result += ch_map[current] ?? current;
+}
+return result;
+},
+getArgs(default_args, passed_args) {
+let args = default_args;
+for (var arg in passed_args) {
+if (!default_args.hasOwnProperty(arg)) {
+args[arg] = passed_args[arg];
+continue;
+}
+if (
+default_args.hasOwnProperty(arg) &&
+fastn_utils.getStaticValue(passed_args[arg]) !== undefined
+) {
+args[arg] = passed_args[arg];
+}
+}
+return args;
+},
+replaceBodyStyleAndChildren(newChildrenWrapper) {
+let styles = document.getElementById("styles");
+styles.innerHTML = fastn_dom.getClassesAsStringWithoutStyleTag();
+fastn_utils.private.replaceChildren(document.body, newChildrenWrapper);
+},
+};
+fastn_utils.private = {
+flattenArray(arr) {
+return arr.reduce((acc, item) => {
+return acc.concat(
+Array.isArray(item)
+? fastn_utils.private.flattenArray(item)
+: item,
+);
+}, []);
+},
+spaces(s) {
+let space_before = 0;
+for (let i = 0; i < s.length; i++) {
+if (s[i] !== " ") {
+space_before = i;
+break;
+}
+space_before = i + 1;
+}
+if (space_before === s.length) {
+return { space_before, space_after: 0 };
+}
+let space_after = 0;
+for (let i = s.length - 1; i >= 0; i--) {
+if (s[i] !== " ") {
+space_after = s.length - 1 - i;
+break;
+}
+space_after = i + 1;
+}
+return { space_before, space_after };
+},
+replace_last_occurrence(s, old_word, new_word) {
+if (!s.includes(old_word)) {
+return s;
+}
+const idx = s.lastIndexOf(old_word);
+return s.slice(0, idx) + new_word + s.slice(idx + old_word.length);
+},
+repeated_space(n) {
+return Array.from({ length: n }, () => " ").join("");
+},
+mergeNumbers(numbers) {
+if (numbers.length === 0) {
+return "";
+}
+const mergedRanges = [];
+let start = numbers[0];
+let end = numbers[0];
+for (let i = 1; i < numbers.length; i++) {
+if (numbers[i] === end + 1) {
+end = numbers[i];
+} else {
+if (start === end) {
+mergedRanges.push(start.toString());
+} else {
+mergedRanges.push(`${start}-${end}`);
+}
+start = end = numbers[i];
+}
+}
+if (start === end) {
+mergedRanges.push(start.toString());
+} else {
+mergedRanges.push(`${start}-${end}`);
+}
+return mergedRanges.join(",");
+},
+addUnderscoreToStart(text) {
+if (/^\d/.test(text)) {
+return "_" + text;
+}
+return text;
+},
+replaceChildren(parent, newChildrenWrapper) {
+var children = parent.children;
+for (var i = children.length - 1; i >= 0; i--) {
+var child = children[i];
+if (child.tagName === "DIV") {
+parent.removeChild(child);
+}
+}
+while (newChildrenWrapper.firstChild) {
+parent.appendChild(newChildrenWrapper.firstChild);
+}
+},
+setCookie(cookieName, cookieValue) {
+cookieName = fastn_utils.getStaticValue(cookieName);
+cookieValue = fastn_utils.getStaticValue(cookieValue);
+var expires = "";
+var expirationDays = 30;
+if (expirationDays) {
+var date = new Date();
+date.setTime(date.getTime() + expirationDays * 24 * 60 * 60 * 1000);
+expires = "; expires=" + date.toUTCString();
+}
+document.cookie =
+cookieName +
+"=" +
+encodeURIComponent(cookieValue) +
+expires +
+"; path=/";
+},
+getCookie(cookieName) {
+cookieName = fastn_utils.getStaticValue(cookieName);
+var name = cookieName + "=";
+var decodedCookie = decodeURIComponent(document.cookie);
+var cookieArray = decodedCookie.split(";");
+for (var i = 0; i < cookieArray.length; i++) {
+var cookie = cookieArray[i].trim();
+if (cookie.indexOf(name) === 0) {
+return cookie.substring(name.length, cookie.length);
+}
+}
+return "None";
+},
+};
+let fastnVirtual = {};
+let id_counter = 0;
+let ssr = false;
+let doubleBuffering = false;
+let hydrating = false;
+let hydrationFailed = false;
+let hydrationElements = [];
+class ClassList {
+#classes = [];
+add(item) {
+this.#classes.push(item);
+}
+remove(itemToRemove) {
+this.#classes.filter((item) => item !== itemToRemove);
+}
+toString() {
+return this.#classes.join(" ");
+}
+getClasses() {
+return this.#classes;
+}
+}
+class Node {
+id;
+#dataId;
+#tagName;
+#children;
+#attributes;
+constructor(id, tagName) {
+this.#tagName = tagName;
+this.#dataId = id;
+this.classList = new ClassList();
+this.#children = [];
+this.#attributes = {};
+this.innerHTML = "";
+this.style = {};
+this.onclick = null;
+this.id = null;
+}
+appendChild(c) {
+this.#children.push(c);
+}
+insertBefore(node, index) {
+this.#children.splice(index, 0, node);
+}
+getChildren() {
+return this.#children;
+}
+setAttribute(attribute, value) {
+this.#attributes[attribute] = value;
+}
+getAttribute(attribute) {
+return this.#attributes[attribute];
+}
+removeAttribute(attribute) {
+if (attribute in this.#attributes) delete this.#attributes[attribute];
+}
+updateTagName(tagName) {
+this.#tagName = tagName;
+}
+toHtmlAsString() {
+const openingTag = `<${
+            this.#tagName
+        }${this.getDataIdString()}${this.getIdString()}${this.getAttributesString()}${this.getClassString()}${this.getStyleString()}>`;
+const closingTag = `</${this.#tagName}>`;
+const innerHTML = this.innerHTML;
+const childNodes = this.#children
+.map((child) => child.toHtmlAsString())
+.join("");
+return `${openingTag}${innerHTML}${childNodes}${closingTag}`;
+}
+getDataIdString() {
+return ` data-id="${this.#dataId}"`;
+}
+getIdString() {
+return fastn_utils.isNull(this.id) ? "" : ` id="${this.id}"`;
+}
+getClassString() {
+const classList = this.classList.toString();
+return classList ? ` class="${classList}"` : "";
+}
+getStyleString() {
+const styleProperties = Object.entries(this.style)
+.map(([prop, value]) => `${prop}:${value}`)
+.join(";");
+return styleProperties ? ` style="${styleProperties}"` : "";
+}
+getAttributesString() {
+const nodeAttributes = Object.entries(this.#attributes)
+.map(([attribute, value]) => {
+if (value !== undefined && value !== null && value !== "") {
+return `${attribute}=\"${value}\"`;
+}
+return `${attribute}`;
+})
+.join(" ");
+return nodeAttributes ? ` ${nodeAttributes}` : "";
+}
+}
+class Document2 {
+createElement(tagName) {
+id_counter++;
+if (ssr) {
+return new Node(id_counter, tagName);
+}
+if (tagName === "body") {
+return window.document.body;
+}
+let element = this.#createElement(tagName);
+if (hydrating) {
+hydrationElements.push(element);
+}
+return element;
+}
+#createElement(tagName) {
+if (hydrating && !fastn_utils.isWrapperNode(tagName)) {
+let element = hydratedElement(tagName);
+if (element) {
+return element;
+}
+hydrationFailed = true;
+}
+if (fastn_utils.isWrapperNode(tagName)) {
+return window.document.createComment(fastn_dom.commentMessage);
+}
+if (fastn_utils.isCommentNode(tagName)) {
+return window.document.createComment(fastn_dom.commentMessage);
+}
+return window.document.createElement(tagName);
+}
+}
+const convertedTags = {
+div: ["a"],
+input: ["textarea"],
+};
+function hydratedElement(tagName) {
+let element = window.document.querySelector(`[data-id="${id_counter}"]`);
+if (!element) {
+return;
+}
+let ssrTagName = element.tagName.toLowerCase();
+if (fastn_utils.isCommentNode(tagName) || fastn_utils.isCommentNode(ssrTagName)) {
+if (tagName !== ssrTagName) {
+return;
+}
+let comment = window.document.createComment(fastn_dom.commentMessage);
+element.replaceWith(comment);
+return comment;
+}
+if (
+tagName !== ssrTagName &&
+!convertedTags[tagName]?.includes(ssrTagName)
+) {
+return;
+}
+return element;
+}
+function discardHydration(closures) {
+for (let [target, closure] of closures) {
+target.removeClosure(closure);
+}
+for (let element of hydrationElements) {
+if (element !== document.body) {
+element.remove();
+}
+}
+hydrationElements = [];
+document.body
+.querySelectorAll("[data-id]")
+.forEach((element) => element.remove());
+ftd.clickOutsideEvents = [];
+ftd.globalKeyEvents = [];
+ftd.globalKeySeqEvents = [];
+fastn_dom.webComponent = [];
+}
+fastnVirtual.document = new Document2();
+function addClosureToBreakpointWidth() {
+let closure = fastn.closureWithoutExecute(function () {
+let current = ftd.get_device();
+let lastDevice = ftd.device.get();
+if (current === lastDevice) {
+return;
+}
+console.log("last_device", lastDevice, "current_device", current);
+ftd.device.set(current);
+});
+ftd.breakpoint_width.addClosure(closure);
+}
+fastnVirtual.doubleBuffer = function (main) {
+addClosureToBreakpointWidth();
+let parent = document.createElement("div");
+let current_device = ftd.get_device();
+ftd.device = fastn.mutable(current_device);
+doubleBuffering = true;
+fastnVirtual.root = parent;
+main(parent);
+fastn_utils.replaceBodyStyleAndChildren(parent);
+doubleBuffering = false;
+fastnVirtual.root = document.body;
+};
+fastnVirtual.hydrate = function (main) {
+let ssr_device = fastnVirtual.ssrDevice;
+if (fastn_utils.isNull(ssr_device) || !document.body.hasAttribute("data-id")) {
+fastnVirtual.doubleBuffer(main);
+return;
+}
+let current_device = ftd.get_device();
+ftd.device = fastn.mutable(ssr_device);
+doubleBuffering = true;
+hydrating = true;
+hydrationFailed = false;
+id_counter = 0;
+fastn.recordedClosures = [];
+fastnVirtual.root = document.body;
+main(fastnVirtual.document.createElement("body"));
+hydrating = false;
+let closures = fastn.recordedClosures;
+fastn.recordedClosures = null;
+if (hydrationFailed) {
+discardHydration(closures);
+fastnVirtual.doubleBuffer(main);
+return;
+}
+hydrationElements = [];
+let styles = document.getElementById("styles");
+styles.innerHTML = fastn_dom.getClassesAsStringWithoutStyleTag();
+doubleBuffering = false;
+addClosureToBreakpointWidth();
+if (current_device !== ssr_device) {
+ftd.device.set(current_device);
+}
+};
+fastnVirtual.ssr = function (main) {
+ssr = true;
+let body = fastnVirtual.document.createElement("body");
+main(body);
+ssr = false;
+id_counter = 0;
+return body.toHtmlAsString() + fastn_dom.getClassesAsString();
+};
+const ftd = (function () {
+const exports = {};
+const riveNodes = {};
+const global = {};
+const onLoadListeners = new Set();
+let fastnLoaded = false;
+exports.global = global;
+exports.riveNodes = riveNodes;
+exports.is_empty = (value) => {
+value = fastn_utils.getFlattenStaticValue(value);
+return fastn_utils.isNull(value) || value.length === 0;
+};
+exports.len = (data) => {
+if (!!data && data instanceof fastn.mutableListClass) {
+if (data.getLength) return data.getLength();
+return -1;
+}
+if (!!data && data instanceof fastn.mutableClass) {
+let inner_data = data.get();
+return exports.len(inner_data);
+}
+if (!!data && data.length) {
+return data.length;
+}
+return -2;
+};
+const staticList = (list) => {
+list = fastn_utils.getStaticValue(list);
+return list.map((v) =>
+fastn_utils.getFlattenStaticValue(
+v instanceof Object && "item" in v ? v.item : v,
+),
+);
+};
+const clampedRange = (start, end, length) => {
+const clamp = (index) =>
+Math.max(0, Math.min(length, fastn_utils.getStaticValue(index)));
+start = clamp(start);
+return [start, Math.max(start, clamp(end))];
+};
+exports.str = {
+to_lowercase: (s) => fastn_utils.getStaticValue(s).toLowerCase(),
+to_uppercase: (s) => fastn_utils.getStaticValue(s).toUpperCase(),
+trim: (s) => fastn_utils.getStaticValue(s).trim(),
+contains: (s, pattern) =>
+fastn_utils
+.getStaticValue(s)
+.includes(fastn_utils.getStaticValue(pattern)),
+starts_with: (s, prefix) =>
+fastn_utils
+.getStaticValue(s)
+.startsWith(fastn_utils.getStaticValue(prefix)),
+ends_with: (s, suffix) =>
+fastn_utils
+.getStaticValue(s)
+.endsWith(fastn_utils.getStaticValue(suffix)),
+replace: (s, from, to) =>
+fastn_utils
+.getStaticValue(s)
+.replaceAll(
+fastn_utils.getStaticValue(from),
+fastn_utils.getStaticValue(to),
+),
+split: (s, separator) => {
+s = fastn_utils.getStaticValue(s);
+separator = fastn_utils.getStaticValue(separator);
+let parts = separator === "" ? Array.from(s) : s.split(separator);
+return fastn.mutableList(parts);
+},
+substring: (s, start, end) => {
+let chars = Array.from(fastn_utils.getStaticValue(s));
+return chars
+.slice(...clampedRange(start, end, chars.length))
+.join("");
+},
+};
+exports.list = {
+join: (list, separator) =>
+staticList(list).join(fastn_utils.getStaticValue(separator)),
+get: (list, index) => {
+list = staticList(list);
+index = fastn_utils.getStaticValue(index);
+if (!Number.isInteger(index) || index < 0 || index >= list.length) {
+throw new Error(
+`index ${index} is out of range for a list of length ${list.length}`,
+);
+}
+return list[index];
+},
+slice: (list, start, end) => {
+list = staticList(list);
+return fastn.mutableList(
+list.slice(...clampedRange(start, end, list.length)),
+);
+},
+contains: (list, value) =>
+staticList(list).includes(fastn_utils.getFlattenStaticValue(value)),
+reverse: (list) => fastn.mutableList(staticList(list).reverse()),
+};
+const MONTHS = [
+"January",
+"February",
+"March",
+"April",
+"May",
+"June",
+"July",
+"August",
+"September",
+"October",
+"November",
+"December",
+];
+const WEEKDAYS = [
+"Sunday",
+"Monday",
+"Tuesday",
+"Wednesday",
+"Thursday",
+"Friday",
+"Saturday",
+];
+const parseDate = (date) => {
+let match =
+/^(\d{4})-(\d{2})-(\d{2})(?:T(\d{2}):(\d{2}):(\d{2})(?:\.\d+)?(Z|[+-]\d{2}:\d{2})?)?$/.exec(
+date,
+);
+let invalid = new Error(
+`invalid date \`${date}\`, expected \`YYYY-MM-DD\` or \`YYYY-MM-DDTHH:MM:SS\``,
+);
+if (!match) {
+throw invalid;
+}
+let [year, month, day, hour, minute, second] = match
+.slice(1, 7)
+.map((v) => (v === undefined ? 0 : Number(v)));
+let utc = new Date(0);
+utc.setUTCFullYear(year, month - 1, day);
+utc.setUTCHours(hour, minute, second);
+if (
+utc.getUTCFullYear() !== year ||
+utc.getUTCMonth() !== month - 1 ||
+utc.getUTCDate() !== day ||
+hour > 23 ||
+minute > 59 ||
+second > 59
+) {
+throw invalid;
+}
+let zone = match[7];
+if (zone !== undefined && zone !== "Z") {
+let offset = Number(zone.slice(1, 3)) * 60 + Number(zone.slice(4, 6));
+utc = new Date(utc.getTime() - (zone[0] === "-" ? -offset : offset) * 60000);
+}
+return utc;
+};
+exports.date = {
+format: (date, pattern) => {
+date = parseDate(fastn_utils.getStaticValue(date));
+pattern = fastn_utils.getStaticValue(pattern);
+let hour = date.getUTCHours();
+let fields = {
+Y: String(date.getUTCFullYear()).padStart(4, "0"),
+y: String(date.getUTCFullYear() % 100).padStart(2, "0"),
+m: date.getUTCMonth() + 1,
+d: date.getUTCDate(),
+H: hour,
+I: hour % 12 === 0 ? 12 : hour % 12,
+M: String(date.getUTCMinutes()).padStart(2, "0"),
+S: String(date.getUTCSeconds()).padStart(2, "0"),
+p: hour < 12 ? "AM" : "PM",
+B: MONTHS[date.getUTCMonth()],
+b: MONTHS[date.getUTCMonth()].slice(0, 3),
+A: WEEKDAYS[date.getUTCDay()],
+a: WEEKDAYS[date.getUTCDay()].slice(0, 3),
+"%": "%",
+};
+let out = "";
+for (let i = 0; i < pattern.length; i++) {
+if (pattern[i] !== "%") {
+out += pattern[i];
+continue;
+}
+let pad = pattern[i + 1] !== "-";
+let specifier = pattern[pad ? i + 1 : i + 2];
+i += pad ? 1 : 2;
+let value = fields[specifier];
+if (value === undefined) {
+throw new Error(
+`unknown date format specifier \`%${specifier ?? ""}\` in \`${pattern}\``,
+);
+}
+out +=
+typeof value === "number" && pad
+? String(value).padStart(2, "0")
+: value;
+}
+return out;
+},
+};
+exports.copy_to_clipboard = (args) => {
+let text = args.a;
+if (text instanceof fastn.mutableClass)
+text = fastn_utils.getStaticValue(text);
+if (text.startsWith("\\", 0)) {
+text = text.substring(1);
+}
+if (!navigator.clipboard) {
+fallbackCopyTextToClipboard(text);
+return;
+}
+navigator.clipboard.writeText(text).then(
+function () {
+console.log("Async: Copying to clipboard was successful!");
+},
+function (err) {
+console.error("Async: Could not copy text: ", err);
+},
+);
+};
+exports.clean_code = (args) => args.a;
+exports.go_back = () => {
+window.history.back();
+};
+exports.set_rive_boolean = (args, node) => {
+if (!!args.rive) {
+let riveNode = riveNodes[`${args.rive}__${exports.device.get()}`];
+node = riveNode ? riveNode : node;
+}
+let riveConst = node.getExtraData().rive;
+const stateMachineName = riveConst.stateMachineNames[0];
+const inputs = riveConst.stateMachineInputs(stateMachineName);
+const bumpTrigger = inputs.find((i) => i.name === args.input);
+bumpTrigger.value = args.value;
+};
+exports.toggle_rive_boolean = (args, node) => {
+if (!!args.rive) {
+let riveNode = riveNodes[`${args.rive}__${exports.device.get()}`];
+node = riveNode ? riveNode : node;
+}
+let riveConst = node.getExtraData().rive;
+const stateMachineName = riveConst.stateMachineNames[0];
+const inputs = riveConst.stateMachineInputs(stateMachineName);
+const trigger = inputs.find((i) => i.name === args.input);
+trigger.value = !trigger.value;
+};
+exports.set_rive_integer = (args, node) => {
+if (!!args.rive) {
+let riveNode = riveNodes[`${args.rive}__${exports.device.get()}`];
+node = riveNode ? riveNode : node;
+}
+let riveConst = node.getExtraData().rive;
+const stateMachineName = riveConst.stateMachineNames[0];
+const inputs = riveConst.stateMachineInputs(stateMachineName);
+const trigger = inputs.find((i) => i.name === args.input);
+trigger.value = args.value;
+};
+exports.fire_rive = (args, node) => {
+if (!!args.rive) {
+let riveNode = riveNodes[`${args.rive}__${exports.device.get()}`];
+node = riveNode ? riveNode : node;
+}
+let riveConst = node.getExtraData().rive;
+const stateMachineName = riveConst.stateMachineNames[0];
+const inputs = riveConst.stateMachineInputs(stateMachineName);
+const trigger = inputs.find((i) => i.name === args.input);
+trigger.fire();
+};
+exports.play_rive = (args, node) => {
+if (!!args.rive) {
+let riveNode = riveNodes[`${args.rive}__${exports.device.get()}`];
+node = riveNode ? riveNode : node;
+}
+node.getExtraData().rive.play(args.input);
+};
+exports.pause_rive = (args, node) => {
+if (!!args.rive) {
+let riveNode = riveNodes[`${args.rive}__${exports.device.get()}`];
+node = riveNode ? riveNode : node;
+}
+node.getExtraData().rive.pause(args.input);
+};
+exports.toggle_play_rive = (args, node) => {
+if (!!args.rive) {
+let riveNode = riveNodes[`${args.rive}__${exports.device.get()}`];
+node = riveNode ? riveNode : node;
+}
+let riveConst = node.getExtraData().rive;
+riveConst.playingAnimationNames.includes(args.input)
+? riveConst.pause(args.input)
+: riveConst.play(args.input);
+};
+exports.get = (value, index) => {
+return fastn_utils.getStaticValue(
+fastn_utils.getterByKey(value, index),
+);
+};
+exports.component_data = (component) => {
+let attributesIndex = component.getAttribute(
+fastn_dom.webComponentArgument,
+);
+let attributes = fastn_dom.webComponent[attributesIndex];
+return Object.fromEntries(
+Object.entries(attributes).map(([k, v]) => {
+if (v instanceof fastn.mutableClass) {
+v = fastn.webComponentVariable.mutable(v);
+} else if (v instanceof fastn.mutableListClass) {
+v = fastn.webComponentVariable.mutableList(v);
+} else if (v instanceof fastn.recordInstanceClass) {
+v = fastn.webComponentVariable.record(v);
+} else {
+v = fastn.webComponentVariable.static(v);
+}
+return [k, v];
+}),
+);
+};
+exports.field_with_default_js = function (name, default_value) {
+let r = fastn.recordInstance();
+r.set("name", fastn_utils.getFlattenStaticValue(name));
+r.set("value", fastn_utils.getFlattenStaticValue(default_value));
+r.set("error", null);
+return r;
+};
+exports.append = function (list, item) {
+list.push(item);
+};
+exports.pop = function (list) {
+list.pop();
+};
+exports.insert_at = function (list, index, item) {
+list.insertAt(index, item);
+};
+exports.delete_at = function (list, index) {
+list.deleteAt(index);
+};
+exports.clear_all = function (list) {
+list.clearAll();
+};
+exports.clear = exports.clear_all;
+exports.set_list = function (list, value) {
+list.set(value);
+};
+exports.http = function (url, method, headers, ...body) {
+if (url instanceof fastn.mutableClass) url = url.get();
+if (method instanceof fastn.mutableClass) method = method.get();
+method = method.trim().toUpperCase();
+const init = {
+method,
+headers: { "Content-Type": "application/json" },
+};
+if (headers && headers instanceof fastn.recordInstanceClass) {
+Object.assign(init.headers, headers.toObject());
+}
+if (method !== "GET") {
+init.headers["Content-Type"] = "application/json";
+}
+if (
+body &&
+body instanceof fastn.recordInstanceClass &&
+method !== "GET"
+) {
+init.body = JSON.stringify(body.toObject());
+} else if (body && method !== "GET") {
+let json = body[0];
+if (
+body.length !== 1 ||
+(body[0].length === 2 && Array.isArray(body[0]))
+) {
+let new_json = {};
+for (let [header, value] of Object.entries(body)) {
+let [key, val] =
+value.length == 2 ? value : [header, value];
+new_json[key] = fastn_utils.getFlattenStaticValue(val);
+}
+json = new_json;
+}
+init.body = JSON.stringify(json);
+}
+let json;
+fetch(url, init)
+.then((res) => {
+if (!res.ok) {
+return new Error("[http]: Request failed: " + res);
+}
+return res.json();
+})
+.then((response) => {
+console.log("[http]: Response OK", response);
+if (response.redirect) {
+window.location.href = response.redirect;
+} else if (!!response && !!response.reload) {
+window.location.reload();
+} else {
+let data = {};
+if (!!response.errors) {
+for (let key of Object.keys(response.errors)) {
+let value = response.errors[key];
+if (Array.isArray(value)) {
+value = value.join(" ");
+key = key + "-error";
+}
+data[key] = value;
+}
+}
+if (!!response.data) {
+if (Object.keys(data).length !== 0) {
+console.log(
+"both .errors and .data are present in response, ignoring .data",
+);
+} else {
+data = response.data;
+}
+}
+console.log(response);
+for (let ftd_variable of Object.keys(data)) {
+window.ftd.set_value(ftd_variable, data[ftd_variable]);
+}
+}
+})
+.catch(console.error);
+return json;
+};
+exports.navigate = function (url, request_data) {
+let query_parameters = new URLSearchParams();
+if (request_data instanceof fastn.recordInstanceClass) {
+for (let [header, value] of Object.entries(
+request_data.toObject(),
+)) {
+let [key, val] = value.length === 2 ? value : [header, value];
+query_parameters.set(key, val);
+}
+}
+let query_string = query_parameters.toString();
+if (query_string) {
+window.location.href = url + "?" + query_parameters.toString();
+} else {
+window.location.href = url;
+}
+};
+exports.toggle_dark_mode = function () {
+const is_dark_mode = exports.get(exports.dark_mode);
+if (is_dark_mode) {
+enable_light_mode();
+} else {
+enable_dark_mode();
+}
+};
+exports.local_storage = {
+_get_key(key) {
+if (key instanceof fastn.mutableClass) {
+key = key.get();
+}
+const packageNamePrefix = __fastn_package_name__
+? `${__fastn_package_name__}_`
+: "";
+const snakeCaseKey = fastn_utils.toSnakeCase(key);
+return `${packageNamePrefix}${snakeCaseKey}`;
+},
+set(key, value) {
+key = this._get_key(key);
+value = fastn_utils.getFlattenStaticValue(value);
+localStorage.setItem(
+key,
+value && typeof value === "object"
+? JSON.stringify(value)
+: value,
+);
+},
+get(key) {
+key = this._get_key(key);
+if (ssr) {
+return;
+}
+const item = localStorage.getItem(key);
+if (!item) {
+return;
+}
+try {
+const obj = JSON.parse(item);
+return fastn_utils.staticToMutables(obj);
+} catch {
+return item;
+}
+},
+delete(key) {
+key = this._get_key(key);
+localStorage.removeItem(key);
+},
+};
+exports.on_load = (listener) => {
+if (typeof listener !== "function") {
+throw new Error("listener must be a function");
+}
+if (fastnLoaded) {
+listener();
+return;
+}
+onLoadListeners.add(listener);
+};
+exports.emit_on_load = () => {
+if (fastnLoaded) return;
+fastnLoaded = true;
+onLoadListeners.forEach((listener) => listener());
+};
+function legacyNameToJS(s) {
+let name = s.toString();
+if (name[0].charCodeAt(0) >= 48 && name[0].charCodeAt(0) <= 57) {
+name = "_" + name;
+}
+return name
+.replaceAll("#", "__")
+.replaceAll("-", "_")
+.replaceAll(":", "___")
+.replaceAll(",", "$")
+.replaceAll("\\", "/")
+.replaceAll("/", "_")
+.replaceAll(".", "_");
+}
+function getDocNameAndRemaining(s) {
+let part1 = "";
+let patternToSplitAt = s;
+const split1 = s.split("#");
+if (split1.length === 2) {
+part1 = split1[0] + "#";
+patternToSplitAt = split1[1];
+}
+const split2 = patternToSplitAt.split(".");
+if (split2.length === 2) {
+return [part1 + split2[0], split2[1]];
+} else {
+return [s, null];
+}
+}
+function isMutable(obj) {
+return (
+obj instanceof fastn.mutableClass ||
+obj instanceof fastn.mutableListClass ||
+obj instanceof fastn.recordInstanceClass
+);
+}
+exports.set_value = function (variable, value) {
+const [var_name, remaining] = getDocNameAndRemaining(variable);
+let name = legacyNameToJS(var_name);
+if (global[name] === undefined) {
+console.log(
+`[ftd-legacy]: ${variable} is not in global map, ignoring`,
+);
+return;
+}
+const mutable = global[name];
+if (!isMutable(mutable)) {
+console.log(`[ftd-legacy]: ${variable} is not a mutable, ignoring`);
+return;
+}
+if (remaining) {
+mutable.get(remaining).set(value);
+} else {
+let mutableValue = fastn_utils.staticToMutables(value);
+if (mutableValue instanceof fastn.mutableClass) {
+mutableValue = mutableValue.get();
+}
+mutable.set(mutableValue);
+}
+};
+exports.get_value = function (variable) {
+const [var_name, remaining] = getDocNameAndRemaining(variable);
+let name = legacyNameToJS(var_name);
+if (global[name] === undefined) {
+console.log(
+`[ftd-legacy]: ${variable} is not in global map, ignoring`,
+);
+return;
+}
+const value = global[name];
+if (isMutable(value)) {
+if (remaining) {
+return value.get(remaining);
+} else {
+return value.get();
+}
+} else {
+return value;
+}
+};
+exports.set_current_language = function (language) {
+language = fastn_utils.getStaticValue(language);
+fastn_utils.private.setCookie("fastn-lang", language);
+location.reload();
+};
+exports.get_current_language = function () {
+return fastn_utils.private.getCookie("fastn-lang");
+};
+exports.submit_form = function (url, ...args) {
+if (url instanceof fastn.mutableClass) url = url.get();
+let data = {};
+let arg_map = {};
+for (let i = 0, len = args.length; i < len; i += 1) {
+let obj = args[i];
+if (obj instanceof fastn.mutableClass) {
+obj = obj.get();
+}
+console.assert(obj instanceof fastn.recordInstanceClass);
+let name = obj.get("name").get();
+arg_map[name] = obj;
+obj.get("error").set(null);
+data[name] = fastn_utils.getFlattenStaticValue(obj.get("value"));
+}
+let init = {
+method: "POST",
+redirect: "error",
+credentials: "same-origin",
+headers: { "Content-Type": "application/json" },
+body: JSON.stringify(data),
+};
+console.log(url, data);
+fetch(url, init)
+.then((res) => {
+if (!res.ok) {
+return new Error("[http_post]: Request failed: " + res);
+}
+return res.json();
+})
+.then((response) => {
+console.log("[http]: Response OK", response);
+if (response.redirect) {
+window.location.href = response.redirect;
+} else if (!!response && !!response.reload) {
+window.location.reload();
+} else if (!!response.errors) {
+for (let key of Object.keys(response.errors)) {
+let obj = arg_map[key];
+if (!obj) {
+console.warn("found unknown key, ignoring: ", key);
+continue;
+}
+let error = response.errors[key];
+if (Array.isArray(error)) {
+error = error.join(" ");
+}
+obj.get("error").set(error);
+}
+} else if (!!response.data) {
+console.error("data not yet implemented");
+} else {
+console.error("found invalid response", response);
+}
+})
+.catch(console.error);
+};
+return exports;
+})();
+const len = ftd.len;
+const global = ftd.global;
+ftd.clickOutsideEvents = [];
+ftd.globalKeyEvents = [];
+ftd.globalKeySeqEvents = [];
+ftd.get_device = function () {
+const MOBILE_CLASS = "mobile";
+let width = window.innerWidth;
+let mobile_breakpoint = fastn_utils.getStaticValue(
+ftd.breakpoint_width.get("mobile"),
+);
+if (width <= mobile_breakpoint) {
+document.body.classList.add(MOBILE_CLASS);
+return fastn_dom.DeviceData.Mobile;
+}
+if (document.body.classList.contains(MOBILE_CLASS)) {
+document.body.classList.remove(MOBILE_CLASS);
+}
+return fastn_dom.DeviceData.Desktop;
+};
+ftd.post_init = function () {
+const DARK_MODE_COOKIE = "fastn-dark-mode";
+const COOKIE_SYSTEM_LIGHT = "system-light";
+const COOKIE_SYSTEM_DARK = "system-dark";
+const COOKIE_DARK_MODE = "dark";
+const COOKIE_LIGHT_MODE = "light";
+const DARK_MODE_CLASS = "dark";
+let last_device = ftd.device.get();
+window.onresize = function () {
+initialise_device();
+};
+function initialise_click_outside_events() {
+document.addEventListener("click", function (event) {
+ftd.clickOutsideEvents.forEach(([ftdNode, func]) => {
+let node = ftdNode.getNode();
+if (
+!!node &&
+node.style.display !== "none" &&
+!node.contains(event.target)
+) {
+func();
+}
+});
+});
+}
+function initialise_global_key_events() {
+let globalKeys = {};
+let buffer = [];
+let lastKeyTime = Date.now();
+document.addEventListener("keydown", function (event) {
+let eventKey = fastn_utils.getEventKey(event);
+globalKeys[eventKey] = true;
+const currentTime = Date.now();
+if (currentTime - lastKeyTime > 1000) {
+buffer = [];
+}
+lastKeyTime = currentTime;
+if (
+(event.target.nodeName === "INPUT" ||
+event.target.nodeName === "TEXTAREA") &&
+eventKey !== "ArrowDown" &&
+eventKey !== "ArrowUp" &&
+eventKey !== "ArrowRight" &&
+eventKey !== "ArrowLeft" &&
+event.target.nodeName === "INPUT" &&
+eventKey !== "Enter"
+) {
+return;
+}
+buffer.push(eventKey);
+ftd.globalKeyEvents.forEach(([_ftdNode, func, array]) => {
+let globalKeysPresent = array.reduce(
+(accumulator, currentValue) =>
+accumulator && !!globalKeys[currentValue],
+true,
+);
+if (
+globalKeysPresent &&
+buffer.join(",").includes(array.join(","))
+) {
+func();
+globalKeys[eventKey] = false;
+buffer = [];
+}
+return;
+});
+ftd.globalKeySeqEvents.forEach(([_ftdNode, func, array]) => {
+if (buffer.join(",").includes(array.join(","))) {
+func();
+globalKeys[eventKey] = false;
+buffer = [];
+}
+return;
+});
+});
+document.addEventListener("keyup", function (event) {
+globalKeys[fastn_utils.getEventKey(event)] = false;
+});
+}
+function initialise_device() {
+let current = ftd.get_device();
+if (current === last_device) {
+return;
+}
+console.log("last_device", last_device, "current_device", current);
+ftd.device.set(current);
+last_device = current;
+}
+window.enable_dark_mode = function () {
+ftd.dark_mode.set(true);
+ftd.follow_system_dark_mode.set(false);
+ftd.system_dark_mode.set(system_dark_mode());
+document.body.classList.add(DARK_MODE_CLASS);
+set_cookie(DARK_MODE_COOKIE, COOKIE_DARK_MODE);
+};
+window.enable_light_mode = function () {
+ftd.dark_mode.set(false);
+ftd.follow_system_dark_mode.set(false);
+ftd.system_dark_mode.set(system_dark_mode());
+if (document.body.classList.contains(DARK_MODE_CLASS)) {
+document.body.classList.remove(DARK_MODE_CLASS);
+}
+set_cookie(DARK_MODE_COOKIE, COOKIE_LIGHT_MODE);
+};
+window.enable_system_mode = function () {
+let systemMode = system_dark_mode();
+ftd.follow_system_dark_mode.set(true);
+ftd.system_dark_mode.set(systemMode);
+if (systemMode) {
+ftd.dark_mode.set(true);
+document.body.classList.add(DARK_MODE_CLASS);
+set_cookie(DARK_MODE_COOKIE, COOKIE_SYSTEM_DARK);
+} else {
+ftd.dark_mode.set(false);
+if (document.body.classList.contains(DARK_MODE_CLASS)) {
+document.body.classList.remove(DARK_MODE_CLASS);
+}
+set_cookie(DARK_MODE_COOKIE, COOKIE_SYSTEM_LIGHT);
+}
+};
+function set_cookie(name, value) {
+document.cookie = name + "=" + value + "; path=/";
+}
+function system_dark_mode() {
+return !!(
+window.matchMedia &&
+window.matchMedia("(prefers-color-scheme: dark)").matches
+);
+}
+function initialise_dark_mode() {
+update_dark_mode();
+start_watching_dark_mode_system_preference();
+}
+function get_cookie(name, def) {
+let regex = document.cookie.match(
+"(^|;)\\s*" + name + "\\s*=\\s*([^;]+)",
+);
+return regex !== null ? regex.pop() : def;
+}
+function update_dark_mode() {
+let current_dark_mode_cookie = get_cookie(
+DARK_MODE_COOKIE,
+COOKIE_SYSTEM_LIGHT,
+);
+switch (current_dark_mode_cookie) {
+case COOKIE_SYSTEM_LIGHT:
+case COOKIE_SYSTEM_DARK:
+window.enable_system_mode();
+break;
+case COOKIE_LIGHT_MODE:
+window.enable_light_mode();
+break;
+case COOKIE_DARK_MODE:
+window.enable_dark_mode();
+break;
+default:
+console_log("cookie value is wrong", current_dark_mode_cookie);
+window.enable_system_mode();
+}
+}
+function start_watching_dark_mode_system_preference() {
+window
+.matchMedia("(prefers-color-scheme: dark)")
+.addEventListener("change", update_dark_mode);
+}
+initialise_device();
+initialise_dark_mode();
+initialise_click_outside_events();
+initialise_global_key_events();
+fastn_utils.resetFullHeight();
+fastn_utils.setFullHeight();
+};
+window.ftd = ftd;
+
+ftd.toggle = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(!fastn_utils.getStaticValue(__args__.a));
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.integer_field_with_default = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+return (ftd.field_with_default_js(__args__.name, __args__.default));
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.decimal_field_with_default = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+return (ftd.field_with_default_js(__args__.name, __args__.default));
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.boolean_field_with_default = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+return (ftd.field_with_default_js(__args__.name, __args__.default));
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.string_field_with_default = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+return (ftd.field_with_default_js(__args__.name, __args__.default));
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.increment = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(fastn_utils.getStaticValue(__args__.a) + 1);
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.increment_by = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(fastn_utils.getStaticValue(__args__.a) + fastn_utils.getStaticValue(__args__.v));
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.decrement = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(fastn_utils.getStaticValue(__args__.a) - 1);
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.decrement_by = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(fastn_utils.getStaticValue(__args__.a) - fastn_utils.getStaticValue(__args__.v));
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.enable_light_mode = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+return (enable_light_mode());
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.enable_dark_mode = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+return (enable_dark_mode());
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.enable_system_mode = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+return (enable_system_mode());
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.set_bool = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(__args__.v);
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.set_boolean = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(__args__.v);
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.set_string = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(__args__.v);
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.set_integer = function (args) {
+let __fastn_super_package_name__ = __fastn_package_name__;
+__fastn_package_name__ = "www_amitu_com";
+try {
+let __args__ = fastn_utils.getArgs({
+}, args);
+let fastn_utils_val___args___a = fastn_utils.clone(__args__.v);
+if (fastn_utils_val___args___a instanceof fastn.mutableClass) {
+fastn_utils_val___args___a = fastn_utils_val___args___a.get();
+}
+if (!fastn_utils.setter(__args__.a, fastn_utils_val___args___a)) {
+__args__.a = fastn_utils_val___args___a;
+}
+} finally {
+__fastn_package_name__ = __fastn_super_package_name__;
+}
+}
+ftd.dark_mode = fastn.mutable(false);
+ftd.empty = "";
+ftd.space = " ";
+ftd.nbsp = "&nbsp;";
+ftd.non_breaking_space = "&nbsp;";
+ftd.system_dark_mode = fastn.mutable(false);
+ftd.follow_system_dark_mode = fastn.mutable(true);
+ftd.font_display = fastn.mutable("sans-serif");
+ftd.font_copy = fastn.mutable("sans-serif");
+ftd.font_code = fastn.mutable("sans-serif");
+ftd.default_types = function () {
+let record = fastn.recordInstance({
+});
+record.set("heading_large", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(50));
+record.set("line_height", fastn_dom.FontSize.Px(65));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(36));
+record.set("line_height", fastn_dom.FontSize.Px(54));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("heading_medium", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(38));
+record.set("line_height", fastn_dom.FontSize.Px(57));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(26));
+record.set("line_height", fastn_dom.FontSize.Px(40));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("heading_small", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(24));
+record.set("line_height", fastn_dom.FontSize.Px(31));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(22));
+record.set("line_height", fastn_dom.FontSize.Px(29));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("heading_hero", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(80));
+record.set("line_height", fastn_dom.FontSize.Px(104));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(48));
+record.set("line_height", fastn_dom.FontSize.Px(64));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("heading_tiny", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(20));
+record.set("line_height", fastn_dom.FontSize.Px(26));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(18));
+record.set("line_height", fastn_dom.FontSize.Px(24));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("copy_small", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(14));
+record.set("line_height", fastn_dom.FontSize.Px(24));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_copy);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(12));
+record.set("line_height", fastn_dom.FontSize.Px(16));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_copy);
+return record;
+}());
+return record;
+}());
+record.set("copy_regular", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(18));
+record.set("line_height", fastn_dom.FontSize.Px(30));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_copy);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(16));
+record.set("line_height", fastn_dom.FontSize.Px(24));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_copy);
+return record;
+}());
+return record;
+}());
+record.set("copy_large", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(22));
+record.set("line_height", fastn_dom.FontSize.Px(34));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_copy);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(18));
+record.set("line_height", fastn_dom.FontSize.Px(28));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_copy);
+return record;
+}());
+return record;
+}());
+record.set("fine_print", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(12));
+record.set("line_height", fastn_dom.FontSize.Px(16));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_code);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(12));
+record.set("line_height", fastn_dom.FontSize.Px(16));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_code);
+return record;
+}());
+return record;
+}());
+record.set("blockquote", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(16));
+record.set("line_height", fastn_dom.FontSize.Px(21));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_code);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(16));
+record.set("line_height", fastn_dom.FontSize.Px(21));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_code);
+return record;
+}());
+return record;
+}());
+record.set("source_code", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(18));
+record.set("line_height", fastn_dom.FontSize.Px(30));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_code);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(16));
+record.set("line_height", fastn_dom.FontSize.Px(21));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_code);
+return record;
+}());
+return record;
+}());
+record.set("button_small", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(14));
+record.set("line_height", fastn_dom.FontSize.Px(19));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(14));
+record.set("line_height", fastn_dom.FontSize.Px(19));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("button_medium", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(16));
+record.set("line_height", fastn_dom.FontSize.Px(21));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(16));
+record.set("line_height", fastn_dom.FontSize.Px(21));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("button_large", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(18));
+record.set("line_height", fastn_dom.FontSize.Px(24));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(18));
+record.set("line_height", fastn_dom.FontSize.Px(24));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("link", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(14));
+record.set("line_height", fastn_dom.FontSize.Px(19));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(14));
+record.set("line_height", fastn_dom.FontSize.Px(19));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("label_large", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(14));
+record.set("line_height", fastn_dom.FontSize.Px(19));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(14));
+record.set("line_height", fastn_dom.FontSize.Px(19));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+record.set("label_small", function () {
+let record = fastn.recordInstance({
+});
+record.set("desktop", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(12));
+record.set("line_height", fastn_dom.FontSize.Px(16));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+record.set("mobile", function () {
+let record = fastn.recordInstance({
+});
+record.set("size", fastn_dom.FontSize.Px(12));
+record.set("line_height", fastn_dom.FontSize.Px(16));
+record.set("letter_spacing", null);
+record.set("weight", 400);
+record.set("font_family", ftd.font_display);
+return record;
+}());
+return record;
+}());
+return record;
+}();
+ftd.default_colors = function () {
+let record = fastn.recordInstance({
+});
+record.set("background", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#e7e7e4");
+record.set("dark", "#18181b");
+return record;
+}());
+record.set("step_1", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#f3f3f3");
+record.set("dark", "#141414");
+return record;
+}());
+record.set("step_2", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#c9cece");
+record.set("dark", "#585656");
+return record;
+}());
+record.set("overlay", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "rgba(0, 0, 0, 0.8)");
+record.set("dark", "rgba(0, 0, 0, 0.8)");
+return record;
+}());
+record.set("code", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#F5F5F5");
+record.set("dark", "#21222C");
+return record;
+}());
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#434547");
+record.set("dark", "#434547");
+return record;
+}());
+record.set("border_strong", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#919192");
+record.set("dark", "#919192");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#584b42");
+record.set("dark", "#a8a29e");
+return record;
+}());
+record.set("text_strong", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#141414");
+record.set("dark", "#ffffff");
+return record;
+}());
+record.set("shadow", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#007f9b");
+record.set("dark", "#007f9b");
+return record;
+}());
+record.set("scrim", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#007f9b");
+record.set("dark", "#007f9b");
+return record;
+}());
+record.set("cta_primary", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#2dd4bf");
+record.set("dark", "#2dd4bf");
+return record;
+}());
+record.set("hover", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#2c9f90");
+record.set("dark", "#2c9f90");
+return record;
+}());
+record.set("pressed", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#2cc9b5");
+record.set("dark", "#2cc9b5");
+return record;
+}());
+record.set("disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "rgba(44, 201, 181, 0.1)");
+record.set("dark", "rgba(44, 201, 181, 0.1)");
+return record;
+}());
+record.set("focused", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#2cbfac");
+record.set("dark", "#2cbfac");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#2b8074");
+record.set("dark", "#2b8074");
+return record;
+}());
+record.set("border_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#65b693");
+record.set("dark", "#65b693");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#feffff");
+record.set("dark", "#feffff");
+return record;
+}());
+record.set("text_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#65b693");
+record.set("dark", "#65b693");
+return record;
+}());
+return record;
+}());
+record.set("cta_secondary", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#4fb2df");
+record.set("dark", "#4fb2df");
+return record;
+}());
+record.set("hover", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#40afe1");
+record.set("dark", "#40afe1");
+return record;
+}());
+record.set("pressed", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#4fb2df");
+record.set("dark", "#4fb2df");
+return record;
+}());
+record.set("disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "rgba(79, 178, 223, 0.1)");
+record.set("dark", "rgba(79, 178, 223, 0.1)");
+return record;
+}());
+record.set("focused", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#4fb1df");
+record.set("dark", "#4fb1df");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#209fdb");
+record.set("dark", "#209fdb");
+return record;
+}());
+record.set("border_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#65b693");
+record.set("dark", "#65b693");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#584b42");
+record.set("dark", "#ffffff");
+return record;
+}());
+record.set("text_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#65b693");
+record.set("dark", "#65b693");
+return record;
+}());
+return record;
+}());
+record.set("cta_tertiary", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#556375");
+record.set("dark", "#556375");
+return record;
+}());
+record.set("hover", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#c7cbd1");
+record.set("dark", "#c7cbd1");
+return record;
+}());
+record.set("pressed", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#3b4047");
+record.set("dark", "#3b4047");
+return record;
+}());
+record.set("disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "rgba(85, 99, 117, 0.1)");
+record.set("dark", "rgba(85, 99, 117, 0.1)");
+return record;
+}());
+record.set("focused", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#e0e2e6");
+record.set("dark", "#e0e2e6");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#e2e4e7");
+record.set("dark", "#e2e4e7");
+return record;
+}());
+record.set("border_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#65b693");
+record.set("dark", "#65b693");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#ffffff");
+record.set("dark", "#ffffff");
+return record;
+}());
+record.set("text_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#65b693");
+record.set("dark", "#65b693");
+return record;
+}());
+return record;
+}());
+record.set("cta_danger", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#1C1B1F");
+record.set("dark", "#1C1B1F");
+return record;
+}());
+record.set("hover", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#1C1B1F");
+record.set("dark", "#1C1B1F");
+return record;
+}());
+record.set("pressed", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#1C1B1F");
+record.set("dark", "#1C1B1F");
+return record;
+}());
+record.set("disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#1C1B1F");
+record.set("dark", "#1C1B1F");
+return record;
+}());
+record.set("focused", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#1C1B1F");
+record.set("dark", "#1C1B1F");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#1C1B1F");
+record.set("dark", "#1C1B1F");
+return record;
+}());
+record.set("border_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#feffff");
+record.set("dark", "#feffff");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#1C1B1F");
+record.set("dark", "#1C1B1F");
+return record;
+}());
+record.set("text_disabled", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#feffff");
+record.set("dark", "#feffff");
+return record;
+}());
+return record;
+}());
+record.set("accent", function () {
+let record = fastn.recordInstance({
+});
+record.set("primary", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#2dd4bf");
+record.set("dark", "#2dd4bf");
+return record;
+}());
+record.set("secondary", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#4fb2df");
+record.set("dark", "#4fb2df");
+return record;
+}());
+record.set("tertiary", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#c5cbd7");
+record.set("dark", "#c5cbd7");
+return record;
+}());
+return record;
+}());
+record.set("error", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#f5bdbb");
+record.set("dark", "#311b1f");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#c62a21");
+record.set("dark", "#c62a21");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#df2b2b");
+record.set("dark", "#df2b2b");
+return record;
+}());
+return record;
+}());
+record.set("success", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#e3f0c4");
+record.set("dark", "#405508ad");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#467b28");
+record.set("dark", "#479f16");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#3d741f");
+record.set("dark", "#3d741f");
+return record;
+}());
+return record;
+}());
+record.set("info", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#c4edfd");
+record.set("dark", "#15223a");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#205694");
+record.set("dark", "#1f6feb");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#205694");
+record.set("dark", "#205694");
+return record;
+}());
+return record;
+}());
+record.set("warning", function () {
+let record = fastn.recordInstance({
+});
+record.set("base", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#fbefba");
+record.set("dark", "#544607a3");
+return record;
+}());
+record.set("text", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#966220");
+record.set("dark", "#d07f19");
+return record;
+}());
+record.set("border", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#966220");
+record.set("dark", "#966220");
+return record;
+}());
+return record;
+}());
+record.set("custom", function () {
+let record = fastn.recordInstance({
+});
+record.set("one", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#ed753a");
+record.set("dark", "#ed753a");
+return record;
+}());
+record.set("two", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#f3db5f");
+record.set("dark", "#f3db5f");
+return record;
+}());
+record.set("three", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#8fdcf8");
+record.set("dark", "#8fdcf8");
+return record;
+}());
+record.set("four", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#7a65c7");
+record.set("dark", "#7a65c7");
+return record;
+}());
+record.set("five", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#eb57be");
+record.set("dark", "#eb57be");
+return record;
+}());
+record.set("six", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#ef8dd6");
+record.set("dark", "#ef8dd6");
+return record;
+}());
+record.set("seven", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#7564be");
+record.set("dark", "#7564be");
+return record;
+}());
+record.set("eight", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#d554b3");
+record.set("dark", "#d554b3");
+return record;
+}());
+record.set("nine", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#ec8943");
+record.set("dark", "#ec8943");
+return record;
+}());
+record.set("ten", function () {
+let record = fastn.recordInstance({
+});
+record.set("light", "#da7a4a");
+record.set("dark", "#da7a4a");
+return record;
+}());
+return record;
+}());
+return record;
+}();
+ftd.breakpoint_width = function () {
+let record = fastn.recordInstance({
+});
+record.set("mobile", 768);
+return record;
+}();
+ftd.device = fastn.mutable(fastn_dom.DeviceData.Mobile);
+let inherited = function () {
+let record = fastn.recordInstance({
+});
+record.set("colors", ftd.default_colors.getClone().setAndReturn("is_root", true));
+record.set("types", ftd.default_types.getClone().setAndReturn("is_root", true));
+return record;
+}();
\ No newline at end of file
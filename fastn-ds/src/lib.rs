@@ -5,11 +5,13 @@ extern crate self as fastn_ds;
 
 mod create_pool;
 pub mod http;
+mod pg;
 pub mod reqwest_util;
 mod utils;
 pub mod wasm;
 
 pub use create_pool::create_pool;
+pub use pg::is_pg_url;
 
 #[derive(Debug, Clone)]
 pub struct DocumentStore {
@@ -18,6 +20,18 @@ pub struct DocumentStore {
     root: Path,
 }
 
+/// A statement to run as part of `DocumentStore::sql_transaction`.
+#[derive(Debug)]
+pub enum SqlStatement {
+    /// One or more statements without parameters, like the body of a migration.
+    Batch(String),
+    /// A single statement, with `$1`, `$2` etc bound to `params`.
+    Execute {
+        query: String,
+        params: Vec<ft_sys_shared::SqliteRawValue>,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Path {
     path: camino::Utf8PathBuf,
@@ -189,11 +203,18 @@ impl DocumentStore {
                 .unwrap_or_else(|_| "fastn.sqlite".to_string()),
         };
 
-        if let Some(p) = self.pg_pools.get(db_url.as_str()) {
+        Ok(self.pg_pool(db_url.as_str()).await?)
+    }
+
+    pub async fn pg_pool(
+        &self,
+        db_url: &str,
+    ) -> Result<deadpool_postgres::Pool, deadpool_postgres::CreatePoolError> {
+        if let Some(p) = self.pg_pools.get(db_url) {
             return Ok(p.get().clone());
         }
 
-        let pool = fastn_ds::create_pool(db_url.as_str()).await?;
+        let pool = fastn_ds::create_pool(db_url).await?;
 
         fastn_ds::insert_or_update(&self.pg_pools, db_url.to_string(), pool.clone());
 
@@ -249,6 +270,10 @@ impl DocumentStore {
         query: &str,
         params: Vec<ft_sys_shared::SqliteRawValue>,
     ) -> Result<Vec<Vec<serde_json::Value>>, fastn_utils::SqlError> {
+        if fastn_ds::pg::is_pg_url(db_url) {
+            return fastn_ds::pg::query(&self.pg_pool(db_url).await?, query, params).await;
+        }
+
         let db_path = initialize_sqlite_db(db_url).await?;
         let conn = rusqlite::Connection::open_with_flags(
             db_path,
@@ -270,6 +295,10 @@ impl DocumentStore {
         query: &str,
         params: Vec<ft_sys_shared::SqliteRawValue>,
     ) -> Result<Vec<Vec<serde_json::Value>>, fastn_utils::SqlError> {
+        if fastn_ds::pg::is_pg_url(db_url) {
+            return fastn_ds::pg::execute(&self.pg_pool(db_url).await?, query, params).await;
+        }
+
         let db_path = initialize_sqlite_db(db_url).await?;
         let conn = rusqlite::Connection::open_with_flags(
             db_path,
@@ -287,20 +316,56 @@ impl DocumentStore {
         db_url: &str,
         query: &str,
     ) -> Result<Vec<Vec<serde_json::Value>>, fastn_utils::SqlError> {
+        if fastn_ds::pg::is_pg_url(db_url) {
+            fastn_ds::pg::batch(&self.pg_pool(db_url).await?, query).await?;
+        } else {
+            let db_path = initialize_sqlite_db(db_url).await?;
+            let conn = rusqlite::Connection::open_with_flags(
+                db_path,
+                rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
+            )
+            .map_err(fastn_utils::SqlError::Connection)?;
+
+            conn.execute_batch(query)
+                .map_err(fastn_utils::SqlError::Execute)?;
+        }
+
+        // we are sending 1 as processor has to return some value, this means this
+        // processor can only be used against integer type, and returned integer is
+        // always 1.
+        Ok(vec![vec![1.into()]])
+    }
+
+    /// Runs all `statements` in a single transaction, nothing is written if any of them fails.
+    pub async fn sql_transaction(
+        &self,
+        db_url: &str,
+        statements: Vec<SqlStatement>,
+    ) -> Result<(), fastn_utils::SqlError> {
+        if fastn_ds::pg::is_pg_url(db_url) {
+            return fastn_ds::pg::transaction(&self.pg_pool(db_url).await?, statements).await;
+        }
+
         let db_path = initialize_sqlite_db(db_url).await?;
-        let conn = rusqlite::Connection::open_with_flags(
+        let mut conn = rusqlite::Connection::open_with_flags(
             db_path,
             rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
         )
         .map_err(fastn_utils::SqlError::Connection)?;
-
-        conn.execute_batch(query)
+        let transaction = conn.transaction().map_err(fastn_utils::SqlError::Execute)?;
+
+        // if any statement fails the transaction is dropped, which rolls it back
+        for statement in statements {
+            match statement {
+                SqlStatement::Batch(query) => transaction.execute_batch(&query),
+                SqlStatement::Execute { query, params } => transaction
+                    .execute(&query, rusqlite::params_from_iter(params))
+                    .map(|_| ()),
+            }
             .map_err(fastn_utils::SqlError::Execute)?;
+        }
 
-        // we are sending 1 as processor has to return some value, this means this
-        // processor can only be used against integer type, and returned integer is
-        // always 1.
-        Ok(vec![vec![1.into()]])
+        transaction.commit().map_err(fastn_utils::SqlError::Execute)
     }
 
     pub fn root(&self) -> fastn_ds::Path {
@@ -474,7 +539,7 @@ impl DocumentStore {
                 body: req.body().to_vec(),
             },
             module,
-            self.pg_pools.clone(),
+            self.clone(),
             self.env("DATABASE_URL")
                 .await
                 .unwrap_or_else(|_| "fastn.sqlite".to_string()),
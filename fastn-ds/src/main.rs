@@ -17,10 +17,11 @@ async fn main() {
     )
     .unwrap();
 
-    let resp =
-        fastn_ds::wasm::process_http_request(req, module, Default::default(), "".to_string())
-            .await
-            .unwrap();
+    let ds = fastn_ds::DocumentStore::new(".", Default::default());
+
+    let resp = fastn_ds::wasm::process_http_request(req, module, ds, "".to_string())
+        .await
+        .unwrap();
 
     println!("{:?}", resp);
 }
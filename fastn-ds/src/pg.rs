@@ -0,0 +1,138 @@
+//! The postgres backend of `DocumentStore::sql_*`. Queries use `$1`, `$2` etc placeholders,
+//! which sqlite also understands, so callers can use the same query for both databases.
+
+pub fn is_pg_url(db_url: &str) -> bool {
+    db_url.starts_with("postgres://") || db_url.starts_with("postgresql://")
+}
+
+pub(crate) async fn query(
+    pool: &deadpool_postgres::Pool,
+    query: &str,
+    params: Vec<ft_sys_shared::SqliteRawValue>,
+) -> Result<Vec<Vec<serde_json::Value>>, fastn_utils::SqlError> {
+    let client = pool.get().await?;
+    let params = params.into_iter().map(Param).collect::<Vec<_>>();
+    client
+        .query(query, param_refs(&params).as_slice())
+        .await?
+        .iter()
+        .map(row_to_json)
+        .collect()
+}
+
+pub(crate) async fn execute(
+    pool: &deadpool_postgres::Pool,
+    query: &str,
+    params: Vec<ft_sys_shared::SqliteRawValue>,
+) -> Result<Vec<Vec<serde_json::Value>>, fastn_utils::SqlError> {
+    let client = pool.get().await?;
+    let params = params.into_iter().map(Param).collect::<Vec<_>>();
+    let count = client
+        .execute(query, param_refs(&params).as_slice())
+        .await?;
+    Ok(vec![vec![count.into()]])
+}
+
+pub(crate) async fn batch(
+    pool: &deadpool_postgres::Pool,
+    query: &str,
+) -> Result<(), fastn_utils::SqlError> {
+    let client = pool.get().await?;
+    client.batch_execute(query).await?;
+    Ok(())
+}
+
+pub(crate) async fn transaction(
+    pool: &deadpool_postgres::Pool,
+    statements: Vec<fastn_ds::SqlStatement>,
+) -> Result<(), fastn_utils::SqlError> {
+    let mut client = pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    // if any statement fails the transaction is dropped, which rolls it back
+    for statement in statements {
+        match statement {
+            fastn_ds::SqlStatement::Batch(query) => transaction.batch_execute(&query).await?,
+            fastn_ds::SqlStatement::Execute { query, params } => {
+                let params = params.into_iter().map(Param).collect::<Vec<_>>();
+                transaction
+                    .execute(&query, param_refs(&params).as_slice())
+                    .await?;
+            }
+        }
+    }
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+fn param_refs(params: &[Param]) -> Vec<&(dyn tokio_postgres::types::ToSql + Sync)> {
+    params
+        .iter()
+        .map(|p| p as &(dyn tokio_postgres::types::ToSql + Sync))
+        .collect()
+}
+
+/// Binds a sqlite value to whatever type postgres expects for the parameter, so an
+/// `Integer` can be passed for an `INTEGER` as well as a `BIGINT` column.
+#[derive(Debug)]
+struct Param(ft_sys_shared::SqliteRawValue);
+
+impl tokio_postgres::types::ToSql for Param {
+    fn to_sql(
+        &self,
+        ty: &tokio_postgres::types::Type,
+        out: &mut bytes::BytesMut,
+    ) -> Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        use tokio_postgres::types::Type;
+
+        match &self.0 {
+            ft_sys_shared::SqliteRawValue::Null => Ok(tokio_postgres::types::IsNull::Yes),
+            ft_sys_shared::SqliteRawValue::Integer(i) => match *ty {
+                Type::INT2 => i16::try_from(*i)?.to_sql(ty, out),
+                Type::INT4 => i32::try_from(*i)?.to_sql(ty, out),
+                Type::BOOL => (*i != 0).to_sql(ty, out),
+                Type::FLOAT8 => (*i as f64).to_sql(ty, out),
+                _ => i.to_sql(ty, out),
+            },
+            ft_sys_shared::SqliteRawValue::Real(f) => match *ty {
+                Type::FLOAT4 => (*f as f32).to_sql(ty, out),
+                _ => f.to_sql(ty, out),
+            },
+            ft_sys_shared::SqliteRawValue::Text(s) => s.to_sql(ty, out),
+            ft_sys_shared::SqliteRawValue::Blob(b) => b.to_sql(ty, out),
+        }
+    }
+
+    fn accepts(_ty: &tokio_postgres::types::Type) -> bool {
+        // the actual type check happens when the value is encoded
+        true
+    }
+
+    tokio_postgres::types::to_sql_checked!();
+}
+
+fn row_to_json(row: &tokio_postgres::Row) -> Result<Vec<serde_json::Value>, fastn_utils::SqlError> {
+    use tokio_postgres::types::Type;
+
+    let mut values = Vec::with_capacity(row.len());
+    for (i, column) in row.columns().iter().enumerate() {
+        let value = match *column.type_() {
+            Type::BOOL => row.try_get::<_, Option<bool>>(i)?.into(),
+            Type::INT2 => row.try_get::<_, Option<i16>>(i)?.into(),
+            Type::INT4 => row.try_get::<_, Option<i32>>(i)?.into(),
+            Type::INT8 => row.try_get::<_, Option<i64>>(i)?.into(),
+            Type::FLOAT4 => row.try_get::<_, Option<f32>>(i)?.into(),
+            Type::FLOAT8 => row.try_get::<_, Option<f64>>(i)?.into(),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                row.try_get::<_, Option<String>>(i)?.into()
+            }
+            Type::JSON | Type::JSONB => row
+                .try_get::<_, Option<serde_json::Value>>(i)?
+                .unwrap_or_default(),
+            _ => return Err(fastn_utils::SqlError::UnsupportedColumnType(i)),
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
@@ -1,15 +1,186 @@
+/// Blobs written by `hostn_tejar_write` are stored in this folder inside the package directory,
+/// one file per blob, named by the uppercase hex sha256 of its content.
+pub const TEJAR_DIR: &str = ".tejar";
+
+#[derive(serde::Serialize, serde::Deserialize, thiserror::Error, Debug, PartialEq)]
+pub enum TejarError {
+    #[error("invalid tejar id {0}")]
+    InvalidId(String),
+    #[error("tejar blob not found {0}")]
+    NotFound(String),
+    #[error("tejar blob {0} is corrupted")]
+    Corrupted(String),
+    #[error("tejar io error {0}")]
+    Io(String),
+}
+
+/// `hostn_tejar_write(ptr, len)`: the guest sends the raw bytes of the blob, and gets back a json
+/// encoded `Result<String, TejarError>`, where the string is the id to read the blob back with.
 pub async fn tejar_write(
-    _caller: wasmtime::Caller<'_, fastn_ds::wasm::Store>,
-    _ptr: i32,
-    _len: i32,
+    mut caller: wasmtime::Caller<'_, fastn_ds::wasm::Store>,
+    ptr: i32,
+    len: i32,
 ) -> wasmtime::Result<i32> {
-    unimplemented!()
+    let content = fastn_ds::wasm::helpers::get_bytes(ptr, len, &mut caller)?;
+    let res = caller.data().tejar_write(content.as_slice()).await;
+    fastn_ds::wasm::helpers::send_json(res, &mut caller).await
 }
 
+/// `hostn_tejar_read(ptr, len)`: the guest sends the id returned by `hostn_tejar_write`, and gets
+/// back a json encoded `Result<Vec<u8>, TejarError>`.
 pub async fn tejar_read(
-    _caller: wasmtime::Caller<'_, fastn_ds::wasm::Store>,
-    _ptr: i32,
-    _len: i32,
+    mut caller: wasmtime::Caller<'_, fastn_ds::wasm::Store>,
+    ptr: i32,
+    len: i32,
 ) -> wasmtime::Result<i32> {
-    unimplemented!()
+    let id = fastn_ds::wasm::helpers::get_str(ptr, len, &mut caller)?;
+    let res = caller.data().tejar_read(id.as_str()).await;
+    fastn_ds::wasm::helpers::send_json(res, &mut caller).await
+}
+
+impl fastn_ds::wasm::Store {
+    pub async fn tejar_write(&self, content: &[u8]) -> Result<String, TejarError> {
+        static TEMP_FILES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+        let id = tejar_id(content);
+        let path = tejar_path(id.as_str());
+
+        // content addressed, so a blob with this name is kept if it has this content, and is
+        // replaced if it does not, e.g. it was damaged on disk
+        if let Ok(existing) = self.ds.read_content(&path).await {
+            if tejar_id(existing.as_slice()) == id {
+                return Ok(id);
+            }
+        }
+
+        // the blob is written to a temporary file first and then renamed, so readers never see
+        // a half written blob, even if fastn stops in the middle of the write
+        let temp = tejar_path(
+            format!(
+                "{id}.{}.{}.tmp",
+                std::process::id(),
+                TEMP_FILES.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            )
+            .as_str(),
+        );
+        self.ds
+            .write_content(&temp, content)
+            .await
+            .map_err(|e| TejarError::Io(e.to_string()))?;
+        let (temp, path) = (
+            self.ds.root().join(temp.to_string()),
+            self.ds.root().join(path.to_string()),
+        );
+        if let Err(e) = self.ds.rename(&temp, &path).await {
+            self.ds.remove(&temp).await.ok();
+            return Err(TejarError::Io(e.to_string()));
+        }
+
+        Ok(id)
+    }
+
+    pub async fn tejar_read(&self, id: &str) -> Result<Vec<u8>, TejarError> {
+        if !is_valid_tejar_id(id) {
+            return Err(TejarError::InvalidId(id.to_string()));
+        }
+
+        let content = self
+            .ds
+            .read_content(&tejar_path(id))
+            .await
+            .map_err(|e| match e {
+                fastn_ds::ReadError::NotFound(_) => TejarError::NotFound(id.to_string()),
+                e => TejarError::Io(e.to_string()),
+            })?;
+
+        // a write may have been interrupted half way, never hand out such a blob
+        if tejar_id(content.as_slice()) != id {
+            return Err(TejarError::Corrupted(id.to_string()));
+        }
+
+        Ok(content)
+    }
+}
+
+fn tejar_id(content: &[u8]) -> String {
+    use sha2::digest::FixedOutput;
+    use sha2::Digest;
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(content);
+    format!("{:X}", hasher.finalize_fixed())
+}
+
+fn tejar_path(id: &str) -> fastn_ds::Path {
+    fastn_ds::Path::new(TEJAR_DIR).join(id)
+}
+
+/// The id ends up in a file path, so anything but a sha256 hex digest is rejected.
+fn is_valid_tejar_id(id: &str) -> bool {
+    id.len() == 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn tejar_id() {
+        assert_eq!(
+            super::tejar_id(b"hello"),
+            "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824"
+        );
+    }
+
+    #[test]
+    fn is_valid_tejar_id() {
+        assert!(super::is_valid_tejar_id(&super::tejar_id(b"hello")));
+        assert!(!super::is_valid_tejar_id(
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        ));
+        assert!(!super::is_valid_tejar_id("../FASTN.ftd"));
+        assert!(!super::is_valid_tejar_id(""));
+    }
+
+    #[tokio::test]
+    async fn write_and_read() {
+        let root = std::env::temp_dir().join(format!("fastn-tejar-{}", std::process::id()));
+        let root = camino::Utf8PathBuf::from_path_buf(root).unwrap();
+        let ds = fastn_ds::DocumentStore::new(&root, Default::default());
+        let store = fastn_ds::wasm::Store::new(
+            ft_sys_shared::Request {
+                uri: "/".to_string(),
+                method: "get".to_string(),
+                headers: vec![],
+                body: vec![],
+            },
+            ds,
+            "".to_string(),
+        );
+
+        let id = store.tejar_write(b"hello").await.unwrap();
+        assert_eq!(store.tejar_write(b"hello").await.unwrap(), id);
+        assert_eq!(store.tejar_read(id.as_str()).await.unwrap(), b"hello");
+        assert_eq!(
+            std::fs::read_dir(root.join(super::TEJAR_DIR))
+                .unwrap()
+                .count(),
+            1
+        );
+
+        // a damaged blob is reported, and replaced by the next write of its content
+        std::fs::write(root.join(super::TEJAR_DIR).join(id.as_str()), b"hel").unwrap();
+        assert_eq!(
+            store.tejar_read(id.as_str()).await,
+            Err(super::TejarError::Corrupted(id.clone()))
+        );
+        assert_eq!(store.tejar_write(b"hello").await.unwrap(), id);
+        assert_eq!(store.tejar_read(id.as_str()).await.unwrap(), b"hello");
+        assert_eq!(
+            store.tejar_read(super::tejar_id(b"missing").as_str()).await,
+            Err(super::TejarError::NotFound(super::tejar_id(b"missing")))
+        );
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
 }
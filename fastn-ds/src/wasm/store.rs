@@ -5,6 +5,8 @@ pub struct Store {
     pub sqlite: Option<std::sync::Arc<async_lock::Mutex<rusqlite::Connection>>>,
     pub response: Option<ft_sys_shared::Request>,
     pub db_url: String,
+    /// used by the `hostn_tejar_*` functions to store blobs inside the package directory
+    pub ds: fastn_ds::DocumentStore,
 }
 
 pub struct Conn {
@@ -12,18 +14,15 @@ pub struct Conn {
 }
 
 impl Store {
-    pub fn new(
-        req: ft_sys_shared::Request,
-        pg_pools: actix_web::web::Data<scc::HashMap<String, deadpool_postgres::Pool>>,
-        db_url: String,
-    ) -> Store {
+    pub fn new(req: ft_sys_shared::Request, ds: fastn_ds::DocumentStore, db_url: String) -> Store {
         Self {
             req,
             response: None,
             clients: Default::default(),
-            pg_pools,
+            pg_pools: ds.pg_pools.clone(),
             db_url,
             sqlite: None,
+            ds,
         }
     }
 }
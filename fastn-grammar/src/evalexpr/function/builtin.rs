@@ -2,6 +2,8 @@
 use regex::Regex;
 
 use fastn_grammar::evalexpr::{
+    error::EvalexprResult,
+    function::date::DateTime,
     value::{FloatType, IntType},
     EvalexprError, Function, Value, ValueType,
 };
@@ -45,6 +47,31 @@ macro_rules! int_function {
     };
 }
 
+fn two_strings(argument: &Value) -> EvalexprResult<(String, String)> {
+    let arguments = argument.as_fixed_len_tuple(2)?;
+    Ok((arguments[0].as_string()?, arguments[1].as_string()?))
+}
+
+/// The `start..end` range of a string or list of length `len`, both ends are clamped to the
+/// string or list.
+fn range(start: &Value, end: &Value, len: usize) -> EvalexprResult<(usize, usize)> {
+    let clamp = |index: IntType| index.clamp(0, len as IntType) as usize;
+    let start = clamp(start.as_int()?);
+    let end = clamp(end.as_int()?);
+    Ok((start, end.max(start)))
+}
+
+/// `value` as an item of `list::join`, converted to a string like javascript's `Array.join()`
+/// does, so `ftd.list.join` gives the same result in the browser.
+fn join_item(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Empty => String::new(),
+        Value::Tuple(items) => items.iter().map(join_item).collect::<Vec<_>>().join(","),
+        value => value.to_string(),
+    }
+}
+
 pub fn builtin_function(identifier: &str) -> Option<Function> {
     match identifier {
         // Log
@@ -207,6 +234,88 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         "str::from" => Some(Function::new(|argument| {
             Ok(Value::String(argument.to_string()))
         })),
+        "str::contains" => Some(Function::new(|argument| {
+            let (subject, pattern) = two_strings(argument)?;
+            Ok(Value::Boolean(subject.contains(pattern.as_str())))
+        })),
+        "str::starts_with" => Some(Function::new(|argument| {
+            let (subject, prefix) = two_strings(argument)?;
+            Ok(Value::Boolean(subject.starts_with(prefix.as_str())))
+        })),
+        "str::ends_with" => Some(Function::new(|argument| {
+            let (subject, suffix) = two_strings(argument)?;
+            Ok(Value::Boolean(subject.ends_with(suffix.as_str())))
+        })),
+        "str::replace" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let subject = arguments[0].as_string()?;
+            let from = arguments[1].as_string()?;
+            let to = arguments[2].as_string()?;
+            Ok(Value::from(subject.replace(from.as_str(), to.as_str())))
+        })),
+        "str::split" => Some(Function::new(|argument| {
+            let (subject, separator) = two_strings(argument)?;
+            // like javascript, an empty separator splits into characters
+            let parts: Vec<Value> = if separator.is_empty() {
+                subject
+                    .chars()
+                    .map(|c| Value::from(c.to_string()))
+                    .collect()
+            } else {
+                subject.split(separator.as_str()).map(Value::from).collect()
+            };
+            Ok(Value::Tuple(parts))
+        })),
+        "str::substring" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let subject: Vec<char> = arguments[0].as_string()?.chars().collect();
+            let (start, end) = range(&arguments[1], &arguments[2], subject.len())?;
+            Ok(Value::from(subject[start..end].iter().collect::<String>()))
+        })),
+        // List functions
+        "list::join" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let list = arguments[0].as_tuple()?;
+            let separator = arguments[1].as_string()?;
+            let items: Vec<String> = list.iter().map(join_item).collect();
+            Ok(Value::from(items.join(separator.as_str())))
+        })),
+        "list::get" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let list = arguments[0].as_tuple()?;
+            let index = arguments[1].as_int()?;
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| list.get(index).cloned())
+                .ok_or_else(|| {
+                    EvalexprError::CustomMessage(format!(
+                        "index {index} is out of range for a list of length {}",
+                        list.len()
+                    ))
+                })
+        })),
+        "list::slice" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(3)?;
+            let list = arguments[0].as_tuple()?;
+            let (start, end) = range(&arguments[1], &arguments[2], list.len())?;
+            Ok(Value::Tuple(list[start..end].to_vec()))
+        })),
+        "list::contains" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let list = arguments[0].as_tuple()?;
+            Ok(Value::Boolean(list.contains(&arguments[1])))
+        })),
+        "list::reverse" => Some(Function::new(|argument| {
+            let mut list = argument.as_tuple()?;
+            list.reverse();
+            Ok(Value::Tuple(list))
+        })),
+        // Date functions
+        "date::format" => Some(Function::new(|argument| {
+            let (date, pattern) = two_strings(argument)?;
+            let date = DateTime::parse(date.as_str())?;
+            Ok(Value::from(date.format(pattern.as_str())?))
+        })),
         #[cfg(feature = "rand")]
         "random" => Some(Function::new(|argument| {
             argument.as_empty()?;
@@ -222,3 +331,58 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fastn_grammar::evalexpr::{eval, Value};
+
+    #[track_caller]
+    fn e(expression: &str, expected: impl Into<Value>) {
+        assert_eq!(eval(expression), Ok(expected.into()));
+    }
+
+    #[test]
+    fn string_functions() {
+        e(r#"str::contains("fastn", "st")"#, true);
+        e(r#"str::starts_with("fastn", "fa")"#, true);
+        e(r#"str::ends_with("fastn", "fa")"#, false);
+        e(r#"str::replace("a-b-c", "-", "+")"#, "a+b+c");
+        e(
+            r#"str::split("a,b", ",")"#,
+            Value::Tuple(vec!["a".into(), "b".into()]),
+        );
+        e(
+            r#"str::split("ab", "")"#,
+            Value::Tuple(vec!["a".into(), "b".into()]),
+        );
+        e(r#"str::substring("héllo", 1, 3)"#, "él");
+        e(r#"str::substring("hello", 3, 10)"#, "lo");
+        e(r#"str::substring("hello", 3, 1)"#, "");
+    }
+
+    #[test]
+    fn list_functions() {
+        e(r#"list::join(("a", "b", "c"), ", ")"#, "a, b, c");
+        e(r#"list::join((1, 2.5, true, "a"), "-")"#, "1-2.5-true-a");
+        e(r#"list::get(("a", "b"), 1)"#, "b");
+        assert!(eval(r#"list::get(("a", "b"), 2)"#).is_err());
+        e(
+            "list::slice((1, 2, 3, 4), 1, 3)",
+            Value::Tuple(vec![2.into(), 3.into()]),
+        );
+        e("list::contains((1, 2), 2)", true);
+        e(
+            "list::reverse((1, 2, 3))",
+            Value::Tuple(vec![3.into(), 2.into(), 1.into()]),
+        );
+    }
+
+    #[test]
+    fn date_functions() {
+        e(
+            r#"date::format("2024-03-09T18:00:00Z", "%b %-d, %-I %p")"#,
+            "Mar 9, 6 PM",
+        );
+        assert!(eval(r#"date::format("09/03/2024", "%Y")"#).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+//! Dates for the `date::` builtin functions.
+//!
+//! Dates are strings in the ISO 8601 subset `YYYY-MM-DD`, optionally followed by a time
+//! `THH:MM:SS`, fractional seconds and a `Z` or `+HH:MM` offset. They are always formatted in
+//! UTC, so a page renders the same date on the server and in every browser. The `ftd.js`
+//! runtime has the same implementation, keep the two in sync.
+
+use fastn_grammar::evalexpr::{error::EvalexprResult, EvalexprError};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// A point in time, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DateTime {
+    year: i64,
+    /// 1 to 12
+    month: i64,
+    /// 1 to 31
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    /// 0 is Sunday
+    weekday: i64,
+}
+
+impl DateTime {
+    pub(crate) fn parse(date: &str) -> EvalexprResult<DateTime> {
+        let invalid = || {
+            EvalexprError::CustomMessage(format!(
+                "invalid date `{date}`, expected `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SS`"
+            ))
+        };
+        let (day_part, time_part) = match date.split_once('T') {
+            Some((day, time)) => (day, Some(time)),
+            None => (date, None),
+        };
+
+        let [year, month, day] = numbers(day_part, '-', [4, 2, 2]).ok_or_else(invalid)?;
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+
+        let (mut hour, mut minute, mut second, mut offset) = (0, 0, 0, 0);
+        if let Some(time) = time_part {
+            let (time, zone) = match time.find(['Z', '+', '-']) {
+                Some(i) => time.split_at(i),
+                None => (time, ""),
+            };
+            let time = match time.split_once('.') {
+                Some((time, fraction))
+                    if !fraction.is_empty() && fraction.chars().all(|c| c.is_ascii_digit()) =>
+                {
+                    time
+                }
+                Some(_) => return Err(invalid()),
+                None => time,
+            };
+            [hour, minute, second] = numbers(time, ':', [2, 2, 2]).ok_or_else(invalid)?;
+            if hour > 23 || minute > 59 || second > 59 {
+                return Err(invalid());
+            }
+            offset = match zone {
+                "" | "Z" => 0,
+                zone => {
+                    let [hours, minutes] = numbers(&zone[1..], ':', [2, 2]).ok_or_else(invalid)?;
+                    let offset = hours * 60 + minutes;
+                    if zone.starts_with('-') {
+                        -offset
+                    } else {
+                        offset
+                    }
+                }
+            };
+        }
+
+        let seconds =
+            days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second
+                - offset * 60;
+        Ok(DateTime::from_timestamp(seconds))
+    }
+
+    /// `seconds` since 1970-01-01T00:00:00Z.
+    fn from_timestamp(seconds: i64) -> DateTime {
+        let days = seconds.div_euclid(86400);
+        let seconds = seconds.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds % 3600 / 60,
+            second: seconds % 60,
+            // 1970-01-01 was a Thursday
+            weekday: (days + 4).rem_euclid(7),
+        }
+    }
+
+    /// Formats the date with `strftime` like specifiers: `%Y`, `%y`, `%m`, `%d`, `%H`, `%I`,
+    /// `%M`, `%S`, `%p`, `%B`, `%b`, `%A`, `%a` and `%%`. `%-m`, `%-d`, `%-H` and `%-I` are
+    /// not padded with zeros.
+    pub(crate) fn format(&self, pattern: &str) -> EvalexprResult<String> {
+        let hour12 = match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        let mut out = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let (pad, specifier) = match chars.next() {
+                Some('-') => (false, chars.next()),
+                specifier => (true, specifier),
+            };
+            let number = |n: i64| {
+                if pad {
+                    format!("{n:02}")
+                } else {
+                    n.to_string()
+                }
+            };
+            match specifier {
+                Some('Y') => out.push_str(format!("{:04}", self.year).as_str()),
+                Some('y') => out.push_str(format!("{:02}", self.year.rem_euclid(100)).as_str()),
+                Some('m') => out.push_str(number(self.month).as_str()),
+                Some('d') => out.push_str(number(self.day).as_str()),
+                Some('H') => out.push_str(number(self.hour).as_str()),
+                Some('I') => out.push_str(number(hour12).as_str()),
+                Some('M') => out.push_str(format!("{:02}", self.minute).as_str()),
+                Some('S') => out.push_str(format!("{:02}", self.second).as_str()),
+                Some('p') => out.push_str(if self.hour < 12 { "AM" } else { "PM" }),
+                Some('B') => out.push_str(MONTHS[self.month as usize - 1]),
+                Some('b') => out.push_str(&MONTHS[self.month as usize - 1][..3]),
+                Some('A') => out.push_str(WEEKDAYS[self.weekday as usize]),
+                Some('a') => out.push_str(&WEEKDAYS[self.weekday as usize][..3]),
+                Some('%') => out.push('%'),
+                specifier => {
+                    return Err(EvalexprError::CustomMessage(format!(
+                        "unknown date format specifier `%{}` in `{pattern}`",
+                        specifier.map(String::from).unwrap_or_default()
+                    )))
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Splits `text` at `separator` into parts of exactly `digits` digits each.
+fn numbers<const N: usize>(text: &str, separator: char, digits: [usize; N]) -> Option<[i64; N]> {
+    let mut result = [0; N];
+    let mut parts = text.split(separator);
+    for (i, digits) in digits.into_iter().enumerate() {
+        let part = parts.next()?;
+        if part.len() != digits || !part.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        result[i] = part.parse().ok()?;
+    }
+    match parts.next() {
+        Some(_) => None,
+        None => Some(result),
+    }
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01, see <https://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The inverse of `days_from_civil()`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    #[track_caller]
+    fn f(date: &str, pattern: &str, expected: &str) {
+        assert_eq!(
+            super::DateTime::parse(date)
+                .unwrap()
+                .format(pattern)
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn format() {
+        f("2024-02-29", "%Y-%m-%d %H:%M:%S", "2024-02-29 00:00:00");
+        f(
+            "2024-02-29",
+            "%A, %B %-d, %Y",
+            "Thursday, February 29, 2024",
+        );
+        f("1970-01-01T00:00:00Z", "%a %b %d %y", "Thu Jan 01 70");
+        f(
+            "2023-12-31T22:30:05-02:00",
+            "%Y-%m-%d %-I:%M %p",
+            "2024-01-01 12:30 AM",
+        );
+        f(
+            "2023-07-04T13:05:00.250+05:30",
+            "%d/%m/%Y %I:%M%p %%",
+            "04/07/2023 07:35AM %",
+        );
+        f(
+            "1969-12-31T23:59:59",
+            "%Y-%m-%d %H:%M:%S %A",
+            "1969-12-31 23:59:59 Wednesday",
+        );
+        f("0050-03-01", "%Y %y %A", "0050 50 Tuesday");
+    }
+
+    #[test]
+    fn invalid() {
+        for date in [
+            "2023-02-29",
+            "2023-13-01",
+            "2023-1-01",
+            "2023-01-01T24:00:00",
+            "2023-01-01T10:00",
+            "2023-01-01T10:00:00+0530",
+            "yesterday",
+        ] {
+            assert!(super::DateTime::parse(date).is_err(), "{date}");
+        }
+        assert!(super::DateTime::parse("2023-01-01")
+            .unwrap()
+            .format("%Q")
+            .is_err());
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt;
 use fastn_grammar::evalexpr::{error::EvalexprResult, value::Value};
 
 pub(crate) mod builtin;
+pub(crate) mod date;
 
 /// A helper trait to enable cloning through `Fn` trait objects.
 trait ClonableFn
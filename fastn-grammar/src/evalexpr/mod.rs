@@ -362,6 +362,18 @@
 //! | `str::to_uppercase`  | 1               | String                 | Returns the upper-case version of the string |
 //! | `str::trim`          | 1               | String                 | Strips whitespace from the start and the end of the string |
 //! | `str::from`          | >= 0            | Any                    | Returns passed value as string |
+//! | `str::contains`      | 2               | String, String         | Returns true if the first argument contains the second argument |
+//! | `str::starts_with`   | 2               | String, String         | Returns true if the first argument starts with the second argument |
+//! | `str::ends_with`     | 2               | String, String         | Returns true if the first argument ends with the second argument |
+//! | `str::replace`       | 3               | String, String, String | Returns the first argument with all occurrences of the second argument replaced by the third argument |
+//! | `str::split`         | 2               | String, String         | Splits the first argument at every occurrence of the second argument, an empty separator splits into characters |
+//! | `str::substring`     | 3               | String, Int, Int       | Returns the characters from the start index up to, but not including, the end index. Both indices are clamped to the string |
+//! | `list::join`         | 2               | Tuple, String          | Joins a tuple of strings with the separator in the second argument |
+//! | `list::get`          | 2               | Tuple, Int             | Returns the element at the index, throws an error if the index is out of range |
+//! | `list::slice`        | 3               | Tuple, Int, Int        | Returns the elements from the start index up to, but not including, the end index. Both indices are clamped to the tuple |
+//! | `list::contains`     | 2               | Tuple, Any             | Returns true if the tuple contains the second argument |
+//! | `list::reverse`      | 1               | Tuple                  | Returns the tuple in reverse order |
+//! | `date::format`       | 2               | String, String         | Formats an ISO 8601 date, like `2024-03-09` or `2024-03-09T18:00:00+05:30`, in UTC with `strftime` like specifiers: `%Y`, `%y`, `%m`, `%d`, `%H`, `%I`, `%M`, `%S`, `%p`, `%B`, `%b`, `%A`, `%a` and `%%`. `%-m`, `%-d`, `%-H` and `%-I` are not zero padded |
 //! | `bitand`             | 2               | Int                    | Computes the bitwise and of the given integers |
 //! | `bitor`              | 2               | Int                    | Computes the bitwise or of the given integers |
 //! | `bitxor`             | 2               | Int                    | Computes the bitwise xor of the given integers |
@@ -376,6 +388,10 @@
 //!
 //! The regex functions require the feature flag `regex_support`.
 //!
+//! The `str::`, `list::` and `date::` functions, except for `str::from` and the regex functions,
+//! are also implemented by the `ftd.js` runtime, where `str::trim` is `ftd.str.trim`. Both have to
+//! return the same values, so a page renders the same on the server and in the browser.
+//!
 //! ### Values
 //!
 //! Operators take values as arguments and produce values as results.
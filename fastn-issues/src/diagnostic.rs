@@ -0,0 +1,347 @@
+/// Errors in ftd documents, rendered with the lines of the document around the error, like
+///
+/// ```text
+/// error[E0004]: component `cards` not found
+///   --> index.ftd:20
+///    |
+/// 18 | -- integer card-count: 1
+/// 19 |
+/// 20 | -- cards: hello
+///    |    ^^^^^^^^^^^^
+///    |
+///    = help: The name is not defined in this document, or in the document it is imported from.
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub code: Code,
+    pub message: String,
+    /// The document the error is in, as named by the interpreter, e.g. `my-package/index/`.
+    pub doc_id: Option<String>,
+    /// 1-based, `0` if the error has no location.
+    pub line_number: usize,
+    /// The path and the content of the document the error is in, to show the offending lines.
+    pub source: Option<(String, String)>,
+}
+
+/// The kind of a diagnostic, every code but `Code::Other` has an explanation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Code {
+    Syntax,
+    InvalidSection,
+    InvalidKind,
+    NotFound,
+    InvalidValue,
+    Arguments,
+    InvalidAccess,
+    Expression,
+    Other,
+}
+
+/// Lines shown before and after the line of the error.
+const CONTEXT_LINES: usize = 2;
+
+impl Code {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Code::Syntax => "E0001",
+            Code::InvalidSection => "E0002",
+            Code::InvalidKind => "E0003",
+            Code::NotFound => "E0004",
+            Code::InvalidValue => "E0005",
+            Code::Arguments => "E0006",
+            Code::InvalidAccess => "E0007",
+            Code::Expression => "E0008",
+            Code::Other => "E0000",
+        }
+    }
+
+    /// `None` for `Code::Other`, it has no explanation.
+    pub fn explanation(&self) -> Option<&'static str> {
+        Some(match self {
+            Code::Syntax => "The document is not valid ftd, e.g. a section does not start with `-- ` or a header is repeated.",
+            Code::InvalidSection => "The section is valid ftd, but not a valid import, definition or invocation.",
+            Code::InvalidKind => "The type of a variable, field or argument is not a known type.",
+            Code::NotFound => "The name is not defined in this document, or in the document it is imported from.",
+            Code::InvalidValue => "The value can not be converted to the type it is assigned to.",
+            Code::Arguments => "The arguments passed do not match the arguments of the component, record or function.",
+            Code::InvalidAccess => "The thing is not accessible from here, e.g. it is private to its component.",
+            Code::Expression => "The expression could not be evaluated.",
+            Code::Other => return None,
+        })
+    }
+}
+
+impl Diagnostic {
+    pub fn new(code: Code, message: impl Into<String>) -> Self {
+        Diagnostic {
+            code,
+            message: message.into(),
+            doc_id: None,
+            line_number: 0,
+            source: None,
+        }
+    }
+
+    pub fn set_location(mut self, doc_id: &str, line_number: usize) -> Self {
+        self.doc_id = Some(doc_id.to_string());
+        self.line_number = line_number;
+        self
+    }
+
+    pub fn set_source(mut self, path: &str, source: &str) -> Self {
+        self.source = Some((path.to_string(), source.to_string()));
+        self
+    }
+
+    /// If the error is in the document `doc_id`, ignoring leading and trailing `/`.
+    pub fn is_in(&self, doc_id: &str) -> bool {
+        self.doc_id
+            .as_deref()
+            .is_some_and(|d| d.trim_matches('/') == doc_id.trim_matches('/'))
+    }
+
+    /// The error as a page for the browser, shown by `fastn serve` in place of the document.
+    pub fn to_html(&self) -> String {
+        let help = match self.code.explanation() {
+            Some(explanation) => format!(
+                "<p>{}: {}</p>",
+                self.code.as_str(),
+                escape_html(explanation)
+            ),
+            None => String::new(),
+        };
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n\
+            <body style=\"font-family: sans-serif; margin: 2em;\">\n<pre style=\"background: #f6f6f6; \
+            padding: 1em; overflow: auto;\">{}</pre>\n{help}\n</body>\n</html>\n",
+            escape_html(self.message.as_str()),
+            escape_html(self.to_string().as_str()),
+        )
+    }
+
+    fn location(&self) -> Option<String> {
+        let name = match (&self.source, &self.doc_id) {
+            (Some((path, _)), _) => path.as_str(),
+            (None, Some(doc_id)) => doc_id.as_str(),
+            (None, None) => return None,
+        };
+        Some(match self.line_number {
+            0 => name.to_string(),
+            l => format!("{name}:{l}"),
+        })
+    }
+
+    /// The lines around the error, with their line numbers, and the index of the line of the
+    /// error in them.
+    fn snippet(&self) -> Option<(Vec<(usize, &str)>, usize)> {
+        let (_, source) = self.source.as_ref()?;
+        let lines: Vec<&str> = source.split('\n').collect();
+        if self.line_number == 0 || self.line_number > lines.len() {
+            return None;
+        }
+
+        let start = self.line_number.saturating_sub(CONTEXT_LINES + 1);
+        let end = std::cmp::min(self.line_number + CONTEXT_LINES, lines.len());
+        let snippet = (start..end).map(|i| (i + 1, lines[i])).collect();
+        Some((snippet, self.line_number - 1 - start))
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "error[{}]: {}", self.code.as_str(), self.message)?;
+        let snippet = self.snippet();
+        let width = match &snippet {
+            Some((lines, _)) => lines.last().map_or(1, |(n, _)| n.to_string().len()),
+            None => 1,
+        };
+        let pad = " ".repeat(width);
+
+        if let Some(location) = self.location() {
+            writeln!(f, "{pad} --> {location}")?;
+        }
+        if let Some((lines, error_line)) = snippet {
+            writeln!(f, "{pad} |")?;
+            for (i, (number, line)) in lines.into_iter().enumerate() {
+                let line = line.trim_end();
+                match line {
+                    "" => writeln!(f, "{number:>width$} |")?,
+                    line => writeln!(f, "{number:>width$} | {line}")?,
+                }
+                if i == error_line {
+                    let content = line.trim_start();
+                    let indent = line.len() - content.len();
+                    writeln!(
+                        f,
+                        "{pad} | {}{}",
+                        " ".repeat(indent),
+                        "^".repeat(std::cmp::max(content.chars().count(), 1))
+                    )?;
+                }
+            }
+            writeln!(f, "{pad} |")?;
+        }
+        if let Some(explanation) = self.code.explanation() {
+            writeln!(f, "{pad} = help: {explanation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&ftd_p1::Error> for Diagnostic {
+    fn from(e: &ftd_p1::Error) -> Self {
+        let (message, doc_id, line_number) = match e {
+            ftd_p1::Error::SectionNotFound {
+                doc_id,
+                line_number,
+            } => ("section not found".to_string(), doc_id, line_number),
+            ftd_p1::Error::MoreThanOneCaption {
+                doc_id,
+                line_number,
+            } => ("more than one caption".to_string(), doc_id, line_number),
+            ftd_p1::Error::ParseError {
+                message,
+                doc_id,
+                line_number,
+            } => (message.to_string(), doc_id, line_number),
+            ftd_p1::Error::MoreThanOneHeader {
+                key,
+                doc_id,
+                line_number,
+            } => (format!("more than one `{key}` header"), doc_id, line_number),
+            ftd_p1::Error::HeaderNotFound {
+                key,
+                doc_id,
+                line_number,
+            } => (format!("header `{key}` not found"), doc_id, line_number),
+        };
+        Diagnostic::new(Code::Syntax, message).set_location(doc_id, *line_number)
+    }
+}
+
+impl From<&ftd_ast::Error> for Diagnostic {
+    fn from(e: &ftd_ast::Error) -> Self {
+        match e {
+            ftd_ast::Error::P1(e) => e.into(),
+            ftd_ast::Error::Parse {
+                message,
+                doc_id,
+                line_number,
+            } => Diagnostic::new(Code::InvalidSection, message).set_location(doc_id, *line_number),
+            ftd_ast::Error::ParseBool(e) => Diagnostic::new(Code::InvalidValue, e.to_string()),
+        }
+    }
+}
+
+impl From<&ftd::interpreter::Error> for Diagnostic {
+    fn from(e: &ftd::interpreter::Error) -> Self {
+        use ftd::interpreter::Error;
+
+        match e {
+            Error::P1Error(e) => e.into(),
+            Error::ASTError(e) => e.into(),
+            Error::OldP1Error(ftd::ftd2021::p1::Error::ParseError {
+                message,
+                doc_id,
+                line_number,
+            }) => Diagnostic::new(Code::Syntax, message).set_location(doc_id, *line_number),
+            Error::InvalidKind {
+                doc_id,
+                line_number,
+                message,
+            } => Diagnostic::new(Code::InvalidKind, message).set_location(doc_id, *line_number),
+            Error::ValueNotFound {
+                doc_id,
+                line_number,
+                message,
+            } => Diagnostic::new(Code::NotFound, message).set_location(doc_id, *line_number),
+            Error::ParseError {
+                message,
+                doc_id,
+                line_number,
+            } => Diagnostic::new(Code::InvalidValue, message).set_location(doc_id, *line_number),
+            Error::InvalidAccessError {
+                message,
+                line_number,
+            } => Diagnostic {
+                line_number: *line_number,
+                ..Diagnostic::new(Code::InvalidAccess, message)
+            },
+            Error::ParseIntError(e) => Diagnostic::new(Code::InvalidValue, e.to_string()),
+            Error::ParseFloatError(e) => Diagnostic::new(Code::InvalidValue, e.to_string()),
+            Error::ParseBoolError(e) => Diagnostic::new(Code::InvalidValue, e.to_string()),
+            Error::EvalexprError(e) => Diagnostic::new(Code::Expression, e.to_string()),
+            e => Diagnostic::new(Code::Other, e.to_string()),
+        }
+    }
+}
+
+impl From<&ftd_tc::Error> for Diagnostic {
+    fn from(e: &ftd_tc::Error) -> Self {
+        let code = match e {
+            ftd_tc::Error::Ast(e) => return e.into(),
+            ftd_tc::Error::NotAType { .. } => Code::InvalidKind,
+            ftd_tc::Error::NotFound { .. }
+            | ftd_tc::Error::NotAComponent { .. }
+            | ftd_tc::Error::NotAFunction { .. }
+            | ftd_tc::Error::UnknownVariant { .. } => Code::NotFound,
+            ftd_tc::Error::UnknownArgument { .. }
+            | ftd_tc::Error::MissingArgument { .. }
+            | ftd_tc::Error::NoCaptionOrBody { .. }
+            | ftd_tc::Error::UnknownField { .. }
+            | ftd_tc::Error::MissingField { .. }
+            | ftd_tc::Error::MutabilityMismatch { .. } => Code::Arguments,
+            ftd_tc::Error::TypeMismatch { .. } => Code::InvalidValue,
+        };
+        // the message of every error with a location follows it, e.g. `foo.ftd:2 -> message`
+        let message = e.to_string();
+        let message = message
+            .split_once(" -> ")
+            .map_or(message.as_str(), |(_, m)| m);
+        let diagnostic = Diagnostic::new(code, message);
+        match e.location() {
+            Some((doc_id, line_number)) => {
+                diagnostic.set_location(doc_id.logical.as_str(), line_number)
+            }
+            None => diagnostic,
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render() {
+        let d = super::Diagnostic::new(super::Code::NotFound, "component `cards` not found")
+            .set_location("foo/index/", 3)
+            .set_source(
+                "index.ftd",
+                "-- import: lib\n\n  -- cards: hello\n\n-- ftd.text: hi\n\n-- end: x\n",
+            );
+        assert_eq!(
+            d.to_string(),
+            "error[E0004]: component `cards` not found\n  \
+            --> index.ftd:3\n  \
+            |\n\
+            1 | -- import: lib\n\
+            2 |\n\
+            3 |   -- cards: hello\n  \
+            |   ^^^^^^^^^^^^^^^\n\
+            4 |\n\
+            5 | -- ftd.text: hi\n  \
+            |\n  \
+            = help: The name is not defined in this document, or in the document it is imported \
+            from.\n"
+        );
+
+        let d = super::Diagnostic::new(super::Code::Other, "io error");
+        assert_eq!(d.to_string(), "error[E0000]: io error\n");
+    }
+}
@@ -2,6 +2,7 @@
 
 extern crate self as fastn_issues;
 
+pub mod diagnostic;
 pub mod initialization;
 pub mod initialization_display;
 pub mod reporter;
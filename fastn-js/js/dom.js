@@ -875,7 +875,9 @@ class Node2 {
             this.#parent = parentOrSibiling;
             return;
         }
-        if (sibiling) {
+        if (hydrating && this.#node.parentNode === this.#parent) {
+            // the server rendered node is already in place
+        } else if (sibiling) {
             this.#parent.insertBefore(
                 this.#node,
                 fastn_utils.nextSibling(sibiling, this.#parent),
@@ -965,6 +967,8 @@ class Node2 {
     updateTagName(name) {
         if (ssr) {
             this.#node.updateTagName(name);
+        } else if (hydrating && this.#node.tagName.toLowerCase() === name) {
+            // the server already changed it
         } else {
             let newElement = document.createElement(name);
             newElement.innerHTML = this.#node.innerHTML;
@@ -994,8 +998,8 @@ class Node2 {
         if (node_kind === fastn_dom.ElementKind.Image) {
             let anchorElement = document.createElement("a");
             anchorElement.href = url;
+            this.#parent.replaceChild(anchorElement, this.#node);
             anchorElement.appendChild(this.#node);
-            this.#parent.appendChild(anchorElement);
             this.#node = anchorElement;
         } else {
             this.updateTagName("a");
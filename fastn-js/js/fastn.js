@@ -131,6 +131,7 @@ const fastn = (function (fastn) {
 
         addClosure(closure) {
             this.#closures.push(closure);
+            fastn.recordClosure(this, closure);
         }
 
         removeClosure(closure) {
@@ -177,6 +178,7 @@ const fastn = (function (fastn) {
 
         addClosure(closure) {
             this.#closures.push(closure);
+            fastn.recordClosure(this, closure);
         }
 
         removeClosure(closure) {
@@ -227,6 +229,11 @@ const fastn = (function (fastn) {
 
         addClosure(closure) {
             this.#closures.push(closure);
+            fastn.recordClosure(this, closure);
+        }
+
+        removeClosure(closure) {
+            this.#closures = this.#closures.filter((c) => c !== closure);
         }
 
         unlinkNode(node) {
@@ -354,6 +361,16 @@ const fastn = (function (fastn) {
         return new Closure(func, false);
     };
 
+    // While this is a list, the closures added to mutables are also pushed to it, as
+    // `[mutable, closure]`, so they can be removed again, see `fastnVirtual.hydrate`.
+    fastn.recordedClosures = null;
+
+    fastn.recordClosure = function (target, closure) {
+        if (fastn.recordedClosures !== null) {
+            fastn.recordedClosures.push([target, closure]);
+        }
+    };
+
     fastn.formula = function (deps, func) {
         let closure = fastn.closure(func);
         let mutable = new Mutable(closure.get());
@@ -432,6 +449,11 @@ const fastn = (function (fastn) {
 
         addClosure(closure) {
             this.#closures.push(closure);
+            fastn.recordClosure(this, closure);
+        }
+
+        removeClosure(closure) {
+            this.#closures = this.#closures.filter((c) => c !== closure);
         }
 
         unlinkNode(node) {
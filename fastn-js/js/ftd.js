@@ -33,6 +33,196 @@ const ftd = (function () {
         return -2;
     };
 
+    // The `str::`, `list::` and `date::` builtins of the expression language, `str::trim(a)`
+    // is `ftd.str.trim(a)` here. They have to return what the builtins in
+    // `fastn_grammar::evalexpr::function::builtin` return on the server.
+    const staticList = (list) => {
+        list = fastn_utils.getStaticValue(list);
+        return list.map((v) =>
+            fastn_utils.getFlattenStaticValue(
+                v instanceof Object && "item" in v ? v.item : v,
+            ),
+        );
+    };
+
+    // `start..end` clamped to `length`, like `range()` in the builtins
+    const clampedRange = (start, end, length) => {
+        const clamp = (index) =>
+            Math.max(0, Math.min(length, fastn_utils.getStaticValue(index)));
+        start = clamp(start);
+        return [start, Math.max(start, clamp(end))];
+    };
+
+    exports.str = {
+        to_lowercase: (s) => fastn_utils.getStaticValue(s).toLowerCase(),
+        to_uppercase: (s) => fastn_utils.getStaticValue(s).toUpperCase(),
+        trim: (s) => fastn_utils.getStaticValue(s).trim(),
+        contains: (s, pattern) =>
+            fastn_utils
+                .getStaticValue(s)
+                .includes(fastn_utils.getStaticValue(pattern)),
+        starts_with: (s, prefix) =>
+            fastn_utils
+                .getStaticValue(s)
+                .startsWith(fastn_utils.getStaticValue(prefix)),
+        ends_with: (s, suffix) =>
+            fastn_utils
+                .getStaticValue(s)
+                .endsWith(fastn_utils.getStaticValue(suffix)),
+        replace: (s, from, to) =>
+            fastn_utils
+                .getStaticValue(s)
+                .replaceAll(
+                    fastn_utils.getStaticValue(from),
+                    fastn_utils.getStaticValue(to),
+                ),
+        split: (s, separator) => {
+            s = fastn_utils.getStaticValue(s);
+            separator = fastn_utils.getStaticValue(separator);
+            // split by code points, not UTF-16 units, like rust's `chars()`
+            let parts = separator === "" ? Array.from(s) : s.split(separator);
+            return fastn.mutableList(parts);
+        },
+        substring: (s, start, end) => {
+            let chars = Array.from(fastn_utils.getStaticValue(s));
+            return chars
+                .slice(...clampedRange(start, end, chars.length))
+                .join("");
+        },
+    };
+
+    exports.list = {
+        join: (list, separator) =>
+            staticList(list).join(fastn_utils.getStaticValue(separator)),
+        get: (list, index) => {
+            list = staticList(list);
+            index = fastn_utils.getStaticValue(index);
+            if (!Number.isInteger(index) || index < 0 || index >= list.length) {
+                throw new Error(
+                    `index ${index} is out of range for a list of length ${list.length}`,
+                );
+            }
+            return list[index];
+        },
+        slice: (list, start, end) => {
+            list = staticList(list);
+            return fastn.mutableList(
+                list.slice(...clampedRange(start, end, list.length)),
+            );
+        },
+        contains: (list, value) =>
+            staticList(list).includes(fastn_utils.getFlattenStaticValue(value)),
+        reverse: (list) => fastn.mutableList(staticList(list).reverse()),
+    };
+
+    const MONTHS = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    const WEEKDAYS = [
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+    ];
+
+    // Same as `DateTime::parse()` in `fastn_grammar::evalexpr::function::date`: dates without
+    // an offset are in UTC, not in the timezone of the browser.
+    const parseDate = (date) => {
+        let match =
+            /^(\d{4})-(\d{2})-(\d{2})(?:T(\d{2}):(\d{2}):(\d{2})(?:\.\d+)?(Z|[+-]\d{2}:\d{2})?)?$/.exec(
+                date,
+            );
+        let invalid = new Error(
+            `invalid date \`${date}\`, expected \`YYYY-MM-DD\` or \`YYYY-MM-DDTHH:MM:SS\``,
+        );
+        if (!match) {
+            throw invalid;
+        }
+        let [year, month, day, hour, minute, second] = match
+            .slice(1, 7)
+            .map((v) => (v === undefined ? 0 : Number(v)));
+        // `Date.UTC()` would map years 0 to 99 to 1900 to 1999
+        let utc = new Date(0);
+        utc.setUTCFullYear(year, month - 1, day);
+        utc.setUTCHours(hour, minute, second);
+        // invalid dates roll over, e.g. `2023-02-29` to March 1st
+        if (
+            utc.getUTCFullYear() !== year ||
+            utc.getUTCMonth() !== month - 1 ||
+            utc.getUTCDate() !== day ||
+            hour > 23 ||
+            minute > 59 ||
+            second > 59
+        ) {
+            throw invalid;
+        }
+        let zone = match[7];
+        if (zone !== undefined && zone !== "Z") {
+            let offset = Number(zone.slice(1, 3)) * 60 + Number(zone.slice(4, 6));
+            utc = new Date(utc.getTime() - (zone[0] === "-" ? -offset : offset) * 60000);
+        }
+        return utc;
+    };
+
+    exports.date = {
+        format: (date, pattern) => {
+            date = parseDate(fastn_utils.getStaticValue(date));
+            pattern = fastn_utils.getStaticValue(pattern);
+            let hour = date.getUTCHours();
+            let fields = {
+                Y: String(date.getUTCFullYear()).padStart(4, "0"),
+                y: String(date.getUTCFullYear() % 100).padStart(2, "0"),
+                m: date.getUTCMonth() + 1,
+                d: date.getUTCDate(),
+                H: hour,
+                I: hour % 12 === 0 ? 12 : hour % 12,
+                M: String(date.getUTCMinutes()).padStart(2, "0"),
+                S: String(date.getUTCSeconds()).padStart(2, "0"),
+                p: hour < 12 ? "AM" : "PM",
+                B: MONTHS[date.getUTCMonth()],
+                b: MONTHS[date.getUTCMonth()].slice(0, 3),
+                A: WEEKDAYS[date.getUTCDay()],
+                a: WEEKDAYS[date.getUTCDay()].slice(0, 3),
+                "%": "%",
+            };
+            let out = "";
+            for (let i = 0; i < pattern.length; i++) {
+                if (pattern[i] !== "%") {
+                    out += pattern[i];
+                    continue;
+                }
+                let pad = pattern[i + 1] !== "-";
+                let specifier = pattern[pad ? i + 1 : i + 2];
+                i += pad ? 1 : 2;
+                let value = fields[specifier];
+                if (value === undefined) {
+                    throw new Error(
+                        `unknown date format specifier \`%${specifier ?? ""}\` in \`${pattern}\``,
+                    );
+                }
+                out +=
+                    typeof value === "number" && pad
+                        ? String(value).padStart(2, "0")
+                        : value;
+            }
+            return out;
+        },
+    };
+
     exports.copy_to_clipboard = (args) => {
         let text = args.a;
         if (text instanceof fastn.mutableClass)
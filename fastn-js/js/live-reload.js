@@ -0,0 +1,14 @@
+(function () {
+    if (!window.EventSource) {
+        return;
+    }
+    // `fastn serve --live-reload` sends a `reload` event on this endpoint
+    // whenever a file in the package changes.
+    let source = new EventSource("/-/live-reload/");
+    source.onmessage = function (event) {
+        if (event.data === "reload") {
+            source.close();
+            window.location.reload();
+        }
+    };
+})();
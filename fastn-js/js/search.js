@@ -0,0 +1,105 @@
+// Searches the index `fastn build` writes to `-/search-index.json`, for the `fastn/search`
+// module. Searches like `fastn_core::search::Index::search()` does, keep the two in sync.
+const fastn_search = (function () {
+    const exports = {};
+    const DEFAULT_LIMIT = 10;
+
+    let index = null;
+    let latest = 0;
+
+    function base() {
+        return document.querySelector("base")
+            ? document.baseURI
+            : location.origin + "/";
+    }
+
+    function terms(text) {
+        return text
+            .split(/[^\p{Alphabetic}\p{N}]+/u)
+            .filter((t) => [...t].length >= 2)
+            .map((t) => t.toLowerCase());
+    }
+
+    function loadIndex() {
+        if (index === null) {
+            index = fetch(new URL("-/search-index.json", base()))
+                .then((response) => {
+                    if (!response.ok) {
+                        throw new Error(`search index: ${response.status}`);
+                    }
+                    return response.json();
+                })
+                .catch((e) => {
+                    // try again on the next search
+                    index = null;
+                    throw e;
+                });
+        }
+        return index;
+    }
+
+    exports.search = function (index, query, limit = DEFAULT_LIMIT) {
+        const query_terms = terms(query);
+        let scores = null;
+        query_terms.forEach((term, i) => {
+            const is_last = i + 1 === query_terms.length;
+            const term_scores = new Map();
+            for (const [t, postings] of Object.entries(index.terms)) {
+                if (t !== term && !(is_last && t.startsWith(term))) {
+                    continue;
+                }
+                for (let p = 0; p < postings.length; p += 2) {
+                    const page = postings[p];
+                    term_scores.set(
+                        page,
+                        (term_scores.get(page) || 0) + postings[p + 1],
+                    );
+                }
+            }
+            if (scores === null) {
+                scores = term_scores;
+                return;
+            }
+            const both = new Map();
+            for (const [page, score] of scores) {
+                if (term_scores.has(page)) {
+                    both.set(page, score + term_scores.get(page));
+                }
+            }
+            scores = both;
+        });
+
+        return [...(scores || new Map())]
+            .sort((a, b) => b[1] - a[1] || a[0] - b[0])
+            .slice(0, limit)
+            .filter(([page]) => index.pages[page] !== undefined)
+            .map(([page, score]) => ({ ...index.pages[page], score }));
+    };
+
+    // Sets `list`, a `fastn/search` result list, to the pages matching `q`.
+    exports.query = function (q, list, limit = DEFAULT_LIMIT) {
+        q = fastn_utils.getFlattenStaticValue(q) || "";
+        const current = ++latest;
+        if (terms(q).length === 0) {
+            list.set(fastn.mutableList([]));
+            return;
+        }
+        loadIndex()
+            .then((index) => {
+                if (current !== latest) {
+                    return;
+                }
+                const results = exports.search(index, q, limit).map((r) =>
+                    fastn.recordInstance({
+                        url: new URL(r.url.replace(/^\//, ""), base()).pathname,
+                        title: r.title,
+                        excerpt: r.excerpt,
+                    }),
+                );
+                list.set(fastn.mutableList(results));
+            })
+            .catch((e) => console.error(e));
+    };
+
+    return exports;
+})();
@@ -3,6 +3,13 @@ let fastnVirtual = {};
 let id_counter = 0;
 let ssr = false;
 let doubleBuffering = false;
+// While hydrating, elements the server rendered (see `fastnVirtual.ssr`) are reused instead of
+// creating new ones. `createElement` is called in the same order on both sides, so the element
+// for the nth call is the one with `data-id="n"`.
+let hydrating = false;
+let hydrationFailed = false;
+// The elements returned by `createElement` while hydrating, removed if hydration fails.
+let hydrationElements = [];
 
 class ClassList {
     #classes = [];
@@ -125,6 +132,22 @@ class Document2 {
             return window.document.body;
         }
 
+        let element = this.#createElement(tagName);
+        if (hydrating) {
+            hydrationElements.push(element);
+        }
+        return element;
+    }
+
+    #createElement(tagName) {
+        if (hydrating && !fastn_utils.isWrapperNode(tagName)) {
+            let element = hydratedElement(tagName);
+            if (element) {
+                return element;
+            }
+            hydrationFailed = true;
+        }
+
         if (fastn_utils.isWrapperNode(tagName)) {
             return window.document.createComment(fastn_dom.commentMessage);
         }
@@ -135,6 +158,63 @@ class Document2 {
     }
 }
 
+// The tags an element may already have on the server, other than the one it is created with,
+// because it is changed after it is created: `div` to `a` for links, `input` to `textarea` for
+// multiline inputs.
+const convertedTags = {
+    div: ["a"],
+    input: ["textarea"],
+};
+
+// The element the server rendered for the current `id_counter`, `undefined` if the page rendered
+// something else, e.g. because it depends on local storage.
+function hydratedElement(tagName) {
+    let element = window.document.querySelector(`[data-id="${id_counter}"]`);
+    if (!element) {
+        return;
+    }
+    let ssrTagName = element.tagName.toLowerCase();
+    if (fastn_utils.isCommentNode(tagName) || fastn_utils.isCommentNode(ssrTagName)) {
+        if (tagName !== ssrTagName) {
+            return;
+        }
+        // the server renders comments as `<comment>` elements
+        let comment = window.document.createComment(fastn_dom.commentMessage);
+        element.replaceWith(comment);
+        return comment;
+    }
+    if (
+        tagName !== ssrTagName &&
+        !convertedTags[tagName]?.includes(ssrTagName)
+    ) {
+        return;
+    }
+    return element;
+}
+
+// Undoes a hydration that failed: removes the elements it created or reused, the closures it
+// added to mutables, and the events it registered, so only the page rendered from scratch is
+// left.
+function discardHydration(closures) {
+    for (let [target, closure] of closures) {
+        target.removeClosure(closure);
+    }
+    for (let element of hydrationElements) {
+        if (element !== document.body) {
+            element.remove();
+        }
+    }
+    hydrationElements = [];
+    // what the server rendered that was not reached before hydration failed
+    document.body
+        .querySelectorAll("[data-id]")
+        .forEach((element) => element.remove());
+    ftd.clickOutsideEvents = [];
+    ftd.globalKeyEvents = [];
+    ftd.globalKeySeqEvents = [];
+    fastn_dom.webComponent = [];
+}
+
 fastnVirtual.document = new Document2();
 
 function addClosureToBreakpointWidth() {
@@ -164,6 +244,51 @@ fastnVirtual.doubleBuffer = function (main) {
     fastnVirtual.root = document.body;
 };
 
+/**
+ * Attaches the page to the html the server rendered for it, reusing its elements. If the
+ * page renders differently in the browser, or there is no server rendered html, the page is
+ * rendered from scratch like `fastnVirtual.doubleBuffer` does.
+ *
+ * The server sets `fastnVirtual.ssrDevice` to the device it rendered the page for.
+ */
+fastnVirtual.hydrate = function (main) {
+    let ssr_device = fastnVirtual.ssrDevice;
+    if (fastn_utils.isNull(ssr_device) || !document.body.hasAttribute("data-id")) {
+        fastnVirtual.doubleBuffer(main);
+        return;
+    }
+
+    let current_device = ftd.get_device();
+    // the page has to render like it did on the server to reuse its elements, if the device
+    // differs it is updated afterwards, like when the window is resized
+    ftd.device = fastn.mutable(ssr_device);
+    doubleBuffering = true;
+    hydrating = true;
+    hydrationFailed = false;
+    id_counter = 0;
+    fastn.recordedClosures = [];
+    fastnVirtual.root = document.body;
+    main(fastnVirtual.document.createElement("body"));
+    hydrating = false;
+    let closures = fastn.recordedClosures;
+    fastn.recordedClosures = null;
+
+    if (hydrationFailed) {
+        discardHydration(closures);
+        fastnVirtual.doubleBuffer(main);
+        return;
+    }
+    hydrationElements = [];
+
+    let styles = document.getElementById("styles");
+    styles.innerHTML = fastn_dom.getClassesAsStringWithoutStyleTag();
+    doubleBuffering = false;
+    addClosureToBreakpointWidth();
+    if (current_device !== ssr_device) {
+        ftd.device.set(current_device);
+    }
+};
+
 fastnVirtual.ssr = function (main) {
     ssr = true;
     let body = fastnVirtual.document.createElement("body");
@@ -0,0 +1,361 @@
+/// A file of the JS runtime. Pages load a bundle of the modules they need, see
+/// `fastn_js::runtime_modules()` and `fastn_js::bundle()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RuntimeModule {
+    /// Highlighting of `ftd` code in code blocks, on top of prism.
+    FtdLanguage,
+    /// The `marked` library, used to render text.
+    Markdown,
+    Fastn,
+    Dom,
+    Utils,
+    Virtual,
+    WebComponent,
+    /// The client side of the `fastn/search` module.
+    Search,
+    Ftd,
+    PostInit,
+}
+
+impl RuntimeModule {
+    /// All modules, in the order they are bundled.
+    pub const ALL: [RuntimeModule; 10] = [
+        RuntimeModule::FtdLanguage,
+        RuntimeModule::Markdown,
+        RuntimeModule::Fastn,
+        RuntimeModule::Dom,
+        RuntimeModule::Utils,
+        RuntimeModule::Virtual,
+        RuntimeModule::WebComponent,
+        RuntimeModule::Search,
+        RuntimeModule::Ftd,
+        RuntimeModule::PostInit,
+    ];
+
+    /// The JS a page has to contain for the module to be needed, `None` for modules every page
+    /// needs.
+    fn used_if(&self) -> Option<&'static str> {
+        match self {
+            RuntimeModule::FtdLanguage => Some("fastn_dom.ElementKind.Code"),
+            RuntimeModule::Markdown => Some("fastn_dom.PropertyKind.StringValue"),
+            RuntimeModule::WebComponent => Some("fastn_dom.ElementKind.WebComponent"),
+            RuntimeModule::Search => Some("fastn_search."),
+            _ => None,
+        }
+    }
+
+    pub fn js(&self) -> String {
+        match self {
+            RuntimeModule::FtdLanguage => include_str!("../js/ftd-language.js").to_string(),
+            RuntimeModule::Markdown => fastn_js::markdown_js().to_string(),
+            RuntimeModule::Fastn => fastn_js::include_str_with_debug!("../js/fastn.js"),
+            RuntimeModule::Dom => fastn_js::include_str_with_debug!("../js/dom.js"),
+            RuntimeModule::Utils => fastn_js::include_str_with_debug!("../js/utils.js"),
+            RuntimeModule::Virtual => fastn_js::include_str_with_debug!("../js/virtual.js"),
+            RuntimeModule::WebComponent => {
+                fastn_js::include_str_with_debug!("../js/web-component.js")
+            }
+            RuntimeModule::Search => fastn_js::include_str_with_debug!("../js/search.js"),
+            RuntimeModule::Ftd => fastn_js::include_str_with_debug!("../js/ftd.js"),
+            RuntimeModule::PostInit => fastn_js::include_str_with_debug!("../js/postInit.js"),
+        }
+    }
+}
+
+/// The runtime modules needed by `js`, the JS generated for a page by `fastn_js::to_js()`.
+pub fn runtime_modules(js: &str) -> Vec<RuntimeModule> {
+    RuntimeModule::ALL
+        .into_iter()
+        .filter(|m| match m.used_if() {
+            Some(used_if) => js.contains(used_if),
+            None => true,
+        })
+        .collect()
+}
+
+/// Every set of modules `fastn_js::runtime_modules()` can return.
+pub fn all_runtime_module_sets() -> Vec<Vec<RuntimeModule>> {
+    let optional: Vec<_> = RuntimeModule::ALL
+        .into_iter()
+        .filter(|m| m.used_if().is_some())
+        .collect();
+    (0..1 << optional.len())
+        .map(|included: usize| {
+            RuntimeModule::ALL
+                .into_iter()
+                .filter(|m| match optional.iter().position(|o| o == m) {
+                    Some(i) => included & (1 << i) != 0,
+                    None => true,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The minified JS of `modules`, in the order of `RuntimeModule::ALL`.
+pub fn bundle(modules: &[RuntimeModule]) -> String {
+    let mut js = String::new();
+    for module in RuntimeModule::ALL.iter().filter(|m| modules.contains(m)) {
+        match module {
+            // `marked` is already minified, and a lot harder to scan
+            RuntimeModule::Markdown => js.push_str(module.js().as_str()),
+            _ => js.push_str(minify(module.js().as_str()).as_str()),
+        }
+        js.push('\n');
+    }
+    js.push_str("window.ftd = ftd;\n");
+    js
+}
+
+/// Removes comments, indentation, trailing whitespace and blank lines from `js`.
+///
+/// Line breaks are kept, so automatic semicolon insertion works like before, and strings,
+/// template literals and regular expressions are copied as is. Block comments with a license
+/// or copyright notice are kept.
+pub fn minify(js: &str) -> String {
+    let chars: Vec<char> = js.chars().collect();
+    let mut out = String::with_capacity(js.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        match chars[i] {
+            '\n' => {
+                line_break(&mut out);
+                i += 1;
+                continue;
+            }
+            ' ' | '\t' | '\r' if out.is_empty() || out.ends_with('\n') => {
+                i += 1;
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i = (i + 2).min(chars.len());
+                let comment: String = chars[start..i].iter().collect();
+                if comment.starts_with("/*!")
+                    || comment.contains("@license")
+                    || comment.contains("Copyright")
+                {
+                    out.push_str(comment.as_str());
+                } else if comment.contains('\n') {
+                    // a comment with a line break separates statements like a line break does
+                    line_break(&mut out);
+                } else {
+                    if !out.is_empty() && !out.ends_with([' ', '\n']) {
+                        out.push(' ');
+                    }
+                    while i < chars.len() && matches!(chars[i], ' ' | '\t') {
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            '/' if regex_can_start(out.as_str()) => {
+                i = skip_regex(&chars, i).unwrap_or(i + 1);
+            }
+            q @ ('"' | '\'') => i = skip_string(&chars, i, q),
+            '`' => i = skip_template(&chars, i),
+            _ => i += 1,
+        }
+        out.extend(&chars[start..i]);
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Ends the current line of `out`, unless it is empty.
+fn line_break(out: &mut String) {
+    out.truncate(out.trim_end_matches([' ', '\t', '\r']).len());
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Whether a `/` after `code` starts a regular expression, instead of being a division.
+fn regex_can_start(code: &str) -> bool {
+    const KEYWORDS: [&str; 14] = [
+        "return",
+        "typeof",
+        "case",
+        "do",
+        "else",
+        "in",
+        "of",
+        "new",
+        "delete",
+        "void",
+        "throw",
+        "instanceof",
+        "yield",
+        "await",
+    ];
+    let code = code.trim_end();
+    match code.chars().last() {
+        None => true,
+        Some(c) if is_identifier_char(c) => {
+            let word_start = code
+                .rfind(|c: char| !is_identifier_char(c))
+                .map_or(0, |i| i + 1);
+            KEYWORDS.contains(&&code[word_start..])
+        }
+        Some(c) => "(,=:[!&|?{};+-*%<>~^".contains(c),
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// The index after the string starting at `i`. Strings can not span lines, so we stop at an
+/// (unescaped) line break, in case we got confused.
+fn skip_string(chars: &[char], i: usize, quote: char) -> usize {
+    let mut i = i + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '\n' => return i,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+/// The index after the template literal starting at `i`, including the expressions in it.
+fn skip_template(chars: &[char], i: usize) -> usize {
+    let mut i = i + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '`' => return i + 1,
+            '$' if chars.get(i + 1) == Some(&'{') => {
+                i += 2;
+                let mut depth = 1;
+                while i < chars.len() && depth > 0 {
+                    match chars[i] {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        q @ ('"' | '\'') => {
+                            i = skip_string(chars, i, q);
+                            continue;
+                        }
+                        '`' => {
+                            i = skip_template(chars, i);
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+/// The index after the regular expression, with its flags, starting at `i`. `None` if it is not
+/// a regular expression after all: they can not span lines.
+fn skip_regex(chars: &[char], i: usize) -> Option<usize> {
+    let mut i = i + 1;
+    let mut in_class = false;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '\n' => return None,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => {
+                i += 1;
+                while i < chars.len() && is_identifier_char(chars[i]) {
+                    i += 1;
+                }
+                return Some(i);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    #[track_caller]
+    fn m(js: &str, expected: &str) {
+        assert_eq!(super::minify(js), expected);
+    }
+
+    #[test]
+    fn minify() {
+        m(
+            indoc::indoc!(
+                "
+                /* the counter */
+                let count = 0; // starts at zero
+
+                function inc(by) {
+                    // comment only line
+                    count += by;
+                    return count /* new value */ / 2;
+                }
+                "
+            ),
+            "let count = 0;\nfunction inc(by) {\ncount += by;\nreturn count / 2;\n}",
+        );
+        m(
+            "let s = \"a // b\";\nlet t = 'c /* d */';\n",
+            "let s = \"a // b\";\nlet t = 'c /* d */';",
+        );
+        m(
+            "let t = `line\n    indented ${ x ? `${y}` : '}' } // not a comment`;\n",
+            "let t = `line\n    indented ${ x ? `${y}` : '}' } // not a comment`;",
+        );
+        m(
+            "let r = s.replace(/\\/\\/ [\"']/g, \"\");\nlet d = a / b; // half\nreturn /x/.test(a);\n",
+            "let r = s.replace(/\\/\\/ [\"']/g, \"\");\nlet d = a / b;\nreturn /x/.test(a);",
+        );
+        m(
+            "/*!\n * keep me\n */\nlet a = 1;\n",
+            "/*!\n * keep me\n */\nlet a = 1;",
+        );
+        m("let a = b\n/* x\n */\nc()\n", "let a = b\nc()");
+    }
+
+    #[test]
+    fn runtime_modules() {
+        use super::RuntimeModule;
+
+        assert_eq!(
+            super::runtime_modules("fastn_dom.createKernel(parent, fastn_dom.ElementKind.Column)"),
+            vec![
+                RuntimeModule::Fastn,
+                RuntimeModule::Dom,
+                RuntimeModule::Utils,
+                RuntimeModule::Virtual,
+                RuntimeModule::Ftd,
+                RuntimeModule::PostInit,
+            ]
+        );
+        assert_eq!(
+            super::runtime_modules(
+                "fastn_dom.ElementKind.Code; fastn_dom.PropertyKind.StringValue"
+            )[..2],
+            [RuntimeModule::FtdLanguage, RuntimeModule::Markdown]
+        );
+
+        let sets = super::all_runtime_module_sets();
+        assert_eq!(sets.len(), 16);
+        assert!(sets.contains(&RuntimeModule::ALL.to_vec()));
+        assert!(sets.contains(&super::runtime_modules("")));
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct Component {
     pub name: String,
     pub params: Vec<String>,
@@ -1,4 +1,4 @@
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct Kernel {
     pub element_kind: ElementKind,
     pub name: String,
@@ -20,7 +20,7 @@ impl Kernel {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub enum ElementKind {
     Row,
     Column,
@@ -41,7 +41,7 @@ pub enum ElementKind {
     WebComponent(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct InstantiateComponent {
     pub component: InstantiateComponentData,
     pub arguments: Vec<(String, fastn_js::SetPropertyValue, bool)>,
@@ -51,7 +51,7 @@ pub struct InstantiateComponent {
     pub already_formatted: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub enum InstantiateComponentData {
     Name(String),
     // Todo: add closure to `uis` to display 0th item
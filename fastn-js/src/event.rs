@@ -1,11 +1,11 @@
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct EventHandler {
     pub event: fastn_js::Event,
     pub action: fastn_js::Function,
     pub element_name: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub enum Event {
     Click,
     MouseEnter,
@@ -19,7 +19,7 @@ pub enum Event {
     Focus,
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub enum FunctionData {
     Name(String),
     // -- component bar:
@@ -30,7 +30,7 @@ pub enum FunctionData {
     Definition(fastn_js::SetPropertyValue),
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct Function {
     pub name: Box<FunctionData>,
     pub parameters: Vec<(String, fastn_js::SetPropertyValue)>,
@@ -3,6 +3,7 @@
 extern crate self as fastn_js;
 
 mod ast;
+mod bundle;
 mod component;
 mod component_invocation;
 mod component_statement;
@@ -15,6 +16,7 @@ mod mutable_variable;
 mod or_type;
 mod property;
 mod record;
+mod source_map;
 mod ssr;
 mod static_variable;
 mod to_js;
@@ -23,6 +25,7 @@ mod udf_statement;
 pub mod utils;
 
 pub use ast::Ast;
+pub use bundle::{all_runtime_module_sets, bundle, minify, runtime_modules, RuntimeModule};
 pub use component::{component0, component1, component2, component_with_params, Component};
 pub use component_invocation::{
     ElementKind, InstantiateComponent, InstantiateComponentData, Kernel,
@@ -39,6 +42,7 @@ pub use property::{
     ConditionalValue, Formula, FormulaType, PropertyKind, SetProperty, SetPropertyValue, Value,
 };
 pub use record::RecordInstance;
+pub use source_map::{to_js_with_source_map, SourceLocation, SourceMap};
 pub use ssr::{run_test, ssr, ssr_raw_string_without_test, ssr_str, ssr_with_js_string, SSRError};
 pub use static_variable::{static_integer, static_string, StaticVariable};
 pub use to_js::to_js;
@@ -60,6 +64,10 @@ pub fn fastn_test_js() -> &'static str {
     include_str!("../js/fastn_test.js")
 }
 
+pub fn live_reload_js() -> &'static str {
+    include_str!("../js/live-reload.js")
+}
+
 pub fn all_js_without_test_and_ftd_langugage_js() -> String {
     let markdown_js = fastn_js::markdown_js();
     let fastn_js = include_str_with_debug!("../js/fastn.js");
@@ -68,8 +76,9 @@ pub fn all_js_without_test_and_ftd_langugage_js() -> String {
     let virtual_js = include_str_with_debug!("../js/virtual.js");
     let ftd_js = include_str_with_debug!("../js/ftd.js");
     let web_component_js = include_str_with_debug!("../js/web-component.js");
+    let search_js = include_str_with_debug!("../js/search.js");
     let post_init_js = include_str_with_debug!("../js/postInit.js");
-    format!("{markdown_js}{fastn_js}{dom_js}{utils_js}{virtual_js}{web_component_js}{ftd_js}{post_init_js}")
+    format!("{markdown_js}{fastn_js}{dom_js}{utils_js}{virtual_js}{web_component_js}{search_js}{ftd_js}{post_init_js}")
 }
 
 #[macro_export]
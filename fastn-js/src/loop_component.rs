@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct ForLoop {
     pub list_variable: fastn_js::SetPropertyValue,
     pub statements: Vec<fastn_js::ComponentStatement>,
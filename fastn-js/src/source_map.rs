@@ -0,0 +1,201 @@
+/// The `.ftd` document and line a `fastn_js::Ast` was generated from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLocation {
+    /// The document id, e.g. `fastn.com/index`.
+    pub document: String,
+    /// 1 based, like the line numbers of `ftd_p1`, `ftd_ast` and the interpreter.
+    pub line_number: usize,
+}
+
+impl SourceLocation {
+    pub fn new(document: &str, line_number: usize) -> SourceLocation {
+        SourceLocation {
+            document: document.to_string(),
+            line_number,
+        }
+    }
+}
+
+/// A [source map (v3)](https://sourcemaps.info/spec.html) of the JS generated for a document.
+///
+/// The granularity is a line: every line of the JS generated for an ast maps to the line the
+/// ast was defined at, e.g. the body of a function maps to its `-- string foo():` line.
+#[derive(Debug, Default, PartialEq)]
+pub struct SourceMap {
+    sources: Vec<String>,
+    sources_content: Vec<Option<String>>,
+    /// For every generated line, the index in `sources` and the 0 based line it comes from.
+    lines: Vec<Option<(usize, usize)>>,
+}
+
+/// Like `fastn_js::to_js()`, but also returns the source map of the generated JS.
+/// `locations` are the source locations of `ast`, in the same order.
+pub fn to_js_with_source_map(
+    ast: &[fastn_js::Ast],
+    locations: &[Option<SourceLocation>],
+    package_name: &str,
+) -> (String, SourceMap) {
+    let mut js = String::new();
+    let mut map = SourceMap::default();
+    for (i, ast) in ast.iter().enumerate() {
+        // each ast is rendered on its own so we know which lines it ends up at
+        let chunk = fastn_js::to_js(std::slice::from_ref(ast), package_name);
+        let source = locations.get(i).cloned().flatten().map(|l| {
+            (
+                map.source_index(l.document.as_str()),
+                l.line_number.saturating_sub(1),
+            )
+        });
+        for line in chunk.lines() {
+            js.push_str(line);
+            js.push('\n');
+            map.lines.push(source);
+        }
+    }
+    (js, map)
+}
+
+impl SourceMap {
+    /// Shifts the mappings down by `offset` lines, for when the generated JS does not start at
+    /// the first line of the script it is put in.
+    pub fn set_line_offset(self, offset: usize) -> Self {
+        let mut map = self;
+        map.lines.splice(0..0, (0..offset).map(|_| None));
+        map
+    }
+
+    /// Embeds the content of `document` in the map, so the browser does not have to fetch it.
+    pub fn set_source_content(self, document: &str, content: &str) -> Self {
+        let mut map = self;
+        if let Some(i) = map.sources.iter().position(|s| *s == source_name(document)) {
+            map.sources_content[i] = Some(content.to_string());
+        }
+        map
+    }
+
+    pub fn to_json(&self, file: &str) -> String {
+        serde_json::json!({
+            "version": 3,
+            "file": file,
+            "sources": self.sources,
+            "sourcesContent": self.sources_content,
+            "names": [],
+            "mappings": self.mappings(),
+        })
+        .to_string()
+    }
+
+    fn source_index(&mut self, document: &str) -> usize {
+        let name = source_name(document);
+        match self.sources.iter().position(|s| *s == name) {
+            Some(i) => i,
+            None => {
+                self.sources.push(name);
+                self.sources_content.push(None);
+                self.sources.len() - 1
+            }
+        }
+    }
+
+    /// One segment at column 0 of every mapped line. The source index and line of a segment are
+    /// relative to the previous segment, even across lines.
+    fn mappings(&self) -> String {
+        let (mut previous_source, mut previous_line) = (0, 0);
+        let mut mappings = vec![];
+        for line in self.lines.iter() {
+            let Some((source, line)) = *line else {
+                mappings.push(String::new());
+                continue;
+            };
+            let mut segment = String::new();
+            vlq(&mut segment, 0);
+            vlq(&mut segment, source as i64 - previous_source as i64);
+            vlq(&mut segment, line as i64 - previous_line as i64);
+            vlq(&mut segment, 0);
+            mappings.push(segment);
+            (previous_source, previous_line) = (source, line);
+        }
+        mappings.join(";")
+    }
+}
+
+/// `fastn.com/` is `fastn.com/index.ftd`, `fastn.com/assets` is `fastn.com/assets.ftd`.
+fn source_name(document: &str) -> String {
+    let document = document.trim_end_matches(".ftd");
+    match document.strip_suffix('/') {
+        Some(document) => format!("{document}/index.ftd"),
+        None => format!("{document}.ftd"),
+    }
+}
+
+/// Appends `value` to `out` as a base64 VLQ.
+fn vlq(out: &mut String, value: i64) {
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut value = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = value & 0b11111;
+        value >>= 5;
+        if value > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn vlq() {
+        for (value, expected) in [
+            (0, "A"),
+            (1, "C"),
+            (-1, "D"),
+            (15, "e"),
+            (16, "gB"),
+            (-17, "jB"),
+        ] {
+            let mut out = String::new();
+            super::vlq(&mut out, value);
+            assert_eq!(out, expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn mappings() {
+        let mut map = super::SourceMap::default();
+        let foo = map.source_index("foo");
+        let bar = map.source_index("bar.ftd");
+        assert_eq!(map.source_index("foo.ftd"), foo);
+        assert_eq!(super::source_name("fastn.com/"), "fastn.com/index.ftd");
+        map.lines = vec![
+            Some((foo, 2)),
+            Some((foo, 2)),
+            None,
+            Some((bar, 0)),
+            Some((foo, 9)),
+        ];
+        let map = map
+            .set_line_offset(1)
+            .set_source_content("foo", "-- ftd.text: foo");
+
+        assert_eq!(map.mappings(), ";AAEA;AAAA;;ACFA;ADSA");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(map.to_json("index.js").as_str()).unwrap(),
+            serde_json::json!({
+                "version": 3,
+                "file": "index.js",
+                "sources": ["foo.ftd", "bar.ftd"],
+                "sourcesContent": ["-- ftd.text: foo", null],
+                "names": [],
+                "mappings": ";AAEA;AAAA;;ACFA;ADSA",
+            })
+        );
+    }
+}
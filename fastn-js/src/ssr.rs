@@ -38,9 +38,10 @@ pub fn ssr_str(js: &str) -> Result<String> {
 
     #[cfg(target_os = "windows")]
     {
-        Ok(rquickjs::Context::full(&rquickjs::Runtime::new().unwrap())
+        rquickjs::Context::full(&rquickjs::Runtime::new().unwrap())
             .unwrap()
-            .with(|ctx| ctx.eval::<String, _>(js).unwrap()))
+            .with(|ctx| ctx.eval::<String, _>(js))
+            .map_err(|e| SSRError::EvalError(e.to_string()))
     }
     #[cfg(not(target_os = "windows"))]
     {
@@ -53,7 +54,9 @@ pub fn ssr_str(js: &str) -> Result<String> {
             )
             .build()
             .unwrap();
-        Ok::<std::string::String, SSRError>(context.eval_as::<String>(js.as_str()).unwrap())
+        context
+            .eval_as::<String>(js.as_str())
+            .map_err(|e| SSRError::EvalError(e.to_string()))
     }
 }
 
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct StaticVariable {
     pub name: String,
     pub value: fastn_js::SetPropertyValue,
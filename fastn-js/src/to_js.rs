@@ -965,6 +965,11 @@ impl ExpressionGenerator {
                     result.push(value);
                 }
             }
+            // builtins like `str::trim` are `ftd.str.trim` in the runtime
+            let function_name = match function_name.split_once("::") {
+                Some((namespace, name)) => format!("ftd.{namespace}.{name}"),
+                None => function_name,
+            };
             return format!("{}({})", function_name, result.join(","));
         }
 
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct UDF {
     pub name: String,
     pub params: Vec<String>,
@@ -78,6 +78,9 @@ pub struct EndpointData {
 #[derive(serde::Deserialize, Debug, Clone)]
 pub struct PackageTemp {
     pub name: String,
+    /// The semver version of this package, dependents can constrain it with `version` in their
+    /// `fastn.dependency`.
+    pub version: Option<String>,
     pub versioned: bool,
     #[serde(rename = "translation-of")]
     pub translation_of: Option<String>,
@@ -0,0 +1,180 @@
+use snafu::ResultExt;
+
+/// `fastn vendor` copies the archives of all dependencies in this directory of the package root,
+/// with the same layout as the global cache, so the package can be built without any network
+/// access.
+pub const VENDOR_DIR: &str = ".fastn-vendor";
+
+/// A package archive in the cache, along with the manifest it was verified against. Entries are
+/// keyed by the checksum of the manifest, so the same version of a package is shared by every
+/// package on the machine.
+pub(crate) struct CachedPackage {
+    pub manifest: fastn_core::Manifest,
+    pub archive: bytes::Bytes,
+}
+
+fn global_dir(ds: &fastn_ds::DocumentStore) -> fastn_ds::Path {
+    ds.home().join(".fastn").join("cache").join("packages")
+}
+
+fn vendor_dir(ds: &fastn_ds::DocumentStore) -> fastn_ds::Path {
+    ds.root().join(VENDOR_DIR)
+}
+
+/// Checksums come from `fastn.lock` and `manifest.json`, we do not want them to point us outside
+/// the cache.
+fn is_valid_checksum(checksum: &str) -> bool {
+    !checksum.is_empty() && checksum.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// The vendored copy is preferred over the global cache. Entries that can not be read are
+/// treated as missing, the caller has to verify the content anyway.
+pub(crate) async fn get(ds: &fastn_ds::DocumentStore, checksum: &str) -> Option<CachedPackage> {
+    if !is_valid_checksum(checksum) {
+        return None;
+    }
+
+    for dir in [vendor_dir(ds), global_dir(ds)] {
+        if let Some(cached) = read_entry(ds, &dir, checksum).await {
+            return Some(cached);
+        }
+    }
+
+    None
+}
+
+async fn read_entry(
+    ds: &fastn_ds::DocumentStore,
+    dir: &fastn_ds::Path,
+    checksum: &str,
+) -> Option<CachedPackage> {
+    let manifest = ds
+        .read_content(&dir.join(format!("{checksum}.json")))
+        .await
+        .ok()?;
+    let manifest: fastn_core::Manifest = serde_json::de::from_slice(&manifest).ok()?;
+    if manifest.checksum != checksum {
+        return None;
+    }
+    let archive = ds
+        .read_content(&dir.join(format!("{checksum}.zip")))
+        .await
+        .ok()?;
+
+    Some(CachedPackage {
+        manifest,
+        archive: archive.into(),
+    })
+}
+
+/// Adds a verified archive to the global cache. The cache only saves downloads, so failing to
+/// write it does not fail the update.
+pub(crate) async fn store(
+    ds: &fastn_ds::DocumentStore,
+    package_name: &str,
+    manifest: &fastn_core::Manifest,
+    archive: &[u8],
+) {
+    if let Err(e) = write_entry(ds, &global_dir(ds), package_name, manifest, archive).await {
+        tracing::warn!("{e}");
+    }
+}
+
+pub(crate) async fn vendor(
+    ds: &fastn_ds::DocumentStore,
+    package_name: &str,
+    manifest: &fastn_core::Manifest,
+    archive: &[u8],
+) -> Result<(), fastn_update::CacheError> {
+    write_entry(ds, &vendor_dir(ds), package_name, manifest, archive).await
+}
+
+async fn write_entry(
+    ds: &fastn_ds::DocumentStore,
+    dir: &fastn_ds::Path,
+    package_name: &str,
+    manifest: &fastn_core::Manifest,
+    archive: &[u8],
+) -> Result<(), fastn_update::CacheError> {
+    if !is_valid_checksum(manifest.checksum.as_str()) {
+        return fastn_update::InvalidCacheChecksumSnafu {
+            package: package_name,
+            checksum: manifest.checksum.as_str(),
+        }
+        .fail();
+    }
+
+    let manifest_bytes = serde_json::ser::to_vec_pretty(manifest).context(
+        fastn_update::SerializeCachedManifestSnafu {
+            package: package_name,
+        },
+    )?;
+
+    // the manifest is written last, an entry without it is never read
+    let archive_path = dir.join(format!("{}.zip", manifest.checksum));
+    ds.write_content(&archive_path, archive)
+        .await
+        .context(fastn_update::WriteCacheSnafu {
+            package: package_name,
+            path: archive_path.to_string(),
+        })?;
+    let manifest_path = dir.join(format!("{}.json", manifest.checksum));
+    ds.write_content(&manifest_path, &manifest_bytes)
+        .await
+        .context(fastn_update::WriteCacheSnafu {
+            package: package_name,
+            path: manifest_path.to_string(),
+        })
+}
+
+/// Removes the vendored packages whose checksum is not in `checksums`, left over from versions
+/// that are no longer locked.
+pub(crate) async fn remove_stale_vendored(
+    ds: &fastn_ds::DocumentStore,
+    checksums: &std::collections::HashSet<String>,
+) -> Result<(), fastn_update::CacheError> {
+    let dir = vendor_dir(ds);
+    if !ds.exists(&dir).await {
+        return Ok(());
+    }
+
+    let mut entries = ds
+        .read_dir(&dir)
+        .await
+        .context(fastn_update::ListVendoredSnafu)?;
+
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .context(fastn_update::ListVendoredSnafu)?
+    {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let checksum = file_name
+            .strip_suffix(".zip")
+            .or_else(|| file_name.strip_suffix(".json"));
+        if checksum.map_or(false, |c| checksums.contains(c)) {
+            continue;
+        }
+
+        let path = dir.join(file_name.as_str());
+        ds.remove(&path)
+            .await
+            .context(fastn_update::RemoveVendoredSnafu {
+                path: path.to_string(),
+            })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn is_valid_checksum() {
+        assert!(super::is_valid_checksum(
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+        ));
+        assert!(!super::is_valid_checksum(""));
+        assert!(!super::is_valid_checksum("../../etc/passwd"));
+    }
+}
@@ -4,7 +4,10 @@ use snafu::prelude::*;
 
 extern crate self as fastn_update;
 
+pub mod cache;
+pub mod lock;
 mod utils;
+mod version;
 
 #[derive(Snafu, Debug)]
 pub enum ManifestError {
@@ -25,6 +28,67 @@ pub enum ManifestError {
         package: String,
         source: fastn_ds::ReadError,
     },
+    #[snafu(display("Failed to serialize manifest.json for package '{package}'"))]
+    SerializeManifest {
+        package: String,
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Snafu, Debug)]
+pub enum VersionError {
+    #[snafu(display("Invalid version '{constraint}' for dependency '{package}'"))]
+    InvalidVersionConstraint {
+        package: String,
+        constraint: String,
+        source: semver::Error,
+    },
+    #[snafu(display("No version of package '{package}' matches '{constraint}'"))]
+    NoMatchingVersion { package: String, constraint: String },
+    #[snafu(display("Package '{package}' does not publish versions, remove its `version`"))]
+    VersionsNotSupported { package: String },
+    #[snafu(display("Failed to list tags for package '{package}'"))]
+    ListTags {
+        package: String,
+        source: fastn_core::Error,
+    },
+    #[snafu(display("Failed to deserialize tags for package '{package}'"))]
+    DeserializeTags {
+        package: String,
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Snafu, Debug)]
+pub enum LockError {
+    #[snafu(display("Failed to read {}", lock::LOCK_FILE))]
+    ReadLockFile { source: fastn_ds::ReadError },
+    #[snafu(display("Failed to deserialize {}", lock::LOCK_FILE))]
+    DeserializeLockFile { source: serde_json::Error },
+    #[snafu(display("Failed to serialize {}", lock::LOCK_FILE))]
+    SerializeLockFile { source: serde_json::Error },
+    #[snafu(display("Failed to write {}", lock::LOCK_FILE))]
+    WriteLockFile { source: fastn_ds::WriteError },
+    #[snafu(display(
+        "Package '{package}' has changed since it was locked, expected checksum {expected}, \
+        found {found}. Run `fastn update` to lock the new content"
+    ))]
+    LockedChecksumMismatch {
+        package: String,
+        expected: String,
+        found: String,
+    },
+    #[snafu(display(
+        "The lock of package '{package}' can not be reproduced: the package no longer publishes \
+        the locked version, and the archive at {zip_url} has changed since it was locked, \
+        expected checksum {expected}, found {found}. Run `fastn update` to lock the new content"
+    ))]
+    LockNotReproducible {
+        package: String,
+        zip_url: String,
+        expected: String,
+        found: String,
+    },
 }
 
 #[derive(Snafu, Debug)]
@@ -53,6 +117,55 @@ pub enum ArchiveError {
         package: String,
         source: fastn_ds::WriteError,
     },
+    #[snafu(display("Failed to read file '{file}' of package '{package}'"))]
+    ReadPackageContent {
+        package: String,
+        file: String,
+        source: fastn_ds::ReadError,
+    },
+    #[snafu(display("File '{file}' of package '{package}' is missing"))]
+    MissingFile { package: String, file: String },
+    #[snafu(display(
+        "Checksum mismatch for file '{file}' of package '{package}', expected {expected}, found {found}"
+    ))]
+    FileChecksumMismatch {
+        package: String,
+        file: String,
+        expected: String,
+        found: String,
+    },
+    #[snafu(display(
+        "Checksum mismatch for package '{package}', expected {expected}, found {found}"
+    ))]
+    ChecksumMismatch {
+        package: String,
+        expected: String,
+        found: String,
+    },
+}
+
+#[derive(Snafu, Debug)]
+pub enum CacheError {
+    #[snafu(display("Invalid checksum '{checksum}' in manifest.json of package '{package}'"))]
+    InvalidCacheChecksum { package: String, checksum: String },
+    #[snafu(display("Failed to serialize the cached manifest of package '{package}'"))]
+    SerializeCachedManifest {
+        package: String,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Failed to write '{path}' for package '{package}'"))]
+    WriteCache {
+        package: String,
+        path: String,
+        source: fastn_ds::WriteError,
+    },
+    #[snafu(display("Failed to list {}", cache::VENDOR_DIR))]
+    ListVendored { source: std::io::Error },
+    #[snafu(display("Failed to remove '{path}'"))]
+    RemoveVendored {
+        path: String,
+        source: fastn_ds::RemoveError,
+    },
 }
 
 #[derive(Snafu, Debug)]
@@ -67,6 +180,7 @@ pub enum DependencyError {
 #[derive(Debug)]
 pub enum CheckError {
     WriteDuringCheck { package: String, file: String },
+    NotLocked { package: String },
 }
 
 impl std::fmt::Display for CheckError {
@@ -84,6 +198,15 @@ impl std::fmt::Display for CheckError {
                     "Write Attempt".yellow()
                 )
             }
+            CheckError::NotLocked { package } => {
+                write!(
+                    f,
+                    "{}\n\nThe package '{}' is out of sync with the {} file.\n\nRun `fastn update` to lock it.",
+                    "Error: Out of Sync Lock File".red().bold(),
+                    package,
+                    fastn_update::lock::LOCK_FILE,
+                )
+            }
         }
     }
 }
@@ -98,6 +221,12 @@ pub enum UpdateError {
     Archive(#[from] ArchiveError),
     #[error("Dependency error: {0}")]
     Dependency(#[from] DependencyError),
+    #[error("Version error: {0}")]
+    Version(#[from] VersionError),
+    #[error("Lock error: {0}")]
+    Lock(#[from] LockError),
+    #[error("Cache error: {0}")]
+    Cache(#[from] CacheError),
     #[error("Check error: {0}")]
     Check(#[from] CheckError),
     #[error("Config error: {0}")]
@@ -106,13 +235,17 @@ pub enum UpdateError {
     InvalidPackage(String),
 }
 
+/// With a `lock`, dependencies are installed at the locked versions where they still match the
+/// `version` in FASTN.ftd. Returns the number of packages updated and the lock for what is now
+/// installed.
 async fn update_dependencies(
     ds: &fastn_ds::DocumentStore,
     packages_root: fastn_ds::Path,
     current_package: &fastn_core::Package,
     pb: &indicatif::ProgressBar,
     check: bool,
-) -> Result<usize, UpdateError> {
+    lock: Option<&lock::LockFile>,
+) -> Result<(usize, lock::LockFile), UpdateError> {
     let mut stack = vec![current_package.clone()];
     let mut resolved = std::collections::HashSet::new();
     resolved.insert(current_package.name.to_string());
@@ -129,17 +262,24 @@ async fn update_dependencies(
             let package_name = dep_package.name.clone();
             let dependency_path = &packages_root.join(&package_name);
 
-            if is_fifthtry_site_package(package_name.as_str()) {
+            let target = version::target(&dependency, lock)?;
+            if check && lock.is_some() && !matches!(target, version::Target::Locked(_)) {
+                return Err(UpdateError::Check(CheckError::NotLocked {
+                    package: package_name,
+                }));
+            }
+
+            let manifest = if is_fifthtry_site_package(package_name.as_str()) {
                 update_fifthtry_site_dependency(
                     &dependency,
                     ds,
                     packages_root.clone(),
                     pb,
                     &mut updated_packages,
-                    &mut all_packages,
                     check,
+                    &target,
                 )
-                .await?;
+                .await?
             } else {
                 update_github_dependency(
                     &dependency,
@@ -147,11 +287,12 @@ async fn update_dependencies(
                     packages_root.clone(),
                     pb,
                     &mut updated_packages,
-                    &mut all_packages,
                     check,
+                    &target,
                 )
-                .await?;
-            }
+                .await?
+            };
+            all_packages.push((package_name.to_string(), manifest));
 
             if package_name.eq(&fastn_core::FASTN_UI_INTERFACE) {
                 resolved.insert(package_name.to_string());
@@ -168,6 +309,8 @@ async fn update_dependencies(
         pb.inc(1);
     }
 
+    let new_lock = lock::LockFile::from_manifests(all_packages.as_slice());
+
     fastn_core::ConfigTemp::write(
         ds,
         current_package.name.clone(),
@@ -175,7 +318,7 @@ async fn update_dependencies(
     )
     .await?;
 
-    Ok(updated_packages)
+    Ok((updated_packages, new_lock))
 }
 
 async fn update_github_dependency(
@@ -184,9 +327,9 @@ async fn update_github_dependency(
     packages_root: fastn_ds::Path,
     pb: &indicatif::ProgressBar,
     updated_packages: &mut usize,
-    all_packages: &mut Vec<(String, fastn_core::Manifest)>,
     check: bool,
-) -> Result<(), fastn_update::UpdateError> {
+    target: &version::Target<'_>,
+) -> Result<fastn_core::Manifest, fastn_update::UpdateError> {
     let dep_package = &dependency.package;
     let package_name = dep_package.name.clone();
     let dependency_path = &packages_root.join(&package_name);
@@ -197,56 +340,123 @@ async fn update_github_dependency(
         )));
     }
 
-    pb.set_message(format!("Resolving {}/manifest.json", &package_name));
-    let (manifest, manifest_bytes) = utils::get_manifest(ds, &package_name).await?;
-
     let manifest_path = dependency_path.join(fastn_core::manifest::MANIFEST_FILE);
+    let existing_manifest =
+        read_installed_manifest(ds, dependency_path, package_name.as_str()).await?;
 
-    // Download the archive if:
-    // 1. The package does not yet exist
-    // 2. The checksums of the downloaded package manifest
-    //    and the existing manifest does not match
-    let should_download_archive = if !ds.exists(dependency_path).await {
-        true
-    } else {
-        let existing_manifest_bytes =
-            ds.read_content(&manifest_path)
-                .await
-                .context(ReadManifestSnafu {
-                    package: package_name.clone(),
-                })?;
-        let existing_manifest: fastn_core::Manifest =
-            utils::read_manifest(&existing_manifest_bytes, &package_name)?;
-
-        existing_manifest.checksum.ne(manifest.checksum.as_str())
-    };
-
-    if !should_download_archive {
-        pb.set_message(format!(
-            "Skipping download for package \"{}\" as it already exists.",
-            &package_name
-        ));
-    } else {
-        pb.set_message(format!("Downloading {} archive", &package_name));
+    if let (version::Target::Locked(locked), Some(existing_manifest)) =
+        (target, existing_manifest.as_ref())
+    {
+        if existing_manifest.checksum == locked.checksum {
+            pb.set_message(format!(
+                "Skipping download for package \"{}\" as the locked version is installed.",
+                &package_name
+            ));
+            return Ok(existing_manifest.clone());
+        }
+    }
 
-        download_unpack_zip_and_get_manifest(
-            dependency_path.clone(),
-            manifest.zip_url.as_str(),
+    // the locked version may be cached, then we do not need the network at all
+    if let version::Target::Locked(locked) = target {
+        if let Some(manifest) = install_from_cache(
             ds,
+            dependency_path,
             package_name.as_str(),
-            Some(&manifest),
+            locked.checksum.as_str(),
             pb,
             check,
         )
-        .await?;
+        .await?
+        {
+            *updated_packages += 1;
+            return Ok(manifest);
+        }
+    }
+
+    pb.set_message(format!("Resolving {}/manifest.json", &package_name));
+    let manifest = match version::resolve(ds, package_name.as_str(), target).await? {
+        version::Resolution::Manifest {
+            manifest,
+            bytes: manifest_bytes,
+        } => {
+            // Download the archive if:
+            // 1. The package does not yet exist
+            // 2. The checksums of the downloaded package manifest
+            //    and the existing manifest does not match
+            let should_download_archive = existing_manifest.map_or(true, |existing| {
+                existing.checksum.ne(manifest.checksum.as_str())
+            });
+
+            if !should_download_archive {
+                pb.set_message(format!(
+                    "Skipping download for package \"{}\" as it already exists.",
+                    &package_name
+                ));
+            } else if install_from_cache(
+                ds,
+                dependency_path,
+                package_name.as_str(),
+                manifest.checksum.as_str(),
+                pb,
+                check,
+            )
+            .await?
+            .is_some()
+            {
+                *updated_packages += 1;
+            } else {
+                pb.set_message(format!("Downloading {} archive", &package_name));
+
+                let (_, archive) = download_unpack_zip_and_get_manifest(
+                    dependency_path.clone(),
+                    manifest.zip_url.as_str(),
+                    ds,
+                    package_name.as_str(),
+                    Some(&manifest),
+                    None,
+                    pb,
+                    check,
+                )
+                .await?;
+
+                write_archive_content(ds, &manifest_path, &manifest_bytes, &package_name, check)
+                    .await?;
+                cache::store(ds, package_name.as_str(), &manifest, &archive).await;
+
+                *updated_packages += 1;
+            }
 
-        write_archive_content(ds, &manifest_path, &manifest_bytes, &package_name, check).await?;
+            manifest
+        }
+        version::Resolution::Archive { zip_url, version } => {
+            pb.set_message(format!("Downloading {} archive", &package_name));
+
+            let locked = match target {
+                version::Target::Locked(locked) => Some(*locked),
+                _ => None,
+            };
+            let manifest = install_archive(
+                dependency_path.clone(),
+                zip_url.as_str(),
+                version,
+                locked,
+                ds,
+                package_name.as_str(),
+                pb,
+                check,
+            )
+            .await?;
+
+            *updated_packages += 1;
+            manifest
+        }
+    };
 
-        *updated_packages += 1;
+    if let version::Target::Locked(locked) = target {
+        locked.verify(package_name.as_str(), &manifest)?;
     }
 
-    all_packages.push((package_name.to_string(), manifest));
-    Ok(())
+    Ok(manifest)
 }
 
 async fn update_fifthtry_site_dependency(
@@ -255,9 +465,9 @@ async fn update_fifthtry_site_dependency(
     packages_root: fastn_ds::Path,
     pb: &indicatif::ProgressBar,
     updated_packages: &mut usize,
-    all_packages: &mut Vec<(String, fastn_core::Manifest)>,
     check: bool,
-) -> Result<(), fastn_update::UpdateError> {
+    target: &version::Target<'_>,
+) -> Result<fastn_core::Manifest, fastn_update::UpdateError> {
     let dep_package = &dependency.package;
     let package_name = dep_package.name.clone();
 
@@ -271,46 +481,246 @@ async fn update_fifthtry_site_dependency(
     let dependency_path = &packages_root.join(&package_name);
     let site_zip_url = fastn_core::utils::fifthtry_site_zip_url(site_slug);
 
-    let manifest = download_unpack_zip_and_get_manifest(
+    match target {
+        version::Target::Latest => {}
+        version::Target::Matching(_) => {
+            return Err(VersionError::VersionsNotSupported {
+                package: package_name,
+            }
+            .into())
+        }
+        version::Target::Locked(locked) => {
+            // fifthtry sites only serve their latest content, so if that changed after the lock
+            // was written we can not install the locked version
+            if let Some(existing_manifest) =
+                read_installed_manifest(ds, dependency_path, package_name.as_str()).await?
+            {
+                if existing_manifest.checksum == locked.checksum {
+                    return Ok(existing_manifest);
+                }
+            }
+
+            if let Some(manifest) = install_from_cache(
+                ds,
+                dependency_path,
+                package_name.as_str(),
+                locked.checksum.as_str(),
+                pb,
+                check,
+            )
+            .await?
+            {
+                *updated_packages += 1;
+                return Ok(manifest);
+            }
+        }
+    }
+
+    let locked = match target {
+        version::Target::Locked(locked) => Some(*locked),
+        _ => None,
+    };
+    let manifest = install_archive(
         dependency_path.clone(),
         site_zip_url.as_str(),
+        None,
+        locked,
         ds,
         package_name.as_str(),
-        None,
         pb,
         check,
     )
     .await?;
 
-    all_packages.push((package_name.to_string(), manifest));
+    if let version::Target::Locked(locked) = target {
+        locked.verify(package_name.as_str(), &manifest)?;
+    }
+
     *updated_packages += 1;
-    Ok(())
+    Ok(manifest)
 }
 
-async fn download_unpack_zip_and_get_manifest(
+/// The manifest of the package installed in `.packages`, if any.
+async fn read_installed_manifest(
+    ds: &fastn_ds::DocumentStore,
+    dependency_path: &fastn_ds::Path,
+    package_name: &str,
+) -> Result<Option<fastn_core::Manifest>, fastn_update::UpdateError> {
+    let manifest_path = dependency_path.join(fastn_core::manifest::MANIFEST_FILE);
+    match ds.read_content(&manifest_path).await {
+        Ok(bytes) => Ok(Some(utils::read_manifest(&bytes, package_name)?)),
+        Err(fastn_ds::ReadError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e)
+            .context(ReadManifestSnafu {
+                package: package_name,
+            })
+            .map_err(Into::into),
+    }
+}
+
+/// Installs the package with `checksum` from the vendored copy or the global cache. `None` if
+/// neither has it, or if the cached archive does not match its manifest, the package is then
+/// downloaded again.
+async fn install_from_cache(
+    ds: &fastn_ds::DocumentStore,
+    dependency_path: &fastn_ds::Path,
+    package_name: &str,
+    checksum: &str,
+    pb: &indicatif::ProgressBar,
+    check: bool,
+) -> Result<Option<fastn_core::Manifest>, fastn_update::UpdateError> {
+    let cached = match cache::get(ds, checksum).await {
+        Some(cached) => cached,
+        None => return Ok(None),
+    };
+
+    pb.set_message(format!("Installing {} from the cache", package_name));
+    let contents =
+        match read_archive_contents(cached.archive, package_name, Some(&cached.manifest), pb)
+            .and_then(|contents| {
+                verify_package_content(package_name, &cached.manifest, &contents)?;
+                Ok(contents)
+            }) {
+            Ok(contents) => contents,
+            Err(e) => {
+                tracing::warn!("Ignoring the cached archive of package '{package_name}': {e}");
+                return Ok(None);
+            }
+        };
+
+    write_package_content(ds, dependency_path, package_name, contents, pb, check).await?;
+    write_installed_manifest(ds, dependency_path, package_name, &cached.manifest, check).await?;
+
+    Ok(Some(cached.manifest))
+}
+
+/// Unpacks an archive that has no `manifest.json` of its own, and writes the manifest we built
+/// from its content, so the next update can tell if the installed package is up to date.
+/// `locked` is the lock of the package, if we install the locked version.
+#[allow(clippy::too_many_arguments)]
+async fn install_archive(
     dependency_path: fastn_ds::Path,
     zip_url: &str,
+    version: Option<String>,
+    locked: Option<&lock::LockedPackage>,
     ds: &fastn_ds::DocumentStore,
     package_name: &str,
-    manifest: Option<&fastn_core::Manifest>,
     pb: &indicatif::ProgressBar,
     check: bool,
 ) -> Result<fastn_core::Manifest, fastn_update::UpdateError> {
-    use sha2::digest::FixedOutput;
-    use sha2::Digest;
+    let (mut manifest, archive) = download_unpack_zip_and_get_manifest(
+        dependency_path.clone(),
+        zip_url,
+        ds,
+        package_name,
+        None,
+        locked,
+        pb,
+        check,
+    )
+    .await?;
+    manifest.version = version;
 
-    let mut files: std::collections::BTreeMap<String, fastn_core::manifest::File> = match manifest {
-        Some(manifest) => manifest.files.clone(),
-        None => Default::default(),
-    };
+    write_installed_manifest(ds, &dependency_path, package_name, &manifest, check).await?;
+    cache::store(ds, package_name, &manifest, &archive).await;
 
-    let mut archive = utils::download_archive(ds, zip_url.to_string())
+    Ok(manifest)
+}
+
+async fn write_installed_manifest(
+    ds: &fastn_ds::DocumentStore,
+    dependency_path: &fastn_ds::Path,
+    package_name: &str,
+    manifest: &fastn_core::Manifest,
+    check: bool,
+) -> Result<(), fastn_update::UpdateError> {
+    let manifest_bytes =
+        serde_json::ser::to_vec_pretty(manifest).context(SerializeManifestSnafu {
+            package: package_name,
+        })?;
+    write_archive_content(
+        ds,
+        &dependency_path.join(fastn_core::manifest::MANIFEST_FILE),
+        &manifest_bytes,
+        package_name,
+        check,
+    )
+    .await
+}
+
+/// Downloads the archive at `zip_url` and unpacks it in `dependency_path`, without the top level
+/// directory of the archive, see `package_file_path()`. The archive is verified against
+/// `manifest`, or, for packages that do not publish one, a manifest is built from its content,
+/// which must match `locked` when installing a locked package. The archive is returned along
+/// with the manifest, so it can be cached.
+#[allow(clippy::too_many_arguments)]
+async fn download_unpack_zip_and_get_manifest(
+    dependency_path: fastn_ds::Path,
+    zip_url: &str,
+    ds: &fastn_ds::DocumentStore,
+    package_name: &str,
+    manifest: Option<&fastn_core::Manifest>,
+    locked: Option<&lock::LockedPackage>,
+    pb: &indicatif::ProgressBar,
+    check: bool,
+) -> Result<(fastn_core::Manifest, bytes::Bytes), fastn_update::UpdateError> {
+    let archive = fastn_core::http::http_get(ds, zip_url)
         .await
         .context(DownloadArchiveSnafu {
             package: package_name,
         })?;
 
-    let mut hasher = sha2::Sha256::new();
+    // The whole archive is read and verified before anything is written, so a corrupted or
+    // tampered download never ends up in `.packages`
+    let contents = read_archive_contents(archive.clone(), package_name, manifest, pb)?;
+
+    let manifest = match manifest {
+        Some(manifest) => {
+            verify_package_content(package_name, manifest, &contents)?;
+            manifest.clone()
+        }
+        None => {
+            // Creating file entries for manifest using archive files
+            let files = contents
+                .iter()
+                .map(|(name, content)| {
+                    (
+                        name.to_string(),
+                        fastn_core::manifest::File::new(
+                            name.to_string(),
+                            fastn_core::utils::generate_hash(content),
+                            content.len(),
+                        ),
+                    )
+                })
+                .collect();
+            let checksum = content_checksum(contents.values().map(|c| c.as_slice()));
+            fastn_core::Manifest::new(files, zip_url.to_string(), checksum)
+        }
+    };
+    if let Some(locked) = locked {
+        // the url of a locked archive can serve other content by now, e.g. the latest commit
+        locked.verify_archive(package_name, &manifest)?;
+    }
+
+    write_package_content(ds, &dependency_path, package_name, contents, pb, check).await?;
+
+    Ok((manifest, archive))
+}
+
+/// The files of the archive, by their path in the package, see `package_file_path()`, mapped to
+/// their content. With a `manifest` only the files it lists are read.
+fn read_archive_contents(
+    archive: bytes::Bytes,
+    package_name: &str,
+    manifest: Option<&fastn_core::Manifest>,
+    pb: &indicatif::ProgressBar,
+) -> Result<std::collections::BTreeMap<String, Vec<u8>>, ArchiveError> {
+    let mut archive = utils::open_archive(archive).context(ArchiveEntryReadSnafu {
+        package: package_name,
+    })?;
+
+    let mut contents: std::collections::BTreeMap<String, Vec<u8>> = Default::default();
     for i in 0..archive.len() {
         let mut entry = archive.by_index(i).context(ArchiveEntryReadSnafu {
             package: package_name,
@@ -329,41 +739,101 @@ async fn download_unpack_zip_and_get_manifest(
             let path_string = path.to_string_lossy().into_owned();
             let path_normalized = path_string.replace('\\', "/");
 
-            let file_name = path_normalized.clone();
-            let file_hash = fastn_core::utils::generate_hash(buffer.clone());
-            let file_size = buffer.len();
-
-            let path_without_prefix = match path_normalized.split_once('/') {
-                Some((_, path)) => path,
-                None => &path_normalized,
+            let path_without_prefix = package_file_path(path_normalized.as_str());
+            let keep = match manifest {
+                Some(manifest) => manifest.files.contains_key(path_without_prefix),
+                // we write our own manifest for archives that do not come with one
+                None => path_without_prefix.ne(fastn_core::manifest::MANIFEST_FILE),
             };
-            if manifest.is_some() {
-                if !files.contains_key(path_without_prefix) {
-                    continue;
-                }
-            } else {
-                hasher.update(&buffer);
-                // Creating file entry for manifest using archive files
-                files.insert(
-                    file_name.clone(),
-                    fastn_core::manifest::File::new(file_name, file_hash, file_size),
-                );
+            if keep {
+                contents.insert(path_without_prefix.to_string(), buffer);
             }
-            let output_path = &dependency_path.join(path_without_prefix);
-            write_archive_content(ds, output_path, &buffer, package_name, check).await?;
             pb.tick();
         }
     }
 
-    let manifest = match manifest {
-        Some(manifest) => manifest.clone(),
-        None => {
-            let checksum = format!("{:X}", hasher.finalize_fixed());
-            fastn_core::Manifest::new(files, zip_url.to_string(), checksum)
+    Ok(contents)
+}
+
+/// The path of a file of the package, from its path in the archive. Package archives, like the
+/// ones github makes of a repository, have the package in a top level directory, e.g.
+/// `repo-main/index.ftd`, the package does not know its name so it is dropped.
+fn package_file_path(archive_path: &str) -> &str {
+    match archive_path.split_once('/') {
+        Some((_, path)) => path,
+        None => archive_path,
+    }
+}
+
+async fn write_package_content(
+    ds: &fastn_ds::DocumentStore,
+    dependency_path: &fastn_ds::Path,
+    package_name: &str,
+    contents: std::collections::BTreeMap<String, Vec<u8>>,
+    pb: &indicatif::ProgressBar,
+    check: bool,
+) -> Result<(), fastn_update::UpdateError> {
+    for (name, content) in contents {
+        let output_path = &dependency_path.join(name);
+        write_archive_content(ds, output_path, &content, package_name, check).await?;
+        pb.tick();
+    }
+
+    Ok(())
+}
+
+/// The checksum of a package, as `fastn build` writes it in `manifest.json`: the sha256 of the
+/// content of all its files, in the order of their names.
+fn content_checksum<'a>(contents: impl Iterator<Item = &'a [u8]>) -> String {
+    use sha2::digest::FixedOutput;
+    use sha2::Digest;
+
+    let mut hasher = sha2::Sha256::new();
+    for content in contents {
+        hasher.update(content);
+    }
+    format!("{:X}", hasher.finalize_fixed())
+}
+
+/// `contents` maps file names to their content, it must have every file in `manifest`, with the
+/// checksum recorded there.
+fn verify_package_content(
+    package_name: &str,
+    manifest: &fastn_core::Manifest,
+    contents: &std::collections::BTreeMap<String, Vec<u8>>,
+) -> Result<(), ArchiveError> {
+    let mut files = Vec::with_capacity(manifest.files.len());
+    for (name, file) in manifest.files.iter() {
+        let content = contents.get(name).context(MissingFileSnafu {
+            package: package_name,
+            file: name,
+        })?;
+
+        let checksum = fastn_core::utils::generate_hash(content);
+        if checksum.ne(&file.checksum) {
+            return FileChecksumMismatchSnafu {
+                package: package_name,
+                file: name,
+                expected: file.checksum.as_str(),
+                found: checksum,
+            }
+            .fail();
         }
-    };
 
-    Ok(manifest)
+        files.push(content.as_slice());
+    }
+
+    let checksum = content_checksum(files.into_iter());
+    if checksum.ne(&manifest.checksum) {
+        return ChecksumMismatchSnafu {
+            package: package_name,
+            expected: manifest.checksum.as_str(),
+            found: checksum,
+        }
+        .fail();
+    }
+
+    Ok(())
 }
 
 fn is_fifthtry_site_package(package_name: &str) -> bool {
@@ -392,8 +862,169 @@ async fn write_archive_content(
         })?)
 }
 
+/// `fastn update`: resolves every dependency again, to the latest version matching its `version`
+/// in FASTN.ftd, and records what got installed in `fastn.lock`.
+///
+/// With `check` nothing is written, and the installed packages must be the ones in `fastn.lock`
+/// (or the latest ones, if there is no `fastn.lock` yet).
 #[tracing::instrument(skip_all)]
 pub async fn update(ds: &fastn_ds::DocumentStore, check: bool) -> fastn_core::Result<()> {
+    let lock = if check { read_lock(ds).await? } else { None };
+    update_(ds, check, lock).await
+}
+
+/// Installs the versions recorded in `fastn.lock`, done before `fastn build`, `fastn serve` and
+/// `fastn test`. Dependencies missing from `fastn.lock` are resolved and added to it.
+#[tracing::instrument(skip_all)]
+pub async fn sync(ds: &fastn_ds::DocumentStore) -> fastn_core::Result<()> {
+    let lock = read_lock(ds).await?;
+    update_(ds, false, lock).await
+}
+
+/// `fastn update --verify`: checks the packages unpacked in `.packages` against the manifests
+/// they were installed from, so a dependency that was modified, or only partly written, after
+/// it was installed does not go unnoticed.
+#[tracing::instrument(skip_all)]
+pub async fn verify(ds: &fastn_ds::DocumentStore) -> fastn_core::Result<()> {
+    let packages_root = ds.root().join(".packages");
+    let config =
+        fastn_core::ConfigTemp::read(ds)
+            .await
+            .map_err(|e| fastn_core::Error::UpdateError {
+                message: e.to_string(),
+            })?;
+
+    let mut failed: usize = 0;
+    for (package_name, manifest) in config.all_packages.iter() {
+        if let Err(e) = verify_installed_package(
+            ds,
+            &packages_root.join(package_name),
+            package_name,
+            manifest,
+        )
+        .await
+        {
+            eprintln!("{e}");
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(fastn_core::Error::UpdateError {
+            message: format!(
+                "{failed} of {} packages failed verification, run `fastn update` to install them again.",
+                config.all_packages.len()
+            ),
+        });
+    }
+
+    println!("Verified {} packages.", config.all_packages.len());
+    Ok(())
+}
+
+/// `fastn vendor`: installs the locked dependencies, like `sync`, and copies their archives to
+/// `.fastn-vendor`. Commit that directory and later installs take the dependencies from it, no
+/// network access needed.
+#[tracing::instrument(skip_all)]
+pub async fn vendor(ds: &fastn_ds::DocumentStore) -> fastn_core::Result<()> {
+    sync(ds).await?;
+
+    let config =
+        fastn_core::ConfigTemp::read(ds)
+            .await
+            .map_err(|e| fastn_core::Error::UpdateError {
+                message: e.to_string(),
+            })?;
+
+    let mut checksums = std::collections::HashSet::new();
+    for (package_name, manifest) in config.all_packages.iter() {
+        vendor_package(ds, package_name, manifest)
+            .await
+            .map_err(|e| fastn_core::Error::UpdateError {
+                message: e.to_string(),
+            })?;
+        checksums.insert(manifest.checksum.to_string());
+    }
+
+    cache::remove_stale_vendored(ds, &checksums)
+        .await
+        .map_err(|e| fastn_core::Error::UpdateError {
+            message: e.to_string(),
+        })?;
+
+    println!(
+        "Vendored {} packages in {}.",
+        config.all_packages.len(),
+        cache::VENDOR_DIR
+    );
+    Ok(())
+}
+
+async fn vendor_package(
+    ds: &fastn_ds::DocumentStore,
+    package_name: &str,
+    manifest: &fastn_core::Manifest,
+) -> Result<(), UpdateError> {
+    // packages installed before they were cached have to be downloaded again
+    let archive = match cache::get(ds, manifest.checksum.as_str()).await {
+        Some(cached) => cached.archive,
+        None => fastn_core::http::http_get(ds, manifest.zip_url.as_str())
+            .await
+            .context(DownloadArchiveSnafu {
+                package: package_name,
+            })?,
+    };
+
+    let contents = read_archive_contents(
+        archive.clone(),
+        package_name,
+        Some(manifest),
+        &indicatif::ProgressBar::hidden(),
+    )?;
+    verify_package_content(package_name, manifest, &contents)?;
+
+    Ok(cache::vendor(ds, package_name, manifest, &archive).await?)
+}
+
+async fn verify_installed_package(
+    ds: &fastn_ds::DocumentStore,
+    dependency_path: &fastn_ds::Path,
+    package_name: &str,
+    manifest: &fastn_core::Manifest,
+) -> Result<(), ArchiveError> {
+    let mut contents = std::collections::BTreeMap::new();
+    for name in manifest.files.keys() {
+        match ds.read_content(&dependency_path.join(name)).await {
+            Ok(content) => {
+                contents.insert(name.to_string(), content);
+            }
+            // verify_package_content reports the missing file
+            Err(fastn_ds::ReadError::NotFound(_)) => {}
+            Err(e) => {
+                return Err(e).context(ReadPackageContentSnafu {
+                    package: package_name,
+                    file: name,
+                })
+            }
+        }
+    }
+
+    verify_package_content(package_name, manifest, &contents)
+}
+
+async fn read_lock(ds: &fastn_ds::DocumentStore) -> fastn_core::Result<Option<lock::LockFile>> {
+    lock::LockFile::read(ds)
+        .await
+        .map_err(|e| fastn_core::Error::UpdateError {
+            message: e.to_string(),
+        })
+}
+
+async fn update_(
+    ds: &fastn_ds::DocumentStore,
+    check: bool,
+    lock: Option<lock::LockFile>,
+) -> fastn_core::Result<()> {
     let packages_root = ds.root().join(".packages");
     let current_package = utils::read_current_package(ds).await?;
 
@@ -415,22 +1046,39 @@ pub async fn update(ds: &fastn_ds::DocumentStore, check: bool) -> fastn_core::Re
     pb.set_style(spinner_style);
     pb.set_prefix("Updating dependencies");
 
-    let updated_packages =
-        match update_dependencies(ds, packages_root, &current_package, &pb, check).await {
-            Ok(n) => n,
-            Err(UpdateError::Check(e)) => {
-                eprintln!("{}", e);
-                std::process::exit(7);
-            }
-            Err(e) => {
-                return Err(fastn_core::Error::UpdateError {
-                    message: e.to_string(),
-                });
-            }
-        };
+    let (updated_packages, new_lock) = match update_dependencies(
+        ds,
+        packages_root,
+        &current_package,
+        &pb,
+        check,
+        lock.as_ref(),
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(UpdateError::Check(e)) => {
+            eprintln!("{}", e);
+            std::process::exit(7);
+        }
+        Err(e) => {
+            return Err(fastn_core::Error::UpdateError {
+                message: e.to_string(),
+            });
+        }
+    };
 
     pb.finish_and_clear();
 
+    if !check && lock.as_ref() != Some(&new_lock) {
+        new_lock
+            .write(ds)
+            .await
+            .map_err(|e| fastn_core::Error::UpdateError {
+                message: e.to_string(),
+            })?;
+    }
+
     match updated_packages {
         0 => println!("No packages updated."),
         1 => println!("Updated package dependency."),
@@ -439,3 +1087,62 @@ pub async fn update(ds: &fastn_ds::DocumentStore, check: bool) -> fastn_core::Re
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    fn manifest(contents: &std::collections::BTreeMap<String, Vec<u8>>) -> fastn_core::Manifest {
+        let files = contents
+            .iter()
+            .map(|(name, content)| {
+                (
+                    name.to_string(),
+                    fastn_core::manifest::File::new(
+                        name.to_string(),
+                        fastn_core::utils::generate_hash(content),
+                        content.len(),
+                    ),
+                )
+            })
+            .collect();
+        let checksum = super::content_checksum(contents.values().map(|c| c.as_slice()));
+        fastn_core::Manifest::new(files, "https://example.com/a.zip".to_string(), checksum)
+    }
+
+    #[test]
+    fn package_file_path() {
+        assert_eq!(super::package_file_path("repo-main/index.ftd"), "index.ftd");
+        assert_eq!(super::package_file_path("repo-main/a/b.ftd"), "a/b.ftd");
+        assert_eq!(super::package_file_path("FASTN.ftd"), "FASTN.ftd");
+    }
+
+    #[test]
+    fn verify_package_content() {
+        let contents = std::collections::BTreeMap::from([
+            ("FASTN.ftd".to_string(), b"-- import: fastn".to_vec()),
+            ("index.ftd".to_string(), b"-- ftd.text: hello".to_vec()),
+        ]);
+        let manifest = manifest(&contents);
+        assert!(super::verify_package_content("a.com", &manifest, &contents).is_ok());
+
+        let mut tampered = contents.clone();
+        tampered.insert("index.ftd".to_string(), b"-- ftd.text: bye".to_vec());
+        assert!(matches!(
+            super::verify_package_content("a.com", &manifest, &tampered),
+            Err(super::ArchiveError::FileChecksumMismatch { file, .. }) if file == "index.ftd"
+        ));
+
+        let mut missing = contents.clone();
+        missing.remove("index.ftd");
+        assert!(matches!(
+            super::verify_package_content("a.com", &manifest, &missing),
+            Err(super::ArchiveError::MissingFile { file, .. }) if file == "index.ftd"
+        ));
+
+        let mut wrong_checksum = manifest.clone();
+        wrong_checksum.checksum = super::content_checksum(std::iter::empty());
+        assert!(matches!(
+            super::verify_package_content("a.com", &wrong_checksum, &contents),
+            Err(super::ArchiveError::ChecksumMismatch { .. })
+        ));
+    }
+}
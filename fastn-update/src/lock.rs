@@ -0,0 +1,103 @@
+use snafu::ResultExt;
+
+/// `fastn update` records the exact version of every dependency, direct or not, in this file in
+/// the package root. Other commands install what is recorded here, so a build gets the same
+/// dependencies on every machine till someone runs `fastn update` again.
+pub const LOCK_FILE: &str = "fastn.lock";
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, PartialEq)]
+pub struct LockFile {
+    pub packages: std::collections::BTreeMap<String, LockedPackage>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct LockedPackage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub zip_url: String,
+    /// The `checksum` from the manifest of the installed package.
+    pub checksum: String,
+}
+
+impl LockFile {
+    pub(crate) fn from_manifests(all_packages: &[(String, fastn_core::Manifest)]) -> LockFile {
+        LockFile {
+            packages: all_packages
+                .iter()
+                .map(|(name, manifest)| {
+                    (
+                        name.to_string(),
+                        LockedPackage {
+                            version: manifest.version.clone(),
+                            zip_url: manifest.zip_url.to_string(),
+                            checksum: manifest.checksum.to_string(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// `None` if the package has no lock file yet.
+    pub async fn read(
+        ds: &fastn_ds::DocumentStore,
+    ) -> Result<Option<LockFile>, fastn_update::LockError> {
+        match ds.read_content(&ds.root().join(LOCK_FILE)).await {
+            Ok(bytes) => Ok(Some(
+                serde_json::de::from_slice(&bytes)
+                    .context(fastn_update::DeserializeLockFileSnafu)?,
+            )),
+            Err(fastn_ds::ReadError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e).context(fastn_update::ReadLockFileSnafu),
+        }
+    }
+
+    pub async fn write(&self, ds: &fastn_ds::DocumentStore) -> Result<(), fastn_update::LockError> {
+        let mut content =
+            serde_json::ser::to_vec_pretty(self).context(fastn_update::SerializeLockFileSnafu)?;
+        // Append newline character
+        content.push(b'\n');
+
+        ds.write_content(&ds.root().join(LOCK_FILE), &content)
+            .await
+            .context(fastn_update::WriteLockFileSnafu)
+    }
+}
+
+impl LockedPackage {
+    /// The package we installed for a locked dependency must be the one we locked.
+    pub(crate) fn verify(
+        &self,
+        package_name: &str,
+        manifest: &fastn_core::Manifest,
+    ) -> Result<(), fastn_update::LockError> {
+        if manifest.checksum != self.checksum {
+            return Err(fastn_update::LockError::LockedChecksumMismatch {
+                package: package_name.to_string(),
+                expected: self.checksum.to_string(),
+                found: manifest.checksum.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The archive at `zip_url` must still have the content we locked, `manifest` is the one
+    /// built from it.
+    pub(crate) fn verify_archive(
+        &self,
+        package_name: &str,
+        manifest: &fastn_core::Manifest,
+    ) -> Result<(), fastn_update::LockError> {
+        if manifest.checksum != self.checksum {
+            return Err(fastn_update::LockError::LockNotReproducible {
+                package: package_name.to_string(),
+                zip_url: self.zip_url.to_string(),
+                expected: self.checksum.to_string(),
+                found: manifest.checksum.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
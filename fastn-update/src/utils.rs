@@ -25,17 +25,10 @@ pub async fn read_current_package(
     from_fastn_doc(ds, &fastn_path).await
 }
 
-pub(crate) async fn download_archive(
-    ds: &fastn_ds::DocumentStore,
-    url: String,
-) -> fastn_core::Result<zip::ZipArchive<std::io::Cursor<bytes::Bytes>>> {
-    use std::io::Seek;
-
-    let zipball = fastn_core::http::http_get(ds, &url).await?;
-    let mut zipball_cursor = std::io::Cursor::new(zipball);
-    zipball_cursor.seek(std::io::SeekFrom::Start(0))?;
-    let archive = zip::ZipArchive::new(zipball_cursor)?;
-    Ok(archive)
+pub(crate) fn open_archive(
+    archive: bytes::Bytes,
+) -> zip::result::ZipResult<zip::ZipArchive<std::io::Cursor<bytes::Bytes>>> {
+    zip::ZipArchive::new(std::io::Cursor::new(archive))
 }
 
 pub(crate) fn read_manifest(
@@ -0,0 +1,201 @@
+use snafu::ResultExt;
+
+/// Which version of a dependency to install.
+pub(crate) enum Target<'a> {
+    /// The dependency has no `version`, we install whatever the package publishes now.
+    Latest,
+    /// The highest version matching the `version` of the dependency.
+    Matching(semver::VersionReq),
+    /// The version recorded in `fastn.lock`, it matches the `version` of the dependency, if any.
+    Locked(&'a fastn_update::lock::LockedPackage),
+}
+
+/// Where to get the content of a dependency from.
+pub(crate) enum Resolution {
+    /// The `manifest.json` the package publishes, and the archive it points to.
+    Manifest {
+        manifest: fastn_core::Manifest,
+        bytes: bytes::Bytes,
+    },
+    /// An archive with no `manifest.json`, like a git tag, the manifest is built from the content
+    /// of the archive.
+    Archive {
+        zip_url: String,
+        version: Option<String>,
+    },
+}
+
+pub(crate) fn target<'a>(
+    dependency: &fastn_core::package::dependency::Dependency,
+    lock: Option<&'a fastn_update::lock::LockFile>,
+) -> Result<Target<'a>, fastn_update::VersionError> {
+    let package_name = dependency.package.name.as_str();
+    let constraint = match dependency.version.as_ref() {
+        Some(v) => Some(semver::VersionReq::parse(v).context(
+            fastn_update::InvalidVersionConstraintSnafu {
+                package: package_name,
+                constraint: v,
+            },
+        )?),
+        None => None,
+    };
+
+    if let Some(locked) = lock.and_then(|l| l.packages.get(package_name)) {
+        // if `version` was changed in FASTN.ftd since the lock was written, we resolve again
+        if constraint
+            .as_ref()
+            .map_or(true, |c| matches(c, locked.version.as_deref()))
+        {
+            return Ok(Target::Locked(locked));
+        }
+    }
+
+    Ok(match constraint {
+        Some(c) => Target::Matching(c),
+        None => Target::Latest,
+    })
+}
+
+pub(crate) async fn resolve(
+    ds: &fastn_ds::DocumentStore,
+    package_name: &str,
+    target: &Target<'_>,
+) -> Result<Resolution, fastn_update::UpdateError> {
+    let manifest = fastn_update::utils::get_manifest(ds, package_name).await;
+
+    match target {
+        Target::Latest => {
+            let (manifest, bytes) = manifest?;
+            Ok(Resolution::Manifest { manifest, bytes })
+        }
+        Target::Locked(locked) => match manifest {
+            // the package still publishes the version we locked
+            Ok((manifest, bytes)) if manifest.checksum == locked.checksum => {
+                Ok(Resolution::Manifest { manifest, bytes })
+            }
+            _ => Ok(Resolution::Archive {
+                zip_url: locked.zip_url.to_string(),
+                version: locked.version.clone(),
+            }),
+        },
+        Target::Matching(constraint) => {
+            if let Ok((manifest, bytes)) = manifest {
+                if matches(constraint, manifest.version.as_deref()) {
+                    return Ok(Resolution::Manifest { manifest, bytes });
+                }
+            }
+
+            // older versions are only available as tags of the github repository
+            let no_match = fastn_update::NoMatchingVersionSnafu {
+                package: package_name,
+                constraint: constraint.to_string(),
+            };
+            let (username, repository) =
+                match fastn_core::manifest::utils::extract_github_details(package_name) {
+                    Some(v) => v,
+                    None => return Err(no_match.build().into()),
+                };
+            let tags = github_tags(ds, package_name, &username, &repository).await?;
+            match best_match(tags.as_slice(), constraint) {
+                Some((tag, version)) => Ok(Resolution::Archive {
+                    zip_url: format!(
+                        "https://codeload.github.com/{username}/{repository}/zip/refs/tags/{tag}"
+                    ),
+                    version: Some(version.to_string()),
+                }),
+                None => Err(no_match.build().into()),
+            }
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct GithubTag {
+    name: String,
+}
+
+async fn github_tags(
+    ds: &fastn_ds::DocumentStore,
+    package_name: &str,
+    username: &str,
+    repository: &str,
+) -> Result<Vec<String>, fastn_update::VersionError> {
+    // github rejects api requests without a user agent
+    let headers =
+        std::collections::HashMap::from([("User-Agent".to_string(), "fastn".to_string())]);
+    let bytes = fastn_core::http::http_get_with_cookie(
+        ds,
+        &Default::default(),
+        format!("https://api.github.com/repos/{username}/{repository}/tags?per_page=100").as_str(),
+        &headers,
+        false,
+    )
+    .await
+    .and_then(|(r, _)| r)
+    .context(fastn_update::ListTagsSnafu {
+        package: package_name,
+    })?;
+
+    let tags: Vec<GithubTag> =
+        serde_json::de::from_slice(&bytes).context(fastn_update::DeserializeTagsSnafu {
+            package: package_name,
+        })?;
+
+    Ok(tags.into_iter().map(|t| t.name).collect())
+}
+
+/// Tags can be `1.2.3` or `v1.2.3`, tags that are not versions are ignored.
+fn parse_version(v: &str) -> Option<semver::Version> {
+    semver::Version::parse(v.strip_prefix('v').unwrap_or(v)).ok()
+}
+
+fn matches(constraint: &semver::VersionReq, version: Option<&str>) -> bool {
+    version
+        .and_then(parse_version)
+        .map_or(false, |v| constraint.matches(&v))
+}
+
+fn best_match(
+    tags: &[String],
+    constraint: &semver::VersionReq,
+) -> Option<(String, semver::Version)> {
+    tags.iter()
+        .filter_map(|tag| parse_version(tag).map(|v| (tag.to_string(), v)))
+        .filter(|(_, v)| constraint.matches(v))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn best_match() {
+        let tags = [
+            "v1.0.0",
+            "v1.2.0",
+            "1.3.1",
+            "v2.0.0",
+            "v1.4.0-beta",
+            "latest",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+        let best = |c: &str| {
+            super::best_match(&tags, &semver::VersionReq::parse(c).unwrap()).map(|(t, _)| t)
+        };
+
+        assert_eq!(best("^1"), Some("1.3.1".to_string()));
+        assert_eq!(best("~1.2"), Some("v1.2.0".to_string()));
+        assert_eq!(best(">=2"), Some("v2.0.0".to_string()));
+        assert_eq!(best("^3"), None);
+    }
+
+    #[test]
+    fn matches() {
+        let constraint = semver::VersionReq::parse("^1.2").unwrap();
+        assert!(super::matches(&constraint, Some("1.2.5")));
+        assert!(super::matches(&constraint, Some("v1.9.0")));
+        assert!(!super::matches(&constraint, Some("2.0.0")));
+        assert!(!super::matches(&constraint, None));
+    }
+}
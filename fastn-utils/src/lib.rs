@@ -97,6 +97,14 @@ pub enum SqlError {
     FoundBlob,
     #[error("unknown db error")]
     UnknownDB,
+    #[error("postgres error {0}")]
+    Postgres(#[from] tokio_postgres::Error),
+    #[error("postgres pool error {0}")]
+    PgPool(#[from] deadpool_postgres::PoolError),
+    #[error("postgres create pool error {0}")]
+    CreatePool(#[from] deadpool_postgres::CreatePoolError),
+    #[error("unsupported type for column {0}")]
+    UnsupportedColumnType(usize),
 }
 
 pub fn rows_to_json(
@@ -12,7 +12,11 @@ pub fn main() {
 
 async fn outer_main() {
     if let Err(e) = async_main().await {
-        eprintln!("{:?}", e);
+        match e {
+            // errors in documents are shown with the lines around them
+            Error::FastnCoreError(fastn_core::Error::Diagnostic(d)) => eprintln!("{d}"),
+            e => eprintln!("{:?}", e),
+        }
         std::process::exit(1);
     }
 }
@@ -63,10 +67,27 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
     let ds = fastn_ds::DocumentStore::new(current_dir, pg_pools);
 
     if let Some(update) = matches.subcommand_matches("update") {
+        if update.get_flag("verify") {
+            return fastn_update::verify(&ds).await;
+        }
         let check = update.get_flag("check");
         return fastn_update::update(&ds, check).await;
     }
 
+    if matches.subcommand_matches("vendor").is_some() {
+        return fastn_update::vendor(&ds).await;
+    }
+
+    if matches.subcommand_matches("lsp").is_some() {
+        return fastn_core::lsp(ds).await;
+    }
+
+    if let Some(fmt) = matches.subcommand_matches("fmt") {
+        if fmt.get_flag("stdin") {
+            return fastn_core::fmt_stdin(fmt.get_flag("noidentation"));
+        }
+    }
+
     if let Some(serve) = matches.subcommand_matches("serve") {
         let port = serve.value_of_("port").map(|p| match p.parse::<u16>() {
             Ok(v) => v,
@@ -83,9 +104,24 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
         let external_css = serve.values_of_("external-css");
         let inline_css = serve.values_of_("css");
         let offline = serve.get_flag("offline");
+        let live_reload = serve.get_flag("live-reload");
+        let source_maps = serve.get_flag("source-maps");
+        let tls = match (serve.value_of_("tls-cert"), serve.value_of_("tls-key")) {
+            (Some(cert), Some(key)) => Some(fastn_core::Tls::Pem {
+                cert: cert.into(),
+                key: key.into(),
+            }),
+            _ if serve.get_flag("tls-self-signed") => Some(fastn_core::Tls::SelfSigned),
+            _ => None,
+        };
+        let response_cache = match serve.value_of_("cache-dir") {
+            Some(dir) => Some(fastn_core::response_cache::Storage::Disk(dir.into())),
+            None if serve.get_flag("cache") => Some(fastn_core::response_cache::Storage::Memory),
+            None => None,
+        };
 
         if cfg!(feature = "use-config-json") && !offline {
-            fastn_update::update(&ds, false).await?;
+            fastn_update::sync(&ds).await?;
         }
 
         let config = fastn_core::Config::read(ds, false)
@@ -94,9 +130,12 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
             .add_external_js(external_js.clone())
             .add_inline_js(inline_js.clone())
             .add_external_css(external_css.clone())
-            .add_inline_css(inline_css.clone());
+            .add_inline_css(inline_css.clone())
+            .set_live_reload(live_reload)
+            .set_source_maps(source_maps)
+            .set_response_cache(response_cache);
 
-        return fastn_core::listen(std::sync::Arc::new(config), bind.as_str(), port).await;
+        return fastn_core::listen(std::sync::Arc::new(config), bind.as_str(), port, tls).await;
     }
 
     if let Some(test) = matches.subcommand_matches("test") {
@@ -108,7 +147,7 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
         let offline: bool = test.get_flag("offline");
 
         if !offline {
-            fastn_update::update(&ds, false).await?;
+            fastn_update::sync(&ds).await?;
         }
 
         let mut config = fastn_core::Config::read(ds, true).await?;
@@ -146,7 +185,7 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
         let offline: bool = build.get_flag("offline");
 
         if !offline {
-            fastn_update::update(&ds, false).await?;
+            fastn_update::sync(&ds).await?;
         }
 
         let mut config = fastn_core::Config::read(ds, true).await?;
@@ -166,14 +205,36 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
             matches.get_flag("test"),
             build.get_flag("check-build"),
             zip_url,
+            build.get_one::<usize>("jobs").copied().unwrap_or(1).max(1),
         )
         .await;
     }
 
+    if let Some(migrate) = matches.subcommand_matches("migrate") {
+        let command = match migrate.subcommand() {
+            Some(("up", up)) => fastn_core::MigrateCommand::Up {
+                dry_run: up.get_flag("dry-run"),
+            },
+            Some(("down", down)) => fastn_core::MigrateCommand::Down {
+                count: down.get_one::<usize>("count").copied().unwrap_or(1),
+                dry_run: down.get_flag("dry-run"),
+            },
+            _ => fastn_core::MigrateCommand::Status,
+        };
+        let config = fastn_core::Config::read_without_migrations(ds, false).await?;
+        return fastn_core::migrate(&config, command).await;
+    }
+
     let config = fastn_core::Config::read(ds, true).await?;
 
     if let Some(fmt) = matches.subcommand_matches("fmt") {
-        return fastn_core::fmt(&config, fmt.value_of_("file"), fmt.get_flag("noidentation")).await;
+        return fastn_core::fmt(
+            &config,
+            fmt.value_of_("file"),
+            fmt.get_flag("noidentation"),
+            fmt.get_flag("check"),
+        )
+        .await;
     }
 
     if let Some(wasmc) = matches.subcommand_matches("wasmc") {
@@ -193,8 +254,11 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
         .await;
     }
 
-    if matches.subcommand_matches("check").is_some() {
-        return fastn_core::post_build_check(&config).await;
+    if let Some(check) = matches.subcommand_matches("check") {
+        if check.get_flag("build") {
+            return fastn_core::post_build_check(&config).await;
+        }
+        return fastn_core::type_check(&config).await;
     }
 
     Ok(())
@@ -266,6 +330,9 @@ fn app(version: &'static str) -> clap::Command {
                 .arg(clap::arg!(--"zip-url" <URL> "The zip archive url for this package"))
                 .arg(clap::arg!(--"ignore-failed" "Ignore failed files."))
                 .arg(clap::arg!(--"check-build" "Checks .build for index files validation."))
+                .arg(clap::arg!(-j --jobs <JOBS> "Number of documents to render at the same time")
+                    .value_parser(clap::value_parser!(usize))
+                    .default_value("1"))
                 .arg(clap::arg!(--"external-js" <URL> "Script added in ftd files")
                     .action(clap::ArgAction::Append))
                 .arg(clap::arg!(--"js" <URL> "Script text added in ftd files")
@@ -282,6 +349,8 @@ fn app(version: &'static str) -> clap::Command {
                 .about("Format the fastn package")
                 .arg(clap::arg!(file: [FILE]... "The file to format").required(false))
                 .arg(clap::arg!(-i --noidentation "No identation added to file/package").required(false))
+                .arg(clap::arg!(--check "Do not write the files, print a diff and fail if any file is not formatted"))
+                .arg(clap::arg!(--stdin "Format the document read from stdin and print it to stdout").conflicts_with_all(["file", "check"]))
         )
         .subcommand(
             clap::Command::new("wasmc")
@@ -310,20 +379,48 @@ fn app(version: &'static str) -> clap::Command {
         .subcommand(
             clap::Command::new("query")
                 .about("JSON Dump in various stages")
-                .arg(clap::arg!(--stage <STAGE> "The stage. Currently supported (p1)").required
-                (true))
+                .arg(clap::arg!(--stage <STAGE> "The stage: p1, ast, interpreter, executor, node or js")
+                    .required(true)
+                    .value_parser(fastn_core::commands::query::STAGES))
                 .arg(clap::arg!(-p --path [PATH] "The path of the file"))
                 .arg(clap::arg!(-n --null "JSON with null and empty list"))
         )
+        .subcommand(
+            clap::Command::new("lsp")
+                .about("Start a language server for the .ftd files of this package, over stdin and stdout")
+        )
         .subcommand(
             clap::Command::new("check")
-                .about("Check if everything is fine with current fastn package")
-                .hide(true) // hidden since the feature is not being released yet.
+                .about("Type check the .ftd files of this package, without running processors or rendering")
+                .arg(clap::arg!(--build "Only check that the .build folder has an index.html, as `fastn check` used to"))
+        )
+        .subcommand(
+            clap::Command::new("migrate")
+                .about("Manage database migrations of this fastn package")
+                .subcommand(clap::Command::new("status").about("List migrations and whether they are applied"))
+                .subcommand(
+                    clap::Command::new("up")
+                        .about("Apply all pending migrations")
+                        .arg(clap::arg!(--"dry-run" "Print the SQL instead of running it"))
+                )
+                .subcommand(
+                    clap::Command::new("down")
+                        .about("Revert the last <COUNT> applied migrations, using their `down` SQL")
+                        .arg(clap::arg!(count: [COUNT] "Number of migrations to revert")
+                            .value_parser(clap::value_parser!(usize))
+                            .default_value("1"))
+                        .arg(clap::arg!(--"dry-run" "Print the SQL instead of running it"))
+                )
         )
         .subcommand(
             clap::Command::new("update")
                 .about("Update dependency packages for this fastn package")
-                .arg(clap::arg!(--check "Check if packages are in sync with FASTN.ftd without performing updates."))
+                .arg(clap::arg!(--check "Check if packages are in sync with FASTN.ftd and fastn.lock without performing updates."))
+                .arg(clap::arg!(--verify "Verify the checksums of the packages in .packages, without performing updates.").conflicts_with("check"))
+        )
+        .subcommand(
+            clap::Command::new("vendor")
+                .about("Copy the locked dependency packages to .fastn-vendor, so they can be installed without network access")
         )
         .subcommand(sub_command::serve())
 }
@@ -347,7 +444,15 @@ mod sub_command {
             .arg(clap::arg!(--"css" <URL> "CSS text added in ftd files")
                 .action(clap::ArgAction::Append))
             .arg(clap::arg!(--"download-base-url" <URL> "If running without files locally, download needed files from here"))
-            .arg(clap::arg!(--offline "Disables automatic package update checks to operate in offline mode"));
+            .arg(clap::arg!(--offline "Disables automatic package update checks to operate in offline mode"))
+            .arg(clap::arg!(--"live-reload" "Reload pages in the browser when files in the package change"))
+            .arg(clap::arg!(--"source-maps" "Serve source maps that map the JS of pages back to the .ftd documents, for debugging in the browser"))
+            .arg(clap::arg!(--"tls-cert" <PATH> "Serve over HTTPS with this PEM certificate (chain)").requires("tls-key"))
+            .arg(clap::arg!(--"tls-key" <PATH> "The PEM private key of the --tls-cert certificate").requires("tls-cert"))
+            .arg(clap::arg!(--"tls-self-signed" "Serve over HTTPS with a self-signed certificate, for local development")
+                .conflicts_with("tls-cert"))
+            .arg(clap::arg!(--cache "Cache the rendered pages that do not depend on the request, instead of rendering them on every request"))
+            .arg(clap::arg!(--"cache-dir" <DIR> "Cache rendered pages in this directory too, so they survive restarts, implies --cache"));
         if cfg!(feature = "remote") {
             serve
         } else {
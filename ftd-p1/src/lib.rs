@@ -14,7 +14,9 @@ mod section;
 pub mod utils;
 
 pub use header::{AccessModifier, BlockRecordHeader, Header, Headers, SectionHeader, KV};
-pub use parser::{parse, parse_with_line_number};
+pub use parser::{
+    parse, parse_recovering, parse_recovering_with_line_number, parse_with_line_number,
+};
 pub use section::Body;
 pub use section::Section;
 
@@ -48,4 +50,16 @@ pub enum Error {
     },
 }
 
+impl Error {
+    pub fn line_number(&self) -> usize {
+        match self {
+            Error::SectionNotFound { line_number, .. }
+            | Error::MoreThanOneCaption { line_number, .. }
+            | Error::ParseError { line_number, .. }
+            | Error::MoreThanOneHeader { line_number, .. }
+            | Error::HeaderNotFound { line_number, .. } => *line_number,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
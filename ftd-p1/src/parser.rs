@@ -72,6 +72,62 @@ impl State {
         Ok(())
     }
 
+    /// Like `next()`, but after an error parsing resumes at the next `-- ` line after the error,
+    /// so all the errors of the document are collected. The section that had the error keeps
+    /// what was parsed of it before the error.
+    fn next_recovering(&mut self) -> Vec<ftd_p1::Error> {
+        let total_lines = self.content.trim_end().split('\n').count();
+        let first_line_number = self.line_number;
+        let mut errors: Vec<ftd_p1::Error> = vec![];
+        let mut recovered_at = None;
+
+        while let Err(e) = self.next() {
+            let line_number = e.line_number() as i32;
+            // nothing was parsed since the last recovery, the section line we resumed at is
+            // itself invalid, so it has to be skipped
+            let stuck = recovered_at == Some(self.content.len());
+            if !stuck || errors.last().map(ToString::to_string) != Some(e.to_string()) {
+                errors.push(e);
+            }
+
+            // `content` is always what is left of the original content, so the line it starts
+            // at is known from the number of lines left
+            let consumed = total_lines.saturating_sub(self.content.trim_end().split('\n').count());
+            let content_line_number = first_line_number + consumed as i32;
+            let lines = self.content.split('\n').collect::<Vec<_>>();
+            let start = if stuck {
+                lines
+                    .iter()
+                    .position(|l| valid_line(l) && !l.trim().is_empty())
+                    .map_or(lines.len(), |p| p + 1)
+            } else {
+                0
+            };
+            let next_section = lines.iter().enumerate().skip(start).find(|(i, line)| {
+                is_section_start(line) && content_line_number + (*i as i32) + 1 > line_number
+            });
+
+            match next_section {
+                Some((i, _)) => {
+                    self.content = content_index(self.content.as_str(), Some(i));
+                    self.line_number = content_line_number + i as i32;
+                    recovered_at = Some(self.content.len());
+                }
+                None => {
+                    self.content = "".to_string();
+                    break;
+                }
+            }
+        }
+
+        // the sections still in `state` are moved to `sections` only once the whole content is
+        // parsed, which is not the case if the document ends with an error
+        let sections = self.state.drain(..).map(|(v, _)| v).collect::<Vec<_>>();
+        self.sections.extend(sections);
+
+        errors
+    }
+
     fn end(&mut self, change_state: &mut Option<ParsingStateReading>) -> ftd_p1::Result<()> {
         let (scan_line_number, content) = self.clean_content();
         let (start_line, rest_lines) = new_line_split(content.as_str());
@@ -96,13 +152,21 @@ impl State {
                 let (section, state) = if let Some(state) = self.remove_latest_state() {
                     state
                 } else {
-                    let section =
-                        self.remove_latest_section()?
-                            .ok_or_else(|| ftd_p1::Error::ParseError {
+                    let section = match self.remove_latest_section() {
+                        Ok(Some(section)) => section,
+                        result => {
+                            // put the collected sections back, so a recovering parse keeps them
+                            for section in sections.into_iter().rev() {
+                                self.state.push((section, vec![]));
+                            }
+                            result?;
+                            return Err(ftd_p1::Error::ParseError {
                                 message: format!("No section found to end: {}", caption),
                                 doc_id: self.doc_id.to_string(),
                                 line_number: ftd_p1::utils::i32_to_usize(self.line_number),
-                            })?;
+                            });
+                        }
+                    };
                     sections.push(section);
                     continue;
                 };
@@ -819,6 +883,33 @@ pub fn parse_with_line_number(
     Ok(state.sections)
 }
 
+/// Like `parse`, but does not stop at the first error: the rest of a section with an error is
+/// skipped, and parsing resumes at the next section. The (partial) sections are returned along
+/// with every error found.
+pub fn parse_recovering(content: &str, doc_id: &str) -> (Vec<ftd_p1::Section>, Vec<ftd_p1::Error>) {
+    parse_recovering_with_line_number(content, doc_id, 0)
+}
+
+pub fn parse_recovering_with_line_number(
+    content: &str,
+    doc_id: &str,
+    line_number: usize,
+) -> (Vec<ftd_p1::Section>, Vec<ftd_p1::Error>) {
+    let mut state = State {
+        content: content.to_string(),
+        doc_id: doc_id.to_string(),
+        line_number: if line_number > 0 {
+            -(line_number as i32)
+        } else {
+            0
+        },
+        sections: Default::default(),
+        state: Default::default(),
+    };
+    let errors = state.next_recovering();
+    (state.sections, errors)
+}
+
 fn colon_separated_values(
     line_number: usize,
     line: &str,
@@ -993,6 +1084,19 @@ fn remove_inline_comments(line: &str) -> String {
     output.to_string()
 }
 
+/// Parsing can resume at a line starting a section after an error, but not at an `-- end:` line,
+/// as the section it ends may not have been parsed.
+fn is_section_start(line: &str) -> bool {
+    let line = line.trim_start();
+    match line
+        .strip_prefix("-- ")
+        .or_else(|| line.strip_prefix("/-- "))
+    {
+        Some(rest) => !rest.trim_start().starts_with("end:"),
+        None => false,
+    }
+}
+
 fn valid_line(line: &str) -> bool {
     !line.trim().starts_with(";;")
 }
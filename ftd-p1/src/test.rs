@@ -52,6 +52,18 @@ fn f(s: &str, m: &str) {
     }
 }
 
+#[track_caller]
+fn r(s: &str, t: &Vec<ftd_p1::Section>, m: &[&str]) {
+    let (sections, errors) = super::parse_recovering(s, "foo");
+    let data = sections
+        .iter()
+        .map(|v| v.without_line_number())
+        .collect::<Vec<ftd_p1::Section>>();
+    let expected_json = serde_json::to_string_pretty(&data).unwrap();
+    assert_eq!(t, &data, "Expected JSON: {}", expected_json);
+    assert_eq!(m, errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),);
+}
+
 #[test]
 fn p1_test_all() {
     // we are storing files in folder named `t` and not inside `tests`, because `cargo test`
@@ -916,3 +928,70 @@ fn kind() {
             .list(),
     );
 }
+
+#[test]
+fn recovering() {
+    r(
+        "-- foo: bar",
+        &ftd_p1::Section::with_name("foo").and_caption("bar").list(),
+        &[],
+    );
+
+    r(
+        indoc!(
+            "
+            -- foo:
+            This is body
+
+            -- bar:
+            k: v
+
+            -- baz
+
+            -- qux:
+
+            qux body
+            "
+        ),
+        &vec![
+            ftd_p1::Section::with_name("foo"),
+            ftd_p1::Section::with_name("bar").add_header_str("k", "v"),
+            ftd_p1::Section::with_name("qux").and_body("qux body"),
+        ],
+        &[
+            "foo:2 -> start section body 'This is body' after a newline!!",
+            "foo:6 -> : is missing in:  baz",
+        ],
+    );
+
+    r(
+        indoc!(
+            "
+            -- column:
+
+            -- text: hello
+            This is body
+
+            -- text: world
+
+            -- end: column
+
+            -- end: row
+            "
+        ),
+        &ftd_p1::Section::with_name("column")
+            .add_sub_section(ftd_p1::Section::with_name("text").and_caption("hello"))
+            .add_sub_section(ftd_p1::Section::with_name("text").and_caption("world"))
+            .list(),
+        &[
+            "foo:4 -> start section body 'This is body' after a newline!!",
+            "foo:8 -> No section found to end: row",
+        ],
+    );
+
+    r(
+        "invalid\n\n-- foo: bar",
+        &ftd_p1::Section::with_name("foo").and_caption("bar").list(),
+        &["foo:1 -> SectionNotFound"],
+    );
+}
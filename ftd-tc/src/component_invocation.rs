@@ -1,72 +1,211 @@
-use crate::{DocumentID, Lined, Qualified, Type};
-
 #[derive(Debug)]
 pub enum ComponentResolvable {
     /// First step is to resolve name, to see if there is any component with this name
     Name,
-    /// Then we check the `id`. So far we are using `id` as literal, but if id was a reference
-    /// we have to resolve and prove that reference is valid
-    Id,
-    Loop,
-    Property(String),
+    /// Then we check every property against the arguments of the component, by index in
+    /// `properties`
+    Property(usize),
+    /// And that a required argument is passed
     Argument(String),
-    Event(String),
-    Condition,
-    Child(i32),
 }
 
 #[derive(Debug)]
 pub struct CI {
     pub inner: ftd_ast::ComponentInvocation,
     pub to_resolve: Vec<ComponentResolvable>,
-    pub local_types: ftd_p1::Map<Type>,
+    /// the component being invoked, `None` if it is not known, eg `ftd.text`
+    pub component: Option<ftd_tc::Component>,
     pub js_buffer: String,
-    pub document_id: DocumentID,
+    pub document_id: ftd_tc::DocumentID,
+    /// names in scope that are not symbols of the document: the component being defined, eg
+    /// `card` in `-- card.icon:`, and the loop aliases of the parents of the invocation
+    pub locals: Vec<String>,
 }
 
 impl ftd_tc::State {
-    pub fn handle_ci_name(
-        &mut self,
-        ci: &mut CI,
-        thing: &mut ComponentResolvable,
-    ) -> ftd_tc::Result<Option<String>> {
-        // see if name exists in self.global_types, if so move on to verifying
-        // other stuff. if the name doesn't exist in global types, and belongs to
-        // another module, and the module is already loaded, we move to CD state,
-        // component definition. If the module is also not yet loaded we return
-        // module name to load.
-        match self.global_types.get(&ci.inner.name) {
-            Some(Qualified {
-                v: Type::Component(c),
-                ..
-            }) => {
-                // we are done with the first check, there is indeed a component with this name
-                // next we have to check the `id`.
-                ci.to_resolve.push(ComponentResolvable::Id);
-                // TODO: check if the v is accessible in the current document
-                Ok(None)
+    pub fn handle_ci_name(&mut self, ci: &mut CI) -> ftd_tc::Pending<()> {
+        // see if there is a component with this name. If it belongs to another module, and the
+        // module is not yet loaded we return the module name to load.
+        let name = ci.inner.name.as_str();
+        let head = name.split('.').next().unwrap_or(name);
+        // arguments of the component being defined, eg `-- card.icon:` or `-- bar.m.print:` for
+        // a `module m` argument, and loop items, eg `-- object:` with `for: $object in $uis`, are
+        // not modelled by the checker
+        let local = ci.locals.iter().any(|l| l == head)
+            || loop_aliases(ci.inner.iteration.as_ref()).any(|l| l == head);
+        let found = if local {
+            ftd_tc::Resolved::Unknown
+        } else {
+            self.find(name, ci.document_id.logical.as_str())?
+        };
+        let component = match found {
+            ftd_tc::Resolved::Found((key, member)) => {
+                match (self.definition(key.as_str())?, member) {
+                    (ftd_tc::Resolved::Found(ftd_tc::Definition::Component(c)), None) => Some(c),
+                    // a ui variable, or an item of a ui list, eg `-- uis.0:`
+                    (ftd_tc::Resolved::Found(ftd_tc::Definition::Variable(t)), _) if t.is_ui() => {
+                        None
+                    }
+                    (ftd_tc::Resolved::Found(d), None) => {
+                        self.errors.push(ftd_tc::Error::NotAComponent {
+                            name: name.to_string(),
+                            usage_document: ci.document_id.clone(),
+                            usage_line: ci.inner.line_number,
+                            found: d.kind_name(),
+                        });
+                        None
+                    }
+                    (ftd_tc::Resolved::Found(_), Some(_)) | (ftd_tc::Resolved::Missing, _) => {
+                        self.errors.push(ftd_tc::Error::NotFound {
+                            kind: "component",
+                            name: name.to_string(),
+                            usage_document: ci.document_id.clone(),
+                            usage_line: ci.inner.line_number,
+                        });
+                        None
+                    }
+                    (ftd_tc::Resolved::Unknown, _) => None,
+                }
             }
-            Some(t) => {
-                self.errors.push(ftd_tc::Error::NotAComponent {
-                    name: ci.inner.name.clone(),
+            ftd_tc::Resolved::Unknown => None,
+            ftd_tc::Resolved::Missing => {
+                self.errors.push(ftd_tc::Error::NotFound {
+                    kind: "component",
+                    name: name.to_string(),
                     usage_document: ci.document_id.clone(),
                     usage_line: ci.inner.line_number,
-                    found: t.to_owned(),
                 });
-                Ok(None)
+                None
             }
-            None => match self.symbols.get(&ci.inner.name) {
-                Some(Lined {
-                    v: ftd_ast::Ast::ComponentDefinition(c),
-                    ..
-                }) => {
-                    todo!()
+        };
+
+        // properties are checked even for components we do not know, for the functions they
+        // call
+        if let Some(component) = component.as_ref() {
+            for arg in component.args.iter().rev() {
+                if arg.is_required() {
+                    ci.to_resolve
+                        .push(ComponentResolvable::Argument(arg.name.clone()));
                 }
-                Some(t) => {
-                    todo!("syntax error, foo is not a component")
+            }
+        }
+        for i in (0..ci.inner.properties.len()).rev() {
+            ci.to_resolve.push(ComponentResolvable::Property(i));
+        }
+        ci.component = component;
+
+        for event in ci.inner.events.iter() {
+            self.push_function_call(event.action.as_str(), event.line_number, &ci.document_id);
+        }
+        let locals: Vec<String> = ci
+            .locals
+            .iter()
+            .cloned()
+            .chain(loop_aliases(ci.inner.iteration.as_ref()).map(str::to_string))
+            .collect();
+        for child in ci.inner.children.iter() {
+            self.continuable_things
+                .push(ftd_tc::ContinuableThing::from_component_invocation(
+                    child.clone(),
+                    ci.document_id.clone(),
+                    locals.clone(),
+                ));
+        }
+
+        Ok(())
+    }
+
+    fn handle_ci_property(&mut self, ci: &CI, index: usize) -> ftd_tc::Pending<()> {
+        let property = &ci.inner.properties[index];
+        let Some(component) = ci.component.as_ref() else {
+            self.push_function_calls(&property.value, &ci.document_id);
+            return Ok(());
+        };
+        let of = format!("component `{}`", ci.inner.name);
+
+        let mut errors = vec![];
+        let (arg, whole) = match &property.source {
+            ftd_ast::PropertySource::Caption => (component.args.iter().find(|a| a.caption), true),
+            ftd_ast::PropertySource::Body => (component.args.iter().find(|a| a.body), true),
+            ftd_ast::PropertySource::Header { name, mutable } => {
+                // `border.px: 2` passes a part of the `border` argument
+                let (arg_name, rest) = match name.split_once('.') {
+                    Some((arg_name, rest)) => (arg_name, Some(rest)),
+                    None => (name.as_str(), None),
+                };
+                let arg = component.arg(arg_name);
+                match arg {
+                    Some(arg) if arg.mutable != *mutable => {
+                        errors.push(ftd_tc::Error::MutabilityMismatch {
+                            name: arg.name.clone(),
+                            mutable: arg.mutable,
+                            usage_document: ci.document_id.clone(),
+                            usage_line: property.line_number,
+                        })
+                    }
+                    Some(_) => {}
+                    // extra headers are collected in the `kw-args` argument, if there is one
+                    None if component
+                        .args
+                        .iter()
+                        .any(|a| a.type_ == ftd_tc::Type::KwArgs) => {}
+                    // `id` can be passed to any component
+                    None if name == "id" => {}
+                    None => errors.push(ftd_tc::Error::UnknownArgument {
+                        name: arg_name.to_string(),
+                        of: of.clone(),
+                        usage_document: ci.document_id.clone(),
+                        usage_line: property.line_number,
+                    }),
                 }
-                None => todo!(),
-            },
+                (arg, rest.is_none())
+            }
+        };
+
+        match (arg, &property.source) {
+            (Some(arg), _) if whole => {
+                self.check_value(&arg.type_, &property.value, &ci.document_id, &mut errors)?
+            }
+            (None, ftd_ast::PropertySource::Caption) | (None, ftd_ast::PropertySource::Body) => {
+                errors.push(ftd_tc::Error::NoCaptionOrBody {
+                    source_: if matches!(property.source, ftd_ast::PropertySource::Caption) {
+                        "caption"
+                    } else {
+                        "body"
+                    },
+                    of,
+                    usage_document: ci.document_id.clone(),
+                    usage_line: property.line_number,
+                })
+            }
+            _ => {}
+        }
+
+        self.errors.extend(errors);
+        self.push_function_calls(&property.value, &ci.document_id);
+        Ok(())
+    }
+
+    fn handle_ci_argument(&mut self, ci: &CI, name: &str) {
+        let Some(arg) = ci.component.as_ref().and_then(|c| c.arg(name)) else {
+            return;
+        };
+
+        let passed = ci.inner.properties.iter().any(|p| match &p.source {
+            ftd_ast::PropertySource::Caption => arg.caption,
+            ftd_ast::PropertySource::Body => arg.body,
+            ftd_ast::PropertySource::Header { name, .. } => {
+                name.split('.').next() == Some(arg.name.as_str())
+            }
+        });
+
+        if !passed {
+            self.errors.push(ftd_tc::Error::MissingArgument {
+                name: name.to_string(),
+                of: format!("component `{}`", ci.inner.name),
+                usage_document: ci.document_id.clone(),
+                usage_line: ci.inner.line_number,
+            });
         }
     }
 
@@ -74,23 +213,26 @@ impl ftd_tc::State {
         &mut self,
         c: &mut CI,
         thing: &mut ComponentResolvable,
-    ) -> ftd_tc::Result<Option<String>> {
+    ) -> ftd_tc::Pending<()> {
         match thing {
-            ComponentResolvable::Name => self.handle_ci_name(c, thing),
-            ComponentResolvable::Id => todo!(),
-            _ => Ok(None),
+            ComponentResolvable::Name => self.handle_ci_name(c),
+            ComponentResolvable::Property(index) => self.handle_ci_property(c, *index),
+            ComponentResolvable::Argument(name) => {
+                self.handle_ci_argument(c, name.as_str());
+                Ok(())
+            }
         }
     }
 
-    pub fn resolve_component_invocation(&mut self, c: &mut CI) -> ftd_tc::Result<Option<String>> {
+    pub fn resolve_component_invocation(&mut self, c: &mut CI) -> ftd_tc::Pending<()> {
         while let Some(mut thing) = c.to_resolve.pop() {
-            if let Some(document) = self.handle_ci_thing(c, &mut thing)? {
+            if let Err(import) = self.handle_ci_thing(c, &mut thing) {
                 c.to_resolve.push(thing);
-                return Ok(Some(document));
+                return Err(import);
             }
         }
 
-        Ok(None)
+        Ok(())
     }
 }
 
@@ -98,13 +240,24 @@ impl ftd_tc::ContinuableThing {
     pub fn from_component_invocation(
         c: ftd_ast::ComponentInvocation,
         document_id: ftd_tc::DocumentID,
+        locals: Vec<String>,
     ) -> Self {
         ftd_tc::ContinuableThing::CI(ftd_tc::CI {
             inner: c,
-            local_types: ftd_p1::Map::new(),
+            component: None,
             js_buffer: String::new(),
             to_resolve: vec![ftd_tc::ComponentResolvable::Name],
             document_id,
+            locals,
         })
     }
 }
+
+/// the names a loop binds, eg `object` and `i` of `for: $object, i in $uis`
+fn loop_aliases(iteration: Option<&ftd_ast::Loop>) -> impl Iterator<Item = &str> {
+    iteration.into_iter().flat_map(|l| {
+        std::iter::once(l.alias.as_str())
+            .chain(l.loop_counter_alias.as_deref())
+            .map(|a| a.trim_start_matches('$'))
+    })
+}
@@ -0,0 +1,102 @@
+impl ftd_tc::ContinuableThing {
+    /// The thing to check for a definition in the main document: the value of a variable, or
+    /// the kinds of the fields of a record, or-type, component or function.
+    pub fn from_ast(ast: &ftd_ast::Ast, document_id: ftd_tc::DocumentID) -> Self {
+        match ast {
+            ftd_ast::Ast::VariableDefinition(v) => ftd_tc::ContinuableThing::RI(ftd_tc::RI {
+                inner: v.clone(),
+                document_id,
+            }),
+            ast => ftd_tc::ContinuableThing::DI(ftd_tc::DI {
+                inner: ast.clone(),
+                document_id,
+            }),
+        }
+    }
+}
+
+impl ftd_tc::State {
+    /// Checks the kinds of the fields of a definition, and their default values.
+    fn check_fields(
+        &mut self,
+        fields: &[ftd_ast::Field],
+        doc_id: &ftd_tc::DocumentID,
+        errors: &mut Vec<ftd_tc::Error>,
+    ) -> ftd_tc::Pending<()> {
+        for field in fields {
+            let (type_, _, _) =
+                self.resolve_kind(&field.kind, doc_id, field.line_number, errors)?;
+            if let Some(value) = field.value.as_ref() {
+                self.check_value(&type_, value, doc_id, errors)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn resolve_definition(&mut self, d: &mut ftd_tc::DI) -> ftd_tc::Pending<()> {
+        let doc_id = &d.document_id;
+        let mut errors = vec![];
+
+        match &d.inner {
+            ftd_ast::Ast::Record(r) => {
+                self.check_fields(r.fields.as_slice(), doc_id, &mut errors)?
+            }
+            ftd_ast::Ast::OrType(o) => {
+                for variant in o.variants.iter() {
+                    match variant {
+                        ftd_ast::OrTypeVariant::AnonymousRecord(r) => {
+                            self.check_fields(r.fields.as_slice(), doc_id, &mut errors)?
+                        }
+                        ftd_ast::OrTypeVariant::Regular(f)
+                        | ftd_ast::OrTypeVariant::Constant(f) => {
+                            self.check_fields(std::slice::from_ref(f), doc_id, &mut errors)?
+                        }
+                    }
+                }
+            }
+            ftd_ast::Ast::ComponentDefinition(c) => {
+                self.check_fields(c.arguments.as_slice(), doc_id, &mut errors)?
+            }
+            ftd_ast::Ast::WebComponentDefinition(w) => {
+                self.check_fields(w.arguments.as_slice(), doc_id, &mut errors)?
+            }
+            ftd_ast::Ast::FunctionDefinition(f) => {
+                self.check_fields(f.arguments.as_slice(), doc_id, &mut errors)?;
+                self.resolve_kind(&f.kind, doc_id, f.line_number, &mut errors)?;
+            }
+            ftd_ast::Ast::Import(_)
+            | ftd_ast::Ast::VariableDefinition(_)
+            | ftd_ast::Ast::VariableInvocation(_)
+            | ftd_ast::Ast::ComponentInvocation(_) => {}
+        }
+
+        self.errors.extend(errors);
+        // the body of a component is checked like any other component invocation
+        if let ftd_ast::Ast::ComponentDefinition(c) = &d.inner {
+            self.continuable_things
+                .push(ftd_tc::ContinuableThing::from_component_invocation(
+                    c.definition.clone(),
+                    doc_id.clone(),
+                    vec![c.name.clone()],
+                ));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn resolve_record_invocation(&mut self, r: &mut ftd_tc::RI) -> ftd_tc::Pending<()> {
+        let v = &r.inner;
+        let mut errors = vec![];
+
+        let (type_, _, _) =
+            self.resolve_kind(&v.kind, &r.document_id, v.line_number, &mut errors)?;
+        // the value of a variable with a processor comes from the processor, we do not run it
+        if v.processor.is_none() {
+            self.check_value(&type_, &v.value, &r.document_id, &mut errors)?;
+        }
+
+        self.errors.extend(errors);
+        self.push_function_calls(&v.value, &r.document_id);
+        Ok(())
+    }
+}
@@ -3,13 +3,174 @@ pub enum Error {
     #[error("ast: {0}")]
     Ast(#[from] ftd_ast::Error),
 
-    #[error("ftd-p1: {name}")]
+    #[error("{}:{usage_line} -> {kind} `{name}` not found", usage_document.physical)]
+    NotFound {
+        /// what we were looking for, eg `component` or `type`
+        kind: &'static str,
+        name: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> `{name}` is a {found}, not a component", usage_document.physical)]
     NotAComponent {
         name: String,
         usage_document: ftd_tc::DocumentID,
         usage_line: usize,
-        found: ftd_tc::Qualified<ftd_tc::Type>,
+        found: &'static str,
+    },
+
+    #[error("{}:{usage_line} -> `{name}` is a {found}, not a type", usage_document.physical)]
+    NotAType {
+        name: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+        found: &'static str,
+    },
+
+    #[error("{}:{usage_line} -> `{name}` is a {found}, not a function", usage_document.physical)]
+    NotAFunction {
+        name: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+        found: &'static str,
+    },
+
+    #[error("{}:{usage_line} -> {of} has no argument `{name}`", usage_document.physical)]
+    UnknownArgument {
+        name: String,
+        /// the thing invoked, eg "component `foo`"
+        of: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> argument `{name}` of {of} is not passed", usage_document.physical)]
+    MissingArgument {
+        name: String,
+        of: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> {of} takes no {source_}", usage_document.physical)]
+    NoCaptionOrBody {
+        /// `caption` or `body`
+        source_: &'static str,
+        of: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> record `{record}` has no field `{name}`", usage_document.physical)]
+    UnknownField {
+        name: String,
+        record: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> field `{name}` of record `{record}` is not passed", usage_document.physical)]
+    MissingField {
+        name: String,
+        record: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> or-type `{or_type}` has no variant `{name}`", usage_document.physical)]
+    UnknownVariant {
+        name: String,
+        or_type: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> argument `{name}` is {}, use `{}{name}`", usage_document.physical, if *mutable { "mutable" } else { "immutable" }, if *mutable { "$" } else { "" })]
+    MutabilityMismatch {
+        name: String,
+        /// if the argument is mutable
+        mutable: bool,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
+    },
+
+    #[error("{}:{usage_line} -> expected {expected}, found `{value}`", usage_document.physical)]
+    TypeMismatch {
+        expected: &'static str,
+        value: String,
+        usage_document: ftd_tc::DocumentID,
+        usage_line: usize,
     },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// the document and line the error is in, parse errors carry their own location
+    pub fn location(&self) -> Option<(&ftd_tc::DocumentID, usize)> {
+        match self {
+            Error::Ast(_) => None,
+            Error::NotFound {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::NotAComponent {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::NotAType {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::NotAFunction {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::UnknownArgument {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::MissingArgument {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::NoCaptionOrBody {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::UnknownField {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::MissingField {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::UnknownVariant {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::MutabilityMismatch {
+                usage_document,
+                usage_line,
+                ..
+            }
+            | Error::TypeMismatch {
+                usage_document,
+                usage_line,
+                ..
+            } => Some((usage_document, *usage_line)),
+        }
+    }
+}
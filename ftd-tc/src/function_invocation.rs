@@ -0,0 +1,156 @@
+impl ftd_tc::FI {
+    /// The function call in a value, if the value is one, eg `$add(a = 1, b = $x)`.
+    pub fn from_call(
+        value: &str,
+        line_number: usize,
+        document_id: &ftd_tc::DocumentID,
+    ) -> Option<Self> {
+        let (name, arguments) = value.trim().strip_prefix('$')?.split_once('(')?;
+        let arguments = arguments.strip_suffix(')')?;
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return None;
+        }
+
+        let arguments = split_arguments(arguments)?
+            .into_iter()
+            .map(|argument| {
+                argument
+                    .split_once('=')
+                    .map(|(key, _)| key.trim().trim_start_matches('$').to_string())
+            })
+            .collect();
+
+        Some(ftd_tc::FI {
+            name: name.to_string(),
+            arguments,
+            line_number,
+            document_id: document_id.clone(),
+        })
+    }
+}
+
+/// Splits the arguments of a call at the commas that are not in a nested call or a string.
+/// `None` if the call ends before the last parenthesis, eg in `$a(x = 1) + $b(y = 2)`.
+fn split_arguments(arguments: &str) -> Option<Vec<&str>> {
+    let mut result = vec![];
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut start = 0;
+
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '(' | '[' if !in_string => depth += 1,
+            ')' | ']' if !in_string => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+            }
+            ',' if !in_string && depth == 0 => {
+                result.push(&arguments[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    result.push(&arguments[start..]);
+
+    Some(
+        result
+            .into_iter()
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .collect(),
+    )
+}
+
+impl ftd_tc::State {
+    pub(crate) fn resolve_function_invocation(
+        &mut self,
+        f: &mut ftd_tc::FI,
+    ) -> ftd_tc::Pending<()> {
+        let (key, member) = match self.find(f.name.as_str(), f.document_id.logical.as_str())? {
+            ftd_tc::Resolved::Found(found) => found,
+            ftd_tc::Resolved::Unknown => return Ok(()),
+            ftd_tc::Resolved::Missing => {
+                self.errors.push(ftd_tc::Error::NotFound {
+                    kind: "function",
+                    name: f.name.clone(),
+                    usage_document: f.document_id.clone(),
+                    usage_line: f.line_number,
+                });
+                return Ok(());
+            }
+        };
+
+        let function = match (self.definition(key.as_str())?, member) {
+            (ftd_tc::Resolved::Found(ftd_tc::Definition::Function(function)), None) => function,
+            (ftd_tc::Resolved::Found(d), None) => {
+                self.errors.push(ftd_tc::Error::NotAFunction {
+                    name: f.name.clone(),
+                    usage_document: f.document_id.clone(),
+                    usage_line: f.line_number,
+                    found: d.kind_name(),
+                });
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+
+        // arguments passed by position are not checked
+        let Some(arguments) = f.arguments.as_ref() else {
+            return Ok(());
+        };
+        let of = format!("function `{}`", f.name);
+        for argument in arguments {
+            if !function.args.iter().any(|a| &a.name == argument) {
+                self.errors.push(ftd_tc::Error::UnknownArgument {
+                    name: argument.clone(),
+                    of: of.clone(),
+                    usage_document: f.document_id.clone(),
+                    usage_line: f.line_number,
+                });
+            }
+        }
+        for arg in function.args.iter() {
+            if arg.is_required() && !arguments.contains(&arg.name) {
+                self.errors.push(ftd_tc::Error::MissingArgument {
+                    name: arg.name.clone(),
+                    of: of.clone(),
+                    usage_document: f.document_id.clone(),
+                    usage_line: f.line_number,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn from_call() {
+        let doc_id = ftd_tc::DocumentID::new0("foo");
+
+        let fi = ftd_tc::FI::from_call("$add(a = 1, $b = $f(c = 2, d = 3))", 4, &doc_id).unwrap();
+        assert_eq!(fi.name, "add");
+        assert_eq!(fi.arguments, Some(vec!["a".to_string(), "b".to_string()]));
+
+        let fi = ftd_tc::FI::from_call("$lib.greet(name = \"a, b\")", 4, &doc_id).unwrap();
+        assert_eq!(fi.name, "lib.greet");
+        assert_eq!(fi.arguments, Some(vec!["name".to_string()]));
+
+        let fi = ftd_tc::FI::from_call("$len($list)", 4, &doc_id).unwrap();
+        assert_eq!(fi.arguments, None);
+
+        assert!(ftd_tc::FI::from_call("$a + $b", 4, &doc_id).is_none());
+        assert!(ftd_tc::FI::from_call("$a(x = 1) + $b(y = 2)", 4, &doc_id).is_none());
+        assert!(ftd_tc::FI::from_call("hello (world)", 4, &doc_id).is_none());
+    }
+}
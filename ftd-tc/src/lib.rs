@@ -1,11 +1,13 @@
-#![allow(unused)]
 #![deny(unused_crate_dependencies)]
 
 extern crate self as ftd_tc;
 
 mod component_invocation;
+mod definition;
 mod error;
+mod function_invocation;
 mod parser;
+mod resolve;
 mod state;
 mod types;
 
@@ -14,3 +16,6 @@ pub use error::*;
 pub use parser::parse_document_to_ast;
 pub use state::{State, TCState};
 pub use types::*;
+
+#[cfg(test)]
+mod test;
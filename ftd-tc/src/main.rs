@@ -1,3 +1,23 @@
 fn main() {
-    ftd_tc::parse_document_to_ast("-- import: foo", &ftd_tc::DocumentID::new0("foo")).unwrap();
+    let path = std::env::args().nth(1).expect("usage: ftd-tc <file.ftd>");
+    let source = std::fs::read_to_string(path.as_str()).unwrap();
+
+    // imports are not resolved, things from other documents are not checked
+    let mut s =
+        ftd_tc::State::from_document(source.as_str(), ftd_tc::DocumentID::new0(path.as_str()), 0);
+    let state = loop {
+        match s {
+            ftd_tc::TCState::StuckOnImport {
+                document, state, ..
+            } => s = state.continue_after_opaque_import(document.as_str()),
+            ftd_tc::TCState::Done(state) => break state,
+        }
+    };
+
+    for e in state.errors.iter() {
+        eprintln!("{e}");
+    }
+    if !state.errors.is_empty() {
+        std::process::exit(1);
+    }
 }
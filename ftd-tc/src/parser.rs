@@ -1,10 +1,25 @@
+/// Parses a document, `line_number` is the number of lines added before the source, eg by auto
+/// imports. Every syntax error is returned, along with the AST of the document if the rest of it
+/// could be parsed.
 pub fn parse_document_to_ast(
     source: &str,
     doc_id: &ftd_tc::DocumentID,
-) -> ftd_ast::Result<Vec<ftd_ast::Ast>> {
-    let sections = ftd_p1::parse(source, doc_id.logical.as_str())?;
-    let ast = ftd_ast::Ast::from_sections(sections.as_slice(), doc_id.logical.as_str())?;
-    println!("{:?}", ast);
+    line_number: usize,
+) -> (Option<Vec<ftd_ast::Ast>>, Vec<ftd_tc::Error>) {
+    let (sections, errors) =
+        ftd_p1::parse_recovering_with_line_number(source, doc_id.logical.as_str(), line_number);
+    if !errors.is_empty() {
+        return (
+            None,
+            errors
+                .into_iter()
+                .map(|e| ftd_tc::Error::Ast(e.into()))
+                .collect(),
+        );
+    }
 
-    Ok(ast)
+    match ftd_ast::Ast::from_sections(sections.as_slice(), doc_id.logical.as_str()) {
+        Ok(ast) => (Some(ast), vec![]),
+        Err(e) => (None, vec![e.into()]),
+    }
 }
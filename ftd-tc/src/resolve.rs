@@ -0,0 +1,552 @@
+/// re-exports can form a cycle, we give up after following this many of them
+const MAX_EXPORT_DEPTH: usize = 8;
+
+impl ftd_tc::State {
+    /// The module a name used in `doc` is defined in, and its name in that module. eg `lib.card`
+    /// in a document with `-- import: amitu.com/lib` is `card` of `amitu.com/lib`.
+    pub fn qualify(&self, name: &str, doc: &str) -> (String, String) {
+        let name = name.trim_start_matches('$');
+        let document = self.documents.get(doc);
+
+        if let Some((alias, rest)) = name.split_once('.') {
+            if let Some(module) = document.and_then(|d| d.aliases.get(alias)) {
+                return (module.to_string(), rest.to_string());
+            }
+        }
+
+        let head = name.split('.').next().unwrap_or(name);
+        if let Some(module) = document.and_then(|d| d.exposings.get(head)) {
+            return (module.to_string(), name.to_string());
+        }
+
+        (doc.to_string(), name.to_string())
+    }
+
+    /// Finds the symbol a name used in `doc` refers to. Returns its key in `symbols`, and the rest
+    /// of the name if it refers to a member of the symbol, eg `individual` of `lead.individual`.
+    pub fn find(
+        &self,
+        name: &str,
+        doc: &str,
+    ) -> ftd_tc::Pending<ftd_tc::Resolved<(String, Option<String>)>> {
+        let (module, thing) = self.qualify(name, doc);
+        let found = self.find_in(module.as_str(), thing.as_str(), doc, 0)?;
+        if !matches!(found, ftd_tc::Resolved::Missing) || module != doc {
+            return Ok(found);
+        }
+
+        let exposing_all = self
+            .documents
+            .get(doc)
+            .map(|d| d.exposing_all.clone())
+            .unwrap_or_default();
+        for module in exposing_all {
+            match self.find_in(module.as_str(), thing.as_str(), doc, 0)? {
+                ftd_tc::Resolved::Missing => continue,
+                found => return Ok(found),
+            }
+        }
+
+        Ok(ftd_tc::Resolved::Missing)
+    }
+
+    fn find_in(
+        &self,
+        module: &str,
+        thing: &str,
+        caller: &str,
+        depth: usize,
+    ) -> ftd_tc::Pending<ftd_tc::Resolved<(String, Option<String>)>> {
+        let Some(document) = self.documents.get(module) else {
+            return Err(ftd_tc::PendingImport {
+                module: module.to_string(),
+                caller: caller.to_string(),
+            });
+        };
+
+        let (head, member) = match thing.split_once('.') {
+            Some((head, member)) => (head, Some(member.to_string())),
+            None => (thing, None),
+        };
+        if document.opaque || document.foreign.iter().any(|f| f == head) {
+            return Ok(ftd_tc::Resolved::Unknown);
+        }
+
+        let key = format!("{module}#{thing}");
+        if self.symbols.contains_key(key.as_str()) {
+            return Ok(ftd_tc::Resolved::Found((key, None)));
+        }
+        let key = format!("{module}#{head}");
+        if self.symbols.contains_key(key.as_str()) {
+            return Ok(ftd_tc::Resolved::Found((key, member)));
+        }
+
+        if depth < MAX_EXPORT_DEPTH {
+            for (exported_module, export) in document.exports.iter() {
+                let exported = match export {
+                    ftd_ast::Export::All => true,
+                    ftd_ast::Export::Things(things) => things.iter().any(|t| t == head),
+                };
+                if !exported {
+                    continue;
+                }
+                match self.find_in(exported_module, thing, module, depth + 1)? {
+                    ftd_tc::Resolved::Missing => continue,
+                    found => return Ok(found),
+                }
+            }
+        }
+
+        Ok(ftd_tc::Resolved::Missing)
+    }
+
+    /// The definition of a symbol, `key` is as returned by `find()`.
+    pub fn definition(
+        &mut self,
+        key: &str,
+    ) -> ftd_tc::Pending<ftd_tc::Resolved<ftd_tc::Definition>> {
+        if let Some(q) = self.global_types.get(key) {
+            return Ok(ftd_tc::Resolved::Found(q.v.clone()));
+        }
+        let Some(lined) = self.symbols.get(key) else {
+            return Ok(ftd_tc::Resolved::Missing);
+        };
+        let (ast, doc_id, line_number) = (lined.v.clone(), lined.doc_id.clone(), lined.line_number);
+
+        // errors in the kinds of the definition are reported when its own document is checked
+        let mut errors = vec![];
+        let v = match &ast {
+            ftd_ast::Ast::Record(r) => ftd_tc::Definition::Record(ftd_tc::Record {
+                fields: self.fields(r.fields.as_slice(), &doc_id, &mut errors)?,
+            }),
+            ftd_ast::Ast::OrType(o) => ftd_tc::Definition::OrType(ftd_tc::OrType {
+                variants: o.variants.iter().map(|v| v.name()).collect(),
+            }),
+            ftd_ast::Ast::ComponentDefinition(c) => {
+                ftd_tc::Definition::Component(ftd_tc::Component {
+                    args: self.fields(c.arguments.as_slice(), &doc_id, &mut errors)?,
+                })
+            }
+            ftd_ast::Ast::WebComponentDefinition(w) => {
+                ftd_tc::Definition::Component(ftd_tc::Component {
+                    args: self.fields(w.arguments.as_slice(), &doc_id, &mut errors)?,
+                })
+            }
+            ftd_ast::Ast::FunctionDefinition(f) => ftd_tc::Definition::Function(ftd_tc::Function {
+                args: self.fields(f.arguments.as_slice(), &doc_id, &mut errors)?,
+                return_type: self
+                    .resolve_kind(&f.kind, &doc_id, f.line_number, &mut errors)?
+                    .0,
+            }),
+            ftd_ast::Ast::VariableDefinition(v) => ftd_tc::Definition::Variable(
+                self.resolve_kind(&v.kind, &doc_id, v.line_number, &mut errors)?
+                    .0,
+            ),
+            ftd_ast::Ast::Import(_)
+            | ftd_ast::Ast::VariableInvocation(_)
+            | ftd_ast::Ast::ComponentInvocation(_) => return Ok(ftd_tc::Resolved::Unknown),
+        };
+
+        self.global_types.insert(
+            key.to_string(),
+            ftd_tc::Qualified {
+                v: v.clone(),
+                line_number,
+                doc_id,
+                accessible_in: ftd_tc::AccessibleIn::Public,
+            },
+        );
+        Ok(ftd_tc::Resolved::Found(v))
+    }
+
+    pub(crate) fn fields(
+        &self,
+        fields: &[ftd_ast::Field],
+        doc_id: &ftd_tc::DocumentID,
+        errors: &mut Vec<ftd_tc::Error>,
+    ) -> ftd_tc::Pending<Vec<ftd_tc::Field>> {
+        let mut result = vec![];
+        for field in fields {
+            let (type_, caption, body) =
+                self.resolve_kind(&field.kind, doc_id, field.line_number, errors)?;
+            result.push(ftd_tc::Field {
+                name: field.name.clone(),
+                type_,
+                has_default: field.value.is_some(),
+                mutable: field.mutable,
+                caption,
+                body,
+            });
+        }
+        Ok(result)
+    }
+
+    /// The type of a kind written in `doc_id`, eg `caption or body string` or `lib.person list`,
+    /// along with whether the value can be passed as caption and body. Kinds that can not be
+    /// resolved are reported in `errors`, and are of `Type::Unknown`.
+    pub(crate) fn resolve_kind(
+        &self,
+        kind: &ftd_ast::VariableKind,
+        doc_id: &ftd_tc::DocumentID,
+        line_number: usize,
+        errors: &mut Vec<ftd_tc::Error>,
+    ) -> ftd_tc::Pending<(ftd_tc::Type, bool, bool)> {
+        let name = ftd_p1::AccessModifier::remove_modifiers(kind.kind.as_str());
+        let (caption, body, name) = split_caption_and_body(name.as_str());
+
+        let type_ = match name.as_str() {
+            "" | "string" => ftd_tc::Type::String,
+            "object" => ftd_tc::Type::Object,
+            "integer" => ftd_tc::Type::Integer,
+            "decimal" => ftd_tc::Type::Decimal,
+            "boolean" => ftd_tc::Type::Boolean,
+            "void" => ftd_tc::Type::Void,
+            "ftd.ui" => ftd_tc::Type::UI,
+            "module" => ftd_tc::Type::Module,
+            "kw-args" => ftd_tc::Type::KwArgs,
+            "children" => {
+                return Ok((ftd_tc::Type::List(Box::new(ftd_tc::Type::UI)), false, false))
+            }
+            name => self.resolve_kind_name(name, doc_id, line_number, errors)?,
+        };
+
+        let type_ = match kind.modifier {
+            Some(ftd_ast::VariableModifier::List) => ftd_tc::Type::List(Box::new(type_)),
+            Some(ftd_ast::VariableModifier::Optional) => ftd_tc::Type::Optional(Box::new(type_)),
+            Some(ftd_ast::VariableModifier::Constant) | None => type_,
+        };
+
+        Ok((type_, caption, body))
+    }
+
+    fn resolve_kind_name(
+        &self,
+        name: &str,
+        doc_id: &ftd_tc::DocumentID,
+        line_number: usize,
+        errors: &mut Vec<ftd_tc::Error>,
+    ) -> ftd_tc::Pending<ftd_tc::Type> {
+        let (key, member) = match self.find(name, doc_id.logical.as_str())? {
+            ftd_tc::Resolved::Found(found) => found,
+            ftd_tc::Resolved::Unknown => return Ok(ftd_tc::Type::Unknown),
+            ftd_tc::Resolved::Missing => {
+                errors.push(ftd_tc::Error::NotFound {
+                    kind: "type",
+                    name: name.to_string(),
+                    usage_document: doc_id.clone(),
+                    usage_line: line_number,
+                });
+                return Ok(ftd_tc::Type::Unknown);
+            }
+        };
+
+        let symbol = &self.symbols[key.as_str()];
+        Ok(match (&symbol.v, member) {
+            (ftd_ast::Ast::Record(_), None) => ftd_tc::Type::Record(key),
+            (ftd_ast::Ast::OrType(o), member) => {
+                if let Some(member) = member {
+                    if !o.variants.iter().any(|v| v.name() == member) {
+                        errors.push(ftd_tc::Error::UnknownVariant {
+                            name: member,
+                            or_type: o.name.clone(),
+                            usage_document: doc_id.clone(),
+                            usage_line: line_number,
+                        });
+                    }
+                }
+                ftd_tc::Type::OrType(key)
+            }
+            (
+                ftd_ast::Ast::ComponentDefinition(_) | ftd_ast::Ast::WebComponentDefinition(_),
+                None,
+            ) => ftd_tc::Type::UI,
+            // a variable can be used as a kind, it is of the kind of the variable
+            (ftd_ast::Ast::VariableDefinition(v), None) => {
+                let doc_id = symbol.doc_id.clone();
+                self.resolve_kind(&v.kind, &doc_id, v.line_number, &mut vec![])?
+                    .0
+            }
+            (ast, _) => {
+                errors.push(ftd_tc::Error::NotAType {
+                    name: name.to_string(),
+                    usage_document: doc_id.clone(),
+                    usage_line: line_number,
+                    found: ast_kind_name(ast),
+                });
+                ftd_tc::Type::Unknown
+            }
+        })
+    }
+
+    /// Checks a value against its type. Only literals are checked, references and expressions
+    /// are not.
+    pub(crate) fn check_value(
+        &mut self,
+        type_: &ftd_tc::Type,
+        value: &ftd_ast::VariableValue,
+        doc_id: &ftd_tc::DocumentID,
+        errors: &mut Vec<ftd_tc::Error>,
+    ) -> ftd_tc::Pending<()> {
+        match (type_, value) {
+            (_, ftd_ast::VariableValue::String { value, .. }) if is_reference(value) => {}
+            (ftd_tc::Type::Optional(t), ftd_ast::VariableValue::Optional { value, .. }) => {
+                if let Some(value) = value.as_ref() {
+                    self.check_value(t, value, doc_id, errors)?;
+                }
+            }
+            (ftd_tc::Type::Optional(t), value) => self.check_value(t, value, doc_id, errors)?,
+            (ftd_tc::Type::List(t), ftd_ast::VariableValue::List { value, .. }) => {
+                for item in value {
+                    self.check_value(t, &item.value, doc_id, errors)?;
+                }
+            }
+            (
+                ftd_tc::Type::Record(key),
+                ftd_ast::VariableValue::Record { .. } | ftd_ast::VariableValue::String { .. },
+            ) => self.check_record(key, value, doc_id, errors)?,
+            (
+                t,
+                ftd_ast::VariableValue::String {
+                    value, line_number, ..
+                },
+            ) => {
+                if let Some(expected) = t.check_literal(value) {
+                    errors.push(ftd_tc::Error::TypeMismatch {
+                        expected,
+                        value: value.to_string(),
+                        usage_document: doc_id.clone(),
+                        usage_line: *line_number,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn check_record(
+        &mut self,
+        key: &str,
+        value: &ftd_ast::VariableValue,
+        doc_id: &ftd_tc::DocumentID,
+        errors: &mut Vec<ftd_tc::Error>,
+    ) -> ftd_tc::Pending<()> {
+        let ftd_tc::Resolved::Found(ftd_tc::Definition::Record(record)) = self.definition(key)?
+        else {
+            return Ok(());
+        };
+        let name = key.split_once('#').map_or(key, |(_, name)| name);
+
+        let (caption, headers, body, line_number) = match value {
+            // only the caption is passed, eg `-- person p: Amit`
+            ftd_ast::VariableValue::String {
+                value, line_number, ..
+            } => {
+                let Some(field) = record.fields.iter().find(|f| f.caption) else {
+                    errors.push(ftd_tc::Error::NoCaptionOrBody {
+                        source_: "caption",
+                        of: format!("record `{name}`"),
+                        usage_document: doc_id.clone(),
+                        usage_line: *line_number,
+                    });
+                    return Ok(());
+                };
+                if let Some(expected) = field.type_.check_literal(value) {
+                    errors.push(ftd_tc::Error::TypeMismatch {
+                        expected,
+                        value: value.to_string(),
+                        usage_document: doc_id.clone(),
+                        usage_line: *line_number,
+                    });
+                }
+                (None, &[][..], false, *line_number)
+            }
+            ftd_ast::VariableValue::Record {
+                caption,
+                headers,
+                body,
+                line_number,
+                ..
+            } => (
+                caption.as_ref().as_ref(),
+                headers.0.as_slice(),
+                body.is_some(),
+                *line_number,
+            ),
+            _ => return Ok(()),
+        };
+
+        let mut passed = vec![];
+        if matches!(value, ftd_ast::VariableValue::String { .. }) {
+            passed.extend(
+                record
+                    .fields
+                    .iter()
+                    .filter(|f| f.caption)
+                    .map(|f| f.name.as_str()),
+            );
+        }
+        if let Some(caption) = caption {
+            match record.fields.iter().find(|f| f.caption) {
+                Some(field) => {
+                    passed.push(field.name.as_str());
+                    self.check_value(&field.type_, caption, doc_id, errors)?;
+                }
+                None => errors.push(ftd_tc::Error::NoCaptionOrBody {
+                    source_: "caption",
+                    of: format!("record `{name}`"),
+                    usage_document: doc_id.clone(),
+                    usage_line: line_number,
+                }),
+            }
+        }
+        if body {
+            match record.fields.iter().find(|f| f.body) {
+                Some(field) => passed.push(field.name.as_str()),
+                None => errors.push(ftd_tc::Error::NoCaptionOrBody {
+                    source_: "body",
+                    of: format!("record `{name}`"),
+                    usage_document: doc_id.clone(),
+                    usage_line: line_number,
+                }),
+            }
+        }
+        for header in headers {
+            let (field_name, rest) = match header.key.split_once('.') {
+                Some((field_name, rest)) => (field_name, Some(rest)),
+                None => (header.key.as_str(), None),
+            };
+            let Some(field) = record.field(field_name) else {
+                errors.push(ftd_tc::Error::UnknownField {
+                    name: field_name.to_string(),
+                    record: name.to_string(),
+                    usage_document: doc_id.clone(),
+                    usage_line: header.line_number,
+                });
+                continue;
+            };
+            passed.push(field.name.as_str());
+            if rest.is_none() {
+                self.check_value(&field.type_, &header.value, doc_id, errors)?;
+            }
+        }
+
+        for field in record.fields.iter() {
+            if field.is_required() && !passed.contains(&field.name.as_str()) {
+                errors.push(ftd_tc::Error::MissingField {
+                    name: field.name.clone(),
+                    record: name.to_string(),
+                    usage_document: doc_id.clone(),
+                    usage_line: line_number,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Queues the function calls in a value to be checked.
+    pub(crate) fn push_function_calls(
+        &mut self,
+        value: &ftd_ast::VariableValue,
+        doc_id: &ftd_tc::DocumentID,
+    ) {
+        match value {
+            ftd_ast::VariableValue::String {
+                value, line_number, ..
+            } => self.push_function_call(value, *line_number, doc_id),
+            ftd_ast::VariableValue::Optional { value, .. } => {
+                if let Some(value) = value.as_ref() {
+                    self.push_function_calls(value, doc_id);
+                }
+            }
+            ftd_ast::VariableValue::List { value, .. } => {
+                for item in value {
+                    self.push_function_calls(&item.value, doc_id);
+                }
+            }
+            ftd_ast::VariableValue::Record {
+                caption,
+                headers,
+                values,
+                ..
+            } => {
+                if let Some(caption) = caption.as_ref() {
+                    self.push_function_calls(caption, doc_id);
+                }
+                for header in headers.0.iter() {
+                    self.push_function_calls(&header.value, doc_id);
+                }
+                for item in values {
+                    self.push_function_calls(&item.value, doc_id);
+                }
+            }
+            ftd_ast::VariableValue::Constant { .. } => {}
+        }
+    }
+
+    pub(crate) fn push_function_call(
+        &mut self,
+        value: &str,
+        line_number: usize,
+        doc_id: &ftd_tc::DocumentID,
+    ) {
+        if let Some(fi) = ftd_tc::FI::from_call(value, line_number, doc_id) {
+            self.continuable_things
+                .push(ftd_tc::ContinuableThing::FI(fi));
+        }
+    }
+}
+
+fn is_reference(value: &str) -> bool {
+    value.trim_start().starts_with('$')
+}
+
+/// `caption or body string` is `(true, true, "string")`, a kind of just `caption` is a string
+fn split_caption_and_body(kind: &str) -> (bool, bool, String) {
+    let words: Vec<&str> = kind.split_whitespace().collect();
+    match words.as_slice() {
+        ["caption", "or", "body", rest @ ..] | ["body", "or", "caption", rest @ ..] => {
+            (true, true, rest.join(" "))
+        }
+        ["caption", rest @ ..] => (true, false, rest.join(" ")),
+        ["body", rest @ ..] => (false, true, rest.join(" ")),
+        rest => (false, false, rest.join(" ")),
+    }
+}
+
+pub(crate) fn ast_kind_name(ast: &ftd_ast::Ast) -> &'static str {
+    match ast {
+        ftd_ast::Ast::Import(_) => "import",
+        ftd_ast::Ast::Record(_) => "record",
+        ftd_ast::Ast::OrType(_) => "or-type",
+        ftd_ast::Ast::VariableDefinition(_) | ftd_ast::Ast::VariableInvocation(_) => "variable",
+        ftd_ast::Ast::ComponentDefinition(_) | ftd_ast::Ast::ComponentInvocation(_) => "component",
+        ftd_ast::Ast::FunctionDefinition(_) => "function",
+        ftd_ast::Ast::WebComponentDefinition(_) => "web-component",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn split_caption_and_body() {
+        assert_eq!(
+            super::split_caption_and_body("caption or body string"),
+            (true, true, "string".to_string())
+        );
+        assert_eq!(
+            super::split_caption_and_body("caption"),
+            (true, false, "".to_string())
+        );
+        assert_eq!(
+            super::split_caption_and_body("body lib.person"),
+            (false, true, "lib.person".to_string())
+        );
+        assert_eq!(
+            super::split_caption_and_body("integer"),
+            (false, false, "integer".to_string())
+        );
+    }
+}
@@ -1,15 +1,17 @@
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct State {
     /// These are the things we need to resolve.
     ///
-    /// we start by adding every component invocation in the main document and try to resolve
-    /// them. If we find a reference to another document, we load that document and process it.
-    /// We do this in a recursive manner.
+    /// we start by adding every component invocation, variable and definition in the main
+    /// document and try to resolve them. If we find a reference to another document, we load
+    /// that document and process it. We do this in a recursive manner.
     pub continuable_things: Vec<ftd_tc::ContinuableThing>,
     /// Raw symbols from all documents are stored here
     pub symbols: ftd_p1::Map<ftd_tc::Lined<ftd_ast::Ast>>,
     /// any type we have already resolved is stored here
-    pub global_types: ftd_p1::Map<ftd_tc::Qualified<ftd_tc::Type>>,
+    pub global_types: ftd_p1::Map<ftd_tc::Qualified<ftd_tc::Definition>>,
+    /// the imports of every loaded document, by logical id
+    pub documents: ftd_p1::Map<ftd_tc::Document>,
     /// js_buffer contains the generated JS when we resolve any symbol
     pub js_buffer: String,
     pub errors: Vec<ftd_tc::Error>,
@@ -17,11 +19,43 @@ pub struct State {
 
 #[derive(Debug)]
 pub enum TCState {
-    Processing(State),
-    StuckOnImport { document: String, state: State },
+    /// `document` has to be loaded, with `continue_after_import()` or
+    /// `continue_after_opaque_import()`. `caller` is the document that imports it.
+    StuckOnImport {
+        document: String,
+        caller: String,
+        state: State,
+    },
     Done(State),
 }
 
+/// modules that are built into ftd, we do not have their source
+const BUILTIN_MODULES: [&str; 2] = ["ftd", "inherited"];
+
+impl Default for State {
+    fn default() -> Self {
+        let mut documents = ftd_p1::Map::new();
+        for module in BUILTIN_MODULES {
+            documents.insert(
+                module.to_string(),
+                ftd_tc::Document {
+                    opaque: true,
+                    ..Default::default()
+                },
+            );
+        }
+
+        State {
+            continuable_things: vec![],
+            symbols: Default::default(),
+            global_types: Default::default(),
+            documents,
+            js_buffer: String::new(),
+            errors: vec![],
+        }
+    }
+}
+
 impl ftd_tc::State {
     fn merge_ast(
         &mut self,
@@ -29,15 +63,43 @@ impl ftd_tc::State {
         ast: Vec<ftd_ast::Ast>,
         doc_id: ftd_tc::DocumentID,
     ) {
+        let mut document = ftd_tc::Document {
+            aliases: BUILTIN_MODULES
+                .iter()
+                .map(|m| (m.to_string(), m.to_string()))
+                .collect(),
+            ..Default::default()
+        };
+
         for ast in ast {
             match ast {
-                ftd_ast::Ast::Import(_)
-                | ftd_ast::Ast::Record(_)
+                ftd_ast::Ast::Import(i) => {
+                    match i.exposing {
+                        Some(ftd_ast::Exposing::All) => {
+                            document.exposing_all.push(i.module.clone())
+                        }
+                        Some(ftd_ast::Exposing::Things(things)) => {
+                            for thing in things {
+                                document.exposings.insert(thing, i.module.clone());
+                            }
+                        }
+                        None => {}
+                    }
+                    if let Some(export) = i.exports {
+                        document.exports.push((i.module.clone(), export));
+                    }
+                    document.aliases.insert(i.alias, i.module);
+                }
+                ftd_ast::Ast::Record(_)
                 | ftd_ast::Ast::OrType(_)
                 | ftd_ast::Ast::VariableDefinition(_)
                 | ftd_ast::Ast::ComponentDefinition(_)
                 | ftd_ast::Ast::FunctionDefinition(_)
                 | ftd_ast::Ast::WebComponentDefinition(_) => {
+                    if extend_continuable_things {
+                        self.continuable_things
+                            .push(ftd_tc::ContinuableThing::from_ast(&ast, doc_id.clone()));
+                    }
                     self.symbols.insert(
                         format!("{}#{}", doc_id.logical, ast.name()),
                         ftd_tc::Lined {
@@ -47,73 +109,108 @@ impl ftd_tc::State {
                         },
                     );
                 }
-                ftd_ast::Ast::VariableInvocation(_) => unreachable!(),
+                // updates the value of a variable, the value is checked when the variable is
+                // defined
+                ftd_ast::Ast::VariableInvocation(_) => {}
                 ftd_ast::Ast::ComponentInvocation(c) => {
                     if extend_continuable_things {
                         self.continuable_things.push(
                             ftd_tc::ContinuableThing::from_component_invocation(
-                                c.clone(),
+                                c,
                                 doc_id.clone(),
+                                vec![],
                             ),
                         )
                     }
                 }
             }
         }
-    }
-
-    pub fn from_document(source: &str, doc_id: ftd_tc::DocumentID) -> ftd_tc::Result<Self> {
-        let ast = ftd_tc::parse_document_to_ast(source, &doc_id)?;
 
-        let mut s = Self::default();
-        s.merge_ast(false, ast, doc_id);
-
-        Ok(s)
+        self.documents.insert(doc_id.logical, document);
     }
 
-    fn resolve_record_invocation(&mut self, c: &mut ftd_tc::RI) -> ftd_tc::Result<Option<String>> {
-        Ok(None)
+    /// Adds a document whose names can not be checked, eg because it failed to parse.
+    fn merge_opaque(&mut self, module: &str) {
+        self.documents.insert(
+            module.to_string(),
+            ftd_tc::Document {
+                opaque: true,
+                ..Default::default()
+            },
+        );
     }
 
-    fn resolve_function_invocation(
-        &mut self,
-        c: &mut ftd_tc::FI,
-    ) -> ftd_tc::Result<Option<String>> {
-        Ok(None)
+    /// Starts checking the document, `line_number` is the number of lines added before the
+    /// source, eg by auto imports.
+    pub fn from_document(
+        source: &str,
+        doc_id: ftd_tc::DocumentID,
+        line_number: usize,
+    ) -> ftd_tc::TCState {
+        let mut s = Self::default();
+        let (ast, errors) = ftd_tc::parse_document_to_ast(source, &doc_id, line_number);
+        s.errors.extend(errors);
+        match ast {
+            Some(ast) => s.merge_ast(true, ast, doc_id),
+            None => s.merge_opaque(doc_id.logical.as_str()),
+        }
+
+        s.r#continue()
     }
 
-    fn handle_thing(
-        &mut self,
-        thing: &mut ftd_tc::ContinuableThing,
-    ) -> ftd_tc::Result<Option<String>> {
+    fn handle_thing(&mut self, thing: &mut ftd_tc::ContinuableThing) -> ftd_tc::Pending<()> {
         match thing {
             ftd_tc::ContinuableThing::CI(c) => self.resolve_component_invocation(c),
             ftd_tc::ContinuableThing::RI(r) => self.resolve_record_invocation(r),
             ftd_tc::ContinuableThing::FI(f) => self.resolve_function_invocation(f),
+            ftd_tc::ContinuableThing::DI(d) => self.resolve_definition(d),
         }
     }
 
-    fn r#continue(mut self) -> ftd_tc::Result<ftd_tc::TCState> {
+    fn r#continue(mut self) -> ftd_tc::TCState {
         while let Some(mut thing) = self.continuable_things.pop() {
-            if let Some(document) = self.handle_thing(&mut thing)? {
+            if let Err(import) = self.handle_thing(&mut thing) {
                 self.continuable_things.push(thing);
-                return Ok(ftd_tc::TCState::StuckOnImport {
-                    document,
+                return ftd_tc::TCState::StuckOnImport {
+                    document: import.module,
+                    caller: import.caller,
                     state: self,
-                });
+                };
             }
         }
 
-        Ok(ftd_tc::TCState::Done(self))
+        ftd_tc::TCState::Done(self)
     }
 
-    fn continue_after_import(
+    /// Continues with the source of the document we were stuck on. `foreign` are the names the
+    /// document defines outside of its source, eg the `files` of the assets module.
+    ///
+    /// Errors in imported documents are not reported, they are reported when the document
+    /// itself is checked.
+    pub fn continue_after_import(
         mut self,
         doc_id: ftd_tc::DocumentID,
         source: &str,
-    ) -> ftd_tc::Result<TCState> {
-        let ast = ftd_tc::parse_document_to_ast(source, &doc_id)?;
-        self.merge_ast(false, ast, doc_id);
+        line_number: usize,
+        foreign: Vec<String>,
+    ) -> ftd_tc::TCState {
+        match ftd_tc::parse_document_to_ast(source, &doc_id, line_number) {
+            (Some(ast), _) => {
+                let module = doc_id.logical.clone();
+                self.merge_ast(false, ast, doc_id);
+                if let Some(document) = self.documents.get_mut(module.as_str()) {
+                    document.foreign = foreign;
+                }
+            }
+            (None, _) => self.merge_opaque(doc_id.logical.as_str()),
+        }
+        self.r#continue()
+    }
+
+    /// Continues without the source of the document we were stuck on, eg because it could not
+    /// be found. Things from the document are not checked.
+    pub fn continue_after_opaque_import(mut self, module: &str) -> ftd_tc::TCState {
+        self.merge_opaque(module);
         self.r#continue()
     }
 }
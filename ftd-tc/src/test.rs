@@ -0,0 +1,268 @@
+/// Type checks `source` as `foo`, with `documents` as the other documents it can import, and
+/// returns the errors found.
+#[track_caller]
+fn tc(source: &str, documents: &[(&str, &str)]) -> Vec<String> {
+    let mut s = ftd_tc::State::from_document(source, ftd_tc::DocumentID::new0("foo"), 0);
+    let state = loop {
+        match s {
+            ftd_tc::TCState::StuckOnImport {
+                document, state, ..
+            } => {
+                s = match documents.iter().find(|(name, _)| *name == document) {
+                    Some((name, source)) => state.continue_after_import(
+                        ftd_tc::DocumentID::new0(name),
+                        source,
+                        0,
+                        vec![],
+                    ),
+                    None => state.continue_after_opaque_import(document.as_str()),
+                }
+            }
+            ftd_tc::TCState::Done(state) => break state,
+        }
+    };
+
+    let mut errors: Vec<_> = state
+        .errors
+        .iter()
+        .map(|e| {
+            let line = e.location().map_or(0, |(_, line)| line);
+            (line, e.to_string())
+        })
+        .collect();
+    errors.sort();
+    errors.into_iter().map(|(_, e)| e).collect()
+}
+
+#[track_caller]
+fn p(source: &str, documents: &[(&str, &str)], expected: &[&str]) {
+    pretty_assertions::assert_eq!(tc(source, documents), expected);
+}
+
+#[test]
+fn no_errors() {
+    p(
+        indoc::indoc!(
+            "
+        -- import: lib
+
+        -- record person:
+        caption name:
+        integer age: 10
+
+        -- person amit: Amit
+        age: 40
+
+        -- component card:
+        caption title:
+        boolean $open: false
+
+        -- ftd.column:
+
+        -- ftd.text: $card.title
+
+        -- end: ftd.column
+
+        -- end: card
+
+        -- card: hello
+        $open: true
+
+        -- lib.badge: new
+        "
+        ),
+        &[(
+            "lib",
+            "-- component badge:\ncaption text:\n\n-- ftd.text: $badge.text\n\n-- end: badge\n",
+        )],
+        &[],
+    );
+}
+
+#[test]
+fn components() {
+    p(
+        indoc::indoc!(
+            "
+        -- component card:
+        caption title:
+        integer count:
+        string $name: x
+
+        -- ftd.text: $card.title
+
+        -- end: card
+
+        -- card: hello
+        count: many
+        colour: red
+
+        -- card:
+        count: 1
+        name: y
+
+        body is not allowed
+
+        -- cards: hello
+
+        -- integer card-count: 1
+
+        -- card-count: 2
+        "
+        ),
+        &[],
+        &[
+            "foo:11 -> expected integer, found `many`",
+            "foo:12 -> component `card` has no argument `colour`",
+            "foo:14 -> argument `title` of component `card` is not passed",
+            "foo:16 -> argument `name` is mutable, use `$name`",
+            "foo:19 -> component `card` takes no body",
+            "foo:20 -> component `cards` not found",
+            "foo:24 -> `card-count` is a variable, not a component",
+        ],
+    );
+}
+
+#[test]
+fn records_and_or_types() {
+    p(
+        indoc::indoc!(
+            "
+        -- record person:
+        caption name:
+        integer age:
+        optional lib.address address:
+
+        -- or-type lead:
+
+        -- person individual:
+
+        -- string company:
+
+        -- end: lead
+
+        -- person list people:
+
+        -- person: Amit
+        age: 40
+
+        -- person: Arpita
+        height: 6
+
+        -- end: people
+
+        -- boolean flag: yes
+
+        -- lead.organisation l: fifthtry
+        "
+        ),
+        &[("lib", "-- integer x: 1\n")],
+        &[
+            "foo:4 -> type `lib.address` not found",
+            "foo:19 -> field `age` of record `person` is not passed",
+            "foo:20 -> record `person` has no field `height`",
+            "foo:24 -> expected boolean, found `yes`",
+            "foo:26 -> or-type `lead` has no variant `organisation`",
+        ],
+    );
+}
+
+#[test]
+fn functions() {
+    p(
+        indoc::indoc!(
+            "
+        -- import: lib
+
+        -- integer add(a, b):
+        integer a:
+        integer b: 0
+
+        a + b
+
+        -- integer x: $add(a = 1)
+
+        -- integer y: $add(b = 1, c = 2)
+
+        -- integer z: $sub(a = 1)
+
+        -- integer w: $lib.double(a = 1)
+
+        -- ftd.integer: $x
+        $on-click$: $lib.x(a = 1)
+        "
+        ),
+        &[(
+            "lib",
+            "-- integer double(a):\ninteger a:\n\na * 2\n\n-- integer x: 1\n",
+        )],
+        &[
+            "foo:11 -> argument `a` of function `add` is not passed",
+            "foo:11 -> function `add` has no argument `c`",
+            "foo:13 -> function `sub` not found",
+            "foo:18 -> `lib.x` is a variable, not a function",
+        ],
+    );
+}
+
+#[test]
+fn imports() {
+    p(
+        indoc::indoc!(
+            "
+        -- import: lib
+        exposing: card
+
+        -- import: missing
+
+        -- card:
+        title: hello
+
+        -- lib.foo:
+
+        -- missing.anything:
+        whatever: 1
+        "
+        ),
+        &[(
+            "lib",
+            "-- import: lib2\nexport: card\n\n-- component foo:\n\n-- ftd.text: foo\n\n-- end: foo\n",
+        ), (
+            "lib2",
+            "-- component card:\n\n-- ftd.text: card\n\n-- end: card\n",
+        )],
+        &["foo:7 -> component `card` has no argument `title`"],
+    );
+}
+
+#[test]
+fn syntax_errors() {
+    let errors = tc("-- ftd.text: hello\n\n-- ftd.text\n\n-- card:\n", &[]);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].contains(": is missing"), "{errors:?}");
+}
+
+#[test]
+fn ui_arguments_and_variables() {
+    p(include_str!("../../ftd/t/js/36-single-ui.ftd"), &[], &[]);
+    p(include_str!("../../ftd/t/js/73-loops-inside-list.ftd"), &[], &[]);
+}
+
+#[test]
+fn module_arguments() {
+    let basic_module = include_str!("../../ftd/t/js/01-basic-module.ftd");
+    p(
+        include_str!("../../ftd/t/js/44-module.ftd"),
+        &[("01-basic-module", basic_module)],
+        &[],
+    );
+    p(
+        include_str!("../../ftd/t/js/45-re-module.ftd"),
+        &[
+            ("44-module", include_str!("../../ftd/t/js/44-module.ftd")),
+            ("01-basic-module", basic_module),
+        ],
+        &[],
+    );
+    p(include_str!("../../ftd/t/js/82-or-type-module.ftd"), &[], &[]);
+}
@@ -3,26 +3,83 @@ pub enum ContinuableThing {
     RI(RI),
     CI(ftd_tc::CI),
     FI(FI),
+    DI(DI),
 }
 
-#[derive(Debug)]
+/// A function call in a value, eg `$add(a = 1, b = $x)`
+#[derive(Debug, Clone)]
 pub struct FI {
-    //
+    pub name: String,
+    /// the names of the arguments passed, `None` if some argument is passed without a name
+    pub arguments: Option<Vec<String>>,
+    pub line_number: usize,
+    pub document_id: DocumentID,
 }
 
+/// The value of a variable, checked against the kind of the variable
 #[derive(Debug, Clone)]
 pub struct RI {
     pub inner: ftd_ast::VariableDefinition,
-    pub r: Record,
-    pub current_field: i32,
+    pub document_id: DocumentID,
+}
+
+/// A definition whose kinds have to be checked: the fields of a record, the variants of an
+/// or-type, and the arguments of a component or a function
+#[derive(Debug, Clone)]
+pub struct DI {
+    pub inner: ftd_ast::Ast,
+    pub document_id: DocumentID,
 }
 
+/// A module that has to be loaded before we can continue, `caller` is the document that
+/// refers to it
 #[derive(Debug, Clone)]
+pub struct PendingImport {
+    pub module: String,
+    pub caller: String,
+}
+
+pub type Pending<T> = std::result::Result<T, PendingImport>;
+
+#[derive(Debug)]
+pub enum Resolved<T> {
+    Found(T),
+    /// the name is in an opaque document, or is foreign, so we can not check it
+    Unknown,
+    Missing,
+}
+
+/// The type of a value, eg of a variable, a record field or a component argument
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
+    String,
     Integer,
-    MutableInteger,
+    Decimal,
+    Boolean,
+    Object,
+    UI,
+    Void,
+    Module,
+    KwArgs,
+    /// qualified name of the record, eg `amitu.com/foo#person`
+    Record(String),
+    /// qualified name of the or-type
+    OrType(String),
+    List(Box<Type>),
+    Optional(Box<Type>),
+    /// a type defined in a module we do not have the source of, eg `ftd.color`, or in a
+    /// document that failed to parse. Values of this type are not checked.
+    Unknown,
+}
+
+/// A thing we have resolved, stored in `State.global_types`
+#[derive(Debug, Clone)]
+pub enum Definition {
     Record(Record),
+    OrType(OrType),
     Component(Component),
+    Function(Function),
+    Variable(Type),
 }
 
 #[derive(Debug, Clone)]
@@ -50,7 +107,7 @@ pub struct Lined<T> {
     pub doc_id: DocumentID,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DocumentID {
     /// logical id is what we use to refer to a document in the code, eg `amitu.com/foo`
     pub logical: String,
@@ -58,6 +115,24 @@ pub struct DocumentID {
     pub physical: String,
 }
 
+/// What we know about a loaded document, to resolve the names used in it
+#[derive(Debug, Default)]
+pub struct Document {
+    /// alias to module, from the imports of the document and the default aliases
+    pub aliases: ftd_p1::Map<String>,
+    /// names imported with `exposing`, to the module they come from
+    pub exposings: ftd_p1::Map<String>,
+    /// modules imported with `exposing: *`
+    pub exposing_all: Vec<String>,
+    /// modules whose things are re-exported by this document with `export`
+    pub exports: Vec<(String, ftd_ast::Export)>,
+    /// names defined outside ftd, eg the `files` of the assets module. They are not checked.
+    pub foreign: Vec<String>,
+    /// we do not have the source of an opaque document, eg `ftd`, or it failed to parse. All
+    /// names in it are accepted.
+    pub opaque: bool,
+}
+
 /// we use field to model component arguments, record fields, and function arguments etc
 #[derive(Debug, Clone)]
 pub struct Field {
@@ -65,6 +140,11 @@ pub struct Field {
     pub type_: Type,
     /// if the field has a default value, we can skip passing this field in the invocation
     pub has_default: bool,
+    pub mutable: bool,
+    /// the field can be passed as the caption of a section
+    pub caption: bool,
+    /// the field can be passed as the body of a section
+    pub body: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -77,6 +157,17 @@ pub struct Record {
     pub fields: Vec<Field>,
 }
 
+#[derive(Debug, Clone)]
+pub struct OrType {
+    pub variants: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub args: Vec<Field>,
+    pub return_type: Type,
+}
+
 impl DocumentID {
     pub fn new(logical: &str, physical: &str) -> Self {
         Self {
@@ -92,3 +183,69 @@ impl DocumentID {
         }
     }
 }
+
+impl Field {
+    /// a value has to be passed for this field
+    pub fn is_required(&self) -> bool {
+        !self.has_default && !matches!(self.type_, Type::Optional(_) | Type::List(_) | Type::KwArgs)
+    }
+}
+
+impl Record {
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+impl Component {
+    pub fn arg(&self, name: &str) -> Option<&Field> {
+        self.args.iter().find(|f| f.name == name)
+    }
+}
+
+impl Definition {
+    /// what the definition is, for error messages
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Definition::Record(_) => "record",
+            Definition::OrType(_) => "or-type",
+            Definition::Component(_) => "component",
+            Definition::Function(_) => "function",
+            Definition::Variable(_) => "variable",
+        }
+    }
+}
+
+impl Type {
+    /// a value of this type can be invoked as a component, eg `-- uis.0:` for `ftd.ui list uis`
+    pub fn is_ui(&self) -> bool {
+        match self {
+            Type::UI => true,
+            Type::List(t) | Type::Optional(t) => t.is_ui(),
+            _ => false,
+        }
+    }
+
+    /// checks a literal value, ie not a reference or an expression, against the type. Returns
+    /// the name of the expected type if the literal is not valid.
+    pub fn check_literal(&self, value: &str) -> Option<&'static str> {
+        let value = value.trim();
+        let valid = match self {
+            Type::Integer => value.parse::<i64>().is_ok(),
+            Type::Decimal => value.parse::<f64>().is_ok(),
+            Type::Boolean => value == "true" || value == "false",
+            Type::Optional(t) => return t.check_literal(value),
+            _ => return None,
+        };
+
+        if valid {
+            return None;
+        }
+
+        Some(match self {
+            Type::Integer => "integer",
+            Type::Decimal => "decimal",
+            _ => "boolean",
+        })
+    }
+}
@@ -10,6 +10,7 @@ mod markup;
 mod rive;
 mod styles;
 mod tdoc;
+mod text;
 pub(crate) mod utils;
 pub mod value;
 mod youtube_id;
@@ -32,6 +33,7 @@ pub use styles::{
     Spacing, TextAlign, TextInputType, TextStyle, TextTransform, TextWeight, WhiteSpace,
 };
 pub(crate) use tdoc::TDoc;
+pub use text::PageText;
 pub(crate) use value::Value;
 
 #[derive(thiserror::Error, Debug)]
@@ -0,0 +1,268 @@
+/// The text a visitor can read on a page, used by `fastn` to build the search index.
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct PageText {
+    /// The `title` of `ftd.document`, or else the first heading.
+    pub title: Option<String>,
+    /// The text of every heading, i.e. `ftd.text` with a `region`, in order.
+    pub headings: Vec<String>,
+    /// The rest of the text, one line per text, code block or image description.
+    pub body: String,
+}
+
+impl ftd::executor::RT {
+    pub fn text(&self) -> PageText {
+        let mut text = PageText::default();
+        for child in self.main.container.children.iter() {
+            element_text(child, &mut text);
+        }
+        text.title = self
+            .html_data
+            .title
+            .value
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| text.headings.first().cloned());
+        text
+    }
+}
+
+impl PageText {
+    /// The text of `html`, a page rendered by the JS runtime, like `fastn_js::ssr_str()` does.
+    /// The runtime does not render the title of the document, the first heading is the title.
+    pub fn from_html(html: &str) -> PageText {
+        let mut text = PageText::default();
+        let mut line = String::new();
+        let mut heading: Option<String> = None;
+        // the tag whose content is not text, e.g. `script`
+        let mut skip: Option<String> = None;
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            if skip.is_none() {
+                heading
+                    .as_mut()
+                    .unwrap_or(&mut line)
+                    .push_str(&rest[..start]);
+            }
+            let Some(end) = rest[start..].find('>') else {
+                rest = &rest[start..];
+                break;
+            };
+            let tag = &rest[start + 1..start + end];
+            rest = &rest[start + end + 1..];
+            let closing = tag.starts_with('/');
+            let name = tag_name(tag);
+            if let Some(ref s) = skip {
+                if closing && *s == name {
+                    skip = None;
+                }
+                continue;
+            }
+            if INLINE_TAGS.contains(&name.as_str()) {
+                continue;
+            }
+            match name.as_str() {
+                "script" | "style" if !closing => skip = Some(name),
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" if closing => {
+                    if let Some(h) = heading.take() {
+                        let h = clean(h.as_str());
+                        if !h.is_empty() {
+                            text.headings.push(h);
+                        }
+                    }
+                }
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    push_line(&mut text.body, clean(line.as_str()).as_str());
+                    line.clear();
+                    heading = Some(String::new());
+                }
+                _ => match heading.as_mut() {
+                    Some(heading) => heading.push(' '),
+                    None => {
+                        push_line(&mut text.body, clean(line.as_str()).as_str());
+                        line.clear();
+                        if name == "img" {
+                            if let Some(alt) = attribute(tag, "alt") {
+                                push_line(&mut text.body, clean(alt).as_str());
+                            }
+                        }
+                    }
+                },
+            }
+        }
+        if skip.is_none() {
+            line.push_str(rest);
+        }
+        push_line(&mut text.body, clean(line.as_str()).as_str());
+        text.title = text.headings.first().cloned();
+        text
+    }
+}
+
+fn element_text(element: &ftd::executor::Element, text: &mut PageText) {
+    if let Some(common) = element.get_common() {
+        if common.is_not_visible || common.is_dummy {
+            return;
+        }
+    }
+    let children = match element {
+        ftd::executor::Element::Row(r) => r.container.children.as_slice(),
+        ftd::executor::Element::Column(c) => c.container.children.as_slice(),
+        ftd::executor::Element::Container(c) => c.children.as_slice(),
+        ftd::executor::Element::Document(d) => d.children.as_slice(),
+        ftd::executor::Element::RawElement(r) => r.children.as_slice(),
+        ftd::executor::Element::IterativeElement(i) => {
+            return element_text(&i.element, text);
+        }
+        ftd::executor::Element::Text(t) => {
+            let plain = plain_text(t.text.value.rendered.as_str());
+            if t.common.region.value.is_some() {
+                text.headings.push(plain);
+            } else {
+                push_line(&mut text.body, plain.as_str());
+            }
+            return;
+        }
+        ftd::executor::Element::Code(c) => {
+            push_line(&mut text.body, c.text.value.original.as_str());
+            return;
+        }
+        ftd::executor::Element::Image(i) => {
+            if let Some(ref alt) = i.alt.value {
+                push_line(&mut text.body, alt.as_str());
+            }
+            return;
+        }
+        _ => return,
+    };
+    for child in children {
+        element_text(child, text);
+    }
+}
+
+fn push_line(body: &mut String, line: &str) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    if !body.is_empty() {
+        body.push('\n');
+    }
+    body.push_str(line);
+}
+
+const INLINE_TAGS: [&str; 13] = [
+    "a", "b", "code", "del", "em", "i", "mark", "s", "small", "span", "strong", "sub", "sup",
+];
+
+/// The lowercase name of `tag`, the text between `<` and `>`.
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// The value of the double quoted `name` attribute of `tag`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!(" {name}=\"");
+    let start = tag.find(pattern.as_str())? + pattern.len();
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+/// The text of `html`, the rendered markdown of a text, without tags and with whitespace
+/// collapsed. Tags other than inline ones separate words.
+fn plain_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = tag_name(&rest[start + 1..start + end]);
+        if !INLINE_TAGS.contains(&tag.as_str()) {
+            text.push(' ');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    clean(text.as_str())
+}
+
+/// `text` with the entities decoded and whitespace collapsed.
+fn clean(text: &str) -> String {
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn plain_text() {
+        assert_eq!(
+            super::plain_text(
+                "<p>Hello <strong>fastn</strong>!\n &amp; <code>&lt;ftd&gt;</code></p><p>Bye</p>"
+            ),
+            "Hello fastn! & <ftd> Bye"
+        );
+    }
+
+    #[test]
+    fn from_html() {
+        assert_eq!(
+            super::PageText::from_html(
+                "<body data-id=\"1\"><div data-id=\"2\"><h1 data-id=\"3\" id=\"install\">\
+                Install <b>fastn</b></h1><div data-id=\"4\"><p>Run the script &amp; \
+                <code>fastn serve</code>.</p></div><img data-id=\"5\" alt=\"A logo\"></img>\
+                <script>let x = \"<h2>\";</script></div></body><style id=\"styles\">\
+                .__c-1 { color: red }</style>"
+            ),
+            super::PageText {
+                title: Some("Install fastn".to_string()),
+                headings: vec!["Install fastn".to_string()],
+                body: "Run the script & fastn serve.\nA logo".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn text() {
+        let doc = ftd::executor::test::interpret_helper(
+            "foo",
+            indoc::indoc!(
+                "
+                -- ftd.text: Getting Started
+                region: h1
+
+                -- ftd.column:
+
+                -- ftd.text: Install *fastn* first.
+
+                -- ftd.text: Hidden
+                if: { false }
+
+                -- end: ftd.column
+                "
+            ),
+        )
+        .unwrap();
+        let rt = ftd::executor::ExecuteDoc::from_interpreter(doc).unwrap();
+        assert_eq!(
+            rt.text(),
+            super::PageText {
+                title: Some("Getting Started".to_string()),
+                headings: vec!["Getting Started".to_string()],
+                body: "Install fastn first.".to_string(),
+            }
+        );
+    }
+}
@@ -20,6 +20,16 @@ pub fn all_js_without_test(package_name: &str) -> String {
     format!("{all_js}\n{default_bag_js}")
 }
 
+/// The minified runtime, with only `modules` of it, and the things in the `default_bag`.
+pub fn runtime_js(package_name: &str, modules: &[fastn_js::RuntimeModule]) -> String {
+    let runtime_js = fastn_js::bundle(modules);
+    let default_bag_js = fastn_js::to_js(default_bag_into_js_ast().as_slice(), package_name);
+    format!(
+        "{runtime_js}\n{}",
+        fastn_js::minify(default_bag_js.as_str())
+    )
+}
+
 /// This returns asts of things present in `ftd` module or `default_bag`
 pub fn default_bag_into_js_ast() -> Vec<fastn_js::Ast> {
     let mut ftd_asts = vec![];
@@ -86,6 +96,8 @@ pub struct JSAstData {
     /// This contains external scripts provided by user and also `ftd`
     /// internally supports (like rive).
     pub scripts: Vec<String>,
+    /// The `.ftd` document and line each of `asts` comes from, used for source maps.
+    pub locations: Vec<Option<fastn_js::SourceLocation>>,
 }
 
 pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
@@ -98,17 +110,26 @@ pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
         &doc,
         &mut has_rive_components,
     )];
+    let mut locations = vec![document
+        .tree
+        .first()
+        .map(|c| fastn_js::SourceLocation::new(document.name.as_str(), c.line_number))];
     let default_thing_name = ftd::interpreter::default::get_default_bag()
         .into_iter()
         .map(|v| v.0)
         .collect_vec();
 
     let mut export_asts = vec![];
+    let mut export_locations = vec![];
 
     for (key, thing) in document.data.iter() {
         if default_thing_name.contains(&key) {
             continue;
         }
+        // things are stored as `<document id>#<name>`
+        let location = key.split_once('#').map(|(document_id, _)| {
+            fastn_js::SourceLocation::new(document_id, thing.line_number())
+        });
         if let ftd::interpreter::Thing::Component(c) = thing {
             document_asts.push(c.to_ast(&doc, &mut has_rive_components));
         } else if let ftd::interpreter::Thing::Variable(v) = thing {
@@ -128,7 +149,9 @@ pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
             export_asts.push(fastn_js::Ast::Export {
                 from: from.to_string(),
                 to: to.to_string(),
-            })
+            });
+            export_locations.push(location);
+            continue;
         } else if let ftd::interpreter::Thing::OrType(ot) = thing {
             let mut fields = vec![];
             for variant in &ot.variants {
@@ -157,9 +180,12 @@ pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
                 prefix: Some(fastn_js::GLOBAL_VARIABLE_MAP.to_string()),
             }));
         }
+        // records do not generate any js, so there may be nothing to locate
+        locations.resize(document_asts.len(), location);
     }
 
     document_asts.extend(export_asts);
+    locations.extend(export_locations);
     let mut scripts = ftd::js::utils::get_external_scripts(has_rive_components);
     scripts.push(ftd::js::utils::get_js_html(
         document.js.into_iter().collect_vec().as_slice(),
@@ -171,6 +197,7 @@ pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
     JSAstData {
         asts: document_asts,
         scripts,
+        locations,
     }
 }
 